@@ -0,0 +1,98 @@
+//! Measures how paragraph shaping and layout scale with the number of threads.
+//!
+//! Run with `cargo bench -p epgi-2d --bench text_layout`.
+
+use std::time::{Duration, Instant};
+
+use epgi_2d::{
+    Color, FontFamily, FontWeight, Paragraph, TextAlign, TextBaseline, TextDecoration,
+    TextDirection, TextLeadingDistribution, TextSpan, TextStyle,
+};
+use rayon::prelude::*;
+
+const PARAGRAPH_COUNT: usize = 4000;
+const ROUNDS: usize = 5;
+
+fn text_style() -> TextStyle {
+    TextStyle {
+        background_color: None,
+        color: Color::BLACK,
+        debug_label: None,
+        decoration: TextDecoration::empty(),
+        decoration_color: Color::BLACK,
+        decoration_thickness: 1.0,
+        font_family: FontFamily::Named("Roboto"),
+        font_family_fallback: vec![FontFamily::Named("DejaVu Sans")],
+        font_features: Default::default(),
+        font_size: 14.0,
+        font_style: Default::default(),
+        font_variations: Default::default(),
+        font_weight: FontWeight::NORMAL,
+        height: 1.43,
+        leading_distribution: TextLeadingDistribution::Even,
+        letter_spacing: 0.25,
+        locale: Default::default(),
+        overflow: Default::default(),
+        text_baseline: TextBaseline::Alphabetic,
+        word_spacing: Default::default(),
+    }
+}
+
+fn paragraphs(distinct: bool) -> Vec<[TextSpan; 1]> {
+    (0..PARAGRAPH_COUNT)
+        .map(|index| {
+            let label = if distinct { index } else { index % 20 };
+            [TextSpan {
+                text: format!(
+                    "Row {label}: the quick brown fox jumps over the lazy dog, again and again."
+                )
+                .into(),
+                style: None,
+            }]
+        })
+        .collect()
+}
+
+fn layout_all(paragraphs: &[[TextSpan; 1]], style: &TextStyle) {
+    paragraphs.par_iter().for_each(|spans| {
+        let mut paragraph = Paragraph::new(spans, style, TextDirection::Ltr);
+        paragraph.layout(Some(200.0), TextAlign::Start);
+    });
+}
+
+fn bench(name: &str, distinct: bool, thread_counts: &[usize]) {
+    let style = text_style();
+    let paragraphs = paragraphs(distinct);
+    let mut single_thread = None;
+    for &num_threads in thread_counts {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(num_threads)
+            .build()
+            .unwrap();
+        // Warm up font discovery on every thread
+        pool.install(|| layout_all(&paragraphs, &style));
+        let mut best = Duration::MAX;
+        for _ in 0..ROUNDS {
+            let start = Instant::now();
+            pool.install(|| layout_all(&paragraphs, &style));
+            best = best.min(start.elapsed());
+        }
+        let single_thread = *single_thread.get_or_insert(best);
+        println!(
+            "{name:<16} {num_threads:>2} threads: {:>8.2} ms ({:.2}x)",
+            best.as_secs_f64() * 1000.0,
+            single_thread.as_secs_f64() / best.as_secs_f64(),
+        );
+    }
+}
+
+fn main() {
+    let max_threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+    let thread_counts = [1, 2, 4, 8, 16]
+        .into_iter()
+        .filter(|&num_threads| num_threads <= max_threads.max(2))
+        .collect::<Vec<_>>();
+    println!("Laying out {PARAGRAPH_COUNT} paragraphs, best of {ROUNDS} rounds");
+    bench("distinct text", true, &thread_counts);
+    bench("repeated text", false, &thread_counts);
+}
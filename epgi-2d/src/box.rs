@@ -7,7 +7,7 @@ use epgi_core::{
     Provider,
 };
 
-use crate::{Affine2d, Affine2dCanvas, Point2d, Rect};
+use crate::{Affine2d, Affine2dCanvas, Point2d, Rect, TextBaseline};
 
 #[derive(Clone, Copy, Debug)]
 pub struct BoxProtocol {}
@@ -194,7 +194,15 @@ impl BoxSize {
 }
 
 #[derive(
-    derive_more::Add, derive_more::Mul, derive_more::Div, PartialEq, Default, Clone, Copy, Debug,
+    derive_more::Add,
+    derive_more::Sub,
+    derive_more::Mul,
+    derive_more::Div,
+    PartialEq,
+    Default,
+    Clone,
+    Copy,
+    Debug,
 )]
 pub struct BoxOffset {
     pub x: f32,
@@ -203,6 +211,11 @@ pub struct BoxOffset {
 
 impl BoxOffset {
     pub const ZERO: Self = Self { x: 0.0, y: 0.0 };
+
+    /// The length of the offset.
+    pub fn distance(&self) -> f32 {
+        self.x.hypot(self.y)
+    }
 }
 
 impl From<[f32; 2]> for BoxOffset {
@@ -214,6 +227,36 @@ impl From<[f32; 2]> for BoxOffset {
     }
 }
 
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Axis {
+    Horizontal,
+    Vertical,
+}
+
+impl Axis {
+    /// The offset that is `main` along this axis and `cross` along the other.
+    pub fn offset(self, main: f32, cross: f32) -> BoxOffset {
+        match self {
+            Axis::Horizontal => BoxOffset { x: main, y: cross },
+            Axis::Vertical => BoxOffset { x: cross, y: main },
+        }
+    }
+
+    /// The size that is `main` along this axis and `cross` along the other.
+    pub fn size(self, main: f32, cross: f32) -> BoxSize {
+        match self {
+            Axis::Horizontal => BoxSize {
+                width: main,
+                height: cross,
+            },
+            Axis::Vertical => BoxSize {
+                width: cross,
+                height: main,
+            },
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug)]
 pub enum BoxIntrinsics {
     MinWidth { height: f32, res: Option<f32> },
@@ -327,6 +370,8 @@ impl Protocol for BoxProtocol {
 
     type Intrinsics = BoxIntrinsics;
 
+    type Baseline = TextBaseline;
+
     type Offset = BoxOffset;
 
     type Canvas = Affine2dCanvas;
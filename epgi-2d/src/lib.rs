@@ -13,6 +13,9 @@ pub use paint::*;
 mod shape;
 pub use shape::*;
 
+mod sliver;
+pub use sliver::*;
+
 mod text;
 pub use text::*;
 
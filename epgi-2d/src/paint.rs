@@ -3,7 +3,7 @@ use epgi_core::foundation::{Canvas, PaintContext};
 use peniko::kurbo::Stroke;
 
 pub use peniko::{
-    BlendMode, Brush, Color, ColorStops, Extend, Fill, Format, Gradient, GradientKind, Image,
+    BlendMode, Blob, Brush, Color, ColorStops, Extend, Fill, Format, Gradient, GradientKind, Image,
 };
 
 use crate::{
@@ -28,6 +28,30 @@ pub enum Affine2dPaintCommand<'a> {
         paragraph: &'a Paragraph,
         offset: &'a [SingleLineOffset], // transform: Affine2d,
     },
+    /// Draw the `src` rect of the image (in image pixels) stretched onto the `dst` rect.
+    DrawImage {
+        image: &'a Image,
+        src: Rect,
+        dst: Rect,
+        quality: FilterQuality,
+        opacity: f32,
+    },
+}
+
+/// How an image is sampled when it is drawn at a different size than its own.
+///
+/// The vello backend currently samples every image bilinearly regardless of the quality.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum FilterQuality {
+    /// Nearest neighbor sampling.
+    None,
+    /// Bilinear sampling.
+    #[default]
+    Low,
+    /// Bilinear sampling with mipmaps when scaled down.
+    Medium,
+    /// Bicubic sampling.
+    High,
 }
 
 /// Although we provide a circle primitive, but vello does not have a precise circle encoding.
@@ -174,6 +198,14 @@ pub trait Affine2dPaintContextExt {
     fn fill_rrect_padding(&mut self, rrect: RRect, linewidth: f32, painter: FillPainter);
 
     fn draw_image_rect(&mut self, image: Image, src: Rect, dst: Rect);
+    fn draw_image(
+        &mut self,
+        image: &Image,
+        src: Rect,
+        dst: Rect,
+        quality: FilterQuality,
+        opacity: f32,
+    );
 
     fn draw_paragraph(&mut self, paragraph: &Paragraph, offset: &[SingleLineOffset]);
 }
@@ -482,14 +514,25 @@ where
 
     #[inline(always)]
     fn draw_image_rect(&mut self, image: Image, src: Rect, dst: Rect) {
-        self.add_command(Affine2dPaintCommand::DrawShape {
-            shape: Affine2dCanvasShape::Rect(dst),
-            painter: Painter::Fill(FillPainter {
-                fill: Fill::EvenOdd,
-                brush: Brush::Image(image),
-                transform: None,
-            }),
-        });
+        self.draw_image(&image, src, dst, FilterQuality::default(), 1.0)
+    }
+
+    #[inline(always)]
+    fn draw_image(
+        &mut self,
+        image: &Image,
+        src: Rect,
+        dst: Rect,
+        quality: FilterQuality,
+        opacity: f32,
+    ) {
+        self.add_command(Affine2dPaintCommand::DrawImage {
+            image,
+            src,
+            dst,
+            quality,
+            opacity,
+        })
     }
 
     #[inline(always)]
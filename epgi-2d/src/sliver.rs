@@ -0,0 +1,112 @@
+use epgi_core::{
+    foundation::{LayerProtocol, Protocol},
+    tree::{ArcChildElementNode, ArcChildRenderObject, ArcChildWidget, LayerCompositionConfig},
+};
+
+use crate::{Affine2d, Affine2dCanvas, Axis, BoxConstraints, BoxOffset, BoxSize, Point2d, Rect};
+
+/// A protocol for the scrolled content inside a viewport.
+///
+/// A sliver is told how far it has been scrolled and how much of the viewport is left for it,
+/// and reports how long it is and how much of it is visible. This allows a sliver to lay out and paint
+/// only the part of its content that is visible, however long the content is.
+///
+/// The offset of a sliver is where its visible part starts to be painted.
+#[derive(Clone, Copy, Debug)]
+pub struct SliverProtocol {}
+
+pub type ArcSliverWidget = ArcChildWidget<SliverProtocol>;
+pub type ArcSliverElementNode = ArcChildElementNode<SliverProtocol>;
+pub type ArcSliverRenderObject = ArcChildRenderObject<SliverProtocol>;
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct SliverConstraints {
+    /// The scroll axis.
+    pub axis: Axis,
+    /// How far the start of the sliver has been scrolled past the leading edge of the viewport.
+    pub scroll_offset: f32,
+    /// The extent along the axis that is left in the viewport, starting from where the sliver is painted.
+    pub remaining_paint_extent: f32,
+    pub cross_axis_extent: f32,
+    /// The extent of the whole viewport along the axis.
+    pub viewport_main_axis_extent: f32,
+}
+
+impl SliverConstraints {
+    /// Constraints for a box child that is `main_axis_extent` long and fills the cross axis.
+    pub fn as_box_constraints(&self, main_axis_extent: f32) -> BoxConstraints {
+        let size = self.axis.size(main_axis_extent, self.cross_axis_extent);
+        BoxConstraints::new_tight(size.width, size.height)
+    }
+
+    /// Constraints for a box child that fills the cross axis and picks its own extent along the axis.
+    pub fn as_unbounded_box_constraints(&self) -> BoxConstraints {
+        match self.axis {
+            Axis::Horizontal => BoxConstraints::new_tight_height(self.cross_axis_extent),
+            Axis::Vertical => BoxConstraints::new_tight_width(self.cross_axis_extent),
+        }
+    }
+}
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct SliverGeometry {
+    pub axis: Axis,
+    /// The extent of the whole sliver along the axis, including the parts outside the viewport.
+    pub scroll_extent: f32,
+    /// The extent of the visible part along the axis.
+    pub paint_extent: f32,
+    pub cross_axis_extent: f32,
+}
+
+impl SliverGeometry {
+    pub fn zero(constraints: &SliverConstraints) -> Self {
+        Self {
+            axis: constraints.axis,
+            scroll_extent: 0.0,
+            paint_extent: 0.0,
+            cross_axis_extent: constraints.cross_axis_extent,
+        }
+    }
+
+    /// The size of the visible part.
+    pub fn paint_size(&self) -> BoxSize {
+        self.axis.size(self.paint_extent, self.cross_axis_extent)
+    }
+}
+
+impl Protocol for SliverProtocol {
+    type Constraints = SliverConstraints;
+
+    type Size = SliverGeometry;
+
+    type Intrinsics = ();
+
+    type Baseline = ();
+
+    type Offset = BoxOffset;
+
+    type Canvas = Affine2dCanvas;
+
+    fn position_in_shape(position: &Point2d, offset: &BoxOffset, size: &SliverGeometry) -> bool {
+        Rect::new_point_size(*offset, size.paint_size()).contains(position)
+    }
+}
+
+impl LayerProtocol for SliverProtocol {
+    fn zero_offset() -> BoxOffset {
+        BoxOffset::ZERO
+    }
+
+    fn offset_layer_transform(offset: &BoxOffset, transform: &Affine2d) -> Affine2d {
+        transform.mul_translation(offset)
+    }
+
+    fn offset_layer_composition_config(
+        offset: &Self::Offset,
+        config: &LayerCompositionConfig<Self::Canvas>,
+    ) -> LayerCompositionConfig<Self::Canvas> {
+        LayerCompositionConfig {
+            transform: Self::offset_layer_transform(offset, &config.transform),
+        }
+    }
+}
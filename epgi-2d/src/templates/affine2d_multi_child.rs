@@ -38,6 +38,17 @@ pub trait Affine2dMultiChildRender: Send + Sync + Sized + 'static {
         children: &Vec<ArcChildRenderObject<Self::ChildProtocol>>,
         intrinsics: &mut <Self::ParentProtocol as Protocol>::Intrinsics,
     );
+
+    #[allow(unused_variables)]
+    fn compute_distance_to_baseline(
+        &self,
+        size: &<Self::ParentProtocol as Protocol>::Size,
+        memo: &Self::LayoutMemo,
+        children: &[ArcChildRenderObject<Self::ChildProtocol>],
+        baseline: &<Self::ParentProtocol as Protocol>::Baseline,
+    ) -> Option<f32> {
+        None
+    }
 }
 
 impl<
@@ -76,6 +87,16 @@ where
     ) {
         R::compute_intrinsics(render, children, intrinsics)
     }
+
+    fn compute_distance_to_baseline(
+        render: &R,
+        size: &<Self::ParentProtocol as Protocol>::Size,
+        memo: &R::LayoutMemo,
+        children: &Vec<ArcChildRenderObject<Self::ChildProtocol>>,
+        baseline: &<Self::ParentProtocol as Protocol>::Baseline,
+    ) -> Option<f32> {
+        R::compute_distance_to_baseline(render, size, memo, children, baseline)
+    }
 }
 
 impl<
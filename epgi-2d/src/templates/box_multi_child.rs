@@ -5,8 +5,8 @@ use epgi_core::template::TemplateRender;
 use epgi_core::tree::{default_reconcile_vec, ImplRender, RenderBase, RenderImpl};
 use epgi_core::{
     foundation::{
-        AnyRawPointer, Arc, Asc, BuildSuspendedError, Canvas, InlinableDwsizeVec, Key,
-        PaintContext, Protocol, Provide, TypeKey,
+        AnyRawPointer, Arc, Asc, BuildError, Canvas, InlinableDwsizeVec, Key, PaintContext,
+        Protocol, Provide, TypeKey,
     },
     template::{
         ImplByTemplate, TemplateCachedComposite, TemplateComposite, TemplateElement,
@@ -24,7 +24,7 @@ use epgi_core::{
 
 use crate::{
     Affine2dCanvas, Affine2dEncoding, ArcBoxElementNode, ArcBoxRenderObject, ArcBoxWidget,
-    BoxConstraints, BoxIntrinsics, BoxOffset, BoxProtocol, BoxSize, Point2d,
+    BoxConstraints, BoxIntrinsics, BoxOffset, BoxProtocol, BoxSize, Point2d, TextBaseline,
 };
 
 pub struct BoxMultiChildElementTemplate<const PROVIDE_ELEMENT: bool>;
@@ -47,7 +47,7 @@ pub trait BoxMultiChildElement: Clone + Send + Sync + Sized + 'static {
         widget: &Self::ArcWidget,
         ctx: &mut BuildContext<'_>,
         provider_values: InlinableDwsizeVec<Arc<dyn Provide>>,
-    ) -> Result<Vec<ArcBoxWidget>, BuildSuspendedError>;
+    ) -> Result<Vec<ArcBoxWidget>, BuildError>;
 
     /// A major limitation to the Multi child element template is that,
     /// we cannot provide consumed values and build context during the creation the Element itself.
@@ -103,7 +103,7 @@ where
             Vec<ElementReconcileItem<BoxProtocol>>,
             Option<ChildRenderObjectsUpdateCallback<Self::ChildContainer, BoxProtocol>>,
         ),
-        (Vec<ArcBoxElementNode>, BuildSuspendedError),
+        (Vec<ArcBoxElementNode>, BuildError),
     > {
         let new_widgets = match E::get_child_widgets(Some(element), widget, ctx, provider_values) {
             Err(error) => return Err((children, error)),
@@ -120,7 +120,7 @@ where
         widget: &Self::ArcWidget,
         ctx: &mut BuildContext<'_>,
         provider_values: InlinableDwsizeVec<Arc<dyn Provide>>,
-    ) -> Result<(E, Vec<ArcBoxWidget>), BuildSuspendedError> {
+    ) -> Result<(E, Vec<ArcBoxWidget>), BuildError> {
         let element = E::create_element(widget);
         let child_widgets = E::get_child_widgets(None, widget, ctx, provider_values)?;
         Ok((element, child_widgets))
@@ -194,6 +194,17 @@ pub trait BoxMultiChildRender: Send + Sync + Sized + 'static {
         children: &Vec<ArcBoxRenderObject>,
         intrinsics: &mut BoxIntrinsics,
     );
+
+    #[allow(unused_variables)]
+    fn compute_distance_to_baseline(
+        &self,
+        size: &BoxSize,
+        memo: &Self::LayoutMemo,
+        children: &[ArcBoxRenderObject],
+        baseline: &TextBaseline,
+    ) -> Option<f32> {
+        None
+    }
 }
 
 impl<
@@ -227,6 +238,16 @@ where
     ) {
         R::compute_intrinsics(render, children, intrinsics)
     }
+
+    fn compute_distance_to_baseline(
+        render: &R,
+        size: &BoxSize,
+        memo: &R::LayoutMemo,
+        children: &Vec<ArcBoxRenderObject>,
+        baseline: &TextBaseline,
+    ) -> Option<f32> {
+        R::compute_distance_to_baseline(render, size, memo, children, baseline)
+    }
 }
 
 impl<
@@ -11,7 +11,7 @@ use epgi_core::{
 
 use crate::{
     Affine2dCanvas, ArcBoxRenderObject, BoxConstraints, BoxIntrinsics, BoxOffset, BoxProtocol,
-    BoxSize, Point2d,
+    BoxSize, Point2d, TextBaseline,
 };
 
 pub struct BoxProxyRenderTemplate;
@@ -29,6 +29,16 @@ pub trait BoxProxyRender: Send + Sync + Sized + 'static {
         child.get_intrinsics(intrinsics)
     }
 
+    #[allow(unused_variables)]
+    fn compute_distance_to_baseline(
+        &self,
+        size: &BoxSize,
+        child: &ArcBoxRenderObject,
+        baseline: &TextBaseline,
+    ) -> Option<f32> {
+        child.get_distance_to_baseline(baseline)
+    }
+
     #[allow(unused_variables)]
     fn perform_paint(
         &self,
@@ -126,6 +136,16 @@ where
     ) {
         R::compute_intrinsics(render, child, intrinsics)
     }
+
+    fn compute_distance_to_baseline(
+        render: &R,
+        size: &BoxSize,
+        _memo: &(),
+        [child]: &[ArcBoxRenderObject; 1],
+        baseline: &TextBaseline,
+    ) -> Option<f32> {
+        R::compute_distance_to_baseline(render, size, child, baseline)
+    }
 }
 
 impl<R> TemplateRender<R> for BoxProxyRenderTemplate
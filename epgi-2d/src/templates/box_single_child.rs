@@ -2,8 +2,8 @@ use epgi_core::template::TemplateRender;
 use epgi_core::tree::{ImplRender, RenderBase, RenderImpl};
 use epgi_core::{
     foundation::{
-        AnyRawPointer, Arc, ArrayContainer, Asc, BuildSuspendedError, Canvas, InlinableDwsizeVec,
-        Key, PaintContext, Protocol, Provide, TypeKey,
+        AnyRawPointer, Arc, ArrayContainer, Asc, BuildError, Canvas, InlinableDwsizeVec, Key,
+        PaintContext, Protocol, Provide, TypeKey,
     },
     template::{
         ImplByTemplate, TemplateCachedComposite, TemplateComposite, TemplateElement,
@@ -22,7 +22,7 @@ use std::any::{Any, TypeId};
 
 use crate::{
     Affine2dCanvas, Affine2dEncoding, ArcBoxElementNode, ArcBoxRenderObject, ArcBoxWidget,
-    BoxConstraints, BoxIntrinsics, BoxOffset, BoxProtocol, BoxSize, Point2d,
+    BoxConstraints, BoxIntrinsics, BoxOffset, BoxProtocol, BoxSize, Point2d, TextBaseline,
 };
 
 pub struct BoxSingleChildElementTemplate<const RENDER_ELEMENT: bool, const PROVIDE_ELEMENT: bool>;
@@ -40,7 +40,7 @@ pub trait BoxSingleChildElement: Clone + Send + Sync + Sized + 'static {
         widget: &Self::ArcWidget,
         ctx: &mut BuildContext<'_>,
         provider_values: InlinableDwsizeVec<Arc<dyn Provide>>,
-    ) -> Result<ArcBoxWidget, BuildSuspendedError>;
+    ) -> Result<ArcBoxWidget, BuildError>;
 
     /// A major limitation to the single child element template is that,
     /// we cannot provide consumed values and build context during the creation the Element itself.
@@ -96,7 +96,7 @@ where
             [ElementReconcileItem<BoxProtocol>; 1],
             Option<ChildRenderObjectsUpdateCallback<Self::ChildContainer, BoxProtocol>>,
         ),
-        ([ArcBoxElementNode; 1], BuildSuspendedError),
+        ([ArcBoxElementNode; 1], BuildError),
     > {
         let child_widget = match E::get_child_widget(Some(element), widget, ctx, provider_values) {
             Err(error) => return Err(([child], error)),
@@ -116,7 +116,7 @@ where
         widget: &Self::ArcWidget,
         ctx: &mut BuildContext<'_>,
         provider_values: InlinableDwsizeVec<Arc<dyn Provide>>,
-    ) -> Result<(E, [ArcBoxWidget; 1]), BuildSuspendedError> {
+    ) -> Result<(E, [ArcBoxWidget; 1]), BuildError> {
         let element = E::create_element(widget);
         let child_widget = E::get_child_widget(None, widget, ctx, provider_values)?;
         Ok((element, [child_widget]))
@@ -218,6 +218,17 @@ pub trait BoxSingleChildRender: Send + Sync + Sized + 'static {
     const NOOP_DETACH: bool = false;
 
     fn compute_intrinsics(&mut self, child: &ArcBoxRenderObject, intrinsics: &mut BoxIntrinsics);
+
+    #[allow(unused_variables)]
+    fn compute_distance_to_baseline(
+        &self,
+        size: &BoxSize,
+        memo: &Self::LayoutMemo,
+        child: &ArcBoxRenderObject,
+        baseline: &TextBaseline,
+    ) -> Option<f32> {
+        None
+    }
 }
 
 impl<
@@ -251,6 +262,16 @@ where
     ) {
         R::compute_intrinsics(render, child, intrinsics)
     }
+
+    fn compute_distance_to_baseline(
+        render: &R,
+        size: &BoxSize,
+        memo: &R::LayoutMemo,
+        [child]: &[ArcBoxRenderObject; 1],
+        baseline: &TextBaseline,
+    ) -> Option<f32> {
+        R::compute_distance_to_baseline(render, size, memo, child, baseline)
+    }
 }
 
 impl<
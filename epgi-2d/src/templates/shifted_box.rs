@@ -11,7 +11,7 @@ use epgi_core::{
 
 use crate::{
     Affine2dCanvas, ArcBoxRenderObject, BoxConstraints, BoxIntrinsics, BoxOffset, BoxProtocol,
-    BoxSize, Point2d,
+    BoxSize, Point2d, TextBaseline,
 };
 
 pub struct ShiftedBoxRenderTemplate;
@@ -34,6 +34,20 @@ pub trait ShiftedBoxRender: Send + Sync + Sized + 'static {
 
     fn compute_intrinsics(&mut self, child: &ArcBoxRenderObject, intrinsics: &mut BoxIntrinsics);
 
+    /// Defaults to the baseline of the child, shifted by the child offset.
+    fn compute_distance_to_baseline(
+        &self,
+        size: &BoxSize,
+        memo: &Self::LayoutMemo,
+        child: &ArcBoxRenderObject,
+        baseline: &TextBaseline,
+    ) -> Option<f32> {
+        let child_offset = self.get_child_offset(size, &BoxOffset::ZERO, memo);
+        child
+            .get_distance_to_baseline(baseline)
+            .map(|distance| distance + child_offset.y)
+    }
+
     #[allow(unused_variables)]
     fn perform_paint(
         &self,
@@ -135,6 +149,16 @@ where
     ) {
         R::compute_intrinsics(render, child, intrinsics)
     }
+
+    fn compute_distance_to_baseline(
+        render: &R,
+        size: &BoxSize,
+        memo: &R::LayoutMemo,
+        [child]: &[ArcBoxRenderObject; 1],
+        baseline: &TextBaseline,
+    ) -> Option<f32> {
+        R::compute_distance_to_baseline(render, size, memo, child, baseline)
+    }
 }
 
 impl<R> TemplateRender<R> for ShiftedBoxRenderTemplate
@@ -1,3 +1,9 @@
+mod font_context;
+pub(crate) use font_context::*;
+
+mod font_loader;
+pub use font_loader::*;
+
 mod multi_line;
 pub use multi_line::*;
 
@@ -7,6 +13,9 @@ pub use paragraph::*;
 mod render_text;
 pub(crate) use render_text::*;
 
+mod shaping_cache;
+pub(crate) use shaping_cache::*;
+
 mod style;
 pub use style::*;
 
@@ -0,0 +1,104 @@
+use std::{
+    cell::RefCell,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use epgi_core::foundation::{Asc, SyncMutex};
+use hashbrown::HashMap;
+use parley::{
+    fontique::{Collection, CollectionOptions, SourceCache},
+    FontContext, LayoutContext,
+};
+
+use crate::ParleyBrush;
+
+/// The font collection and font data cache that every thread's [`FontContext`] is cloned from.
+///
+/// Both are created in shared mode, so that fonts registered through any clone are visible to all clones,
+/// and each font file is only loaded into memory once.
+/// Nothing ever locks this prototype; each thread works on its own clone.
+struct SharedFontContext {
+    collection: Collection,
+    source_cache: SourceCache,
+}
+
+lazy_static::lazy_static! {
+    static ref SHARED_FONT_CONTEXT: SharedFontContext = SharedFontContext {
+        collection: Collection::new(CollectionOptions {
+            shared: true,
+            system_fonts: true,
+        }),
+        source_cache: SourceCache::new_shared(),
+    };
+    static ref FONT_ALIASES: SyncMutex<Asc<FontAliases>> = Default::default();
+}
+
+/// Family names that resolve to other font families, in the order they should be tried.
+pub(crate) type FontAliases = HashMap<String, Vec<String>>;
+
+/// Bumped whenever fonts or aliases are registered, so that per-thread state can notice and catch up.
+static FONT_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+pub(crate) fn font_generation() -> u64 {
+    FONT_GENERATION.load(Ordering::Acquire)
+}
+
+/// Register every font in the data, and return the names of the families they were added to.
+pub(crate) fn register_fonts(data: Vec<u8>) -> Vec<String> {
+    // Registering through a clone is visible to all clones, since the collection is shared.
+    let mut collection = SHARED_FONT_CONTEXT.collection.clone();
+    let families = collection.register_fonts(data);
+    let mut names = Vec::<String>::new();
+    for (family_id, _fonts) in families {
+        if let Some(name) = collection.family_name(family_id) {
+            if !names.iter().any(|existing| existing == name) {
+                names.push(name.to_owned());
+            }
+        }
+    }
+    FONT_GENERATION.fetch_add(1, Ordering::AcqRel);
+    names
+}
+
+pub(crate) fn add_font_alias(alias: String, family: String) {
+    let mut aliases = FONT_ALIASES.lock();
+    let families = Asc::make_mut(&mut aliases).entry(alias).or_default();
+    if !families.contains(&family) {
+        families.push(family);
+    }
+    FONT_GENERATION.fetch_add(1, Ordering::AcqRel);
+}
+
+thread_local! {
+    // Parley's contexts are not Sync, and hold caches that are only worth keeping if they are reused.
+    // Per-thread contexts let the threads of the pipeline shape text without contending on a lock.
+    static FONT_CONTEXT: RefCell<FontContext> = RefCell::new(FontContext {
+        collection: SHARED_FONT_CONTEXT.collection.clone(),
+        source_cache: SHARED_FONT_CONTEXT.source_cache.clone(),
+    });
+    static LAYOUT_CONTEXT: RefCell<LayoutContext<ParleyBrush>> = RefCell::new(LayoutContext::new());
+    static FONT_ALIASES_SNAPSHOT: RefCell<(u64, Asc<FontAliases>)> = Default::default();
+}
+
+/// The font aliases as of the latest registration, without locking unless they have changed.
+pub(crate) fn font_aliases() -> Asc<FontAliases> {
+    let generation = font_generation();
+    FONT_ALIASES_SNAPSHOT.with_borrow_mut(|(snapshot_generation, aliases)| {
+        if *snapshot_generation != generation {
+            *aliases = FONT_ALIASES.lock().clone();
+            *snapshot_generation = generation;
+        }
+        aliases.clone()
+    })
+}
+
+/// Run `op` with the font context and layout context of the current thread.
+///
+/// Must not be called recursively from within `op`.
+pub(crate) fn with_text_contexts<R>(
+    op: impl FnOnce(&mut FontContext, &mut LayoutContext<ParleyBrush>) -> R,
+) -> R {
+    FONT_CONTEXT.with_borrow_mut(|font_ctx| {
+        LAYOUT_CONTEXT.with_borrow_mut(|layout_ctx| op(font_ctx, layout_ctx))
+    })
+}
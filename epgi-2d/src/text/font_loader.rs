@@ -0,0 +1,91 @@
+use std::{path::PathBuf, sync::Arc};
+
+use crate::{add_font_alias, register_fonts};
+
+/// Where the data of a font file comes from.
+#[derive(Clone, Debug)]
+pub enum FontSource {
+    File(PathBuf),
+    Memory(Arc<[u8]>),
+}
+
+impl FontSource {
+    pub fn file(path: impl Into<PathBuf>) -> Self {
+        Self::File(path.into())
+    }
+
+    pub fn memory(bytes: impl Into<Arc<[u8]>>) -> Self {
+        Self::Memory(bytes.into())
+    }
+}
+
+/// Registers custom fonts for text layout.
+///
+/// Registered fonts are process-wide. They are available to every [`Paragraph`](crate::Paragraph)
+/// created afterwards on any thread, alongside the fonts found on the system.
+/// A family registered from several files, e.g. a regular and a bold file,
+/// is matched against the weight and style of the text like any system family.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FontLoader;
+
+impl FontLoader {
+    /// Register every font in the source. Returns the names of the families the fonts were added to.
+    pub fn load(&self, source: &FontSource) -> Result<Vec<String>, FontLoadError> {
+        let data = match source {
+            FontSource::File(path) => std::fs::read(path).map_err(FontLoadError::Io)?,
+            FontSource::Memory(bytes) => bytes.to_vec(),
+        };
+        let families = register_fonts(data);
+        if families.is_empty() {
+            return Err(FontLoadError::NoFonts);
+        }
+        Ok(families)
+    }
+
+    /// Register every font in the source, and make their families available under `alias` as well.
+    pub fn load_as(
+        &self,
+        source: &FontSource,
+        alias: impl Into<String>,
+    ) -> Result<Vec<String>, FontLoadError> {
+        let families = self.load(source)?;
+        let alias = alias.into();
+        for family in &families {
+            add_font_alias(alias.clone(), family.clone());
+        }
+        Ok(families)
+    }
+
+    /// Make text styled with the `alias` family name use `family` instead.
+    ///
+    /// An alias can be added several times to resolve to several families,
+    /// which are tried in the order they were added before the fallback families of the text style.
+    pub fn add_alias(&self, alias: impl Into<String>, family: impl Into<String>) {
+        add_font_alias(alias.into(), family.into())
+    }
+}
+
+#[derive(Debug)]
+pub enum FontLoadError {
+    Io(std::io::Error),
+    /// The data does not contain any font that could be parsed.
+    NoFonts,
+}
+
+impl std::fmt::Display for FontLoadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FontLoadError::Io(e) => write!(f, "Failed to read font: {}", e),
+            FontLoadError::NoFonts => write!(f, "No fonts found in the font data"),
+        }
+    }
+}
+
+impl std::error::Error for FontLoadError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            FontLoadError::Io(e) => Some(e),
+            FontLoadError::NoFonts => None,
+        }
+    }
+}
@@ -1,4 +1,7 @@
-use epgi_core::foundation::{Intrinsics, Protocol};
+use epgi_core::{
+    foundation::{Intrinsics, Protocol},
+    tree::ChildRenderObject,
+};
 
 use crate::{Affine2dCanvas, Point2d, SingleLineOffset, SingleLineProtocol, SingleLineSize};
 
@@ -19,6 +22,8 @@ impl Protocol for MultiLineProtocol {
 
     type Intrinsics = MultiLineIntrinsics;
 
+    type Baseline = ();
+
     type Canvas = Affine2dCanvas;
 
     fn position_in_shape(
@@ -54,6 +59,7 @@ pub struct MultiLineOffset {
     pub offsets: Vec<SingleLineOffset>,
 }
 
+/// Widths and heights are measured as if the content started on a fresh line.
 #[derive(Clone, Copy, Debug)]
 pub enum MultiLineIntrinsics {
     MinWidth { height: f32, res: Option<f32> },
@@ -66,10 +72,123 @@ pub enum MultiLineIntrinsics {
 
 impl Intrinsics for MultiLineIntrinsics {
     fn eq_tag(&self, other: &Self) -> bool {
-        todo!()
+        use MultiLineIntrinsics::*;
+        matches!(
+            (self, other),
+            (MinWidth { .. }, MinWidth { .. })
+                | (MaxWidth { .. }, MaxWidth { .. })
+                | (MinHeight { .. }, MinHeight { .. })
+                | (MaxHeight { .. }, MaxHeight { .. })
+                | (
+                    AdvanceBeforeFirstBreak { .. },
+                    AdvanceBeforeFirstBreak { .. }
+                )
+                | (EndWithBreak { .. }, EndWithBreak { .. })
+        )
     }
 
     fn eq_param(&self, other: &Self) -> bool {
-        todo!()
+        use MultiLineIntrinsics::*;
+        match (self, other) {
+            (MinWidth { height: x, .. }, MinWidth { height: y, .. })
+            | (MaxWidth { height: x, .. }, MaxWidth { height: y, .. })
+            | (MinHeight { width: x, .. }, MinHeight { width: y, .. })
+            | (MaxHeight { width: x, .. }, MaxHeight { width: y, .. }) => x == y,
+            (AdvanceBeforeFirstBreak { .. }, AdvanceBeforeFirstBreak { .. })
+            | (EndWithBreak { .. }, EndWithBreak { .. }) => true,
+            _ => false,
+        }
+    }
+}
+
+pub trait MultiLineRenderObjectIntrinsicsExt {
+    fn get_min_intrinsic_width(&self, height: f32) -> Option<f32>;
+    fn get_max_intrinsic_width(&self, height: f32) -> Option<f32>;
+    fn get_min_intrinsic_height(&self, width: f32) -> Option<f32>;
+    fn get_max_intrinsic_height(&self, width: f32) -> Option<f32>;
+    fn get_advance_before_first_break(&self) -> Option<f32>;
+    fn get_end_with_break(&self) -> Option<bool>;
+}
+
+impl MultiLineRenderObjectIntrinsicsExt for dyn ChildRenderObject<MultiLineProtocol> {
+    fn get_min_intrinsic_width(&self, height: f32) -> Option<f32> {
+        let mut intrinsics = MultiLineIntrinsics::MinWidth { height, res: None };
+        self.get_intrinsics(&mut intrinsics);
+        let MultiLineIntrinsics::MinWidth {
+            height: new_height,
+            res,
+        } = intrinsics
+        else {
+            panic!("Child returns a wrong intrinsics type.")
+        };
+        debug_assert_eq!(
+            height, new_height,
+            "Intrinsics computation should not modify input parameters"
+        );
+        res
+    }
+    fn get_max_intrinsic_width(&self, height: f32) -> Option<f32> {
+        let mut intrinsics = MultiLineIntrinsics::MaxWidth { height, res: None };
+        self.get_intrinsics(&mut intrinsics);
+        let MultiLineIntrinsics::MaxWidth {
+            height: new_height,
+            res,
+        } = intrinsics
+        else {
+            panic!("Child returns a wrong intrinsics type.")
+        };
+        debug_assert_eq!(
+            height, new_height,
+            "Intrinsics computation should not modify input parameters"
+        );
+        res
+    }
+    fn get_min_intrinsic_height(&self, width: f32) -> Option<f32> {
+        let mut intrinsics = MultiLineIntrinsics::MinHeight { width, res: None };
+        self.get_intrinsics(&mut intrinsics);
+        let MultiLineIntrinsics::MinHeight {
+            width: new_width,
+            res,
+        } = intrinsics
+        else {
+            panic!("Child returns a wrong intrinsics type.")
+        };
+        debug_assert_eq!(
+            width, new_width,
+            "Intrinsics computation should not modify input parameters"
+        );
+        res
+    }
+    fn get_max_intrinsic_height(&self, width: f32) -> Option<f32> {
+        let mut intrinsics = MultiLineIntrinsics::MaxHeight { width, res: None };
+        self.get_intrinsics(&mut intrinsics);
+        let MultiLineIntrinsics::MaxHeight {
+            width: new_width,
+            res,
+        } = intrinsics
+        else {
+            panic!("Child returns a wrong intrinsics type.")
+        };
+        debug_assert_eq!(
+            width, new_width,
+            "Intrinsics computation should not modify input parameters"
+        );
+        res
+    }
+    fn get_advance_before_first_break(&self) -> Option<f32> {
+        let mut intrinsics = MultiLineIntrinsics::AdvanceBeforeFirstBreak { res: None };
+        self.get_intrinsics(&mut intrinsics);
+        let MultiLineIntrinsics::AdvanceBeforeFirstBreak { res } = intrinsics else {
+            panic!("Child returns a wrong intrinsics type.")
+        };
+        res
+    }
+    fn get_end_with_break(&self) -> Option<bool> {
+        let mut intrinsics = MultiLineIntrinsics::EndWithBreak { res: None };
+        self.get_intrinsics(&mut intrinsics);
+        let MultiLineIntrinsics::EndWithBreak { res } = intrinsics else {
+            panic!("Child returns a wrong intrinsics type.")
+        };
+        res
     }
 }
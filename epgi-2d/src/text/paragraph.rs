@@ -1,11 +1,14 @@
 use std::{borrow::Cow, ops::Range};
 
-use epgi_core::foundation::{Asc, SyncMutex};
-use parley::style::{FontStack, StyleProperty};
+use parley::{
+    layout::LineMetrics,
+    style::{FontStack, StyleProperty},
+};
 
 use crate::{
-    LocalTextStyle, MultiLineConstraints, ParleyBrush, SingleLineSize, TextAlign, TextDecoration,
-    TextStyle,
+    font_aliases, shape_cached, with_text_contexts, FontAliases, FontFamily, LocalTextStyle,
+    MultiLineConstraints, ParleyBrush, Point2d, Rect, SingleLineSize, TextAlign, TextDecoration,
+    TextDirection, TextOverFlow, TextStyle,
 };
 
 #[derive(Clone, Debug)]
@@ -14,16 +17,331 @@ pub struct TextSpan {
     pub style: Option<LocalTextStyle>,
 }
 
+/// Room left in a paragraph for an inline box, such as a widget that flows with the text.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Placeholder {
+    /// Where the box is inserted, as a text position in the spans of the paragraph.
+    pub position: usize,
+    pub width: f32,
+    /// How far the box extends above the baseline of its line.
+    pub above: f32,
+    /// How far the box extends below the baseline of its line.
+    pub below: f32,
+}
+
+/// Build the font stack of a text style, expanding the aliases registered with the [`FontLoader`](crate::FontLoader).
+fn resolve_font_stack<'a>(
+    aliases: &'a FontAliases,
+    font_family: FontFamily,
+    font_family_fallback: &[FontFamily],
+) -> Vec<parley::style::FontFamily<'a>> {
+    let mut font_stack = Vec::with_capacity(1 + font_family_fallback.len());
+    for family in std::iter::once(font_family).chain(font_family_fallback.iter().copied()) {
+        match family {
+            FontFamily::Named(name) if aliases.contains_key(name) => font_stack.extend(
+                aliases[name]
+                    .iter()
+                    .map(|family| parley::style::FontFamily::Named(family)),
+            ),
+            family => font_stack.push(family),
+        }
+    }
+    font_stack
+}
+
 pub struct Paragraph {
     pub(crate) layout: parley::Layout<ParleyBrush>,
+    ends_with_break: bool,
+    style: TextStyle,
+    text_direction: TextDirection,
+    /// The text positions where a [`RIGHT_TO_LEFT_ISOLATE`] was inserted into the shaped text.
+    isolates: Vec<usize>,
+    /// How far each visible line is from the left of the paragraph, as of the last layout.
+    line_starts: Vec<f32>,
+    /// The placeholders along with the text positions where they start, which count the placeholder text before them.
+    placeholders: Vec<(usize, Placeholder)>,
+    /// How far the placeholders on each visible line extend it above and below its text, as of the last layout.
+    line_growth: Vec<(f32, f32)>,
+    pub(crate) truncation: Option<Truncation>,
+}
+
+/// Where a paragraph with more lines than its `max_lines` stops, and how its last visible line ends.
+pub(crate) struct Truncation {
+    /// The number of lines that remain visible.
+    pub(crate) line_count: usize,
+    /// Glyphs of the last visible line whose center lies outside this range, measured from the left of the line, are hidden.
+    pub(crate) visible: Range<f32>,
+    /// The text position where the hidden text starts.
+    pub(crate) text_end: usize,
+    pub(crate) ending: TruncationEnding,
+}
+
+pub(crate) enum TruncationEnding {
+    Clip,
+    /// An ellipsis drawn where the visible text ends, laid out as a single line.
+    Ellipsis(Box<Paragraph>),
+    /// The last visible line fades out, from opaque at `start` to transparent at `end`, measured from the left of the line.
+    Fade {
+        start: f32,
+        end: f32,
+    },
+}
+
+const ELLIPSIS: &str = "\u{2026}";
+
+/// Parley always resolves bidirectional text as if the paragraph were left-to-right.
+/// A right-to-left paragraph is shaped with this isolate in front of each of its lines instead,
+/// which resolves the text of the line as if the paragraph were right-to-left.
+///
+/// The isolate is default ignorable, so it is never drawn, but it does shift the text positions of the layout.
+/// Line breaks close every isolate before them, hence one isolate per line.
+const RIGHT_TO_LEFT_ISOLATE: char = '\u{2067}';
+
+const ISOLATE_LEN: usize = RIGHT_TO_LEFT_ISOLATE.len_utf8();
+
+/// A placeholder is shaped as an em dash, which most fonts have a glyph for and which allows line breaks on both sides.
+/// The object replacement character has no glyph in most fonts, so it can not be given a width.
+/// Unlike the object replacement character, two placeholders in a row do not break between them.
+const PLACEHOLDER_TEXT: &str = "\u{2014}";
+
+/// Parley applies the letter spacing of the start of the text to all of it, so placeholders can not be sized by their letter spacing.
+/// They are shaped at these two font sizes to find how their advances grow with the font size,
+/// and then at the font sizes that make them as wide as they should be.
+const PROBE_FONT_SIZES: [f32; 2] = [8.0, 16.0];
+
+/// The font size of a placeholder that should be narrower than the letter spacing alone.
+const MIN_PLACEHOLDER_FONT_SIZE: f32 = 1e-3;
+
+/// A placeholder has no line height, so its glyph does not add to the metrics of its line.
+fn placeholder_span(font_size: f32) -> TextSpan {
+    TextSpan {
+        text: Cow::Borrowed(PLACEHOLDER_TEXT),
+        style: Some(LocalTextStyle {
+            font_size: Some(font_size),
+            height: Some(0.0),
+            ..Default::default()
+        }),
+    }
+}
+
+/// The spans with a placeholder span inserted at the position of each placeholder,
+/// and the text positions where the placeholders start in the text of those spans.
+fn spans_with_placeholders(
+    spans: &[TextSpan],
+    placeholders: &[Placeholder],
+    font_sizes: &[f32],
+) -> (Vec<TextSpan>, Vec<(usize, Placeholder)>) {
+    let mut shaped_spans = Vec::with_capacity(spans.len() + placeholders.len());
+    let mut shaped_placeholders = Vec::with_capacity(placeholders.len());
+    let mut placeholders = std::iter::zip(placeholders, font_sizes).peekable();
+    let mut span_start = 0;
+    for span in spans {
+        let span_end = span_start + span.text.len();
+        let mut copied = span_start;
+        while let Some((placeholder, &font_size)) =
+            placeholders.next_if(|(placeholder, _)| placeholder.position < span_end)
+        {
+            let position = placeholder.position.max(copied);
+            if position > copied {
+                shaped_spans.push(sub_span(span, copied - span_start..position - span_start));
+            }
+            copied = position;
+            let inserted = shaped_placeholders.len() * PLACEHOLDER_TEXT.len();
+            shaped_placeholders.push((position + inserted, *placeholder));
+            shaped_spans.push(placeholder_span(font_size));
+        }
+        if copied == span_start {
+            shaped_spans.push(span.clone());
+        } else if copied < span_end {
+            shaped_spans.push(sub_span(span, copied - span_start..span.text.len()));
+        }
+        span_start = span_end;
+    }
+    for (placeholder, &font_size) in placeholders {
+        let inserted = shaped_placeholders.len() * PLACEHOLDER_TEXT.len();
+        shaped_placeholders.push((span_start + inserted, *placeholder));
+        shaped_spans.push(placeholder_span(font_size));
+    }
+    (shaped_spans, shaped_placeholders)
+}
+
+/// The part of a span within a range of its text.
+fn sub_span(span: &TextSpan, range: Range<usize>) -> TextSpan {
+    TextSpan {
+        text: match &span.text {
+            Cow::Borrowed(text) => Cow::Borrowed(&text[range]),
+            Cow::Owned(text) => Cow::Owned(text[range].to_owned()),
+        },
+        style: span.style.clone(),
+    }
+}
+
+/// The text positions that start a line, except for an empty line at the very end of the text.
+fn isolate_positions(spans: &[TextSpan]) -> Vec<usize> {
+    let text_len = spans.iter().map(|span| span.text.len()).sum::<usize>();
+    let mut positions = vec![0];
+    let mut span_start = 0;
+    for span in spans {
+        positions.extend(
+            span.text
+                .match_indices('\n')
+                .map(|(index, _)| span_start + index + 1)
+                .filter(|&position| position < text_len),
+        );
+        span_start += span.text.len();
+    }
+    positions
+}
+
+/// The position in the shaped text of a text position, after the isolate that may be inserted there.
+fn layout_position(isolates: &[usize], position: usize) -> usize {
+    position + ISOLATE_LEN * isolates.partition_point(|&isolate| isolate <= position)
+}
+
+/// A cluster of a laid out line.
+struct VisualCluster {
+    text_range: Range<usize>,
+    /// The advance from the left of the line.
+    left: f32,
+    advance: f32,
+    is_rtl: bool,
+    is_space: bool,
+}
+
+impl VisualCluster {
+    fn center(&self) -> f32 {
+        self.left + self.advance * 0.5
+    }
+
+    /// The text position on the left side of the cluster.
+    fn left_position(&self) -> usize {
+        if self.is_rtl {
+            self.text_range.end
+        } else {
+            self.text_range.start
+        }
+    }
+
+    /// The text position on the right side of the cluster.
+    fn right_position(&self) -> usize {
+        if self.is_rtl {
+            self.text_range.start
+        } else {
+            self.text_range.end
+        }
+    }
+
+    /// The side of the cluster where a caret in front of it goes.
+    fn leading_edge(&self) -> f32 {
+        if self.is_rtl {
+            self.left + self.advance
+        } else {
+            self.left
+        }
+    }
+
+    /// The side of the cluster where a caret after it goes.
+    fn trailing_edge(&self) -> f32 {
+        if self.is_rtl {
+            self.left
+        } else {
+            self.left + self.advance
+        }
+    }
 }
 
 impl Paragraph {
-    pub fn new(spans: &[TextSpan], default_style: &TextStyle) -> Self {
-        let mut layout_ctx = parley::LayoutContext::new();
-        let mut font_ctx = GLOBAL_FONT_CONTEXT.lock();
+    pub fn new(
+        spans: &[TextSpan],
+        default_style: &TextStyle,
+        text_direction: TextDirection,
+    ) -> Self {
+        Self::new_shaped(spans, Vec::new(), default_style, text_direction)
+    }
+
+    /// A paragraph that leaves room for inline boxes among its text.
+    ///
+    /// Placeholders are sorted by their positions, which must lie on character boundaries.
+    /// Each of them is shaped as a few characters of its own,
+    /// which the text positions of the paragraph count as part of its text.
+    pub fn new_with_placeholders(
+        spans: &[TextSpan],
+        placeholders: &[Placeholder],
+        default_style: &TextStyle,
+        text_direction: TextDirection,
+    ) -> Self {
+        let shaped = |font_sizes: &[f32]| {
+            let (shaped_spans, shaped_placeholders) =
+                spans_with_placeholders(spans, placeholders, font_sizes);
+            Self::new_shaped(
+                &shaped_spans,
+                shaped_placeholders,
+                default_style,
+                text_direction,
+            )
+        };
+        let [small, large] = PROBE_FONT_SIZES
+            .map(|font_size| shaped(&vec![font_size; placeholders.len()]).placeholder_advances());
+        let font_sizes = placeholders
+            .iter()
+            .zip(std::iter::zip(small, large))
+            .map(|(placeholder, (small, large))| {
+                let advance_per_size =
+                    (large - small) / (PROBE_FONT_SIZES[1] - PROBE_FONT_SIZES[0]);
+                let spacing = small - advance_per_size * PROBE_FONT_SIZES[0];
+                if advance_per_size > 0.0 {
+                    ((placeholder.width - spacing) / advance_per_size)
+                        .max(MIN_PLACEHOLDER_FONT_SIZE)
+                } else {
+                    MIN_PLACEHOLDER_FONT_SIZE
+                }
+            })
+            .collect::<Vec<_>>();
+        shaped(&font_sizes)
+    }
+
+    fn new_shaped(
+        spans: &[TextSpan],
+        placeholders: Vec<(usize, Placeholder)>,
+        default_style: &TextStyle,
+        text_direction: TextDirection,
+    ) -> Self {
+        let isolates = match text_direction {
+            TextDirection::Ltr => Vec::new(),
+            TextDirection::Rtl => isolate_positions(spans),
+        };
+        let layout = shape_cached(spans, default_style, text_direction, || {
+            with_text_contexts(|font_ctx, layout_ctx| {
+                Self::shape(spans, default_style, &isolates, font_ctx, layout_ctx)
+            })
+        });
+        let ends_with_break = spans
+            .iter()
+            .rev()
+            .find(|span| !span.text.is_empty())
+            .is_some_and(|span| span.text.ends_with('\n'));
+        // layout.break_all_lines(width, alignment);
+        Self {
+            layout,
+            ends_with_break,
+            style: default_style.clone(),
+            text_direction,
+            isolates,
+            line_starts: Vec::new(),
+            placeholders,
+            line_growth: Vec::new(),
+            truncation: None,
+        }
+    }
 
-        let text = match spans {
+    fn shape(
+        spans: &[TextSpan],
+        default_style: &TextStyle,
+        isolates: &[usize],
+        font_ctx: &mut parley::FontContext,
+        layout_ctx: &mut parley::LayoutContext<ParleyBrush>,
+    ) -> parley::Layout<ParleyBrush> {
+        let mut text = match spans {
             [] => Cow::Borrowed(""),
             [span] => Cow::Borrowed(span.text.as_ref()),
             spans => {
@@ -33,21 +351,29 @@ impl Paragraph {
                 Cow::Owned(text)
             }
         };
-        let mut layout_builder = layout_ctx.ranged_builder(&mut font_ctx, &text, 1.0);
+        if !isolates.is_empty() {
+            let mut isolated = String::with_capacity(text.len() + isolates.len() * ISOLATE_LEN);
+            let mut copied = 0;
+            for &position in isolates {
+                isolated.push_str(&text[copied..position]);
+                isolated.push(RIGHT_TO_LEFT_ISOLATE);
+                copied = position;
+            }
+            isolated.push_str(&text[copied..]);
+            text = Cow::Owned(isolated);
+        }
+        let aliases = font_aliases();
+        let mut layout_builder = layout_ctx.ranged_builder(font_ctx, &text, 1.0);
 
         layout_builder.push_default(&StyleProperty::Brush(ParleyBrush(peniko::Brush::Solid(
             default_style.color,
         ))));
-        if default_style.font_family_fallback.is_empty() {
-            layout_builder.push_default(&StyleProperty::FontStack(FontStack::Single(
-                default_style.font_family.clone(),
-            )));
-        } else {
-            let font_stack = std::iter::once(default_style.font_family)
-                .chain(default_style.font_family_fallback.iter().cloned())
-                .collect::<Vec<_>>();
-            layout_builder.push_default(&StyleProperty::FontStack(FontStack::List(&font_stack)));
-        };
+        let font_stack = resolve_font_stack(
+            &aliases,
+            default_style.font_family,
+            &default_style.font_family_fallback,
+        );
+        layout_builder.push_default(&StyleProperty::FontStack(FontStack::List(&font_stack)));
         layout_builder.push_default(&StyleProperty::FontSize(default_style.font_size));
         layout_builder.push_default(&StyleProperty::FontStyle(default_style.font_style));
         layout_builder.push_default(&StyleProperty::FontWeight(default_style.font_weight));
@@ -85,8 +411,8 @@ impl Paragraph {
             let len = span.text.len();
             if let Some(style) = &span.style {
                 let range = Range {
-                    start: position,
-                    end: position + len,
+                    start: layout_position(isolates, position),
+                    end: layout_position(isolates, position + len),
                 };
                 if let Some(color) = style.color {
                     layout_builder.push(
@@ -100,20 +426,12 @@ impl Paragraph {
                         .font_family_fallback
                         .as_ref()
                         .unwrap_or(&default_style.font_family_fallback);
-                    if font_family_fallback.is_empty() {
-                        layout_builder.push(
-                            &StyleProperty::FontStack(FontStack::Single(font_family)),
-                            range.clone(),
-                        );
-                    } else {
-                        let font_stack = std::iter::once(default_style.font_family)
-                            .chain(font_family_fallback.iter().cloned())
-                            .collect::<Vec<_>>();
-                        layout_builder.push(
-                            &StyleProperty::FontStack(FontStack::List(&font_stack)),
-                            range.clone(),
-                        );
-                    };
+                    let font_stack =
+                        resolve_font_stack(&aliases, font_family, font_family_fallback);
+                    layout_builder.push(
+                        &StyleProperty::FontStack(FontStack::List(&font_stack)),
+                        range.clone(),
+                    );
                 }
                 if let Some(font_size) = style.font_size {
                     layout_builder.push(&StyleProperty::FontSize(font_size), range.clone());
@@ -175,47 +493,36 @@ impl Paragraph {
             }
             position += len;
         }
-        let layout = layout_builder.build();
-        drop(font_ctx);
-        // layout.break_all_lines(width, alignment);
-        Self { layout }
+        layout_builder.build()
     }
 
     pub fn layout(&mut self, width: Option<f32>, alignment: TextAlign) -> Vec<SingleLineSize> {
+        self.truncation = None;
         self.layout.break_all_lines(width, alignment);
-        self.layout
-            .lines()
-            .map(|line| {
-                let metrics = line.metrics();
-                SingleLineSize {
-                    advance: metrics.advance,
-                    above: metrics.ascent + metrics.leading * 0.5,
-                    below: metrics.descent + metrics.leading * 0.5,
-                }
-            })
-            .collect()
+        self.finish_layout()
     }
 
     pub fn layout_single_line(&mut self) -> Vec<SingleLineSize> {
+        self.truncation = None;
         self.layout.break_all_lines(None, TextAlign::Start);
-        self.layout
-            .lines()
-            .map(|line| {
-                let metrics = line.metrics();
-                SingleLineSize {
-                    advance: metrics.advance,
-                    above: metrics.ascent + metrics.leading * 0.5,
-                    below: metrics.descent + metrics.leading * 0.5,
-                }
-            })
-            .collect()
+        self.finish_layout()
     }
 
+    /// Lay out the paragraph, keeping at most `max_lines` lines.
+    ///
+    /// If the text needs more lines, the last visible line ends as the [`TextOverFlow`] of the paragraph's style asks.
+    /// [`TextOverFlow::Visible`] can not paint outside the lines it is given, so it behaves like [`TextOverFlow::Clip`].
     pub fn layout_multi_line(
         &mut self,
         constraints: &MultiLineConstraints,
         alignment: TextAlign,
+        max_lines: Option<usize>,
     ) -> Vec<SingleLineSize> {
+        debug_assert!(
+            max_lines != Some(0),
+            "A paragraph should keep at least one line"
+        );
+        self.truncation = None;
         let mut break_lines = self.layout.break_lines();
         if constraints.first_line_existing_advance != 0.0 {
             break_lines.break_next(
@@ -238,22 +545,593 @@ impl Paragraph {
                 )
             }
         }
+        if let Some(max_lines) = max_lines.filter(|&max_lines| self.layout.len() > max_lines) {
+            let available_width = if max_lines == 1 {
+                constraints.max_width - constraints.first_line_existing_advance
+            } else {
+                constraints.max_width
+            };
+            self.truncation = Some(self.truncate(max_lines, available_width));
+        }
+        self.finish_layout()
+    }
+
+    /// Record where each visible line starts, and return their sizes.
+    ///
+    /// The lines of a right-to-left paragraph are aligned to the right of its widest line.
+    fn finish_layout(&mut self) -> Vec<SingleLineSize> {
+        let line_growth = if self.placeholders.is_empty() {
+            Vec::new()
+        } else {
+            self.layout
+                .lines()
+                .take(self.line_count())
+                .enumerate()
+                .map(|(index, line)| {
+                    self.placeholder_growth(line.metrics(), &self.visible_clusters(index, &line))
+                })
+                .collect()
+        };
+        self.line_growth = line_growth;
+        let sizes = self.line_sizes();
+        let width = sizes.iter().map(|size| size.advance).fold(0.0, f32::max);
+        self.line_starts = sizes
+            .iter()
+            .map(|size| match self.text_direction {
+                TextDirection::Ltr => 0.0,
+                TextDirection::Rtl => width - size.advance,
+            })
+            .collect();
+        sizes
+    }
+
+    fn truncate(&self, line_count: usize, available_width: f32) -> Truncation {
+        let line = self
+            .layout
+            .get(line_count - 1)
+            .expect("The paragraph should have more lines than the ones that remain visible");
+        let metrics = line.metrics();
+        let is_rtl = self.text_direction.is_rtl();
+        let (mut clusters, line_width) = self.visual_clusters(&line);
+        // Measured from the start of the line, which is its right for right-to-left text.
+        let content_advance = metrics.advance - metrics.trailing_whitespace;
+        let from_start = |advance: f32| {
+            if is_rtl {
+                line_width - advance
+            } else {
+                advance
+            }
+        };
+        let mut truncation = Truncation {
+            line_count,
+            visible: f32::NEG_INFINITY..f32::INFINITY,
+            text_end: self.text_position(line.text_range().end),
+            ending: TruncationEnding::Clip,
+        };
+        match self.style.overflow {
+            TextOverFlow::Clip | TextOverFlow::Visible => {}
+            TextOverFlow::Fade => {
+                truncation.ending = TruncationEnding::Fade {
+                    start: from_start((content_advance - self.ellipsis().advance()).max(0.0)),
+                    end: from_start(content_advance),
+                }
+            }
+            TextOverFlow::Ellipsis => {
+                let ellipsis = self.ellipsis();
+                let max_visible = available_width - ellipsis.advance();
+                // Keep the clusters that fit before the ellipsis, without trailing whitespace.
+                let mut advance = 0.0;
+                let mut visible_advance = 0.0;
+                truncation.text_end = self.text_position(line.text_range().start);
+                if is_rtl {
+                    clusters.reverse();
+                }
+                for cluster in clusters {
+                    advance += cluster.advance;
+                    if advance > max_visible {
+                        break;
+                    }
+                    if !cluster.is_space && cluster.advance > 0.0 {
+                        visible_advance = advance;
+                        truncation.text_end = cluster.text_range.end;
+                    }
+                }
+                truncation.visible = if is_rtl {
+                    line_width - visible_advance..line_width
+                } else {
+                    0.0..visible_advance
+                };
+                truncation.ending = TruncationEnding::Ellipsis(Box::new(ellipsis));
+            }
+        }
+        truncation
+    }
+
+    /// An ellipsis in the paragraph's style, laid out as a single line.
+    fn ellipsis(&self) -> Paragraph {
+        let mut ellipsis = Paragraph::new(
+            &[TextSpan {
+                text: Cow::Borrowed(ELLIPSIS),
+                style: None,
+            }],
+            &self.style,
+            self.text_direction,
+        );
+        ellipsis.layout_single_line();
+        ellipsis
+    }
+
+    fn advance(&self) -> f32 {
         self.layout
             .lines()
-            .map(|line| {
+            .map(|line| line.metrics().advance)
+            .fold(0.0, f32::max)
+    }
+
+    /// Sizes of the visible lines.
+    fn line_sizes(&self) -> Vec<SingleLineSize> {
+        let mut sizes = self
+            .layout
+            .lines()
+            .take(self.line_count())
+            .enumerate()
+            .map(|(index, line)| {
                 let metrics = line.metrics();
+                let (grow_above, grow_below) = self.line_growth(index);
                 SingleLineSize {
                     advance: metrics.advance,
-                    above: metrics.ascent + metrics.leading * 0.5,
-                    below: metrics.descent + metrics.leading * 0.5,
+                    above: metrics.ascent + metrics.leading * 0.5 + grow_above,
+                    below: metrics.descent + metrics.leading * 0.5 + grow_below,
                 }
             })
+            .collect::<Vec<_>>();
+        if let Some(Truncation {
+            visible,
+            ending: TruncationEnding::Ellipsis(ellipsis),
+            ..
+        }) = &self.truncation
+        {
+            let last_size = sizes.last_mut().expect("A truncated paragraph has lines");
+            if let Some(ellipsis_size) = ellipsis.line_sizes().first() {
+                last_size.advance = visible.end - visible.start + ellipsis_size.advance;
+                last_size.above = last_size.above.max(ellipsis_size.above);
+                last_size.below = last_size.below.max(ellipsis_size.below);
+            }
+        }
+        sizes
+    }
+
+    fn line_growth(&self, index: usize) -> (f32, f32) {
+        self.line_growth.get(index).copied().unwrap_or_default()
+    }
+
+    /// How far the placeholders among the clusters of a line extend it above and below its text.
+    fn placeholder_growth(&self, metrics: &LineMetrics, clusters: &[VisualCluster]) -> (f32, f32) {
+        let (above, below) = clusters
+            .iter()
+            .filter_map(|cluster| self.placeholder_at(cluster.text_range.start))
+            .fold((0.0f32, 0.0f32), |(above, below), (_, placeholder)| {
+                (above.max(placeholder.above), below.max(placeholder.below))
+            });
+        (
+            (above - metrics.ascent - metrics.leading * 0.5).max(0.0),
+            (below - metrics.descent - metrics.leading * 0.5).max(0.0),
+        )
+    }
+
+    /// The index and the placeholder whose text holds a text position.
+    fn placeholder_at(&self, position: usize) -> Option<(usize, &Placeholder)> {
+        let index = self
+            .placeholders
+            .partition_point(|(start, _)| *start <= position)
+            .checked_sub(1)?;
+        let (start, placeholder) = &self.placeholders[index];
+        (position < start + PLACEHOLDER_TEXT.len()).then_some((index, placeholder))
+    }
+
+    /// The advance of the text of each placeholder as shaped, in the order they were given.
+    fn placeholder_advances(&self) -> Vec<f32> {
+        let mut advances = vec![0.0; self.placeholders.len()];
+        for run in self.layout.runs() {
+            for cluster in run.clusters() {
+                let position = self.text_position(cluster.text_range().start);
+                if let Some((index, _)) = self.placeholder_at(position) {
+                    advances[index] += cluster.advance();
+                }
+            }
+        }
+        advances
+    }
+
+    /// Whether a run of the shaped text holds placeholders, which leave room for boxes instead of drawing anything.
+    pub(crate) fn is_placeholder_run(&self, layout_range: Range<usize>) -> bool {
+        let range = self.text_position(layout_range.start)..self.text_position(layout_range.end);
+        let index = self
+            .placeholders
+            .partition_point(|(position, _)| *position < range.start);
+        self.placeholders
+            .get(index)
+            .is_some_and(|(position, _)| range.contains(position))
+    }
+
+    /// Where each placeholder was placed by the last layout, in the order they were given:
+    /// the index of its visible line, and its left from the left of the paragraph.
+    ///
+    /// Placeholders on hidden lines, or hidden by the truncation of the last visible line, have no position.
+    pub fn placeholder_positions(&self) -> Vec<Option<(usize, f32)>> {
+        let mut positions = vec![None; self.placeholders.len()];
+        for (index, line) in self.layout.lines().take(self.line_count()).enumerate() {
+            let line_left = self.line_left(index);
+            for cluster in self.visible_clusters(index, &line) {
+                if let Some((placeholder_index, _)) = self.placeholder_at(cluster.text_range.start)
+                {
+                    positions[placeholder_index].get_or_insert((index, line_left + cluster.left));
+                }
+            }
+        }
+        positions
+    }
+
+    /// The top, baseline and bottom of a visible line, in the coordinates of the laid out paragraph,
+    /// where the lines are stacked from the top.
+    fn line_bounds(&self, index: usize, metrics: &LineMetrics) -> (f32, f32, f32) {
+        let shift = self
+            .line_growth
+            .iter()
+            .take(index)
+            .map(|(above, below)| above + below)
+            .sum::<f32>();
+        let (grow_above, grow_below) = self.line_growth(index);
+        let baseline = metrics.baseline + shift + grow_above;
+        (
+            baseline - metrics.ascent - metrics.leading * 0.5 - grow_above,
+            baseline,
+            baseline + metrics.descent + metrics.leading * 0.5 + grow_below,
+        )
+    }
+
+    /// Where the left of a visible line is drawn, from the left of the paragraph.
+    ///
+    /// The hidden start of a truncated right-to-left line is replaced by its ellipsis,
+    /// so the visible part of the line is drawn right after the ellipsis.
+    pub(crate) fn line_left(&self, index: usize) -> f32 {
+        let line_start = self.line_starts.get(index).copied().unwrap_or(0.0);
+        match &self.truncation {
+            Some(Truncation {
+                line_count,
+                visible,
+                ending: TruncationEnding::Ellipsis(ellipsis),
+                ..
+            }) if index + 1 == *line_count && self.text_direction.is_rtl() => {
+                line_start + ellipsis.advance() - visible.start
+            }
+            _ => line_start,
+        }
+    }
+
+    /// Where the ellipsis of a truncated paragraph is drawn, from the left of the paragraph.
+    pub(crate) fn ellipsis_left(&self) -> Option<(f32, &Paragraph)> {
+        let Some(Truncation {
+            line_count,
+            visible,
+            ending: TruncationEnding::Ellipsis(ellipsis),
+            ..
+        }) = &self.truncation
+        else {
+            return None;
+        };
+        let left = match self.text_direction {
+            TextDirection::Ltr => self.line_left(line_count - 1) + visible.end,
+            TextDirection::Rtl => self.line_starts.get(line_count - 1).copied().unwrap_or(0.0),
+        };
+        Some((left, ellipsis))
+    }
+
+    /// The number of lines that are visible after the last layout.
+    pub fn line_count(&self) -> usize {
+        self.truncation
+            .as_ref()
+            .map_or(self.layout.len(), |truncation| truncation.line_count)
+    }
+
+    /// Whether the last layout needed more lines than it was allowed to keep.
+    pub fn is_truncated(&self) -> bool {
+        self.truncation.is_some()
+    }
+
+    pub fn text_direction(&self) -> TextDirection {
+        self.text_direction
+    }
+
+    /// The width of the widest piece of text between two line break opportunities.
+    pub fn min_intrinsic_width(&self) -> f32 {
+        self.unbreakable_advances().into_iter().fold(0.0, f32::max)
+    }
+
+    /// The width of the widest line, if the paragraph only breaks at explicit line breaks.
+    pub fn max_intrinsic_width(&self) -> f32 {
+        self.measure_lines(None)
+            .into_iter()
+            .map(|(advance, _height)| advance)
+            .fold(0.0, f32::max)
+    }
+
+    /// The height of the paragraph if it were laid out with `width`, keeping at most `max_lines` lines.
+    pub fn intrinsic_height(&self, width: f32, max_lines: Option<usize>) -> f32 {
+        self.measure_lines(Some(width))
+            .into_iter()
+            .take(max_lines.unwrap_or(usize::MAX))
+            .map(|(_advance, height)| height)
+            .sum()
+    }
+
+    /// The advance of the text before the first line break opportunity.
+    pub fn advance_before_first_break(&self) -> f32 {
+        self.unbreakable_advances()[0]
+    }
+
+    /// Whether the text ends with an explicit line break.
+    pub fn ends_with_break(&self) -> bool {
+        self.ends_with_break
+    }
+
+    /// Advances of the pieces of text between line break opportunities, without trailing whitespace.
+    ///
+    /// The line breaker can not be used here, since it breaks within words when they overflow.
+    fn unbreakable_advances(&self) -> Vec<f32> {
+        let mut advances = Vec::new();
+        let mut advance = 0.0;
+        let mut trailing_whitespace = 0.0;
+        let mut is_first_cluster = true;
+        for run in self.layout.runs() {
+            for cluster in run.clusters() {
+                let is_break = cluster.is_soft_line_break() || cluster.is_hard_line_break();
+                if is_break && !is_first_cluster {
+                    advances.push(advance);
+                    advance = 0.0;
+                    trailing_whitespace = 0.0;
+                }
+                is_first_cluster = false;
+                if cluster.is_space_or_nbsp() {
+                    trailing_whitespace += cluster.advance();
+                } else {
+                    advance += trailing_whitespace + cluster.advance();
+                    trailing_whitespace = 0.0;
+                }
+            }
+        }
+        advances.push(advance);
+        advances
+    }
+
+    /// Advance and height of each line when broken at `max_width`, without trailing whitespace.
+    ///
+    /// Intrinsics can be queried after the paragraph has been laid out, so the lines are broken on a copy.
+    fn measure_lines(&self, max_width: Option<f32>) -> Vec<(f32, f32)> {
+        let mut layout = self.layout.clone();
+        layout.break_all_lines(max_width, TextAlign::Start);
+        layout
+            .lines()
+            .map(|line| {
+                let metrics = line.metrics();
+                let (grow_above, grow_below) = if self.placeholders.is_empty() {
+                    (0.0, 0.0)
+                } else {
+                    self.placeholder_growth(metrics, &self.visual_clusters(&line).0)
+                };
+                (
+                    metrics.advance - metrics.trailing_whitespace,
+                    metrics.size() + grow_above + grow_below,
+                )
+            })
             .collect()
     }
-}
 
-lazy_static::lazy_static! {
-    // For some reason, parley uses a RefCell in its FontContext
-    // We have no other choice but to go for a mutex
-    static ref GLOBAL_FONT_CONTEXT: Asc<SyncMutex<parley::FontContext>> = Asc::new(SyncMutex::new(parley::FontContext::default()));
+    /// The text position of a position in the shaped text. Positions within an isolate map to the start of its line.
+    fn text_position(&self, layout_position: usize) -> usize {
+        let mut shift = 0;
+        for &isolate in &self.isolates {
+            if layout_position < isolate + shift {
+                break;
+            }
+            if layout_position < isolate + shift + ISOLATE_LEN {
+                return isolate;
+            }
+            shift += ISOLATE_LEN;
+        }
+        layout_position - shift
+    }
+
+    fn is_isolate(&self, layout_position: usize) -> bool {
+        self.isolates
+            .iter()
+            .enumerate()
+            .any(|(index, &isolate)| isolate + index * ISOLATE_LEN == layout_position)
+    }
+
+    /// The clusters of a line from left to right, and the width of the line.
+    ///
+    /// The isolates and hard line breaks of a right-to-left paragraph are left out.
+    /// Line breaks are resolved as left-to-right, which would put them on the wrong side of the line.
+    fn visual_clusters(
+        &self,
+        line: &parley::layout::Line<'_, ParleyBrush>,
+    ) -> (Vec<VisualCluster>, f32) {
+        let mut clusters = Vec::new();
+        let mut left = 0.0;
+        for run in line.runs() {
+            for cluster in run.visual_clusters() {
+                let advance = cluster.advance();
+                let text_range = cluster.text_range();
+                let is_left_out = !self.isolates.is_empty()
+                    && (cluster.is_hard_line_break() || self.is_isolate(text_range.start));
+                if !is_left_out {
+                    clusters.push(VisualCluster {
+                        text_range: self.text_position(text_range.start)
+                            ..self.text_position(text_range.end),
+                        left,
+                        advance,
+                        is_rtl: run.is_rtl(),
+                        is_space: cluster.is_space_or_nbsp(),
+                    });
+                }
+                left += advance;
+            }
+        }
+        (clusters, left)
+    }
+
+    /// The clusters of a visible line from left to right, without the ones hidden by truncation.
+    fn visible_clusters(
+        &self,
+        index: usize,
+        line: &parley::layout::Line<'_, ParleyBrush>,
+    ) -> Vec<VisualCluster> {
+        let (mut clusters, _) = self.visual_clusters(line);
+        if let Some(truncation) = &self.truncation {
+            if index + 1 == truncation.line_count {
+                clusters.retain(|cluster| truncation.visible.contains(&cluster.center()));
+            }
+        }
+        clusters
+    }
+
+    /// The text position closest to `x` among the clusters of a line, or `None` if the line has no clusters.
+    fn hit_test_clusters(clusters: &[VisualCluster], x: f32) -> Option<usize> {
+        let first = clusters.first()?;
+        if x < first.left {
+            return Some(first.left_position());
+        }
+        for cluster in clusters {
+            if x < cluster.left + cluster.advance {
+                return Some(if x <= cluster.center() {
+                    cluster.left_position()
+                } else {
+                    cluster.right_position()
+                });
+            }
+        }
+        clusters.last().map(VisualCluster::right_position)
+    }
+
+    /// The text position closest to `position`, given in the coordinates of the laid out paragraph,
+    /// where the first line starts at the top and the following lines are stacked below it.
+    ///
+    /// Positions above the first line or below the last line are clamped to the start or the end of the text.
+    /// Hidden text of a truncated paragraph can not be hit, including the text covered by an ellipsis.
+    pub fn hit_test_text_position(&self, position: Point2d) -> usize {
+        let line_count = self.line_count();
+        let Some((index, line)) =
+            self.layout
+                .lines()
+                .take(line_count)
+                .enumerate()
+                .find(|(index, line)| {
+                    let (_, _, bottom) = self.line_bounds(*index, line.metrics());
+                    position.y <= bottom || index + 1 == line_count
+                })
+        else {
+            return 0;
+        };
+        let (_, _, bottom) = self.line_bounds(index, line.metrics());
+        let x = if position.y < 0.0 {
+            match self.text_direction {
+                TextDirection::Ltr => f32::NEG_INFINITY,
+                TextDirection::Rtl => f32::INFINITY,
+            }
+        } else if position.y > bottom {
+            if let Some(truncation) = &self.truncation {
+                return truncation.text_end;
+            }
+            match self.text_direction {
+                TextDirection::Ltr => f32::INFINITY,
+                TextDirection::Rtl => f32::NEG_INFINITY,
+            }
+        } else {
+            position.x - self.line_left(index)
+        };
+        let text_position = Self::hit_test_clusters(&self.visible_clusters(index, &line), x)
+            .unwrap_or_else(|| self.text_position(line.text_range().start));
+        match &self.truncation {
+            Some(truncation) => text_position.min(truncation.text_end),
+            None => text_position,
+        }
+    }
+
+    /// The caret in front of the text position, as a zero-width rect spanning the height of its line.
+    ///
+    /// In front of a character is on its left for left-to-right text, and on its right for right-to-left text.
+    /// A position at the end of the text places the caret after the last character.
+    /// Positions in the hidden text of a truncated paragraph place the caret where the visible text ends.
+    /// Returns `None` if the paragraph has not been laid out or has no lines.
+    pub fn caret_rect(&self, position: usize) -> Option<Rect> {
+        let position = match &self.truncation {
+            Some(truncation) => position.min(truncation.text_end),
+            None => position,
+        };
+        let line_count = self.line_count();
+        let layout_position = layout_position(&self.isolates, position);
+        let (index, line) = self
+            .layout
+            .lines()
+            .take(line_count)
+            .enumerate()
+            .find(|(_, line)| line.text_range().contains(&layout_position))
+            .or_else(|| {
+                let index = line_count.checked_sub(1)?;
+                Some((index, self.layout.get(index)?))
+            })?;
+        let clusters = self.visible_clusters(index, &line);
+        let x = match clusters
+            .iter()
+            .find(|cluster| cluster.text_range.contains(&position))
+        {
+            Some(cluster) => cluster.leading_edge(),
+            None => {
+                // After the last character of the line, or of the visible text without its trailing whitespace
+                let is_truncated = self
+                    .truncation
+                    .as_ref()
+                    .is_some_and(|truncation| index + 1 == truncation.line_count);
+                clusters
+                    .iter()
+                    .filter(|cluster| !(is_truncated && cluster.is_space))
+                    .max_by_key(|cluster| cluster.text_range.end)
+                    .map_or(0.0, VisualCluster::trailing_edge)
+            }
+        };
+        let x = self.line_left(index) + x;
+        let (top, _, bottom) = self.line_bounds(index, line.metrics());
+        Some(Rect::new_ltrb(x, top, x, bottom))
+    }
+
+    /// One rect per line, covering the clusters that intersect the text range.
+    pub fn selection_rects(&self, range: Range<usize>) -> Vec<Rect> {
+        let mut rects = Vec::new();
+        if range.is_empty() {
+            return rects;
+        }
+        let line_count = self.line_count();
+        for (index, line) in self.layout.lines().take(line_count).enumerate() {
+            let extent = self
+                .visible_clusters(index, &line)
+                .iter()
+                .filter(|cluster| {
+                    cluster.text_range.start < range.end && range.start < cluster.text_range.end
+                })
+                .fold(None, |extent, cluster| {
+                    let right = cluster.left + cluster.advance;
+                    Some(match extent {
+                        None => (cluster.left, right),
+                        Some((l, r)) => (cluster.left.min(l), right.max(r)),
+                    })
+                });
+            if let Some((l, r)) = extent {
+                let line_left = self.line_left(index);
+                let (top, _, bottom) = self.line_bounds(index, line.metrics());
+                rects.push(Rect::new_ltrb(line_left + l, top, line_left + r, bottom));
+            }
+        }
+        rects
+    }
 }
@@ -1,8 +1,17 @@
+use std::ops::Range;
+
 use vello::kurbo::Stroke;
 
 use crate::{
-    Affine2d, Affine2dPaintContextExt, Fill, IntoKurbo, Line, Paragraph, Point2d, SingleLineOffset,
-    StrokePainter, VelloPaintContext,
+    Affine2d, Affine2dPaintContextExt, BlendMode, Brush, Color, Fill, FillPainter, Gradient,
+    IntoKurbo, Line, Painter, Paragraph, ParleyBrush, Point2d, Rect, SingleLineOffset,
+    StrokePainter, TruncationEnding, VelloPaintContext, BLEND_SRC_OVER,
+};
+
+/// Erases the backdrop where the source is opaque.
+const BLEND_DEST_OUT: BlendMode = BlendMode {
+    mix: peniko::Mix::Normal,
+    compose: peniko::Compose::DestOut,
 };
 
 // Adapted from masonry::text_helper.rs
@@ -16,139 +25,251 @@ pub(crate) fn render_text<'a>(
     // scratch_scene.reset();
     debug_assert_eq!(
         offsets.len(),
-        paragraph.layout.len(),
+        paragraph.line_count(),
         "A paragraph should receive the same number of offsets as its line count"
     );
-    for (line, offset) in std::iter::zip(paragraph.layout.lines(), offsets.iter()) {
+    let line_count = offsets.len();
+    for (index, (line, offset)) in
+        std::iter::zip(paragraph.layout.lines(), offsets.iter()).enumerate()
+    {
         let metrics = line.metrics();
-        let baseline_correction = offset.baseline - metrics.baseline;
-        for glyph_run in line.glyph_runs() {
-            // let mut x = glyph_run.offset();
-            // let y = glyph_run.baseline(); // The glyph baseline is generated from line baseline as the glyph is generated from the iterator
-            let mut x = offset.advance;
-            let y = glyph_run.baseline() + baseline_correction;
-            let run = glyph_run.run();
-            let font = run.font();
-            let font_size = run.font_size();
-            let synthesis = run.synthesis();
-            let glyph_xform = synthesis
-                .skew()
-                .map(|angle| vello::kurbo::Affine::skew(angle.to_radians().tan() as f64, 0.0));
-            let style = glyph_run.style();
-            let coords = run
-                .normalized_coords()
-                .iter()
-                .map(|coord| vello::skrifa::instance::NormalizedCoord::from_bits(*coord))
-                .collect::<Vec<_>>();
-            // let text_brush = match &style.brush {
-            //     TextBrush::Normal(text_brush) => text_brush,
-            //     TextBrush::Highlight { text, fill } => {
-            //         encoding.fill(
-            //             Fill::EvenOdd,
-            //             transform,
-            //             fill,
-            //             None,
-            //             &Rect::from_origin_size(
-            //                 (
-            //                     glyph_run.offset() as f64,
-            //                     // The y coordinate is on the baseline. We want to draw from the top of the line
-            //                     // (Note that we are in a y-down coordinate system)
-            //                     (y - metrics.ascent - metrics.leading) as f64,
-            //                 ),
-            //                 (glyph_run.advance() as f64, metrics.size() as f64),
-            //             ),
-            //         );
-
-            //         text
-            //     }
-            // };
-            vello::DrawGlyphs::new(&mut paint_ctx.curr_fragment_encoding, &font)
-                .brush(&style.brush.0)
-                .transform(transform.into_kurbo())
-                .glyph_transform(glyph_xform)
-                .font_size(font_size)
-                .normalized_coords(&coords)
-                .draw(
-                    Fill::NonZero,
-                    glyph_run.glyphs().map(|glyph| {
-                        let gx = x + glyph.x;
-                        let gy = y - glyph.y;
-                        x += glyph.advance;
-                        vello::glyph::Glyph {
-                            id: glyph.id as _,
-                            x: gx,
-                            y: gy,
-                        }
-                    }),
-                );
-            if let Some(underline) = &style.underline {
-                let underline_brush = &underline.brush;
-                let run_metrics = glyph_run.run().metrics();
-                let offset = match underline.offset {
-                    Some(offset) => offset,
-                    None => run_metrics.underline_offset,
-                };
-                let width = match underline.size {
-                    Some(size) => size,
-                    None => run_metrics.underline_size,
+        let line_offset = SingleLineOffset {
+            advance: offset.advance + paragraph.line_left(index),
+            baseline: offset.baseline,
+        };
+        // Only the last visible line of a truncated paragraph is cut or faded.
+        let truncation = paragraph
+            .truncation
+            .as_ref()
+            .filter(|_| index + 1 == line_count);
+        let visible = truncation.map_or(f32::NEG_INFINITY..f32::INFINITY, |truncation| {
+            truncation.visible.clone()
+        });
+        match truncation.map(|truncation| &truncation.ending) {
+            Some(&TruncationEnding::Fade { start, end }) => {
+                // Paint the line into a layer, then erase it with a gradient towards its end.
+                // Glyphs may overshoot the line metrics, so the layer leaves a margin around them.
+                let margin = metrics.size();
+                let top = offset.baseline - metrics.ascent - margin;
+                let bottom = offset.baseline + metrics.descent + margin;
+                let fade_rect = || {
+                    Rect::new_ltrb(
+                        line_offset.advance + start.min(end),
+                        top,
+                        line_offset.advance + start.max(end),
+                        bottom,
+                    )
                 };
-                // The `offset` is the distance from the baseline to the *top* of the underline
-                // so we move the line down by half the width
-                // Remember that we are using a y-down coordinate system
-                let y = glyph_run.baseline() - offset + width / 2.;
+                let bounds = Rect::new_ltrb(
+                    line_offset.advance - margin,
+                    top,
+                    line_offset.advance + metrics.advance.max(start).max(end) + margin,
+                    bottom,
+                );
+                paint_ctx.clip_rect(bounds, BLEND_SRC_OVER, 1.0, |paint_ctx| {
+                    render_line(
+                        paint_ctx,
+                        transform,
+                        paragraph,
+                        &line,
+                        &line_offset,
+                        &visible,
+                    );
+                    paint_ctx.clip_rect(fade_rect(), BLEND_DEST_OUT, 1.0, |paint_ctx| {
+                        paint_ctx.draw_rect(
+                            fade_rect(),
+                            Painter::Fill(FillPainter {
+                                fill: Fill::NonZero,
+                                brush: Brush::Gradient(
+                                    Gradient::new_linear(
+                                        ((line_offset.advance + start) as f64, 0.0),
+                                        ((line_offset.advance + end) as f64, 0.0),
+                                    )
+                                    .with_stops([Color::TRANSPARENT, Color::BLACK]),
+                                ),
+                                transform: None,
+                            }),
+                        )
+                    })
+                })
+            }
+            _ => render_line(
+                paint_ctx,
+                transform,
+                paragraph,
+                &line,
+                &line_offset,
+                &visible,
+            ),
+        }
+        if let Some((ellipsis_left, ellipsis)) =
+            paragraph.ellipsis_left().filter(|_| truncation.is_some())
+        {
+            render_text(
+                paint_ctx,
+                transform,
+                ellipsis,
+                &[SingleLineOffset {
+                    advance: offset.advance + ellipsis_left,
+                    baseline: offset.baseline,
+                }],
+            );
+        }
+    }
+}
 
-                paint_ctx.stroke_line(
-                    Line {
-                        p0: Point2d {
-                            x: glyph_run.offset(),
-                            y,
-                        },
-                        p1: Point2d {
-                            x: glyph_run.offset() + glyph_run.advance(),
-                            y,
-                        },
+/// Paint the glyphs and decorations of a line that lie within `visible`, measured from the left of the line.
+///
+/// Placeholders are left empty, for the boxes that are painted over them.
+fn render_line(
+    paint_ctx: &mut VelloPaintContext<'_>,
+    transform: Affine2d,
+    paragraph: &Paragraph,
+    line: &parley::layout::Line<'_, ParleyBrush>,
+    offset: &SingleLineOffset,
+    visible: &Range<f32>,
+) {
+    let metrics = line.metrics();
+    let baseline_correction = offset.baseline - metrics.baseline;
+    for glyph_run in line.glyph_runs() {
+        // let y = glyph_run.baseline(); // The glyph baseline is generated from line baseline as the glyph is generated from the iterator
+        // Advance of the run from the left of the line
+        let run_start = glyph_run.offset() - metrics.offset;
+        let run_end = run_start + glyph_run.advance();
+        if run_start >= visible.end
+            || run_end <= visible.start
+            || paragraph.is_placeholder_run(glyph_run.run().text_range())
+        {
+            continue;
+        }
+        let mut x = offset.advance + run_start;
+        let y = glyph_run.baseline() + baseline_correction;
+        let run = glyph_run.run();
+        let font = run.font();
+        let font_size = run.font_size();
+        let synthesis = run.synthesis();
+        let glyph_xform = synthesis
+            .skew()
+            .map(|angle| vello::kurbo::Affine::skew(angle.to_radians().tan() as f64, 0.0));
+        let style = glyph_run.style();
+        let coords = run
+            .normalized_coords()
+            .iter()
+            .map(|coord| vello::skrifa::instance::NormalizedCoord::from_bits(*coord))
+            .collect::<Vec<_>>();
+        // let text_brush = match &style.brush {
+        //     TextBrush::Normal(text_brush) => text_brush,
+        //     TextBrush::Highlight { text, fill } => {
+        //         encoding.fill(
+        //             Fill::EvenOdd,
+        //             transform,
+        //             fill,
+        //             None,
+        //             &Rect::from_origin_size(
+        //                 (
+        //                     glyph_run.offset() as f64,
+        //                     // The y coordinate is on the baseline. We want to draw from the top of the line
+        //                     // (Note that we are in a y-down coordinate system)
+        //                     (y - metrics.ascent - metrics.leading) as f64,
+        //                 ),
+        //                 (glyph_run.advance() as f64, metrics.size() as f64),
+        //             ),
+        //         );
+
+        //         text
+        //     }
+        // };
+        vello::DrawGlyphs::new(&mut paint_ctx.curr_fragment_encoding, font)
+            .brush(&style.brush.0)
+            .transform(transform.into_kurbo())
+            .glyph_transform(glyph_xform)
+            .font_size(font_size)
+            .normalized_coords(&coords)
+            .draw(
+                Fill::NonZero,
+                glyph_run.glyphs().filter_map(|glyph| {
+                    // Glyphs are cut at cluster boundaries, so their midpoints are safely on one side
+                    let is_visible = visible.contains(&(x - offset.advance + glyph.advance * 0.5));
+                    let gx = x + glyph.x;
+                    let gy = y - glyph.y;
+                    x += glyph.advance;
+                    is_visible.then_some(vello::glyph::Glyph {
+                        id: glyph.id as _,
+                        x: gx,
+                        y: gy,
+                    })
+                }),
+            );
+        // Decorations span the visible part of the run
+        let decoration_start = offset.advance + run_start.max(visible.start);
+        let decoration_end = offset.advance + run_end.min(visible.end);
+        if let Some(underline) = &style.underline {
+            let underline_brush = &underline.brush;
+            let run_metrics = glyph_run.run().metrics();
+            let offset = match underline.offset {
+                Some(offset) => offset,
+                None => run_metrics.underline_offset,
+            };
+            let width = match underline.size {
+                Some(size) => size,
+                None => run_metrics.underline_size,
+            };
+            // The `offset` is the distance from the baseline to the *top* of the underline
+            // so we move the line down by half the width
+            // Remember that we are using a y-down coordinate system
+            let y = glyph_run.baseline() + baseline_correction - offset + width / 2.;
+
+            paint_ctx.stroke_line(
+                Line {
+                    p0: Point2d {
+                        x: decoration_start,
+                        y,
                     },
-                    StrokePainter {
-                        stroke: Stroke::new(width.into()),
-                        brush: underline_brush.0.clone(),
-                        transform: None,
+                    p1: Point2d {
+                        x: decoration_end,
+                        y,
                     },
-                );
-            }
-            if let Some(strikethrough) = &style.strikethrough {
-                let strikethrough_brush = &strikethrough.brush;
-                let run_metrics = glyph_run.run().metrics();
-                let offset = match strikethrough.offset {
-                    Some(offset) => offset,
-                    None => run_metrics.strikethrough_offset,
-                };
-                let width = match strikethrough.size {
-                    Some(size) => size,
-                    None => run_metrics.strikethrough_size,
-                };
-                // The `offset` is the distance from the baseline to the *top* of the strikethrough
-                // so we move the line down by half the width
-                // Remember that we are using a y-down coordinate system
-                let y = glyph_run.baseline() - offset + width / 2.;
+                },
+                StrokePainter {
+                    stroke: Stroke::new(width.into()),
+                    brush: underline_brush.0.clone(),
+                    transform: None,
+                },
+            );
+        }
+        if let Some(strikethrough) = &style.strikethrough {
+            let strikethrough_brush = &strikethrough.brush;
+            let run_metrics = glyph_run.run().metrics();
+            let offset = match strikethrough.offset {
+                Some(offset) => offset,
+                None => run_metrics.strikethrough_offset,
+            };
+            let width = match strikethrough.size {
+                Some(size) => size,
+                None => run_metrics.strikethrough_size,
+            };
+            // The `offset` is the distance from the baseline to the *top* of the strikethrough
+            // so we move the line down by half the width
+            // Remember that we are using a y-down coordinate system
+            let y = glyph_run.baseline() + baseline_correction - offset + width / 2.;
 
-                paint_ctx.stroke_line(
-                    Line {
-                        p0: Point2d {
-                            x: glyph_run.offset(),
-                            y,
-                        },
-                        p1: Point2d {
-                            x: glyph_run.offset() + glyph_run.advance(),
-                            y,
-                        },
+            paint_ctx.stroke_line(
+                Line {
+                    p0: Point2d {
+                        x: decoration_start,
+                        y,
                     },
-                    StrokePainter {
-                        stroke: Stroke::new(width.into()),
-                        brush: strikethrough_brush.0.clone(),
-                        transform: None,
+                    p1: Point2d {
+                        x: decoration_end,
+                        y,
                     },
-                );
-            }
+                },
+                StrokePainter {
+                    stroke: Stroke::new(width.into()),
+                    brush: strikethrough_brush.0.clone(),
+                    transform: None,
+                },
+            );
         }
     }
 }
@@ -0,0 +1,181 @@
+use std::{
+    borrow::Cow,
+    cell::RefCell,
+    hash::{BuildHasher, Hash, Hasher},
+};
+
+use hashbrown::{HashMap, HashSet};
+
+use crate::{font_generation, LocalTextStyle, ParleyBrush, TextDirection, TextSpan, TextStyle};
+
+/// How many shaped layouts each generation of a thread's cache holds.
+const GENERATION_CAPACITY: usize = 256;
+
+/// How many distinct texts a thread remembers having seen before they are forgotten all at once.
+const SEEN_CAPACITY: usize = 4 * GENERATION_CAPACITY;
+
+thread_local! {
+    static SHAPING_CACHE: RefCell<ShapingCache> = RefCell::new(ShapingCache::default());
+}
+
+/// Get the shaped layout of the spans from the cache of the current thread, or shape and cache it with `shape`.
+///
+/// The returned layout has not been broken into lines yet.
+pub(crate) fn shape_cached(
+    spans: &[TextSpan],
+    style: &TextStyle,
+    text_direction: TextDirection,
+    shape: impl FnOnce() -> parley::Layout<ParleyBrush>,
+) -> parley::Layout<ParleyBrush> {
+    if let Some(layout) =
+        SHAPING_CACHE.with_borrow_mut(|cache| cache.get(spans, style, text_direction))
+    {
+        return layout;
+    }
+    let layout = shape();
+    SHAPING_CACHE.with_borrow_mut(|cache| {
+        if cache.seen_before(spans) {
+            cache.insert(spans, style, text_direction, layout.clone())
+        }
+    });
+    layout
+}
+
+/// A per-thread cache of shaped layouts, keyed by text, style and direction.
+///
+/// Entries are kept in two generations. Once the current generation is full, it replaces the previous one,
+/// and entries that are hit in the previous generation are promoted back to the current one.
+/// This approximates LRU eviction without bookkeeping on every hit.
+///
+/// A layout is only cached the second time its text is shaped.
+/// Most text is only ever shaped once, and keeping all of it around costs more than it saves.
+///
+/// The cache is cleared whenever fonts are registered, since they may change how text is shaped.
+#[derive(Default)]
+struct ShapingCache {
+    current: HashMap<ShapingKey, parley::Layout<ParleyBrush>>,
+    previous: HashMap<ShapingKey, parley::Layout<ParleyBrush>>,
+    seen: HashSet<u64>,
+    font_generation: u64,
+}
+
+struct ShapingKey {
+    spans: Vec<(Cow<'static, str>, Option<LocalTextStyle>)>,
+    style: TextStyle,
+    text_direction: TextDirection,
+}
+
+impl ShapingKey {
+    fn matches(
+        &self,
+        spans: &[TextSpan],
+        style: &TextStyle,
+        text_direction: TextDirection,
+    ) -> bool {
+        self.spans.len() == spans.len()
+            && self
+                .spans
+                .iter()
+                .zip(spans)
+                .all(|((text, span_style), span)| *text == span.text && *span_style == span.style)
+            && self.style == *style
+            && self.text_direction == text_direction
+    }
+}
+
+/// Only the text is hashed, since styles hold floats. Keys are told apart by [`ShapingKey::matches`].
+fn hash_spans(hash_builder: &impl BuildHasher, spans: &[TextSpan]) -> u64 {
+    let mut hasher = hash_builder.build_hasher();
+    for span in spans {
+        span.text.hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+impl PartialEq for ShapingKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.spans == other.spans
+            && self.style == other.style
+            && self.text_direction == other.text_direction
+    }
+}
+
+impl Eq for ShapingKey {}
+
+impl Hash for ShapingKey {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        for (text, _) in &self.spans {
+            text.hash(state);
+        }
+    }
+}
+
+impl ShapingCache {
+    fn get(
+        &mut self,
+        spans: &[TextSpan],
+        style: &TextStyle,
+        text_direction: TextDirection,
+    ) -> Option<parley::Layout<ParleyBrush>> {
+        let font_generation = font_generation();
+        if self.font_generation != font_generation {
+            self.current.clear();
+            self.previous.clear();
+            self.font_generation = font_generation;
+        }
+        let hash = hash_spans(self.current.hasher(), spans);
+        if let Some((_, layout)) = self
+            .current
+            .raw_entry()
+            .from_hash(hash, |key| key.matches(spans, style, text_direction))
+        {
+            return Some(layout.clone());
+        }
+        let hash = hash_spans(self.previous.hasher(), spans);
+        let hashbrown::hash_map::RawEntryMut::Occupied(entry) = self
+            .previous
+            .raw_entry_mut()
+            .from_hash(hash, |key| key.matches(spans, style, text_direction))
+        else {
+            return None;
+        };
+        let (key, layout) = entry.remove_entry();
+        let result = layout.clone();
+        self.insert_key(key, layout);
+        Some(result)
+    }
+
+    /// Record that the text of the spans is being shaped, and return whether it has been shaped before.
+    fn seen_before(&mut self, spans: &[TextSpan]) -> bool {
+        let hash = hash_spans(self.seen.hasher(), spans);
+        if self.seen.len() >= SEEN_CAPACITY {
+            self.seen.clear();
+        }
+        !self.seen.insert(hash)
+    }
+
+    fn insert(
+        &mut self,
+        spans: &[TextSpan],
+        style: &TextStyle,
+        text_direction: TextDirection,
+        layout: parley::Layout<ParleyBrush>,
+    ) {
+        let key = ShapingKey {
+            spans: spans
+                .iter()
+                .map(|span| (span.text.clone(), span.style.clone()))
+                .collect(),
+            style: style.clone(),
+            text_direction,
+        };
+        self.insert_key(key, layout)
+    }
+
+    fn insert_key(&mut self, key: ShapingKey, layout: parley::Layout<ParleyBrush>) {
+        if self.current.len() >= GENERATION_CAPACITY {
+            self.previous = std::mem::take(&mut self.current);
+        }
+        self.current.insert(key, layout);
+    }
+}
@@ -1,6 +1,6 @@
 use epgi_core::foundation::{Intrinsics, Protocol};
 
-use crate::{Affine2dCanvas, BoxConstraints, Point2d, Rect};
+use crate::{Affine2dCanvas, BoxConstraints, Point2d, Rect, TextBaseline};
 
 #[derive(Clone, Copy, Debug)]
 pub struct SingleLineProtocol;
@@ -14,6 +14,8 @@ impl Protocol for SingleLineProtocol {
 
     type Intrinsics = SingleLineIntrinsics;
 
+    type Baseline = ();
+
     type Canvas = Affine2dCanvas;
 
     fn position_in_shape(
@@ -51,6 +53,18 @@ pub struct SingleLineSize {
     // pub trailing_whitespace: f32,
 }
 
+impl SingleLineSize {
+    /// The distance from the top of the line to the requested baseline.
+    ///
+    /// The ideographic baseline is taken as the bottom of the line.
+    pub fn distance_to_baseline(&self, baseline: TextBaseline) -> f32 {
+        match baseline {
+            TextBaseline::Alphabetic => self.above,
+            TextBaseline::Ideographic => self.above + self.below,
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug)]
 pub struct SingleLineOffset {
     pub advance: f32,
@@ -165,6 +165,22 @@ pub struct ParleyBrush(pub vello::peniko::Brush);
 
 pub type TextAlign = parley::layout::Alignment;
 
+/// The direction text flows in, which also decides where "start" and "end" are for directional layouts.
+///
+/// Defaults to [`TextDirection::Ltr`], which is also what directional widgets use outside of any `Directionality`.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Hash, Default)]
+pub enum TextDirection {
+    #[default]
+    Ltr,
+    Rtl,
+}
+
+impl TextDirection {
+    pub fn is_rtl(self) -> bool {
+        self == TextDirection::Rtl
+    }
+}
+
 impl Default for ParleyBrush {
     fn default() -> ParleyBrush {
         ParleyBrush(vello::peniko::Brush::Solid(vello::peniko::Color::rgb8(
@@ -1,7 +1,6 @@
 mod canvas;
 mod encoding;
 mod into_kurbo;
-#[cfg(not(feature = "parallel_paint"))]
 mod paint_ctx;
 #[cfg(feature = "parallel_paint")]
 mod paint_ctx_parallel;
@@ -10,7 +9,6 @@ mod root;
 pub use canvas::*;
 pub use encoding::*;
 pub use into_kurbo::*;
-#[cfg(not(feature = "parallel_paint"))]
 pub use paint_ctx::*;
 #[cfg(feature = "parallel_paint")]
 pub use paint_ctx_parallel::*;
@@ -8,6 +8,9 @@ use crate::{
     VelloPaintScanner,
 };
 
+#[cfg(feature = "parallel_paint")]
+use crate::paint_render_objects_parallel;
+
 pub type Point2d = BoxOffset;
 
 #[derive(Clone)]
@@ -50,24 +53,10 @@ impl Canvas for Affine2dCanvas {
     fn paint_render_objects<P: LayerProtocol<Canvas = Self>>(
         render_objects: impl IntoIterator<Item = ArcChildRenderObject<P>>,
     ) -> PaintResults<Self> {
-        let mut paint_results = PaintResults {
-            children: Default::default(),
-            orphan_layers: Default::default(),
-        };
-        let mut paint_ctx = VelloPaintContext {
-            curr_config: LayerCompositionConfig {
-                transform: Affine2d::IDENTITY,
-            },
-            curr_fragment_encoding: Affine2dEncoding::new(),
-            results: &mut paint_results,
-        };
-        for render_object in render_objects {
-            render_object.paint(&P::zero_offset(), &mut paint_ctx);
-        }
-        // Save the recordings on the tail
-        let new_child = ChildLayerOrFragment::Fragment(paint_ctx.curr_fragment_encoding);
-        paint_results.children.push(new_child);
-        paint_results
+        #[cfg(feature = "parallel_paint")]
+        return paint_render_objects_parallel(render_objects);
+        #[cfg(not(feature = "parallel_paint"))]
+        return paint_render_objects_serial(render_objects);
     }
 
     fn new_encoding() -> Self::Encoding {
@@ -106,3 +95,27 @@ impl Transform<Affine2dCanvas> for Affine2d {
         ]))
     }
 }
+
+/// Paint the render objects one after another into a single [PaintResults].
+pub(crate) fn paint_render_objects_serial<P: LayerProtocol<Canvas = Affine2dCanvas>>(
+    render_objects: impl IntoIterator<Item = ArcChildRenderObject<P>>,
+) -> PaintResults<Affine2dCanvas> {
+    let mut paint_results = PaintResults {
+        children: Default::default(),
+        orphan_layers: Default::default(),
+    };
+    let mut paint_ctx = VelloPaintContext {
+        curr_config: LayerCompositionConfig {
+            transform: Affine2d::IDENTITY,
+        },
+        curr_fragment_encoding: Affine2dEncoding::new(),
+        results: &mut paint_results,
+    };
+    for render_object in render_objects {
+        render_object.paint(&P::zero_offset(), &mut paint_ctx);
+    }
+    // Save the recordings on the tail
+    let new_child = ChildLayerOrFragment::Fragment(paint_ctx.curr_fragment_encoding);
+    paint_results.children.push(new_child);
+    paint_results
+}
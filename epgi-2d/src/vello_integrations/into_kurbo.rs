@@ -58,6 +58,33 @@ impl IntoKurbo for RRect {
     }
 }
 
+/// The area between a rounded rect and the same rounded rect shrunk by `padding` on every side.
+///
+/// The inner outline winds opposite to the outer one, so the hole survives both fill rules.
+pub fn rrect_padding_into_kurbo(rrect: RRect, padding: f32) -> kurbo::BezPath {
+    use kurbo::Shape;
+    const TOLERANCE: f64 = 0.1;
+    let outer = rrect.into_kurbo();
+    let mut path = kurbo::BezPath::from_iter(outer.path_elements(TOLERANCE));
+    let padding = padding as f64;
+    let inner_rect = outer.rect().inset(-padding);
+    if inner_rect.width() <= 0.0 || inner_rect.height() <= 0.0 {
+        return path;
+    }
+    let radii = outer.radii();
+    let inner = kurbo::RoundedRect::from_rect(
+        inner_rect,
+        kurbo::RoundedRectRadii {
+            top_left: (radii.top_left - padding).max(0.0),
+            top_right: (radii.top_right - padding).max(0.0),
+            bottom_right: (radii.bottom_right - padding).max(0.0),
+            bottom_left: (radii.bottom_left - padding).max(0.0),
+        },
+    );
+    path.extend(kurbo::BezPath::from_iter(inner.path_elements(TOLERANCE)).reverse_subpaths());
+    path
+}
+
 pub const KURBO_RECT_ALL: kurbo::Rect = kurbo::Rect {
     x0: f64::MIN,
     y0: f64::MIN,
@@ -9,8 +9,8 @@ use epgi_core::{
 use peniko::BrushRef;
 
 use crate::{
-    render_text, Affine2d, Affine2dCanvas, Affine2dCanvasShape, Affine2dEncoding,
-    Affine2dPaintCommand, BlendMode, Fill, IntoKurbo, Painter,
+    render_text, rrect_padding_into_kurbo, Affine2d, Affine2dCanvas, Affine2dCanvasShape,
+    Affine2dEncoding, Affine2dPaintCommand, BlendMode, Fill, Image, IntoKurbo, Painter, Rect,
 };
 
 pub use peniko::kurbo::{Cap as StrokeCap, Dashes, Join, Stroke};
@@ -26,6 +26,7 @@ pub struct VelloPaintContext<'a> {
 }
 
 // We do not need to scan in a serial painter impl. Therefore a unit type with empty methods.
+#[cfg(not(feature = "parallel_paint"))]
 pub struct VelloPaintScanner;
 
 pub const BLEND_SRC_OVER: BlendMode = BlendMode {
@@ -65,6 +66,14 @@ impl<'a> PaintContext for VelloPaintContext<'a> {
             DrawParagraph { paragraph, offset } => {
                 render_text(self, self.curr_config.transform, &paragraph, offset)
             }
+            // Vello has no choice of image sampling yet.
+            DrawImage {
+                image,
+                src,
+                dst,
+                quality: _,
+                opacity,
+            } => self.draw_image(image, src, dst, opacity, self.curr_config.transform),
         }
     }
 
@@ -120,6 +129,7 @@ impl<'a> PaintContext for VelloPaintContext<'a> {
     }
 }
 
+#[cfg(not(feature = "parallel_paint"))]
 #[allow(unused_variables)]
 impl PaintContext for VelloPaintScanner {
     type Canvas = Affine2dCanvas;
@@ -138,7 +148,6 @@ impl PaintContext for VelloPaintScanner {
         layer: ArcChildLayerRenderObject<Self::Canvas>,
         offset: &P::Offset,
     ) {
-        todo!()
     }
 
     fn add_orphan_layer<P: LayerProtocol<Canvas = Affine2dCanvas>>(
@@ -147,15 +156,14 @@ impl PaintContext for VelloPaintScanner {
         adopter_key: Asc<dyn Key>,
         offset: &P::Offset,
     ) {
-        todo!()
     }
 
     fn with_transform(
         &mut self,
-        offset: <Self::Canvas as Canvas>::Transform,
+        transform: <Self::Canvas as Canvas>::Transform,
         op: impl FnOnce(&mut Self),
     ) {
-        todo!()
+        op(self)
     }
 }
 
@@ -214,6 +222,52 @@ impl<'a> VelloPaintContext<'a> {
         }
     }
 
+    /// Draws the `src` rect of an image onto the `dst` rect, by filling `dst` with the image as a brush.
+    ///
+    /// Vello ignores the alpha of image brushes, so a translucent image is drawn inside a layer instead.
+    fn draw_image(
+        &mut self,
+        image: &Image,
+        src: Rect,
+        dst: Rect,
+        opacity: f32,
+        transform: Affine2d,
+    ) {
+        if opacity <= 0.0 || src.width() <= 0.0 || src.height() <= 0.0 {
+            return;
+        }
+        let scale_x = dst.width() / src.width();
+        let scale_y = dst.height() / src.height();
+        let brush_transform = Affine2d([
+            scale_x,
+            0.0,
+            0.0,
+            scale_y,
+            dst.l - src.l * scale_x,
+            dst.t - src.t * scale_y,
+        ]);
+        let translucent = opacity < 1.0;
+        if translucent {
+            let bounds = Rect::new_ltrb(dst.l, dst.t, dst.r, dst.b);
+            self.push_layer(
+                BLEND_SRC_OVER,
+                opacity,
+                transform,
+                Affine2dCanvasShape::Rect(bounds),
+            );
+        }
+        self.fill(
+            Fill::NonZero,
+            transform,
+            image,
+            Some(brush_transform),
+            Affine2dCanvasShape::Rect(dst),
+        );
+        if translucent {
+            self.pop_layer();
+        }
+    }
+
     /// Strokes a shape using the specified style and brush.
     fn stroke<'b>(
         &mut self,
@@ -247,7 +301,9 @@ impl<'a> VelloPaintContext<'a> {
         match shape {
             Rect(x) => encoding.encode_shape(&x.into_kurbo(), is_fill),
             RRect(x) => encoding.encode_shape(&x.into_kurbo(), is_fill),
-            RRectPadding { rrect, padding } => todo!(),
+            RRectPadding { rrect, padding } => {
+                encoding.encode_shape(&rrect_padding_into_kurbo(rrect, padding), is_fill)
+            }
             Circle(x) => encoding.encode_shape(&x.into_kurbo(), is_fill),
             Ellipse(x) => encoding.encode_shape(&x.into_kurbo(), is_fill),
             RingSector(x) => encoding.encode_shape(&x.into_kurbo(), is_fill),
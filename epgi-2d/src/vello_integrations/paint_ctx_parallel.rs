@@ -1,5 +1,325 @@
-// /// This module is supposed to implement parallel painting
-// ///
-// /// Currently it is not implemented due to the tight coupling with internal vello encoding formats
-// ///
-// /// Prefer waiting until vello stabilizes its encoding
+use epgi_core::{
+    foundation::{
+        Asc, Canvas, Key, LayerProtocol, PaintContext, Protocol, ThreadPoolExt, Transform,
+    },
+    scheduler::get_current_scheduler,
+    tree::{
+        ArcAnyLayerRenderObject, ArcChildLayerRenderObject, ArcChildRenderObject,
+        ChildLayerOrFragment, LayerCompositionConfig, PaintResults, RecordedOrphanLayer,
+    },
+};
+
+use crate::{
+    paint_render_objects_serial, Affine2d, Affine2dCanvas, Affine2dEncoding, VelloPaintContext,
+};
+
+/// How many levels of single-child render objects to scan through while looking for siblings to split.
+const MAX_SCAN_DEPTH: usize = 32;
+
+/// The paint scanner of the parallel painter.
+///
+/// It encodes the commands of the scanned render object as usual, but defers the painting of its children,
+/// so that they can be split across threads afterwards.
+/// Children painted inside an open clip are painted in place instead,
+/// since the begin and the end of a clip can not be split into separate encodings.
+pub struct VelloPaintScanner {
+    curr_config: LayerCompositionConfig<Affine2dCanvas>,
+    curr_fragment_encoding: Affine2dEncoding,
+    items: Vec<ScannedItem>,
+}
+
+enum ScannedItem {
+    Painted(Box<ChildLayerOrFragment<Affine2dCanvas>>),
+    OrphanLayer(RecordedOrphanLayer<Affine2dCanvas>),
+    Deferred(Vec<DeferredChild>),
+}
+
+struct DeferredChild {
+    config: LayerCompositionConfig<Affine2dCanvas>,
+    child: Box<dyn DeferredPaint>,
+}
+
+trait DeferredPaint: Send {
+    fn paint(&self, paint_ctx: &mut VelloPaintContext<'_>);
+
+    fn scan(&self, scanner: &mut VelloPaintScanner);
+}
+
+struct DeferredChildPaint<P: Protocol> {
+    child: ArcChildRenderObject<P>,
+    offset: P::Offset,
+}
+
+impl<P: Protocol<Canvas = Affine2dCanvas>> DeferredPaint for DeferredChildPaint<P> {
+    fn paint(&self, paint_ctx: &mut VelloPaintContext<'_>) {
+        self.child.clone().paint(&self.offset, paint_ctx)
+    }
+
+    fn scan(&self, scanner: &mut VelloPaintScanner) {
+        self.child.clone().paint_scan(&self.offset, scanner)
+    }
+}
+
+impl DeferredChild {
+    fn new<P: Protocol<Canvas = Affine2dCanvas>>(
+        child: ArcChildRenderObject<P>,
+        offset: P::Offset,
+        config: LayerCompositionConfig<Affine2dCanvas>,
+    ) -> Self {
+        Self {
+            config,
+            child: Box::new(DeferredChildPaint { child, offset }),
+        }
+    }
+
+    fn scan(self) -> Vec<ScannedItem> {
+        let mut scanner = VelloPaintScanner {
+            curr_config: self.config,
+            curr_fragment_encoding: Affine2dEncoding::new(),
+            items: Vec::new(),
+        };
+        self.child.scan(&mut scanner);
+        scanner.flush_fragment();
+        scanner.items
+    }
+}
+
+impl VelloPaintScanner {
+    fn flush_fragment(&mut self) {
+        if !self.curr_fragment_encoding.is_empty() {
+            let encoding = std::mem::take(&mut self.curr_fragment_encoding);
+            self.items.push(ScannedItem::Painted(Box::new(
+                ChildLayerOrFragment::Fragment(encoding),
+            )));
+        }
+    }
+
+    /// Run a paint operation in place, as if it were issued on a serial [VelloPaintContext].
+    fn paint_in_place(&mut self, op: impl FnOnce(&mut VelloPaintContext<'_>)) {
+        let mut results = PaintResults {
+            children: Vec::new(),
+            orphan_layers: Vec::new(),
+        };
+        let mut paint_ctx = VelloPaintContext {
+            curr_config: self.curr_config.clone(),
+            curr_fragment_encoding: std::mem::take(&mut self.curr_fragment_encoding),
+            results: &mut results,
+        };
+        op(&mut paint_ctx);
+        self.curr_fragment_encoding = paint_ctx.curr_fragment_encoding;
+        self.items.extend(
+            results
+                .children
+                .into_iter()
+                .map(|child| ScannedItem::Painted(Box::new(child))),
+        );
+        self.items.extend(
+            results
+                .orphan_layers
+                .into_iter()
+                .map(ScannedItem::OrphanLayer),
+        );
+    }
+}
+
+impl PaintContext for VelloPaintScanner {
+    type Canvas = Affine2dCanvas;
+
+    fn add_command(&mut self, command: <Self::Canvas as Canvas>::PaintCommand<'_>) {
+        self.paint_in_place(|paint_ctx| paint_ctx.add_command(command))
+    }
+
+    fn paint<P: Protocol<Canvas = Self::Canvas>>(
+        &mut self,
+        child: &ArcChildRenderObject<P>,
+        offset: &P::Offset,
+    ) {
+        if self.curr_fragment_encoding.n_open_clips > 0 {
+            self.paint_in_place(|paint_ctx| paint_ctx.paint(child, offset));
+            return;
+        }
+        self.flush_fragment();
+        let child = DeferredChild::new(child.clone(), offset.clone(), self.curr_config.clone());
+        if let Some(ScannedItem::Deferred(children)) = self.items.last_mut() {
+            children.push(child);
+        } else {
+            self.items.push(ScannedItem::Deferred(vec![child]));
+        }
+    }
+
+    fn add_layer<P: LayerProtocol<Canvas = Affine2dCanvas>>(
+        &mut self,
+        layer: ArcChildLayerRenderObject<Self::Canvas>,
+        offset: &P::Offset,
+    ) {
+        self.paint_in_place(|paint_ctx| paint_ctx.add_layer::<P>(layer, offset))
+    }
+
+    fn add_orphan_layer<P: LayerProtocol<Canvas = Affine2dCanvas>>(
+        &mut self,
+        layer: ArcAnyLayerRenderObject,
+        adopter_key: Asc<dyn Key>,
+        offset: &P::Offset,
+    ) {
+        self.paint_in_place(|paint_ctx| paint_ctx.add_orphan_layer::<P>(layer, adopter_key, offset))
+    }
+
+    fn with_transform(
+        &mut self,
+        transform: <Self::Canvas as Canvas>::Transform,
+        op: impl FnOnce(&mut Self),
+    ) {
+        let new_transform = Transform::mul(&self.curr_config.transform, &transform);
+        let old_transform = std::mem::replace(&mut self.curr_config.transform, new_transform);
+        op(self);
+        self.curr_config.transform = old_transform;
+    }
+}
+
+/// Paint render objects across the sync threadpool.
+///
+/// Single-child render objects are scanned through until the tree fans out into sibling subtrees.
+/// The siblings are then split into contiguous chunks, one per thread, and each chunk is painted serially.
+/// Finally the chunk results are stitched back together with the scanned encodings in paint order.
+pub(crate) fn paint_render_objects_parallel<P: LayerProtocol<Canvas = Affine2dCanvas>>(
+    render_objects: impl IntoIterator<Item = ArcChildRenderObject<P>>,
+) -> PaintResults<Affine2dCanvas> {
+    let thread_pool = &get_current_scheduler().sync_threadpool;
+    let num_threads = thread_pool.current_num_threads();
+    if num_threads < 2 {
+        return paint_render_objects_serial(render_objects);
+    }
+
+    let root_config = LayerCompositionConfig {
+        transform: Affine2d::IDENTITY,
+    };
+    let mut items = vec![ScannedItem::Deferred(
+        render_objects
+            .into_iter()
+            .map(|render_object| {
+                DeferredChild::new(render_object, P::zero_offset(), root_config.clone())
+            })
+            .collect(),
+    )];
+    let mut num_deferred = count_deferred(&items);
+    for _ in 0..MAX_SCAN_DEPTH {
+        if num_deferred != 1 {
+            break;
+        }
+        items = items
+            .into_iter()
+            .flat_map(|item| match item {
+                ScannedItem::Deferred(children) => {
+                    children.into_iter().flat_map(DeferredChild::scan).collect()
+                }
+                item => vec![item],
+            })
+            .collect();
+        num_deferred = count_deferred(&items);
+    }
+
+    let chunk_size = num_deferred.div_ceil(num_threads).max(1);
+    let mut chunks = Vec::with_capacity(num_threads);
+    let mut chunk_counts = Vec::new();
+    for item in items.iter_mut() {
+        if let ScannedItem::Deferred(children) = item {
+            let num_chunks_before = chunks.len();
+            let mut children = std::mem::take(children).into_iter().peekable();
+            while children.peek().is_some() {
+                chunks.push(children.by_ref().take(chunk_size).collect::<Vec<_>>());
+            }
+            chunk_counts.push(chunks.len() - num_chunks_before);
+        }
+    }
+    let mut chunk_results = thread_pool
+        .par_map_collect_vec(chunks, paint_deferred_children)
+        .into_iter();
+    let mut chunk_counts = chunk_counts.into_iter();
+
+    let mut paint_results = PaintResults {
+        children: Vec::new(),
+        orphan_layers: Vec::new(),
+    };
+    for item in items {
+        match item {
+            ScannedItem::Painted(child) => push_child(&mut paint_results, *child),
+            ScannedItem::OrphanLayer(orphan_layer) => {
+                paint_results.orphan_layers.push(orphan_layer)
+            }
+            ScannedItem::Deferred(_) => {
+                let num_chunks = chunk_counts
+                    .next()
+                    .expect("Every deferred run should be chunked");
+                for chunk_result in chunk_results.by_ref().take(num_chunks) {
+                    for child in chunk_result.children {
+                        push_child(&mut paint_results, child);
+                    }
+                    paint_results
+                        .orphan_layers
+                        .extend(chunk_result.orphan_layers);
+                }
+            }
+        }
+    }
+    // Keep the serial convention of always ending with a fragment
+    if !matches!(
+        paint_results.children.last(),
+        Some(ChildLayerOrFragment::Fragment(_))
+    ) {
+        paint_results
+            .children
+            .push(ChildLayerOrFragment::Fragment(Affine2dEncoding::new()));
+    }
+    paint_results
+}
+
+fn count_deferred(items: &[ScannedItem]) -> usize {
+    items
+        .iter()
+        .map(|item| match item {
+            ScannedItem::Deferred(children) => children.len(),
+            _ => 0,
+        })
+        .sum()
+}
+
+fn paint_deferred_children(children: Vec<DeferredChild>) -> PaintResults<Affine2dCanvas> {
+    let mut paint_results = PaintResults {
+        children: Vec::new(),
+        orphan_layers: Vec::new(),
+    };
+    let mut paint_ctx = VelloPaintContext {
+        curr_config: LayerCompositionConfig {
+            transform: Affine2d::IDENTITY,
+        },
+        curr_fragment_encoding: Affine2dEncoding::new(),
+        results: &mut paint_results,
+    };
+    for DeferredChild { config, child } in children {
+        paint_ctx.curr_config = config;
+        child.paint(&mut paint_ctx);
+    }
+    let new_child = ChildLayerOrFragment::Fragment(paint_ctx.curr_fragment_encoding);
+    paint_results.children.push(new_child);
+    paint_results
+}
+
+/// Append a child to the paint results, merging fragments that end up next to each other.
+fn push_child(
+    paint_results: &mut PaintResults<Affine2dCanvas>,
+    child: ChildLayerOrFragment<Affine2dCanvas>,
+) {
+    match (paint_results.children.last_mut(), child) {
+        (
+            Some(ChildLayerOrFragment::Fragment(encoding)),
+            ChildLayerOrFragment::Fragment(fragment),
+        ) => {
+            if encoding.is_empty() {
+                *encoding = fragment;
+            } else if !fragment.is_empty() {
+                encoding.append(&fragment, &None);
+            }
+        }
+        (_, child) => paint_results.children.push(child),
+    }
+}
@@ -1,5 +1,5 @@
 use epgi_core::{
-    foundation::{Arc, Asc, BuildSuspendedError, Canvas, InlinableDwsizeVec, Provide},
+    foundation::{Arc, Asc, BuildError, Canvas, InlinableDwsizeVec, Provide},
     template::ImplByTemplate,
     tree::{
         BuildContext, ChildLayerProducingIterator, HitTestContext, LayerCompositionConfig,
@@ -54,7 +54,7 @@ impl BoxSingleChildElement for RootElement {
         widget: &Self::ArcWidget,
         _ctx: &mut BuildContext<'_>,
         _provider_values: InlinableDwsizeVec<Arc<dyn Provide>>,
-    ) -> Result<ArcBoxWidget, BuildSuspendedError> {
+    ) -> Result<ArcBoxWidget, BuildError> {
         Ok(widget.child.clone())
     }
 
@@ -22,12 +22,21 @@ pub use container::*;
 mod custom_paint;
 pub use custom_paint::*;
 
+mod directionality;
+pub use directionality::*;
+
 mod flex;
 pub use flex::*;
 
 mod flexible;
 pub use flexible::*;
 
+mod intrinsic_height;
+pub use intrinsic_height::*;
+
+mod intrinsic_width;
+pub use intrinsic_width::*;
+
 mod padding;
 pub use padding::*;
 
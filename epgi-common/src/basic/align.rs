@@ -1,23 +1,33 @@
 use epgi_2d::{
     ArcBoxRenderObject, ArcBoxWidget, BoxConstraints, BoxIntrinsics, BoxOffset, BoxProtocol,
     BoxSingleChildElement, BoxSingleChildElementTemplate, BoxSingleChildRenderElement, BoxSize,
-    ShiftedBoxRender, ShiftedBoxRenderTemplate,
+    ShiftedBoxRender, ShiftedBoxRenderTemplate, TextDirection,
 };
 use epgi_core::{
-    foundation::{set_if_changed, Arc, Asc, BuildSuspendedError, InlinableDwsizeVec, Provide},
+    foundation::{
+        set_if_changed, Arc, Asc, AscProvideExt, BuildError, InlinableDwsizeVec, Provide,
+        SmallVecExt, TypeKey,
+    },
     max,
+    nodes::{ConsumerElement, ConsumerWidget},
+    read_providers,
     template::ImplByTemplate,
-    tree::{BuildContext, RenderAction, Widget},
+    tree::{BuildContext, ElementBase, RenderAction, Widget},
 };
 use epgi_macro::Declarative;
 use typed_builder::TypedBuilder;
 
 use crate::Lerp;
 
+/// Aligns its child within itself.
+///
+/// An [`AlignmentDirectional`] is resolved against the ambient [`TextDirection`],
+/// which is only looked up when the alignment is directional.
 #[derive(Debug, Declarative, TypedBuilder)]
 #[builder(build_method(into=Asc<Align>))]
 pub struct Align {
-    pub alignment: Alignment,
+    #[builder(setter(into))]
+    pub alignment: AlignmentGeometry,
     #[builder(default)]
     pub width_factor: Option<f32>,
     #[builder(default)]
@@ -52,10 +62,148 @@ impl Alignment {
     }
 }
 
+/// An alignment whose horizontal component is measured from the start of the text direction.
+///
+/// `start` is -1.0 at the start edge and 1.0 at the end edge,
+/// i.e. the left and the right edge for left-to-right text, and the other way around for right-to-left text.
+#[derive(Lerp, PartialEq, Clone, Copy, Debug)]
+pub struct AlignmentDirectional {
+    pub start: f32,
+    pub y: f32,
+}
+
+impl AlignmentDirectional {
+    pub const TOP_START: Self = Self {
+        start: -1.0,
+        y: -1.0,
+    };
+    pub const TOP_CENTER: Self = Self {
+        start: 0.0,
+        y: -1.0,
+    };
+    pub const TOP_END: Self = Self {
+        start: 1.0,
+        y: -1.0,
+    };
+    pub const CENTER_START: Self = Self {
+        start: -1.0,
+        y: 0.0,
+    };
+    pub const CENTER: Self = Self { start: 0.0, y: 0.0 };
+    pub const CENTER_END: Self = Self { start: 1.0, y: 0.0 };
+    pub const BOTTOM_START: Self = Self {
+        start: -1.0,
+        y: 1.0,
+    };
+    pub const BOTTOM_CENTER: Self = Self { start: 0.0, y: 1.0 };
+    pub const BOTTOM_END: Self = Self { start: 1.0, y: 1.0 };
+
+    pub fn resolve(&self, text_direction: TextDirection) -> Alignment {
+        Alignment {
+            x: if text_direction.is_rtl() {
+                -self.start
+            } else {
+                self.start
+            },
+            y: self.y,
+        }
+    }
+}
+
+/// Either an [`Alignment`] or an [`AlignmentDirectional`].
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum AlignmentGeometry {
+    Absolute(Alignment),
+    Directional(AlignmentDirectional),
+}
+
+impl AlignmentGeometry {
+    pub fn is_directional(&self) -> bool {
+        matches!(self, AlignmentGeometry::Directional(_))
+    }
+
+    pub fn resolve(&self, text_direction: TextDirection) -> Alignment {
+        match self {
+            AlignmentGeometry::Absolute(alignment) => *alignment,
+            AlignmentGeometry::Directional(alignment) => alignment.resolve(text_direction),
+        }
+    }
+}
+
+impl From<Alignment> for AlignmentGeometry {
+    fn from(value: Alignment) -> Self {
+        AlignmentGeometry::Absolute(value)
+    }
+}
+
+impl From<AlignmentDirectional> for AlignmentGeometry {
+    fn from(value: AlignmentDirectional) -> Self {
+        AlignmentGeometry::Directional(value)
+    }
+}
+
 impl Widget for Align {
     type ParentProtocol = BoxProtocol;
     type ChildProtocol = BoxProtocol;
-    type Element = AlignElement;
+    type Element = ConsumerElement<BoxProtocol>;
+
+    fn into_arc_widget(self: Asc<Self>) -> <Self::Element as ElementBase>::ArcWidget {
+        self
+    }
+}
+
+lazy_static::lazy_static! {
+    static ref DIRECTIONAL_ALIGN_CONSUMED_TYPES: [TypeKey; 1] = [
+        TypeKey::of_or_default::<TextDirection>(),
+    ];
+}
+
+impl ConsumerWidget<BoxProtocol> for Align {
+    fn get_consumed_types(&self) -> &[TypeKey] {
+        if self.alignment.is_directional() {
+            DIRECTIONAL_ALIGN_CONSUMED_TYPES.as_ref()
+        } else {
+            &[]
+        }
+    }
+
+    fn build(
+        &self,
+        _ctx: &mut BuildContext,
+        provider_values: InlinableDwsizeVec<Arc<dyn Provide>>,
+    ) -> ArcBoxWidget {
+        let alignment = match self.alignment {
+            AlignmentGeometry::Absolute(alignment) => alignment,
+            AlignmentGeometry::Directional(alignment) => {
+                let text_direction = read_providers!(provider_values, TextDirection);
+                alignment.resolve(*text_direction)
+            }
+        };
+        Asc::new(RawAlign {
+            alignment,
+            width_factor: self.width_factor,
+            height_factor: self.height_factor,
+            child: self.child.clone(),
+        })
+    }
+}
+
+/// [`Align`] with an alignment that has already been resolved.
+#[derive(Debug, Declarative, TypedBuilder)]
+#[builder(build_method(into=Asc<RawAlign>))]
+pub struct RawAlign {
+    pub alignment: Alignment,
+    #[builder(default)]
+    pub width_factor: Option<f32>,
+    #[builder(default)]
+    pub height_factor: Option<f32>,
+    pub child: ArcBoxWidget,
+}
+
+impl Widget for RawAlign {
+    type ParentProtocol = BoxProtocol;
+    type ChildProtocol = BoxProtocol;
+    type Element = RawAlignElement;
 
     fn into_arc_widget(self: Asc<Self>) -> Asc<Self> {
         self
@@ -63,21 +211,21 @@ impl Widget for Align {
 }
 
 #[derive(Clone)]
-pub struct AlignElement {}
+pub struct RawAlignElement {}
 
-impl ImplByTemplate for AlignElement {
+impl ImplByTemplate for RawAlignElement {
     type Template = BoxSingleChildElementTemplate<true, false>;
 }
 
-impl BoxSingleChildElement for AlignElement {
-    type ArcWidget = Asc<Align>;
+impl BoxSingleChildElement for RawAlignElement {
+    type ArcWidget = Asc<RawAlign>;
 
     fn get_child_widget(
         _element: Option<&mut Self>,
         widget: &Self::ArcWidget,
         _ctx: &mut BuildContext<'_>,
         _provider_values: InlinableDwsizeVec<Arc<dyn Provide>>,
-    ) -> Result<ArcBoxWidget, BuildSuspendedError> {
+    ) -> Result<ArcBoxWidget, BuildError> {
         Ok(widget.child.clone())
     }
 
@@ -86,7 +234,7 @@ impl BoxSingleChildElement for AlignElement {
     }
 }
 
-impl BoxSingleChildRenderElement for AlignElement {
+impl BoxSingleChildRenderElement for RawAlignElement {
     type Render = RenderPositionedBox;
 
     fn create_render(&self, widget: &Self::ArcWidget) -> Self::Render {
@@ -168,11 +316,11 @@ impl ShiftedBoxRender for RenderPositionedBox {
         match intrinsics {
             BoxIntrinsics::MinWidth { res, .. } | BoxIntrinsics::MaxWidth { res, .. } => {
                 res.as_mut()
-                    .map(|res| *res *= self.width_factor.unwrap_or(0.0));
+                    .map(|res| *res *= self.width_factor.unwrap_or(1.0));
             }
             BoxIntrinsics::MinHeight { res, .. } | BoxIntrinsics::MaxHeight { res, .. } => {
                 res.as_mut()
-                    .map(|res| *res *= self.height_factor.unwrap_or(0.0));
+                    .map(|res| *res *= self.height_factor.unwrap_or(1.0));
             }
         }
     }
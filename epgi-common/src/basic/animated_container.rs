@@ -82,13 +82,13 @@ impl ComponentWidget<BoxProtocol> for AnimatedContainer {
             builder =
                 move |_ctx, (alignment, padding, color, width, height, constraints, margin)| {
                     Asc::new(Container {
-                        alignment,
-                        padding,
+                        alignment: alignment.map(Into::into),
+                        padding: padding.map(Into::into),
                         color,
                         width,
                         height,
                         constraints,
-                        margin,
+                        margin: margin.map(Into::into),
                         child: child.clone(),
                     })
                 }
@@ -4,9 +4,7 @@ use epgi_2d::{
     BoxSize, Brush, Color, Fill, FillPainter, Painter, Rect,
 };
 use epgi_core::{
-    foundation::{
-        set_if_changed, Arc, Asc, BuildSuspendedError, InlinableDwsizeVec, PaintContext, Provide,
-    },
+    foundation::{set_if_changed, Arc, Asc, BuildError, InlinableDwsizeVec, PaintContext, Provide},
     template::{ImplByTemplate, ProxyRender, ProxyRenderTemplate},
     tree::{BuildContext, RenderAction, Widget},
 };
@@ -48,7 +46,7 @@ impl BoxSingleChildElement for ColorBoxElement {
         widget: &Self::ArcWidget,
         _ctx: &mut BuildContext<'_>,
         _provider_values: InlinableDwsizeVec<Arc<dyn Provide>>,
-    ) -> Result<ArcBoxWidget, BuildSuspendedError> {
+    ) -> Result<ArcBoxWidget, BuildError> {
         Ok(widget.child.clone())
     }
 
@@ -1,8 +1,12 @@
 use std::marker::PhantomData;
 
-use epgi_2d::{ArcBoxWidget, BoxProtocol};
+use epgi_2d::{ArcBoxWidget, BoxProtocol, TextDirection};
 use epgi_core::{
-    foundation::{set_if_changed, Arc, Asc, BuildSuspendedError, InlinableDwsizeVec, Provide},
+    foundation::{
+        set_if_changed, Arc, Asc, AscProvideExt, BuildError, InlinableDwsizeVec, Provide,
+        SmallVecExt, TypeKey,
+    },
+    read_providers,
     template::{ImplByTemplate, MultiChildElement, MultiChildElementTemplate},
     tree::{BuildContext, RenderAction, Widget},
 };
@@ -11,6 +15,10 @@ use typed_builder::TypedBuilder;
 
 use crate::{Axis, CrossAxisAlignment, MainAxisAlignment, MainAxisSize, RenderFlex};
 
+/// Lays out its children vertically.
+///
+/// Under a right-to-left [`TextDirection`], the start of the cross axis is on the right.
+/// `flip_horizontal` flips the resolved direction once more.
 #[derive(Debug, Declarative, TypedBuilder)]
 #[builder(build_method(into=Asc<Column>))]
 pub struct Column {
@@ -32,6 +40,10 @@ pub struct Column {
     /// How the children should be placed along the cross axis.
     #[builder(default = CrossAxisAlignment::Center)]
     pub cross_axis_alignment: CrossAxisAlignment,
+    /// Defaults to the ambient [`TextDirection`] of the [`Directionality`](crate::Directionality),
+    /// or [`TextDirection::Ltr`] outside of any.
+    #[builder(default, setter(strip_option))]
+    pub text_direction: Option<TextDirection>,
     #[builder(default = false)]
     pub flip_horizontal: bool,
     #[builder(default = false)]
@@ -49,8 +61,17 @@ impl Widget for Column {
     }
 }
 
+lazy_static::lazy_static! {
+    static ref COLUMN_CONSUMED_TYPES: [TypeKey; 1] = [
+        TypeKey::of_or_default::<TextDirection>(),
+    ];
+}
+
 #[derive(Clone)]
-pub struct ColumnElement {}
+pub struct ColumnElement {
+    /// Whether `flip_horizontal` and the text direction together flip the horizontal axis
+    flip_cross_axis: bool,
+}
 
 impl ImplByTemplate for ColumnElement {
     type Template = MultiChildElementTemplate<false>;
@@ -62,17 +83,32 @@ impl MultiChildElement for ColumnElement {
     type ArcWidget = Asc<Column>;
     type Render = RenderFlex<BoxProtocol>;
 
+    fn get_consumed_types(widget: &Self::ArcWidget) -> impl AsRef<[TypeKey]> {
+        if widget.text_direction.is_none() {
+            COLUMN_CONSUMED_TYPES.as_ref()
+        } else {
+            &[]
+        }
+    }
+
     fn get_child_widgets(
-        _element: Option<&mut Self>,
+        element: &mut Self,
         widget: &Self::ArcWidget,
         _ctx: &mut BuildContext<'_>,
-        _provider_values: InlinableDwsizeVec<Arc<dyn Provide>>,
-    ) -> Result<Vec<ArcBoxWidget>, BuildSuspendedError> {
+        provider_values: InlinableDwsizeVec<Arc<dyn Provide>>,
+    ) -> Result<Vec<ArcBoxWidget>, BuildError> {
+        let text_direction = match widget.text_direction {
+            Some(text_direction) => text_direction,
+            None => *read_providers!(provider_values, TextDirection),
+        };
+        element.flip_cross_axis = widget.flip_horizontal ^ text_direction.is_rtl();
         Ok(widget.children.clone())
     }
 
-    fn create_element(_widget: &Self::ArcWidget) -> Self {
-        Self {}
+    fn create_element(widget: &Self::ArcWidget) -> Self {
+        Self {
+            flip_cross_axis: widget.flip_horizontal,
+        }
     }
 
     fn create_render(&self, widget: &Self::ArcWidget) -> Self::Render {
@@ -82,7 +118,7 @@ impl MultiChildElement for ColumnElement {
             main_axis_size: widget.main_axis_size,
             cross_axis_alignment: widget.cross_axis_alignment,
             flip_main_axis: widget.flip_vertical,
-            flip_cross_axis: widget.flip_horizontal,
+            flip_cross_axis: self.flip_cross_axis,
             phantom: PhantomData,
         }
     }
@@ -96,10 +132,16 @@ impl MultiChildElement for ColumnElement {
                 widget.cross_axis_alignment,
             ),
             set_if_changed(&mut render.flip_main_axis, widget.flip_vertical),
-            set_if_changed(&mut render.flip_cross_axis, widget.flip_horizontal),
         ]
         .iter()
         .any(|&changed| changed)
         .then_some(RenderAction::Relayout)
     }
+
+    fn update_render_from_element(&self, render: &mut Self::Render) -> Option<RenderAction> {
+        set_if_changed(&mut render.flip_cross_axis, self.flip_cross_axis)
+            .then_some(RenderAction::Relayout)
+    }
+
+    const NOOP_UPDATE_RENDER_FROM_ELEMENT: bool = false;
 }
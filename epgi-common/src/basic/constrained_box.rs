@@ -6,7 +6,7 @@ use epgi_2d::{
     BoxSingleChildRenderElement, BoxSize,
 };
 use epgi_core::{
-    foundation::{set_if_changed, Asc, BuildSuspendedError, InlinableDwsizeVec, Provide},
+    foundation::{set_if_changed, Asc, BuildError, InlinableDwsizeVec, Provide},
     template::ImplByTemplate,
     tree::{BuildContext, RenderAction, Widget},
 };
@@ -51,7 +51,7 @@ impl BoxSingleChildElement for ConstrainedBoxElement {
         widget: &Self::ArcWidget,
         _ctx: &mut BuildContext<'_>,
         _provider_values: InlinableDwsizeVec<Arc<dyn Provide>>,
-    ) -> Result<ArcBoxWidget, BuildSuspendedError> {
+    ) -> Result<ArcBoxWidget, BuildError> {
         Ok(widget.child.clone())
     }
 
@@ -102,6 +102,34 @@ impl BoxProxyRender for RenderConstrainedBox {
     }
 
     fn compute_intrinsics(&mut self, child: &ArcBoxRenderObject, intrinsics: &mut BoxIntrinsics) {
-        unimplemented!()
+        let BoxConstraints {
+            min_width,
+            max_width,
+            min_height,
+            max_height,
+        } = self.constraints;
+        match intrinsics {
+            BoxIntrinsics::MinWidth { res, .. } | BoxIntrinsics::MaxWidth { res, .. }
+                if min_width == max_width =>
+            {
+                *res = Some(min_width);
+            }
+            BoxIntrinsics::MinHeight { res, .. } | BoxIntrinsics::MaxHeight { res, .. }
+                if min_height == max_height =>
+            {
+                *res = Some(min_height);
+            }
+            _ => {
+                child.get_intrinsics(intrinsics);
+                match intrinsics {
+                    BoxIntrinsics::MinWidth { res, .. } | BoxIntrinsics::MaxWidth { res, .. } => {
+                        *res = res.map(|width| width.max(min_width).min(max_width));
+                    }
+                    BoxIntrinsics::MinHeight { res, .. } | BoxIntrinsics::MaxHeight { res, .. } => {
+                        *res = res.map(|height| height.max(min_height).min(max_height));
+                    }
+                }
+            }
+        }
     }
 }
@@ -7,15 +7,18 @@ use epgi_core::{
 use epgi_macro::Declarative;
 use typed_builder::TypedBuilder;
 
-use crate::{Align, Alignment, ColoredBox, ConstrainedBox, EdgeInsets, Padding, ARC_PHANTOM_BOX};
+use crate::{
+    Align, AlignmentGeometry, ColoredBox, ConstrainedBox, EdgeInsetsGeometry, Padding,
+    ARC_PHANTOM_BOX,
+};
 
 #[derive(Debug, Declarative, TypedBuilder)]
 #[builder(build_method(into=Asc<Container>))]
 pub struct Container {
     #[builder(default, setter(strip_option, into))]
-    pub alignment: Option<Alignment>,
+    pub alignment: Option<AlignmentGeometry>,
     #[builder(default, setter(strip_option, into))]
-    pub padding: Option<EdgeInsets>,
+    pub padding: Option<EdgeInsetsGeometry>,
     #[builder(default, setter(strip_option, into))]
     pub color: Option<Color>,
     // TODO: Decoration
@@ -26,7 +29,7 @@ pub struct Container {
     #[builder(default, setter(strip_option, into))]
     pub constraints: Option<BoxConstraints>,
     #[builder(default, setter(strip_option, into))]
-    pub margin: Option<EdgeInsets>,
+    pub margin: Option<EdgeInsetsGeometry>,
     // TODO: transform
     #[builder(default=ARC_PHANTOM_BOX.clone())]
     pub child: ArcBoxWidget,
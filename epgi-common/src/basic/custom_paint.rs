@@ -6,7 +6,7 @@ use epgi_2d::{
     BoxSingleChildRenderElement, BoxSize, Point2d,
 };
 use epgi_core::{
-    foundation::{Asc, BuildSuspendedError, InlinableDwsizeVec, PaintContext, Provide},
+    foundation::{Asc, BuildError, InlinableDwsizeVec, PaintContext, Provide},
     template::ImplByTemplate,
     tree::{BuildContext, HitTestContext, HitTestResult, RenderAction, Widget},
 };
@@ -85,7 +85,7 @@ impl<B: CustomPainter, F: CustomPainter> BoxSingleChildElement for CustomPaintEl
         widget: &Self::ArcWidget,
         _ctx: &mut BuildContext<'_>,
         _provider_values: InlinableDwsizeVec<Arc<dyn Provide>>,
-    ) -> Result<ArcBoxWidget, BuildSuspendedError> {
+    ) -> Result<ArcBoxWidget, BuildError> {
         Ok(widget.child.clone())
     }
 
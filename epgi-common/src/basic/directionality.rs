@@ -0,0 +1,41 @@
+use epgi_2d::{ArcBoxWidget, BoxProtocol, TextDirection};
+use epgi_core::{
+    foundation::{Arc, Asc},
+    nodes::{ComponentElement, ComponentWidget},
+    tree::{BuildContext, ElementBase, Widget},
+    Provider,
+};
+use epgi_macro::Declarative;
+use typed_builder::TypedBuilder;
+
+/// Provides the [`TextDirection`] of its subtree.
+///
+/// Text is laid out in this direction, and directional widgets such as [`Row`](crate::Row),
+/// [`Align`](crate::Align) with an [`AlignmentDirectional`](crate::AlignmentDirectional),
+/// [`Padding`](crate::Padding) with [`EdgeInsetsDirectional`](crate::EdgeInsetsDirectional) and [`Stack`](crate::Stack)
+/// resolve their start and end against it.
+///
+/// The embeddings provide [`TextDirection::Ltr`] at the root of the app,
+/// which is also what directional widgets fall back to outside of any [`Directionality`].
+#[derive(Debug, Declarative, TypedBuilder)]
+#[builder(build_method(into=Asc<Directionality>))]
+pub struct Directionality {
+    pub text_direction: TextDirection,
+    pub child: ArcBoxWidget,
+}
+
+impl Widget for Directionality {
+    type ParentProtocol = BoxProtocol;
+    type ChildProtocol = BoxProtocol;
+    type Element = ComponentElement<BoxProtocol>;
+
+    fn into_arc_widget(self: Arc<Self>) -> <Self::Element as ElementBase>::ArcWidget {
+        self
+    }
+}
+
+impl ComponentWidget<BoxProtocol> for Directionality {
+    fn build(&self, _ctx: &mut BuildContext<'_>) -> ArcBoxWidget {
+        Provider!(value = self.text_direction, child = self.child.clone())
+    }
+}
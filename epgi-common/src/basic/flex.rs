@@ -1,13 +1,16 @@
 use std::{f32::INFINITY, iter::zip, marker::PhantomData};
 
+pub use epgi_2d::Axis;
+
 use epgi_2d::{
     Affine2dCanvas, Affine2dPaintContextExt, ArcBoxRenderObject, BlendMode, BoxConstraints,
-    BoxOffset, BoxProtocol, BoxSize, PRECISION_ERROR_TOLERANCE,
+    BoxIntrinsics, BoxOffset, BoxProtocol, BoxRenderObjectIntrinsicsExt, BoxSize, TextBaseline,
+    PRECISION_ERROR_TOLERANCE,
 };
 use epgi_core::{
     foundation::{
-        set_if_changed, Arc, Asc, BuildSuspendedError, InlinableDwsizeVec, PaintContext, Protocol,
-        Provide, VecContainer,
+        set_if_changed, Arc, Asc, BuildError, InlinableDwsizeVec, PaintContext, Protocol, Provide,
+        VecContainer,
     },
     template::{
         ImplByTemplate, MultiChildElement, MultiChildElementTemplate, MultiChildHitTest,
@@ -20,12 +23,6 @@ use typed_builder::TypedBuilder;
 
 use crate::{FlexFit, FlexibleConfig};
 
-#[derive(PartialEq, Eq, Clone, Copy, Debug)]
-pub enum Axis {
-    Horizontal,
-    Vertical,
-}
-
 /// How the children should be placed along the main axis in a flex layout.
 ///
 /// See also:
@@ -115,38 +112,41 @@ pub enum CrossAxisAlignment {
     /// This causes the constraints passed to the children to be tight in the
     /// cross axis.
     Stretch,
-    // /// Place the children along the cross axis such that their baselines match.
-    // ///
-    // /// Consider using this value for any horizontal main axis (as with [Row])
-    // /// where the children primarily contain text.  If the different children
-    // /// have text with different font metrics (for example because they differ
-    // /// in [TextStyle.fontSize] or other [TextStyle] properties, or because
-    // /// they use different fonts due to being written in different scripts),
-    // /// then this typically produces better visual alignment than the other
-    // /// [CrossAxisAlignment] values, which use no information about
-    // /// where the text sits vertically within its bounding box.
-    // ///
-    // /// The baseline of a widget is typically the typographic baseline of the
-    // /// first text in the first [Text] or [RichText] widget it encloses, if any.
-    // /// The typographic baseline is a horizontal line used for aligning text,
-    // /// which is specified by each font; for alphabetic scripts, it ordinarily
-    // /// runs along the bottom of letters excluding any descenders.
-    // ///
-    // /// Because baselines are always horizontal, this alignment is intended for
-    // /// horizontal main axes (as with [Row]). If the main axis is vertical
-    // /// (as with [Column]), then this value is treated like [start].
-    // ///
-    // /// For horizontal main axes, if the minimum height constraint passed to the
-    // /// flex layout exceeds the intrinsic height of the cross axis, children will
-    // /// be aligned as close to the top as they can be while honoring the baseline
-    // /// alignment. In other words, the extra space will be below all the children.
-    // ///
-    // /// Children who report no baseline will be top-aligned.
-    // ///
-    // /// See also:
-    // ///
-    // ///  * [RenderBox.getDistanceToBaseline], which defines the baseline of a box.
-    // Baseline,
+
+    /// Place the children along the cross axis such that their baselines match.
+    ///
+    /// The children are aligned by the given kind of baseline.
+    ///
+    /// Consider using this value for any horizontal main axis (as with [Row])
+    /// where the children primarily contain text.  If the different children
+    /// have text with different font metrics (for example because they differ
+    /// in [TextStyle.fontSize] or other [TextStyle] properties, or because
+    /// they use different fonts due to being written in different scripts),
+    /// then this typically produces better visual alignment than the other
+    /// [CrossAxisAlignment] values, which use no information about
+    /// where the text sits vertically within its bounding box.
+    ///
+    /// The baseline of a widget is typically the typographic baseline of the
+    /// first text in the first [Text] or [RichText] widget it encloses, if any.
+    /// The typographic baseline is a horizontal line used for aligning text,
+    /// which is specified by each font; for alphabetic scripts, it ordinarily
+    /// runs along the bottom of letters excluding any descenders.
+    ///
+    /// Because baselines are always horizontal, this alignment is intended for
+    /// horizontal main axes (as with [Row]). If the main axis is vertical
+    /// (as with [Column]), then this value is treated like [CrossAxisAlignment::Start].
+    ///
+    /// For horizontal main axes, if the minimum height constraint passed to the
+    /// flex layout exceeds the intrinsic height of the cross axis, children will
+    /// be aligned as close to the top as they can be while honoring the baseline
+    /// alignment. In other words, the extra space will be below all the children.
+    ///
+    /// Children who report no baseline will be top-aligned.
+    ///
+    /// See also:
+    ///
+    ///  * [ChildRenderObjectLayoutExt::get_distance_to_baseline], which defines the baseline of a box.
+    Baseline(TextBaseline),
 }
 
 /// How much space should be occupied in the main axis.
@@ -252,11 +252,11 @@ where
     type ArcWidget = Asc<Flex<P>>;
     type Render = RenderFlex<P>;
     fn get_child_widgets(
-        _element: Option<&mut Self>,
+        _element: &mut Self,
         widget: &Self::ArcWidget,
         _ctx: &mut BuildContext<'_>,
         _provider_values: InlinableDwsizeVec<Arc<dyn Provide>>,
-    ) -> Result<Vec<ArcChildWidget<P>>, BuildSuspendedError> {
+    ) -> Result<Vec<ArcChildWidget<P>>, BuildError> {
         Ok(widget.children.clone())
     }
 
@@ -310,7 +310,10 @@ impl<P: Protocol> ImplByTemplate for RenderFlex<P> {
     type Template = MultiChildRenderTemplate<false, false, false, false>;
 }
 
-impl<P: Protocol> MultiChildRender for RenderFlex<P> {
+impl<P: Protocol> MultiChildRender for RenderFlex<P>
+where
+    RenderFlex<P>: FlexRender<P>,
+{
     type ParentProtocol = P;
     type ChildProtocol = P;
     type LayoutMemo = (Vec<P::Offset>, f32);
@@ -320,7 +323,17 @@ impl<P: Protocol> MultiChildRender for RenderFlex<P> {
         children: &Vec<ArcChildRenderObject<P>>,
         intrinsics: &mut P::Intrinsics,
     ) {
-        unimplemented!()
+        FlexRender::compute_intrinsics(self, children, intrinsics)
+    }
+
+    fn compute_distance_to_baseline(
+        &self,
+        _size: &P::Size,
+        (child_offsets, _): &Self::LayoutMemo,
+        children: &[ArcChildRenderObject<P>],
+        baseline: &P::Baseline,
+    ) -> Option<f32> {
+        FlexRender::compute_distance_to_baseline(self, child_offsets, children, baseline)
     }
 }
 
@@ -363,16 +376,20 @@ pub fn default_flex_perform_layout<P: Protocol, R: FlexRender<P>>(
 
     let mut cross_size = render.initial_cross_size();
     let mut allocated_size = 0.0;
+    // The distance from the cross-axis start to the baseline of each child, if aligned by baselines.
+    let mut child_baselines = vec![None; children.len()];
+    let mut max_baseline_extents: Option<(f32, f32)> = None;
+    let mut reduce_baseline_extents = |child: &ArcChildRenderObject<P>, child_size: &P::Size| {
+        let (above, below) = render.get_child_baseline_extents(child, child_size)?;
+        let (max_above, max_below) = max_baseline_extents.get_or_insert((above, below));
+        *max_above = max_above.max(above);
+        *max_below = max_below.max(below);
+        Some(above)
+    };
 
-    fn get_flexible_config<P: Protocol>(child: &ArcChildRenderObject<P>) -> Option<FlexibleConfig> {
-        child.as_ref().get_parent_data().and_then(|data| {
-            data.downcast::<FlexibleConfig>()
-                .ok()
-                .map(|config| config.as_ref().clone())
-        })
-    }
-
-    for (child, size) in zip(children.iter(), child_sizes.iter_mut()) {
+    for ((child, size), baseline) in
+        zip(children.iter(), child_sizes.iter_mut()).zip(child_baselines.iter_mut())
+    {
         let FlexibleConfig { flex, fit: _ } = get_flexible_config(child).unwrap_or_default();
         if flex > 0 {
             total_flex += flex;
@@ -381,6 +398,7 @@ pub fn default_flex_perform_layout<P: Protocol, R: FlexRender<P>>(
             let child_size = child.layout_use_size(&inner_constraints);
             allocated_size += render.get_main_size(&child_size);
             render.reduce_cross_size(&mut cross_size, render.get_cross_size(&child_size));
+            *baseline = reduce_baseline_extents(child, &child_size);
             *size = child_size;
         }
     }
@@ -390,7 +408,9 @@ pub fn default_flex_perform_layout<P: Protocol, R: FlexRender<P>>(
 
     if total_flex > 0 {
         let space_per_flex = free_space / total_flex as f32;
-        for (child, size) in zip(children.iter(), child_sizes.iter_mut()) {
+        for ((child, size), baseline) in
+            zip(children.iter(), child_sizes.iter_mut()).zip(child_baselines.iter_mut())
+        {
             let FlexibleConfig { flex, fit } = get_flexible_config(child).unwrap_or_default();
             if flex > 0 {
                 let max_child_extent = if can_flex {
@@ -412,11 +432,16 @@ pub fn default_flex_perform_layout<P: Protocol, R: FlexRender<P>>(
                 allocated_size += child_main_size;
                 allocated_flex_space += max_child_extent;
                 render.reduce_cross_size(&mut cross_size, render.get_cross_size(&child_size));
+                *baseline = reduce_baseline_extents(child, &child_size);
                 *size = child_size;
             }
         }
     }
 
+    if let Some((max_above, max_below)) = max_baseline_extents {
+        render.reduce_cross_size_by_baselines(&mut cross_size, max_above, max_below);
+    }
+
     let ideal_size = match main_axis_size {
         MainAxisSize::Max if can_flex => max_main_size,
         _ => allocated_size,
@@ -456,17 +481,20 @@ pub fn default_flex_perform_layout<P: Protocol, R: FlexRender<P>>(
         leading_space
     };
 
-    let child_offsets = child_sizes
-        .into_iter()
-        .map(|child_size| {
+    let child_offsets = zip(child_sizes, child_baselines)
+        .map(|(child_size, baseline)| {
             if flip_main_axis {
                 child_main_position -= render.get_main_size(&child_size);
             }
 
+            let baseline_offset = max_baseline_extents
+                .zip(baseline)
+                .map(|((max_above, _), above)| max_above - above);
             let child_offset = render.position_child(
                 child_main_position,
                 cross_size.clone(),
                 &child_size,
+                baseline_offset,
                 constraints,
             );
 
@@ -482,6 +510,14 @@ pub fn default_flex_perform_layout<P: Protocol, R: FlexRender<P>>(
     (actual_size, (child_offsets, overflow))
 }
 
+fn get_flexible_config<P: Protocol>(child: &ArcChildRenderObject<P>) -> Option<FlexibleConfig> {
+    child.as_ref().get_parent_data().and_then(|data| {
+        data.downcast::<FlexibleConfig>()
+            .ok()
+            .map(|config| *config.as_ref())
+    })
+}
+
 pub trait FlexRender<P: Protocol>: Send + Sync + 'static {
     type CrossSize: Clone + Send + Sync + 'static;
 
@@ -494,6 +530,28 @@ pub trait FlexRender<P: Protocol>: Send + Sync + 'static {
     fn initial_cross_size(&self) -> Self::CrossSize;
     fn reduce_cross_size(&self, cross_size: &mut Self::CrossSize, child_size: Self::CrossSize);
 
+    /// The extents of a laid-out child above and below its baseline along the cross axis,
+    /// if the children are aligned by their baselines.
+    #[allow(unused_variables)]
+    fn get_child_baseline_extents(
+        &self,
+        child: &ArcChildRenderObject<P>,
+        child_size: &P::Size,
+    ) -> Option<(f32, f32)> {
+        None
+    }
+
+    /// Grows the cross size to fit the children aligned by their baselines,
+    /// given the largest extents above and below the shared baseline.
+    #[allow(unused_variables)]
+    fn reduce_cross_size_by_baselines(
+        &self,
+        cross_size: &mut Self::CrossSize,
+        max_above_baseline: f32,
+        max_below_baseline: f32,
+    ) {
+    }
+
     fn child_constraints(
         &self,
         main_size_range: Option<(f32, f32)>,
@@ -508,11 +566,14 @@ pub trait FlexRender<P: Protocol>: Send + Sync + 'static {
         parent_constraints: &P::Constraints,
     ) -> (P::Size, f32, Self::CrossSize);
 
+    /// `baseline_offset` is the cross-axis offset that lines up the baseline of the child with the others,
+    /// if the children are aligned by their baselines and the child has a baseline.
     fn position_child(
         &self,
         main_offset: f32,
         cross_size: Self::CrossSize,
         child_size: &P::Size,
+        baseline_offset: Option<f32>,
         parent_constraints: &P::Constraints,
     ) -> P::Offset;
 
@@ -525,6 +586,22 @@ pub trait FlexRender<P: Protocol>: Send + Sync + 'static {
         children: &Vec<ArcChildRenderObject<P>>,
         paint_ctx: &mut impl PaintContext<Canvas = P::Canvas>,
     );
+
+    fn compute_intrinsics(
+        &self,
+        children: &[ArcChildRenderObject<P>],
+        intrinsics: &mut P::Intrinsics,
+    );
+
+    #[allow(unused_variables)]
+    fn compute_distance_to_baseline(
+        &self,
+        child_offsets: &[P::Offset],
+        children: &[ArcChildRenderObject<P>],
+        baseline: &P::Baseline,
+    ) -> Option<f32> {
+        None
+    }
 }
 
 impl FlexRender<BoxProtocol> for RenderFlex<BoxProtocol> {
@@ -557,6 +634,30 @@ impl FlexRender<BoxProtocol> for RenderFlex<BoxProtocol> {
         *cross_size = cross_size.max(child_cross_size);
     }
 
+    fn get_child_baseline_extents(
+        &self,
+        child: &ArcBoxRenderObject,
+        child_size: &BoxSize,
+    ) -> Option<(f32, f32)> {
+        let CrossAxisAlignment::Baseline(baseline) = self.cross_axis_alignment else {
+            return None;
+        };
+        if self.direction != Axis::Horizontal {
+            return None;
+        }
+        let distance = child.get_distance_to_baseline(&baseline)?;
+        Some((distance, child_size.height - distance))
+    }
+
+    fn reduce_cross_size_by_baselines(
+        &self,
+        cross_size: &mut f32,
+        max_above_baseline: f32,
+        max_below_baseline: f32,
+    ) {
+        *cross_size = cross_size.max(max_above_baseline + max_below_baseline);
+    }
+
     fn child_constraints(
         &self,
         main_size_range: Option<(f32, f32)>,
@@ -617,6 +718,7 @@ impl FlexRender<BoxProtocol> for RenderFlex<BoxProtocol> {
         main_offset: f32,
         cross_size: f32,
         child_size: &BoxSize,
+        baseline_offset: Option<f32>,
         _parent_constraints: &BoxConstraints,
     ) -> BoxOffset {
         let child_cross_position = match self.cross_axis_alignment {
@@ -630,6 +732,8 @@ impl FlexRender<BoxProtocol> for RenderFlex<BoxProtocol> {
             }
             CrossAxisAlignment::Center => (cross_size - self.get_cross_size(&child_size)) / 2.0,
             CrossAxisAlignment::Stretch => 0.0,
+            // Children without a baseline are placed at the start.
+            CrossAxisAlignment::Baseline(_) => baseline_offset.unwrap_or(0.0),
         };
         let child_offset = match self.direction {
             Axis::Horizontal => BoxOffset {
@@ -666,6 +770,105 @@ impl FlexRender<BoxProtocol> for RenderFlex<BoxProtocol> {
             // todo!(paint overflow indicator)
         };
     }
+
+    fn compute_intrinsics(&self, children: &[ArcBoxRenderObject], intrinsics: &mut BoxIntrinsics) {
+        use BoxIntrinsics::*;
+        let extent = self.compute_intrinsic_extent(children, *intrinsics);
+        let (MinWidth { res, .. }
+        | MaxWidth { res, .. }
+        | MinHeight { res, .. }
+        | MaxHeight { res, .. }) = intrinsics;
+        *res = extent;
+    }
+
+    fn compute_distance_to_baseline(
+        &self,
+        child_offsets: &[BoxOffset],
+        children: &[ArcBoxRenderObject],
+        baseline: &TextBaseline,
+    ) -> Option<f32> {
+        let mut baselines = zip(child_offsets, children).filter_map(|(offset, child)| {
+            Some(child.get_distance_to_baseline(baseline)? + offset.y)
+        });
+        match self.direction {
+            // The highest baseline among the children.
+            Axis::Horizontal => baselines.reduce(f32::min),
+            // The baseline of the first child that has one.
+            Axis::Vertical => baselines.next(),
+        }
+    }
+}
+
+impl RenderFlex<BoxProtocol> {
+    fn compute_intrinsic_extent(
+        &self,
+        children: &[ArcBoxRenderObject],
+        query: BoxIntrinsics,
+    ) -> Option<f32> {
+        use BoxIntrinsics::*;
+        let (extent, along_main_axis) = match query {
+            MinWidth { height, .. } | MaxWidth { height, .. } => {
+                (height, self.direction == Axis::Horizontal)
+            }
+            MinHeight { width, .. } | MaxHeight { width, .. } => {
+                (width, self.direction == Axis::Vertical)
+            }
+        };
+        let child_intrinsic = |child: &ArcBoxRenderObject, extent: f32| match query {
+            MinWidth { .. } => child.get_min_intrinsic_width(extent),
+            MaxWidth { .. } => child.get_max_intrinsic_width(extent),
+            MinHeight { .. } => child.get_min_intrinsic_height(extent),
+            MaxHeight { .. } => child.get_max_intrinsic_height(extent),
+        };
+
+        if along_main_axis {
+            // Flexible children get space in proportion to their flex.
+            // The child needing the most space per flex decides how much space the others get.
+            let mut inflexible_size = 0.0f32;
+            let mut max_flex_fraction = 0.0f32;
+            let mut total_flex = 0;
+            for child in children {
+                let flex = get_flexible_config(child).unwrap_or_default().flex;
+                let child_size = child_intrinsic(child, extent)?;
+                if flex > 0 {
+                    max_flex_fraction = max_flex_fraction.max(child_size / flex as f32);
+                    total_flex += flex;
+                } else {
+                    inflexible_size += child_size;
+                }
+            }
+            Some(inflexible_size + max_flex_fraction * total_flex as f32)
+        } else {
+            // Inflexible children take their max intrinsic main size, flexible children share the rest.
+            let mut inflexible_space = 0.0f32;
+            let mut cross_size = 0.0f32;
+            let mut total_flex = 0;
+            for child in children {
+                let flex = get_flexible_config(child).unwrap_or_default().flex;
+                if flex > 0 {
+                    total_flex += flex;
+                } else {
+                    let main_size = match self.direction {
+                        Axis::Horizontal => child.get_max_intrinsic_width(f32::INFINITY),
+                        Axis::Vertical => child.get_max_intrinsic_height(f32::INFINITY),
+                    }?;
+                    inflexible_space += main_size;
+                    cross_size = cross_size.max(child_intrinsic(child, main_size)?);
+                }
+            }
+            if total_flex > 0 {
+                let space_per_flex = ((extent - inflexible_space) / total_flex as f32).max(0.0);
+                for child in children {
+                    let flex = get_flexible_config(child).unwrap_or_default().flex;
+                    if flex > 0 {
+                        cross_size =
+                            cross_size.max(child_intrinsic(child, space_per_flex * flex as f32)?);
+                    }
+                }
+            }
+            Some(cross_size)
+        }
+    }
 }
 
 impl<P: Protocol> MultiChildPaint for RenderFlex<P>
@@ -693,4 +896,4 @@ where
     }
 }
 
-impl<P: Protocol> MultiChildHitTest for RenderFlex<P> {}
+impl<P: Protocol> MultiChildHitTest for RenderFlex<P> where RenderFlex<P>: FlexRender<P> {}
@@ -1,7 +1,7 @@
 use std::{any::Any, marker::PhantomData};
 
 use epgi_core::{
-    foundation::{Arc, Asc, BuildSuspendedError, InlinableDwsizeVec, Protocol, Provide},
+    foundation::{Arc, Asc, BuildError, InlinableDwsizeVec, Protocol, Provide},
     template::{ImplByTemplate, SingleChildElement, SingleChildElementTemplate},
     tree::{ArcChildWidget, BuildContext, RenderAction, Widget},
 };
@@ -92,7 +92,7 @@ impl<P: Protocol> SingleChildElement for FlexibleElement<P> {
         widget: &Self::ArcWidget,
         _ctx: &mut BuildContext<'_>,
         _provider_values: InlinableDwsizeVec<Arc<dyn Provide>>,
-    ) -> Result<ArcChildWidget<P>, BuildSuspendedError> {
+    ) -> Result<ArcChildWidget<P>, BuildError> {
         Ok(widget.child.clone())
     }
 
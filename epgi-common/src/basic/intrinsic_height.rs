@@ -0,0 +1,128 @@
+use std::sync::Arc;
+
+use epgi_2d::{
+    ArcBoxRenderObject, ArcBoxWidget, BoxConstraints, BoxIntrinsics, BoxProtocol, BoxProxyRender,
+    BoxProxyRenderTemplate, BoxRenderObjectIntrinsicsExt, BoxSingleChildElement,
+    BoxSingleChildElementTemplate, BoxSingleChildRenderElement, BoxSize,
+};
+use epgi_core::{
+    foundation::{Asc, BuildError, InlinableDwsizeVec, Provide},
+    template::ImplByTemplate,
+    tree::{BuildContext, RenderAction, Widget},
+};
+use epgi_macro::Declarative;
+use typed_builder::TypedBuilder;
+
+/// Sizes its child to the child's maximum intrinsic height.
+///
+/// This is useful when the available height is unlimited and a child would rather expand,
+/// or to give all children of a [`Row`](crate::Row) the height of the tallest one.
+///
+/// Intrinsics are computed by walking the subtree, which makes this widget relatively expensive.
+#[derive(Debug, Declarative, TypedBuilder)]
+#[builder(build_method(into=Asc<IntrinsicHeight>))]
+pub struct IntrinsicHeight {
+    pub child: ArcBoxWidget,
+}
+
+impl Widget for IntrinsicHeight {
+    type ParentProtocol = BoxProtocol;
+    type ChildProtocol = BoxProtocol;
+    type Element = IntrinsicHeightElement;
+
+    fn into_arc_widget(self: Arc<Self>) -> Asc<Self> {
+        self
+    }
+}
+
+#[derive(Clone)]
+pub struct IntrinsicHeightElement;
+
+impl ImplByTemplate for IntrinsicHeightElement {
+    type Template = BoxSingleChildElementTemplate<true, false>;
+}
+
+impl BoxSingleChildElement for IntrinsicHeightElement {
+    type ArcWidget = Asc<IntrinsicHeight>;
+
+    fn get_child_widget(
+        _element: Option<&mut Self>,
+        widget: &Self::ArcWidget,
+        _ctx: &mut BuildContext<'_>,
+        _provider_values: InlinableDwsizeVec<Arc<dyn Provide>>,
+    ) -> Result<ArcBoxWidget, BuildError> {
+        Ok(widget.child.clone())
+    }
+
+    fn create_element(_widget: &Self::ArcWidget) -> Self {
+        Self
+    }
+}
+
+impl BoxSingleChildRenderElement for IntrinsicHeightElement {
+    type Render = RenderIntrinsicHeight;
+
+    fn create_render(&self, _widget: &Self::ArcWidget) -> Self::Render {
+        RenderIntrinsicHeight
+    }
+
+    fn update_render(
+        _render: &mut Self::Render,
+        _widget: &Self::ArcWidget,
+    ) -> Option<RenderAction> {
+        None
+    }
+
+    const NOOP_UPDATE_RENDER_OBJECT: bool = true;
+}
+
+pub struct RenderIntrinsicHeight;
+
+impl ImplByTemplate for RenderIntrinsicHeight {
+    type Template = BoxProxyRenderTemplate;
+}
+
+impl BoxProxyRender for RenderIntrinsicHeight {
+    const NOOP_DETACH: bool = true;
+
+    fn perform_layout(
+        &mut self,
+        constraints: &BoxConstraints,
+        child: &ArcBoxRenderObject,
+    ) -> BoxSize {
+        let child_constraints = if constraints.min_height == constraints.max_height {
+            *constraints
+        } else {
+            child
+                .get_max_intrinsic_height(constraints.max_width)
+                .map_or(*constraints, |height| constraints.tighten_height(height))
+        };
+        child.layout_use_size(&child_constraints)
+    }
+
+    fn compute_intrinsics(&mut self, child: &ArcBoxRenderObject, intrinsics: &mut BoxIntrinsics) {
+        match intrinsics {
+            // The child is always given its maximum intrinsic height.
+            BoxIntrinsics::MinHeight { width, res } | BoxIntrinsics::MaxHeight { width, res } => {
+                *res = child.get_max_intrinsic_height(*width)
+            }
+            BoxIntrinsics::MinWidth { height, res } => {
+                *res = intrinsic_height_or(child, *height)
+                    .and_then(|height| child.get_min_intrinsic_width(height))
+            }
+            BoxIntrinsics::MaxWidth { height, res } => {
+                *res = intrinsic_height_or(child, *height)
+                    .and_then(|height| child.get_max_intrinsic_width(height))
+            }
+        }
+    }
+}
+
+/// Without a height limit, the child is laid out at its maximum intrinsic height.
+fn intrinsic_height_or(child: &ArcBoxRenderObject, height: f32) -> Option<f32> {
+    if height.is_finite() {
+        Some(height)
+    } else {
+        child.get_max_intrinsic_height(f32::INFINITY)
+    }
+}
@@ -0,0 +1,128 @@
+use std::sync::Arc;
+
+use epgi_2d::{
+    ArcBoxRenderObject, ArcBoxWidget, BoxConstraints, BoxIntrinsics, BoxProtocol, BoxProxyRender,
+    BoxProxyRenderTemplate, BoxRenderObjectIntrinsicsExt, BoxSingleChildElement,
+    BoxSingleChildElementTemplate, BoxSingleChildRenderElement, BoxSize,
+};
+use epgi_core::{
+    foundation::{Asc, BuildError, InlinableDwsizeVec, Provide},
+    template::ImplByTemplate,
+    tree::{BuildContext, RenderAction, Widget},
+};
+use epgi_macro::Declarative;
+use typed_builder::TypedBuilder;
+
+/// Sizes its child to the child's maximum intrinsic width.
+///
+/// This is useful when the available width is unlimited and a child would rather expand,
+/// or to give all children of a [`Column`](crate::Column) the width of the widest one.
+///
+/// Intrinsics are computed by walking the subtree, which makes this widget relatively expensive.
+#[derive(Debug, Declarative, TypedBuilder)]
+#[builder(build_method(into=Asc<IntrinsicWidth>))]
+pub struct IntrinsicWidth {
+    pub child: ArcBoxWidget,
+}
+
+impl Widget for IntrinsicWidth {
+    type ParentProtocol = BoxProtocol;
+    type ChildProtocol = BoxProtocol;
+    type Element = IntrinsicWidthElement;
+
+    fn into_arc_widget(self: Arc<Self>) -> Asc<Self> {
+        self
+    }
+}
+
+#[derive(Clone)]
+pub struct IntrinsicWidthElement;
+
+impl ImplByTemplate for IntrinsicWidthElement {
+    type Template = BoxSingleChildElementTemplate<true, false>;
+}
+
+impl BoxSingleChildElement for IntrinsicWidthElement {
+    type ArcWidget = Asc<IntrinsicWidth>;
+
+    fn get_child_widget(
+        _element: Option<&mut Self>,
+        widget: &Self::ArcWidget,
+        _ctx: &mut BuildContext<'_>,
+        _provider_values: InlinableDwsizeVec<Arc<dyn Provide>>,
+    ) -> Result<ArcBoxWidget, BuildError> {
+        Ok(widget.child.clone())
+    }
+
+    fn create_element(_widget: &Self::ArcWidget) -> Self {
+        Self
+    }
+}
+
+impl BoxSingleChildRenderElement for IntrinsicWidthElement {
+    type Render = RenderIntrinsicWidth;
+
+    fn create_render(&self, _widget: &Self::ArcWidget) -> Self::Render {
+        RenderIntrinsicWidth
+    }
+
+    fn update_render(
+        _render: &mut Self::Render,
+        _widget: &Self::ArcWidget,
+    ) -> Option<RenderAction> {
+        None
+    }
+
+    const NOOP_UPDATE_RENDER_OBJECT: bool = true;
+}
+
+pub struct RenderIntrinsicWidth;
+
+impl ImplByTemplate for RenderIntrinsicWidth {
+    type Template = BoxProxyRenderTemplate;
+}
+
+impl BoxProxyRender for RenderIntrinsicWidth {
+    const NOOP_DETACH: bool = true;
+
+    fn perform_layout(
+        &mut self,
+        constraints: &BoxConstraints,
+        child: &ArcBoxRenderObject,
+    ) -> BoxSize {
+        let child_constraints = if constraints.min_width == constraints.max_width {
+            *constraints
+        } else {
+            child
+                .get_max_intrinsic_width(constraints.max_height)
+                .map_or(*constraints, |width| constraints.tighten_width(width))
+        };
+        child.layout_use_size(&child_constraints)
+    }
+
+    fn compute_intrinsics(&mut self, child: &ArcBoxRenderObject, intrinsics: &mut BoxIntrinsics) {
+        match intrinsics {
+            // The child is always given its maximum intrinsic width.
+            BoxIntrinsics::MinWidth { height, res } | BoxIntrinsics::MaxWidth { height, res } => {
+                *res = child.get_max_intrinsic_width(*height)
+            }
+            BoxIntrinsics::MinHeight { width, res } => {
+                *res = intrinsic_width_or(child, *width)
+                    .and_then(|width| child.get_min_intrinsic_height(width))
+            }
+            BoxIntrinsics::MaxHeight { width, res } => {
+                *res = intrinsic_width_or(child, *width)
+                    .and_then(|width| child.get_max_intrinsic_height(width))
+            }
+        }
+    }
+}
+
+/// Without a width limit, the child is laid out at its maximum intrinsic width.
+fn intrinsic_width_or(child: &ArcBoxRenderObject, width: f32) -> Option<f32> {
+    if width.is_finite() {
+        Some(width)
+    } else {
+        child.get_max_intrinsic_width(f32::INFINITY)
+    }
+}
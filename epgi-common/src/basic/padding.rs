@@ -1,23 +1,34 @@
 use epgi_2d::{
     ArcBoxRenderObject, ArcBoxWidget, BoxConstraints, BoxIntrinsics, BoxOffset, BoxProtocol,
-    BoxSingleChildElement, BoxSingleChildElementTemplate, BoxSingleChildRenderElement, BoxSize,
-    ShiftedBoxRender, ShiftedBoxRenderTemplate,
+    BoxRenderObjectIntrinsicsExt, BoxSingleChildElement, BoxSingleChildElementTemplate,
+    BoxSingleChildRenderElement, BoxSize, ShiftedBoxRender, ShiftedBoxRenderTemplate,
+    TextDirection,
 };
 use epgi_core::{
-    foundation::{set_if_changed, Arc, Asc, BuildSuspendedError, InlinableDwsizeVec, Provide},
+    foundation::{
+        set_if_changed, Arc, Asc, AscProvideExt, BuildError, InlinableDwsizeVec, Provide,
+        SmallVecExt, TypeKey,
+    },
     max,
+    nodes::{ConsumerElement, ConsumerWidget},
+    read_providers,
     template::ImplByTemplate,
-    tree::{BuildContext, RenderAction, Widget},
+    tree::{BuildContext, ElementBase, RenderAction, Widget},
 };
 use epgi_macro::Declarative;
 use typed_builder::TypedBuilder;
 
 use crate::Lerp;
 
+/// Insets its child by the padding.
+///
+/// [`EdgeInsetsDirectional`] is resolved against the ambient [`TextDirection`],
+/// which is only looked up when the padding is directional.
 #[derive(Debug, Declarative, TypedBuilder)]
 #[builder(build_method(into=Asc<Padding>))]
 pub struct Padding {
-    pub padding: EdgeInsets,
+    #[builder(setter(into))]
+    pub padding: EdgeInsetsGeometry,
     pub child: ArcBoxWidget,
 }
 
@@ -79,6 +90,84 @@ impl EdgeInsets {
     }
 }
 
+/// Edge insets whose horizontal edges are the start and the end of the text direction.
+#[derive(Lerp, PartialEq, Default, Clone, Copy, Debug)]
+pub struct EdgeInsetsDirectional {
+    pub start: f32,
+    pub end: f32,
+    pub t: f32,
+    pub b: f32,
+}
+
+impl EdgeInsetsDirectional {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn start(mut self, start: f32) -> Self {
+        self.start = start;
+        self
+    }
+    pub fn end(mut self, end: f32) -> Self {
+        self.end = end;
+        self
+    }
+    pub fn t(mut self, t: f32) -> Self {
+        self.t = t;
+        self
+    }
+    pub fn b(mut self, b: f32) -> Self {
+        self.b = b;
+        self
+    }
+
+    pub fn resolve(&self, text_direction: TextDirection) -> EdgeInsets {
+        let (l, r) = if text_direction.is_rtl() {
+            (self.end, self.start)
+        } else {
+            (self.start, self.end)
+        };
+        EdgeInsets {
+            l,
+            r,
+            t: self.t,
+            b: self.b,
+        }
+    }
+}
+
+/// Either [`EdgeInsets`] or [`EdgeInsetsDirectional`].
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum EdgeInsetsGeometry {
+    Absolute(EdgeInsets),
+    Directional(EdgeInsetsDirectional),
+}
+
+impl EdgeInsetsGeometry {
+    pub fn is_directional(&self) -> bool {
+        matches!(self, EdgeInsetsGeometry::Directional(_))
+    }
+
+    pub fn resolve(&self, text_direction: TextDirection) -> EdgeInsets {
+        match self {
+            EdgeInsetsGeometry::Absolute(edges) => *edges,
+            EdgeInsetsGeometry::Directional(edges) => edges.resolve(text_direction),
+        }
+    }
+}
+
+impl From<EdgeInsets> for EdgeInsetsGeometry {
+    fn from(value: EdgeInsets) -> Self {
+        EdgeInsetsGeometry::Absolute(value)
+    }
+}
+
+impl From<EdgeInsetsDirectional> for EdgeInsetsGeometry {
+    fn from(value: EdgeInsetsDirectional) -> Self {
+        EdgeInsetsGeometry::Directional(value)
+    }
+}
+
 pub trait BoxGeometryEdgeInsetsExt {
     fn deflate(&self, edges: EdgeInsets) -> Self;
     fn inflate(&self, edges: EdgeInsets) -> Self;
@@ -131,7 +220,59 @@ impl BoxGeometryEdgeInsetsExt for BoxSize {
 impl Widget for Padding {
     type ParentProtocol = BoxProtocol;
     type ChildProtocol = BoxProtocol;
-    type Element = PaddingElement;
+    type Element = ConsumerElement<BoxProtocol>;
+
+    fn into_arc_widget(self: Asc<Self>) -> <Self::Element as ElementBase>::ArcWidget {
+        self
+    }
+}
+
+lazy_static::lazy_static! {
+    static ref DIRECTIONAL_PADDING_CONSUMED_TYPES: [TypeKey; 1] = [
+        TypeKey::of_or_default::<TextDirection>(),
+    ];
+}
+
+impl ConsumerWidget<BoxProtocol> for Padding {
+    fn get_consumed_types(&self) -> &[TypeKey] {
+        if self.padding.is_directional() {
+            DIRECTIONAL_PADDING_CONSUMED_TYPES.as_ref()
+        } else {
+            &[]
+        }
+    }
+
+    fn build(
+        &self,
+        _ctx: &mut BuildContext,
+        provider_values: InlinableDwsizeVec<Arc<dyn Provide>>,
+    ) -> ArcBoxWidget {
+        let padding = match self.padding {
+            EdgeInsetsGeometry::Absolute(padding) => padding,
+            EdgeInsetsGeometry::Directional(padding) => {
+                let text_direction = read_providers!(provider_values, TextDirection);
+                padding.resolve(*text_direction)
+            }
+        };
+        Asc::new(RawPadding {
+            padding,
+            child: self.child.clone(),
+        })
+    }
+}
+
+/// [`Padding`] with a padding that has already been resolved.
+#[derive(Debug, Declarative, TypedBuilder)]
+#[builder(build_method(into=Asc<RawPadding>))]
+pub struct RawPadding {
+    pub padding: EdgeInsets,
+    pub child: ArcBoxWidget,
+}
+
+impl Widget for RawPadding {
+    type ParentProtocol = BoxProtocol;
+    type ChildProtocol = BoxProtocol;
+    type Element = RawPaddingElement;
 
     fn into_arc_widget(self: Asc<Self>) -> Asc<Self> {
         self
@@ -139,21 +280,21 @@ impl Widget for Padding {
 }
 
 #[derive(Clone)]
-pub struct PaddingElement {}
+pub struct RawPaddingElement {}
 
-impl ImplByTemplate for PaddingElement {
+impl ImplByTemplate for RawPaddingElement {
     type Template = BoxSingleChildElementTemplate<true, false>;
 }
 
-impl BoxSingleChildElement for PaddingElement {
-    type ArcWidget = Asc<Padding>;
+impl BoxSingleChildElement for RawPaddingElement {
+    type ArcWidget = Asc<RawPadding>;
 
     fn get_child_widget(
         _element: Option<&mut Self>,
         widget: &Self::ArcWidget,
         _ctx: &mut BuildContext<'_>,
         _provider_values: InlinableDwsizeVec<Arc<dyn Provide>>,
-    ) -> Result<ArcBoxWidget, BuildSuspendedError> {
+    ) -> Result<ArcBoxWidget, BuildError> {
         Ok(widget.child.clone())
     }
 
@@ -162,7 +303,7 @@ impl BoxSingleChildElement for PaddingElement {
     }
 }
 
-impl BoxSingleChildRenderElement for PaddingElement {
+impl BoxSingleChildRenderElement for RawPaddingElement {
     type Render = RenderPadding;
 
     fn create_render(&self, widget: &Self::ArcWidget) -> Self::Render {
@@ -208,15 +349,28 @@ impl ShiftedBoxRender for RenderPadding {
     }
 
     fn compute_intrinsics(&mut self, child: &ArcBoxRenderObject, intrinsics: &mut BoxIntrinsics) {
-        child.get_intrinsics(intrinsics);
+        let horizontal = self.padding.l + self.padding.r;
+        let vertical = self.padding.t + self.padding.b;
         match intrinsics {
-            BoxIntrinsics::MinWidth { res, .. } | BoxIntrinsics::MaxWidth { res, .. } => {
-                res.as_mut()
-                    .map(|res| *res += self.padding.l + self.padding.r);
+            BoxIntrinsics::MinWidth { height, res } => {
+                *res = child
+                    .get_min_intrinsic_width((*height - vertical).max(0.0))
+                    .map(|width| width + horizontal)
+            }
+            BoxIntrinsics::MaxWidth { height, res } => {
+                *res = child
+                    .get_max_intrinsic_width((*height - vertical).max(0.0))
+                    .map(|width| width + horizontal)
+            }
+            BoxIntrinsics::MinHeight { width, res } => {
+                *res = child
+                    .get_min_intrinsic_height((*width - horizontal).max(0.0))
+                    .map(|height| height + vertical)
             }
-            BoxIntrinsics::MinHeight { res, .. } | BoxIntrinsics::MaxHeight { res, .. } => {
-                res.as_mut()
-                    .map(|res| *res *= self.padding.t + self.padding.b);
+            BoxIntrinsics::MaxHeight { width, res } => {
+                *res = child
+                    .get_max_intrinsic_height((*width - horizontal).max(0.0))
+                    .map(|height| height + vertical)
             }
         }
     }
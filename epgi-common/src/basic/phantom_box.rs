@@ -1,8 +1,8 @@
 use std::sync::Arc;
 
-use epgi_2d::{Affine2dCanvas, BoxConstraints, BoxOffset, BoxProtocol, BoxSize};
+use epgi_2d::{Affine2dCanvas, BoxConstraints, BoxIntrinsics, BoxOffset, BoxProtocol, BoxSize};
 use epgi_core::{
-    foundation::{Asc, BuildSuspendedError, InlinableDwsizeVec, PaintContext, Provide},
+    foundation::{Asc, BuildError, InlinableDwsizeVec, PaintContext, Provide},
     template::{ImplByTemplate, LeafElement, LeafElementTemplate, LeafRender, LeafRenderTemplate},
     tree::{BuildContext, Widget},
 };
@@ -44,7 +44,7 @@ impl LeafElement for PhantomBoxElement {
         _widget: &Self::ArcWidget,
         _ctx: &mut BuildContext,
         _provider_values: InlinableDwsizeVec<Arc<dyn Provide>>,
-    ) -> Result<Self, BuildSuspendedError> {
+    ) -> Result<Self, BuildError> {
         Ok(Self)
     }
 
@@ -78,8 +78,14 @@ impl LeafRender for RenderPhantomBox {
 
     fn compute_intrinsics(
         _render: &mut Self,
-        _intrinsics: &mut <Self::Protocol as epgi_core::foundation::Protocol>::Intrinsics,
+        intrinsics: &mut <Self::Protocol as epgi_core::foundation::Protocol>::Intrinsics,
     ) {
-        // No-op
+        use BoxIntrinsics::*;
+        match intrinsics {
+            MinWidth { res, .. }
+            | MaxWidth { res, .. }
+            | MinHeight { res, .. }
+            | MaxHeight { res, .. } => *res = Some(0.0),
+        }
     }
 }
@@ -1,6 +1,8 @@
-use epgi_2d::{ArcBoxWidget, BoxProtocol, BoxSingleChildElement, BoxSingleChildElementTemplate};
+use epgi_2d::{
+    ArcBoxWidget, BoxProtocol, BoxSingleChildElement, BoxSingleChildElementTemplate, TextDirection,
+};
 use epgi_core::{
-    foundation::{Arc, Asc, BuildSuspendedError, InlinableDwsizeVec, Provide},
+    foundation::{Arc, Asc, BuildError, InlinableDwsizeVec, Provide},
     template::ImplByTemplate,
     tree::{BuildContext, RenderAction, Widget},
 };
@@ -13,6 +15,10 @@ pub struct PositionedConfig {
     pub r: Option<f32>,
     pub t: Option<f32>,
     pub b: Option<f32>,
+    /// Overrides `l` or `r`, whichever is the start edge of the text direction of the stack.
+    pub start: Option<f32>,
+    /// Overrides `l` or `r`, whichever is the end edge of the text direction of the stack.
+    pub end: Option<f32>,
     pub width: Option<f32>,
     pub height: Option<f32>,
 }
@@ -21,11 +27,23 @@ impl PositionedConfig {
     pub fn is_positioned(&self) -> bool {
         self.l.is_some()
             || self.r.is_some()
+            || self.start.is_some()
+            || self.end.is_some()
             || self.t.is_some()
             || self.b.is_some()
             || self.width.is_some()
             || self.height.is_some()
     }
+
+    /// The left and right edges, with `start` and `end` resolved against the text direction.
+    pub fn resolve_horizontal(&self, text_direction: TextDirection) -> (Option<f32>, Option<f32>) {
+        let (left, right) = if text_direction.is_rtl() {
+            (self.end, self.start)
+        } else {
+            (self.start, self.end)
+        };
+        (left.or(self.l), right.or(self.r))
+    }
 }
 
 #[derive(Debug, Declarative, TypedBuilder)]
@@ -39,6 +57,12 @@ pub struct Positioned {
     pub t: Option<f32>,
     #[builder(default, setter(strip_option))]
     pub b: Option<f32>,
+    /// The distance from the start edge of the stack, which is the left or the right edge depending on its text direction.
+    #[builder(default, setter(strip_option))]
+    pub start: Option<f32>,
+    /// The distance from the end edge of the stack, which is the left or the right edge depending on its text direction.
+    #[builder(default, setter(strip_option))]
+    pub end: Option<f32>,
     #[builder(default, setter(strip_option))]
     pub width: Option<f32>,
     #[builder(default, setter(strip_option))]
@@ -56,6 +80,8 @@ impl Positioned {
             r: self.r,
             t: self.t,
             b: self.b,
+            start: self.start,
+            end: self.end,
             width: self.width,
             height: self.height,
         }
@@ -89,7 +115,7 @@ impl BoxSingleChildElement for PositionedElement {
         widget: &Self::ArcWidget,
         _ctx: &mut BuildContext<'_>,
         _provider_values: InlinableDwsizeVec<Arc<dyn Provide>>,
-    ) -> Result<ArcBoxWidget, BuildSuspendedError> {
+    ) -> Result<ArcBoxWidget, BuildError> {
         Ok(widget.child.clone())
     }
 
@@ -4,27 +4,33 @@ use epgi_2d::{
     Affine2dCanvas, Affine2dPaintContextExt, ArcBoxRenderObject, ArcBoxWidget, BlendMode,
     BoxConstraints, BoxIntrinsics, BoxMultiChildElement, BoxMultiChildElementTemplate,
     BoxMultiChildHitTest, BoxMultiChildLayout, BoxMultiChildPaint, BoxMultiChildRender,
-    BoxMultiChildRenderTemplate, BoxOffset, BoxProtocol, BoxSize,
+    BoxMultiChildRenderTemplate, BoxOffset, BoxProtocol, BoxSize, TextDirection,
 };
 use epgi_core::{
     foundation::{
-        set_if_changed, Arc, Asc, BuildSuspendedError, InlinableDwsizeVec, PaintContext, Protocol,
-        Provide, ThreadPoolExt,
+        set_if_changed, Arc, Asc, AscProvideExt, BuildError, InlinableDwsizeVec, PaintContext,
+        Protocol, Provide, SmallVecExt, ThreadPoolExt, TypeKey,
     },
+    nodes::{ConsumerElement, ConsumerWidget},
+    read_providers,
     scheduler::get_current_scheduler,
     template::ImplByTemplate,
-    tree::{ArcChildRenderObject, BuildContext, RenderAction, Widget},
+    tree::{ArcChildRenderObject, BuildContext, ElementBase, RenderAction, Widget},
 };
 use epgi_macro::Declarative;
 use typed_builder::TypedBuilder;
 
-use crate::{Alignment, PositionedConfig};
+use crate::{Alignment, AlignmentDirectional, AlignmentGeometry, PositionedConfig};
 
+/// Stacks its children on top of each other.
+///
+/// The alignment of non-positioned children, as well as the `start` and `end` of positioned children,
+/// are resolved against the ambient [`TextDirection`].
 #[derive(Debug, Declarative, TypedBuilder)]
 #[builder(build_method(into=Asc<Stack>))]
 pub struct Stack {
-    #[builder(default=Alignment::TOP_LEFT)]
-    pub alignment: Alignment,
+    #[builder(default=AlignmentDirectional::TOP_START.into(), setter(into))]
+    pub alignment: AlignmentGeometry,
     #[builder(default=StackFit::Loose)]
     pub fit: StackFit,
     //TODO: Clip behavior
@@ -39,6 +45,56 @@ pub enum StackFit {
 }
 
 impl Widget for Stack {
+    type ParentProtocol = BoxProtocol;
+    type ChildProtocol = BoxProtocol;
+    type Element = ConsumerElement<BoxProtocol>;
+
+    fn into_arc_widget(self: Asc<Self>) -> <Self::Element as ElementBase>::ArcWidget {
+        self
+    }
+}
+
+lazy_static::lazy_static! {
+    static ref STACK_CONSUMED_TYPES: [TypeKey; 1] = [
+        TypeKey::of_or_default::<TextDirection>(),
+    ];
+}
+
+impl ConsumerWidget<BoxProtocol> for Stack {
+    fn get_consumed_types(&self) -> &[TypeKey] {
+        STACK_CONSUMED_TYPES.as_ref()
+    }
+
+    fn build(
+        &self,
+        _ctx: &mut BuildContext,
+        provider_values: InlinableDwsizeVec<Arc<dyn Provide>>,
+    ) -> ArcBoxWidget {
+        let text_direction = *read_providers!(provider_values, TextDirection);
+        Asc::new(RawStack {
+            alignment: self.alignment.resolve(text_direction),
+            text_direction,
+            fit: self.fit,
+            children: self.children.clone(),
+        })
+    }
+}
+
+/// [`Stack`] with an alignment and a text direction that have already been resolved.
+#[derive(Debug, Declarative, TypedBuilder)]
+#[builder(build_method(into=Asc<RawStack>))]
+pub struct RawStack {
+    #[builder(default=Alignment::TOP_LEFT)]
+    pub alignment: Alignment,
+    /// The direction that the `start` and `end` of positioned children are resolved against.
+    #[builder(default=TextDirection::Ltr)]
+    pub text_direction: TextDirection,
+    #[builder(default=StackFit::Loose)]
+    pub fit: StackFit,
+    pub children: Vec<ArcBoxWidget>,
+}
+
+impl Widget for RawStack {
     type ParentProtocol = BoxProtocol;
     type ChildProtocol = BoxProtocol;
     type Element = StackElement;
@@ -56,7 +112,7 @@ impl ImplByTemplate for StackElement {
 }
 
 impl BoxMultiChildElement for StackElement {
-    type ArcWidget = Asc<Stack>;
+    type ArcWidget = Asc<RawStack>;
     type Render = RenderStack;
 
     fn get_child_widgets(
@@ -64,7 +120,7 @@ impl BoxMultiChildElement for StackElement {
         widget: &Self::ArcWidget,
         _ctx: &mut BuildContext<'_>,
         _provider_values: InlinableDwsizeVec<Arc<dyn Provide>>,
-    ) -> Result<Vec<ArcBoxWidget>, BuildSuspendedError> {
+    ) -> Result<Vec<ArcBoxWidget>, BuildError> {
         Ok(widget.children.clone())
     }
 
@@ -75,6 +131,7 @@ impl BoxMultiChildElement for StackElement {
     fn create_render(&self, widget: &Self::ArcWidget) -> Self::Render {
         RenderStack {
             alignment: widget.alignment,
+            text_direction: widget.text_direction,
             fit: widget.fit,
         }
     }
@@ -82,6 +139,7 @@ impl BoxMultiChildElement for StackElement {
     fn update_render(render: &mut Self::Render, widget: &Self::ArcWidget) -> Option<RenderAction> {
         [
             set_if_changed(&mut render.alignment, widget.alignment),
+            set_if_changed(&mut render.text_direction, widget.text_direction),
             set_if_changed(&mut render.fit, widget.fit),
         ]
         .iter()
@@ -92,6 +150,7 @@ impl BoxMultiChildElement for StackElement {
 
 pub struct RenderStack {
     pub alignment: Alignment,
+    pub text_direction: TextDirection,
     pub fit: StackFit,
     //TODO: Clip behavior
 }
@@ -108,10 +167,41 @@ impl BoxMultiChildRender for RenderStack {
         children: &Vec<ArcBoxRenderObject>,
         intrinsics: &mut BoxIntrinsics,
     ) {
-        unimplemented!()
+        use BoxIntrinsics::*;
+        let query = *intrinsics;
+        // Only non-positioned children contribute to the size of the stack.
+        let extent = children
+            .iter()
+            .filter(|child| {
+                !get_positioned_config(child)
+                    .unwrap_or_default()
+                    .is_positioned()
+            })
+            .try_fold(0.0f32, |extent, child| {
+                let mut intrinsics = query;
+                child.get_intrinsics(&mut intrinsics);
+                let (MinWidth { res, .. }
+                | MaxWidth { res, .. }
+                | MinHeight { res, .. }
+                | MaxHeight { res, .. }) = intrinsics;
+                Some(extent.max(res?))
+            });
+        let (MinWidth { res, .. }
+        | MaxWidth { res, .. }
+        | MinHeight { res, .. }
+        | MaxHeight { res, .. }) = intrinsics;
+        *res = extent;
     }
 }
 
+fn get_positioned_config<P: Protocol>(child: &ArcChildRenderObject<P>) -> Option<PositionedConfig> {
+    child.as_ref().get_parent_data().and_then(|data| {
+        data.downcast::<PositionedConfig>()
+            .ok()
+            .map(|config| config.as_ref().clone())
+    })
+}
+
 impl BoxMultiChildLayout for RenderStack {
     fn perform_layout(
         &mut self,
@@ -145,16 +235,6 @@ impl BoxMultiChildLayout for RenderStack {
             .take(children.len())
             .collect::<Vec<_>>();
 
-        fn get_positioned_config<P: Protocol>(
-            child: &ArcChildRenderObject<P>,
-        ) -> Option<PositionedConfig> {
-            child.as_ref().get_parent_data().and_then(|data| {
-                data.downcast::<PositionedConfig>()
-                    .ok()
-                    .map(|config| config.as_ref().clone())
-            })
-        }
-
         fn layout_non_positioned_child(
             child: &ArcBoxRenderObject,
             positioned_config: &PositionedConfig,
@@ -205,6 +285,7 @@ impl BoxMultiChildLayout for RenderStack {
             child_size: &mut BoxSize,
             size: BoxSize,
             alignment: Alignment,
+            text_direction: TextDirection,
             has_visual_overflow: &AtomicBool,
         ) -> BoxOffset {
             if !positioned_config.is_positioned() {
@@ -214,13 +295,13 @@ impl BoxMultiChildLayout for RenderStack {
                 })
             } else {
                 let mut child_constraints = BoxConstraints::default();
+                let (l, r) = positioned_config.resolve_horizontal(text_direction);
                 let PositionedConfig {
-                    l,
-                    r,
                     t,
                     b,
                     width,
                     height,
+                    ..
                 } = positioned_config;
                 if let (Some(l), Some(r)) = (l, r) {
                     child_constraints = child_constraints.tighten_width(size.width - l - r);
@@ -237,7 +318,7 @@ impl BoxMultiChildLayout for RenderStack {
                 *child_size = child.layout_use_size(&child_constraints);
 
                 let x = if let Some(l) = l {
-                    *l
+                    l
                 } else if let Some(r) = r {
                     size.width - r - child_size.width
                 } else {
@@ -287,6 +368,7 @@ impl BoxMultiChildLayout for RenderStack {
                     child_size,
                     size,
                     self.alignment,
+                    self.text_direction,
                     &has_visual_overflow,
                 )
             },
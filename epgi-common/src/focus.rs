@@ -0,0 +1,11 @@
+mod manager;
+pub use manager::*;
+
+mod node;
+pub use node::*;
+
+mod use_focus_node;
+pub use use_focus_node::*;
+
+mod widget;
+pub use widget::*;
@@ -0,0 +1,325 @@
+use std::sync::atomic::Ordering::Release;
+
+use epgi_2d::{Rect, TextDirection};
+use epgi_core::{
+    foundation::{Arc, SyncMutex},
+    scheduler::get_current_scheduler,
+};
+
+use crate::{ImeEvent, KeyEvent, KeyEventResult, KeyModifiers, LogicalKey, NamedKey};
+
+use super::FocusNode;
+
+/// Owns the root of the focus tree and tracks the primary focus.
+///
+/// The embedding provides the root scope to the whole tree, routes key events through [`FocusManager::handle_key_event`],
+/// and calls [`FocusManager::flush_focus_changes`] at the beginning of every frame.
+#[derive(Clone)]
+pub struct FocusManager {
+    inner: Arc<SyncMutex<FocusManagerInner>>,
+}
+
+pub(super) struct FocusManagerInner {
+    root_scope: FocusNode,
+    primary_focus: Option<FocusNode>,
+    /// The nodes currently marked with [`FocusNode::has_focus`], from the primary focus up to the root.
+    focused_chain: Vec<FocusNode>,
+    pending_request: Option<FocusRequest>,
+}
+
+enum FocusRequest {
+    /// Focus the node, or the last focused descendant if the node is a scope.
+    Resolve(FocusNode),
+    /// Focus exactly this node.
+    Exact(FocusNode),
+}
+
+impl FocusManager {
+    pub fn new() -> Self {
+        let root_scope = FocusNode::new_scope();
+        let inner = Arc::new(SyncMutex::new(FocusManagerInner {
+            root_scope: root_scope.clone(),
+            primary_focus: None,
+            focused_chain: Vec::new(),
+            pending_request: None,
+        }));
+        root_scope.inner.state.lock().manager = Arc::downgrade(&inner);
+        Self { inner }
+    }
+
+    pub(super) fn from_inner(inner: Arc<SyncMutex<FocusManagerInner>>) -> Self {
+        Self { inner }
+    }
+
+    pub fn root_scope(&self) -> FocusNode {
+        self.inner.lock().root_scope.clone()
+    }
+
+    pub fn primary_focus(&self) -> Option<FocusNode> {
+        self.inner.lock().primary_focus.clone()
+    }
+
+    pub(super) fn request_focus(&self, node: FocusNode) {
+        self.request(FocusRequest::Resolve(node))
+    }
+
+    pub(super) fn request_focus_exact(&self, node: FocusNode) {
+        self.request(FocusRequest::Exact(node))
+    }
+
+    fn request(&self, request: FocusRequest) {
+        self.inner.lock().pending_request = Some(request);
+        get_current_scheduler().request_redraw.store(true, Release);
+    }
+
+    /// Apply the latest focus request and notify the nodes whose focus has changed.
+    pub fn flush_focus_changes(&self) {
+        let Some(request) = self.inner.lock().pending_request.take() else {
+            return;
+        };
+        let target = match request {
+            FocusRequest::Resolve(node) => {
+                if !node.is_scope() && !node.can_request_focus() {
+                    return;
+                }
+                resolve_scope_focus(node)
+            }
+            FocusRequest::Exact(node) => node,
+        };
+        if !target.is_attached() {
+            return;
+        }
+
+        let new_chain = target.ancestors_inclusive();
+        let old_chain = {
+            let mut inner = self.inner.lock();
+            if inner.primary_focus.as_ref() == Some(&target) {
+                return;
+            }
+            inner.primary_focus = Some(target.clone());
+            std::mem::replace(&mut inner.focused_chain, new_chain.clone())
+        };
+
+        for scope in new_chain.iter().skip(1).filter(|node| node.is_scope()) {
+            scope.inner.state.lock().focused_child = Some(Arc::downgrade(&target.inner));
+        }
+
+        let mut callbacks = Vec::new();
+        let changes = old_chain
+            .iter()
+            .filter(|node| !new_chain.contains(node))
+            .map(|node| (node, false))
+            .chain(
+                new_chain
+                    .iter()
+                    .filter(|node| !old_chain.contains(node))
+                    .map(|node| (node, true)),
+            );
+        for (node, has_focus) in changes {
+            let mut state = node.inner.state.lock();
+            state.has_focus = has_focus;
+            if let Some(on_focus_change) = &state.on_focus_change {
+                callbacks.push((on_focus_change.clone(), has_focus));
+            }
+        }
+        if !callbacks.is_empty() {
+            get_current_scheduler().create_sync_job(|job_builder| {
+                for (on_focus_change, has_focus) in callbacks {
+                    on_focus_change(has_focus, job_builder);
+                }
+            });
+        }
+    }
+
+    /// Offer the key event to the primary focus and then to its ancestors, until one of them handles it.
+    ///
+    /// Unhandled Tab and Shift+Tab presses move the focus in traversal order.
+    pub fn handle_key_event(&self, event: &KeyEvent) -> KeyEventResult {
+        let primary_focus = self.primary_focus();
+        let chain = primary_focus
+            .as_ref()
+            .map(FocusNode::ancestors_inclusive)
+            .unwrap_or_default();
+        for node in chain {
+            let Some(on_key_event) = node.inner.state.lock().on_key_event.clone() else {
+                continue;
+            };
+            let mut result = KeyEventResult::Ignored;
+            get_current_scheduler().create_sync_job(|job_builder| {
+                result = on_key_event(event, job_builder);
+            });
+            if result == KeyEventResult::Handled {
+                return KeyEventResult::Handled;
+            }
+        }
+
+        if event.is_pressed() && event.logical_key == LogicalKey::Named(NamedKey::Tab) {
+            let forward = if event.modifiers.is_empty() {
+                true
+            } else if event.modifiers == KeyModifiers::SHIFT {
+                false
+            } else {
+                return KeyEventResult::Ignored;
+            };
+            let from = primary_focus.unwrap_or_else(|| self.root_scope());
+            if self.traverse(&from, forward) {
+                return KeyEventResult::Handled;
+            }
+        }
+        KeyEventResult::Ignored
+    }
+
+    /// Offer the input method event to the primary focus and then to its ancestors, until one of them handles it.
+    pub fn handle_ime_event(&self, event: &ImeEvent) -> KeyEventResult {
+        let chain = self
+            .primary_focus()
+            .as_ref()
+            .map(FocusNode::ancestors_inclusive)
+            .unwrap_or_default();
+        for node in chain {
+            let Some(on_ime_event) = node.inner.state.lock().on_ime_event.clone() else {
+                continue;
+            };
+            let mut result = KeyEventResult::Ignored;
+            get_current_scheduler().create_sync_job(|job_builder| {
+                result = on_ime_event(event, job_builder);
+            });
+            if result == KeyEventResult::Handled {
+                return KeyEventResult::Handled;
+            }
+        }
+        KeyEventResult::Ignored
+    }
+
+    /// Move the focus from `from` to the next or previous traversable node within its enclosing scope.
+    ///
+    /// Nodes are ordered by the position they were last painted at, row by row from top to bottom,
+    /// then from left to right, or from right to left if the scope is laid out right-to-left.
+    pub(super) fn traverse(&self, from: &FocusNode, forward: bool) -> bool {
+        let scope = if from.is_scope() && from.parent().is_none() {
+            from.clone()
+        } else {
+            from.enclosing_scope().unwrap_or_else(|| self.root_scope())
+        };
+        let mut candidates = Vec::new();
+        collect_traversable_descendants(&scope, &mut candidates);
+        if candidates.is_empty() {
+            return false;
+        }
+        let candidates = sort_in_reading_order(candidates, scope.text_direction());
+        let next = match candidates.iter().position(|node| node == from) {
+            Some(index) if forward => (index + 1) % candidates.len(),
+            Some(index) => (index + candidates.len() - 1) % candidates.len(),
+            None if forward => 0,
+            None => candidates.len() - 1,
+        };
+        self.request_focus_exact(candidates[next].clone());
+        true
+    }
+}
+
+impl Default for FocusManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PartialEq for FocusManager {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
+impl std::fmt::Debug for FocusManager {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FocusManager")
+            .field("primary_focus", &self.primary_focus())
+            .finish()
+    }
+}
+
+fn resolve_scope_focus(node: FocusNode) -> FocusNode {
+    if !node.is_scope() {
+        return node;
+    }
+    let focused_child = node
+        .inner
+        .state
+        .lock()
+        .focused_child
+        .as_ref()
+        .and_then(|focused_child| focused_child.upgrade())
+        .map(FocusNode::from_inner);
+    match focused_child {
+        Some(focused_child)
+            if focused_child.can_request_focus()
+                && focused_child.ancestors_inclusive().contains(&node) =>
+        {
+            resolve_scope_focus(focused_child)
+        }
+        _ => node,
+    }
+}
+
+fn collect_traversable_descendants(node: &FocusNode, result: &mut Vec<FocusNode>) {
+    for child in node.children() {
+        let (can_request_focus, skip_traversal) = {
+            let state = child.inner.state.lock();
+            (state.can_request_focus, state.skip_traversal)
+        };
+        if !can_request_focus {
+            continue;
+        }
+        if !child.is_scope() && !skip_traversal {
+            result.push(child.clone());
+        }
+        collect_traversable_descendants(&child, result);
+    }
+}
+
+fn sort_in_reading_order(nodes: Vec<FocusNode>, text_direction: TextDirection) -> Vec<FocusNode> {
+    let (mut placed, unplaced): (Vec<_>, Vec<_>) = nodes
+        .into_iter()
+        .map(|node| (node.rect(), node))
+        .partition(|(rect, _)| rect.is_some());
+    placed.sort_by(|(a, _), (b, _)| a.as_ref().unwrap().t.total_cmp(&b.as_ref().unwrap().t));
+
+    // Within a row, a node comes first if it starts closer to the leading edge.
+    let leading_edge_distance = |rect: &Rect| match text_direction {
+        TextDirection::Ltr => rect.l,
+        TextDirection::Rtl => -rect.r,
+    };
+    // Group the nodes into rows. A node joins the current row if its vertical center lies within the row.
+    let mut rows: Vec<TraversalRow> = Vec::new();
+    for (rect, node) in placed {
+        let rect = rect.unwrap();
+        let center = (rect.t + rect.b) / 2.0;
+        let leading = leading_edge_distance(&rect);
+        match rows.last_mut() {
+            Some(row) if center >= row.top && center <= row.bottom => {
+                row.bottom = row.bottom.max(rect.b);
+                row.nodes.push((leading, node));
+            }
+            _ => rows.push(TraversalRow {
+                top: rect.t,
+                bottom: rect.b,
+                nodes: vec![(leading, node)],
+            }),
+        }
+    }
+    let mut result = Vec::new();
+    for mut row in rows {
+        row.nodes.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+        result.extend(row.nodes.into_iter().map(|(_, node)| node));
+    }
+    // Nodes that have never been painted come last, in tree order.
+    result.extend(unplaced.into_iter().map(|(_, node)| node));
+    result
+}
+
+struct TraversalRow {
+    top: f32,
+    bottom: f32,
+    /// The nodes in this row along with how far they start from the leading edge.
+    nodes: Vec<(f32, FocusNode)>,
+}
@@ -0,0 +1,303 @@
+use std::sync::{
+    atomic::{AtomicU64, Ordering::Relaxed},
+    Weak,
+};
+
+use epgi_2d::{BoxOffset, BoxSize, Rect, TextDirection};
+use epgi_core::{
+    foundation::{Arc, Asc, SyncMutex},
+    scheduler::JobBuilder,
+};
+
+use crate::{ArcImeEventCallback, ArcKeyEventCallback, FocusManager};
+
+use super::FocusManagerInner;
+
+pub type ArcFocusChangeCallback = Asc<dyn Fn(bool, &mut JobBuilder) + Send + Sync>;
+
+/// A node in the focus tree.
+///
+/// Focus nodes are created with [`use_focus_node`](crate::BuildContextFocusExt::use_focus_node)
+/// and attached to the focus tree by the [`Focus`](crate::Focus) and [`FocusScope`](crate::FocusScope) widgets.
+/// A scope node remembers its last focused descendant, and restores it when the scope itself requests focus.
+///
+/// Focus changes requested through a node are applied at the beginning of the next frame.
+#[derive(Clone)]
+pub struct FocusNode {
+    pub(super) inner: Arc<FocusNodeInner>,
+}
+
+pub(super) struct FocusNodeInner {
+    id: u64,
+    is_scope: bool,
+    pub(super) state: SyncMutex<FocusNodeState>,
+}
+
+pub(super) struct FocusNodeState {
+    pub(super) manager: Weak<SyncMutex<FocusManagerInner>>,
+    pub(super) parent: Option<Weak<FocusNodeInner>>,
+    pub(super) children: Vec<FocusNode>,
+    pub(super) can_request_focus: bool,
+    pub(super) skip_traversal: bool,
+    pub(super) has_focus: bool,
+    /// The last focused descendant. Only used by scopes.
+    pub(super) focused_child: Option<Weak<FocusNodeInner>>,
+    /// The rect painted by the attached widget, used to order the traversal.
+    pub(super) rect: Option<(BoxOffset, BoxSize)>,
+    /// The ambient text direction where the node is attached. A scope orders its traversal in this direction.
+    pub(super) text_direction: TextDirection,
+    pub(super) on_key_event: Option<ArcKeyEventCallback>,
+    pub(super) on_ime_event: Option<ArcImeEventCallback>,
+    pub(super) on_focus_change: Option<ArcFocusChangeCallback>,
+}
+
+static NEXT_FOCUS_NODE_ID: AtomicU64 = AtomicU64::new(0);
+
+impl FocusNode {
+    pub fn new() -> Self {
+        Self::new_with(false)
+    }
+
+    pub fn new_scope() -> Self {
+        Self::new_with(true)
+    }
+
+    fn new_with(is_scope: bool) -> Self {
+        Self {
+            inner: Arc::new(FocusNodeInner {
+                id: NEXT_FOCUS_NODE_ID.fetch_add(1, Relaxed),
+                is_scope,
+                state: SyncMutex::new(FocusNodeState {
+                    manager: Weak::new(),
+                    parent: None,
+                    children: Vec::new(),
+                    can_request_focus: true,
+                    skip_traversal: false,
+                    has_focus: false,
+                    focused_child: None,
+                    rect: None,
+                    text_direction: TextDirection::Ltr,
+                    on_key_event: None,
+                    on_ime_event: None,
+                    on_focus_change: None,
+                }),
+            }),
+        }
+    }
+
+    pub(super) fn from_inner(inner: Arc<FocusNodeInner>) -> Self {
+        Self { inner }
+    }
+
+    pub fn is_scope(&self) -> bool {
+        self.inner.is_scope
+    }
+
+    /// Whether this node or any of its descendants has the primary focus.
+    pub fn has_focus(&self) -> bool {
+        self.inner.state.lock().has_focus
+    }
+
+    /// Whether this node itself has the primary focus.
+    pub fn has_primary_focus(&self) -> bool {
+        self.manager()
+            .is_some_and(|manager| manager.primary_focus().as_ref() == Some(self))
+    }
+
+    pub fn can_request_focus(&self) -> bool {
+        self.inner.state.lock().can_request_focus
+    }
+
+    pub fn set_can_request_focus(&self, value: bool) {
+        self.inner.state.lock().can_request_focus = value;
+    }
+
+    pub fn skip_traversal(&self) -> bool {
+        self.inner.state.lock().skip_traversal
+    }
+
+    pub fn set_skip_traversal(&self, value: bool) {
+        self.inner.state.lock().skip_traversal = value;
+    }
+
+    pub fn set_on_key_event(&self, on_key_event: Option<ArcKeyEventCallback>) {
+        self.inner.state.lock().on_key_event = on_key_event;
+    }
+
+    pub fn set_on_ime_event(&self, on_ime_event: Option<ArcImeEventCallback>) {
+        self.inner.state.lock().on_ime_event = on_ime_event;
+    }
+
+    pub fn set_on_focus_change(&self, on_focus_change: Option<ArcFocusChangeCallback>) {
+        self.inner.state.lock().on_focus_change = on_focus_change;
+    }
+
+    /// The rect last painted by the widget this node is attached to.
+    pub fn rect(&self) -> Option<Rect> {
+        let (offset, size) = self.inner.state.lock().rect?;
+        Some(Rect::new_point_size(offset, size))
+    }
+
+    pub(crate) fn set_rect(&self, offset: BoxOffset, size: BoxSize) {
+        self.inner.state.lock().rect = Some((offset, size));
+    }
+
+    /// The ambient text direction where this node is attached.
+    pub fn text_direction(&self) -> TextDirection {
+        self.inner.state.lock().text_direction
+    }
+
+    pub(crate) fn set_text_direction(&self, text_direction: TextDirection) {
+        self.inner.state.lock().text_direction = text_direction;
+    }
+
+    pub fn parent(&self) -> Option<FocusNode> {
+        let parent = self.inner.state.lock().parent.clone()?;
+        parent.upgrade().map(FocusNode::from_inner)
+    }
+
+    /// Whether this node is reachable from the root scope of a [`FocusManager`].
+    pub fn is_attached(&self) -> bool {
+        self.manager().is_some()
+    }
+
+    pub(super) fn manager(&self) -> Option<FocusManager> {
+        self.inner
+            .state
+            .lock()
+            .manager
+            .upgrade()
+            .map(FocusManager::from_inner)
+    }
+
+    /// Request the primary focus for this node.
+    ///
+    /// A scope passes the request on to its last focused descendant, if there is any.
+    /// Has no effect if the node is not attached.
+    pub fn request_focus(&self) {
+        if let Some(manager) = self.manager() {
+            manager.request_focus(self.clone());
+        }
+    }
+
+    /// Give up the focus if this node or any of its descendants has it.
+    ///
+    /// The focus moves to the enclosing scope.
+    pub fn unfocus(&self) {
+        if !self.has_focus() {
+            return;
+        }
+        if let Some(manager) = self.manager() {
+            let scope = self.enclosing_scope().unwrap_or_else(|| self.clone());
+            manager.request_focus_exact(scope);
+        }
+    }
+
+    /// Move the focus to the next node in traversal order within the enclosing scope.
+    pub fn next_focus(&self) -> bool {
+        self.manager()
+            .is_some_and(|manager| manager.traverse(self, true))
+    }
+
+    /// Move the focus to the previous node in traversal order within the enclosing scope.
+    pub fn previous_focus(&self) -> bool {
+        self.manager()
+            .is_some_and(|manager| manager.traverse(self, false))
+    }
+
+    /// The nearest scope strictly above this node.
+    pub fn enclosing_scope(&self) -> Option<FocusNode> {
+        let mut node = self.parent();
+        while let Some(current) = node {
+            if current.is_scope() {
+                return Some(current);
+            }
+            node = current.parent();
+        }
+        None
+    }
+
+    /// This node followed by all of its ancestors.
+    pub(super) fn ancestors_inclusive(&self) -> Vec<FocusNode> {
+        let mut result = vec![self.clone()];
+        while let Some(parent) = result.last().unwrap().parent() {
+            result.push(parent);
+        }
+        result
+    }
+
+    pub(super) fn children(&self) -> Vec<FocusNode> {
+        self.inner.state.lock().children.clone()
+    }
+
+    /// Attach this node, along with its descendants, under `parent`.
+    pub fn attach(&self, parent: &FocusNode) {
+        assert!(self != parent, "A focus node cannot be attached to itself");
+        self.detach();
+        let manager = {
+            let mut parent_state = parent.inner.state.lock();
+            parent_state.children.push(self.clone());
+            parent_state.manager.clone()
+        };
+        self.inner.state.lock().parent = Some(Arc::downgrade(&parent.inner));
+        self.set_manager_recursive(&manager);
+    }
+
+    /// Remove this node, along with its descendants, from the focus tree.
+    ///
+    /// If the focus was inside of the detached subtree, it moves to the previous enclosing scope.
+    pub fn detach(&self) {
+        let Some(parent) = self.parent() else {
+            return;
+        };
+        if self.has_focus() {
+            if let Some(manager) = self.manager() {
+                let scope = self.enclosing_scope().unwrap_or_else(|| parent.clone());
+                manager.request_focus_exact(scope);
+            }
+        }
+        parent
+            .inner
+            .state
+            .lock()
+            .children
+            .retain(|child| child != self);
+        self.inner.state.lock().parent = None;
+        self.set_manager_recursive(&Weak::new());
+    }
+
+    fn set_manager_recursive(&self, manager: &Weak<SyncMutex<FocusManagerInner>>) {
+        let children = {
+            let mut state = self.inner.state.lock();
+            state.manager = manager.clone();
+            state.children.clone()
+        };
+        for child in children {
+            child.set_manager_recursive(manager);
+        }
+    }
+}
+
+impl Default for FocusNode {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PartialEq for FocusNode {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
+impl Eq for FocusNode {}
+
+impl std::fmt::Debug for FocusNode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FocusNode")
+            .field("id", &self.inner.id)
+            .field("is_scope", &self.inner.is_scope)
+            .field("has_focus", &self.has_focus())
+            .finish()
+    }
+}
@@ -0,0 +1,60 @@
+use epgi_core::tree::{BuildContext, Effect, EffectCleanup, Hook, HookState};
+
+use super::FocusNode;
+
+pub trait BuildContextFocusExt {
+    /// A focus node that lives as long as the calling element.
+    ///
+    /// The node is detached from the focus tree when the element is unmounted.
+    /// Pass it to a [`Focus`](crate::Focus) widget to attach it and to receive key events.
+    fn use_focus_node(&mut self) -> FocusNode;
+
+    /// Same as [`use_focus_node`](BuildContextFocusExt::use_focus_node), but creates a scope node.
+    fn use_focus_scope_node(&mut self) -> FocusNode;
+}
+
+impl BuildContextFocusExt for BuildContext<'_> {
+    fn use_focus_node(&mut self) -> FocusNode {
+        let (hook_state, _index) = self.use_hook(FocusNodeHook { is_scope: false });
+        hook_state.node.clone()
+    }
+
+    fn use_focus_scope_node(&mut self) -> FocusNode {
+        let (hook_state, _index) = self.use_hook(FocusNodeHook { is_scope: true });
+        hook_state.node.clone()
+    }
+}
+
+struct FocusNodeHook {
+    is_scope: bool,
+}
+
+impl Hook for FocusNodeHook {
+    type HookState = FocusNodeHookState;
+
+    fn create_hook_state(self) -> (Self::HookState, Option<impl Effect>) {
+        let node = if self.is_scope {
+            FocusNode::new_scope()
+        } else {
+            FocusNode::new()
+        };
+        let node_clone = node.clone();
+        let effect = move || Some(Box::new(move || node_clone.detach()) as Box<dyn EffectCleanup>);
+        (FocusNodeHookState { node }, Some(effect))
+    }
+
+    fn update_hook_state(self, _state: &mut Self::HookState) -> Option<impl Effect> {
+        None::<()>
+    }
+}
+
+#[derive(Clone)]
+struct FocusNodeHookState {
+    node: FocusNode,
+}
+
+impl HookState for FocusNodeHookState {
+    fn clone_box(&self) -> Box<dyn HookState> {
+        Box::new(self.clone())
+    }
+}
@@ -0,0 +1,280 @@
+use epgi_2d::{
+    Affine2dCanvas, ArcBoxRenderObject, ArcBoxWidget, BoxOffset, BoxProtocol,
+    BoxSingleChildElement, BoxSingleChildElementTemplate, BoxSingleChildRenderElement, BoxSize,
+    TextDirection,
+};
+use epgi_core::{
+    foundation::{
+        Arc, Asc, AscProvideExt, BuildError, InlinableDwsizeVec, PaintContext, Provide,
+        SmallVecExt, TypeKey,
+    },
+    nodes::{ComponentElement, ComponentWidget, ConsumerElement, ConsumerWidget},
+    read_providers,
+    scheduler::JobBuilder,
+    template::{ImplByTemplate, ProxyRender, ProxyRenderTemplate},
+    tree::{BuildContext, ElementBase, RenderAction, Widget},
+    Provider,
+};
+use epgi_macro::Declarative;
+use typed_builder::TypedBuilder;
+
+use crate::{ArcImeEventCallback, ArcKeyEventCallback, ImeEvent, KeyEvent, KeyEventResult};
+
+use super::{ArcFocusChangeCallback, BuildContextFocusExt, FocusNode};
+
+/// Attaches a [`FocusNode`] to the focus tree, under the nearest enclosing [`Focus`] or [`FocusScope`].
+///
+/// Key events and input method events are first offered to the node with the primary focus, then bubble up through its ancestors.
+#[derive(Declarative, TypedBuilder)]
+#[builder(build_method(into=Asc<Focus>))]
+pub struct Focus {
+    /// The node to attach. If not provided, the widget manages its own node.
+    #[builder(default, setter(strip_option))]
+    pub focus_node: Option<FocusNode>,
+    #[builder(default = false)]
+    pub autofocus: bool,
+    #[builder(default = true)]
+    pub can_request_focus: bool,
+    #[builder(default = false)]
+    pub skip_traversal: bool,
+    #[builder(default, setter(transform=|op: impl Fn(&KeyEvent, &mut JobBuilder) -> KeyEventResult + Send + Sync + 'static| Some(Asc::new(op) as _)))]
+    pub on_key_event: Option<ArcKeyEventCallback>,
+    /// Receives the input method events while this node or one of its descendants has the primary focus.
+    #[builder(default, setter(transform=|op: impl Fn(&ImeEvent, &mut JobBuilder) -> KeyEventResult + Send + Sync + 'static| Some(Asc::new(op) as _)))]
+    pub on_ime_event: Option<ArcImeEventCallback>,
+    #[builder(default, setter(transform=|op: impl Fn(bool, &mut JobBuilder) + Send + Sync + 'static| Some(Asc::new(op) as _)))]
+    pub on_focus_change: Option<ArcFocusChangeCallback>,
+    pub child: ArcBoxWidget,
+}
+
+impl std::fmt::Debug for Focus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Focus")
+            .field("focus_node", &self.focus_node)
+            .field("autofocus", &self.autofocus)
+            .field("can_request_focus", &self.can_request_focus)
+            .field("skip_traversal", &self.skip_traversal)
+            .field("child", &self.child)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Widget for Focus {
+    type ParentProtocol = BoxProtocol;
+    type ChildProtocol = BoxProtocol;
+    type Element = ComponentElement<BoxProtocol>;
+
+    fn into_arc_widget(self: Asc<Self>) -> <Self::Element as ElementBase>::ArcWidget {
+        self
+    }
+}
+
+impl ComponentWidget<BoxProtocol> for Focus {
+    fn build(&self, ctx: &mut BuildContext<'_>) -> ArcBoxWidget {
+        let own_node = ctx.use_focus_node();
+        let node = self.focus_node.clone().unwrap_or(own_node);
+        node.set_can_request_focus(self.can_request_focus);
+        node.set_skip_traversal(self.skip_traversal);
+        node.set_on_key_event(self.on_key_event.clone());
+        node.set_on_ime_event(self.on_ime_event.clone());
+        node.set_on_focus_change(self.on_focus_change.clone());
+        Asc::new(FocusAttachment {
+            node,
+            autofocus: self.autofocus,
+            child: self.child.clone(),
+        })
+    }
+}
+
+/// Groups its descendant focus nodes. Traversal stays within the nearest scope,
+/// and a scope restores its last focused descendant when it requests focus.
+#[derive(Declarative, TypedBuilder)]
+#[builder(build_method(into=Asc<FocusScope>))]
+pub struct FocusScope {
+    /// The scope node to attach. If not provided, the widget manages its own node.
+    #[builder(default, setter(strip_option))]
+    pub node: Option<FocusNode>,
+    #[builder(default = false)]
+    pub autofocus: bool,
+    #[builder(default, setter(transform=|op: impl Fn(&KeyEvent, &mut JobBuilder) -> KeyEventResult + Send + Sync + 'static| Some(Asc::new(op) as _)))]
+    pub on_key_event: Option<ArcKeyEventCallback>,
+    pub child: ArcBoxWidget,
+}
+
+impl std::fmt::Debug for FocusScope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FocusScope")
+            .field("node", &self.node)
+            .field("autofocus", &self.autofocus)
+            .field("child", &self.child)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Widget for FocusScope {
+    type ParentProtocol = BoxProtocol;
+    type ChildProtocol = BoxProtocol;
+    type Element = ComponentElement<BoxProtocol>;
+
+    fn into_arc_widget(self: Asc<Self>) -> <Self::Element as ElementBase>::ArcWidget {
+        self
+    }
+}
+
+impl ComponentWidget<BoxProtocol> for FocusScope {
+    fn build(&self, ctx: &mut BuildContext<'_>) -> ArcBoxWidget {
+        let own_node = ctx.use_focus_scope_node();
+        let node = self.node.clone().unwrap_or(own_node);
+        assert!(node.is_scope(), "FocusScope requires a scope node");
+        node.set_on_key_event(self.on_key_event.clone());
+        Asc::new(FocusAttachment {
+            node,
+            autofocus: self.autofocus,
+            child: self.child.clone(),
+        })
+    }
+}
+
+#[derive(Debug)]
+struct FocusAttachment {
+    node: FocusNode,
+    autofocus: bool,
+    child: ArcBoxWidget,
+}
+
+impl Widget for FocusAttachment {
+    type ParentProtocol = BoxProtocol;
+    type ChildProtocol = BoxProtocol;
+    type Element = ConsumerElement<BoxProtocol>;
+
+    fn into_arc_widget(self: Asc<Self>) -> <Self::Element as ElementBase>::ArcWidget {
+        self
+    }
+}
+
+lazy_static::lazy_static! {
+    static ref FOCUS_ATTACHMENT_CONSUMED_TYPES: [TypeKey; 2] = [
+        TypeKey::of::<FocusNode>(),
+        TypeKey::of_or_default::<TextDirection>(),
+    ];
+}
+
+impl ConsumerWidget<BoxProtocol> for FocusAttachment {
+    fn get_consumed_types(&self) -> &[TypeKey] {
+        FOCUS_ATTACHMENT_CONSUMED_TYPES.as_ref()
+    }
+
+    fn build(
+        &self,
+        ctx: &mut BuildContext,
+        provider_values: InlinableDwsizeVec<Arc<dyn Provide>>,
+    ) -> ArcBoxWidget {
+        let (parent, text_direction) = read_providers!(provider_values, FocusNode, TextDirection);
+        self.node.set_text_direction(*text_direction);
+        ctx.use_effect(
+            |(node, parent): (FocusNode, FocusNode)| {
+                node.attach(&parent);
+                move || node.detach()
+            },
+            (self.node.clone(), parent.as_ref().clone()),
+        );
+        let autofocus = self.autofocus;
+        ctx.use_effect(
+            move |node: FocusNode| {
+                if autofocus {
+                    node.request_focus()
+                }
+            },
+            self.node.clone(),
+        );
+        let child = Asc::new(RawFocus {
+            node: self.node.clone(),
+            child: self.child.clone(),
+        });
+        Provider!(value = self.node.clone(), child)
+    }
+}
+
+/// Records the painted rect of its child into the focus node, which orders the focus traversal.
+#[derive(Debug)]
+struct RawFocus {
+    node: FocusNode,
+    child: ArcBoxWidget,
+}
+
+impl Widget for RawFocus {
+    type ParentProtocol = BoxProtocol;
+    type ChildProtocol = BoxProtocol;
+    type Element = RawFocusElement;
+
+    fn into_arc_widget(self: Asc<Self>) -> <Self::Element as ElementBase>::ArcWidget {
+        self
+    }
+}
+
+#[derive(Clone)]
+struct RawFocusElement;
+
+impl ImplByTemplate for RawFocusElement {
+    type Template = BoxSingleChildElementTemplate<true, false>;
+}
+
+impl BoxSingleChildElement for RawFocusElement {
+    type ArcWidget = Asc<RawFocus>;
+
+    fn get_child_widget(
+        _element: Option<&mut Self>,
+        widget: &Self::ArcWidget,
+        _ctx: &mut BuildContext<'_>,
+        _provider_values: InlinableDwsizeVec<Arc<dyn Provide>>,
+    ) -> Result<ArcBoxWidget, BuildError> {
+        Ok(widget.child.clone())
+    }
+
+    fn create_element(_widget: &Self::ArcWidget) -> Self {
+        Self
+    }
+}
+
+impl BoxSingleChildRenderElement for RawFocusElement {
+    type Render = RenderFocus;
+
+    fn create_render(&self, widget: &Self::ArcWidget) -> Self::Render {
+        RenderFocus {
+            node: widget.node.clone(),
+        }
+    }
+
+    fn update_render(render: &mut Self::Render, widget: &Self::ArcWidget) -> Option<RenderAction> {
+        if render.node != widget.node {
+            render.node = widget.node.clone();
+            return Some(RenderAction::Repaint);
+        }
+        None
+    }
+}
+
+struct RenderFocus {
+    node: FocusNode,
+}
+
+impl ImplByTemplate for RenderFocus {
+    type Template = ProxyRenderTemplate;
+}
+
+impl ProxyRender for RenderFocus {
+    type Protocol = BoxProtocol;
+
+    fn perform_paint(
+        &self,
+        size: &BoxSize,
+        offset: &BoxOffset,
+        child: &ArcBoxRenderObject,
+        paint_ctx: &mut impl PaintContext<Canvas = Affine2dCanvas>,
+    ) {
+        self.node.set_rect(*offset, *size);
+        paint_ctx.paint(child, offset);
+    }
+
+    const NOOP_DETACH: bool = true;
+}
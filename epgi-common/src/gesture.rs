@@ -12,3 +12,12 @@ pub use recognizers::*;
 
 mod detector;
 pub use detector::*;
+
+mod mouse_cursor;
+pub use mouse_cursor::*;
+
+mod mouse_region;
+pub use mouse_region::*;
+
+mod mouse_tracker;
+pub use mouse_tracker::*;
@@ -5,7 +5,7 @@ use epgi_2d::{
     BoxSingleChildRenderElement, BoxSize, Point2d,
 };
 use epgi_core::{
-    foundation::{AnyRawPointer, Asc, BuildSuspendedError, InlinableDwsizeVec, Provide},
+    foundation::{AnyRawPointer, Asc, BuildError, InlinableDwsizeVec, Provide},
     hit_test_interface_query_table,
     nodes::{ComponentElement, ComponentWidget},
     scheduler::JobBuilder,
@@ -17,15 +17,52 @@ use hashbrown::HashMap;
 use typed_builder::TypedBuilder;
 
 use crate::{
-    ArcJobCallback, GestureRecognizer, GestureRecognizerTeamPolicy, PointerEvent,
-    PointerEventHandler, TapGestureRecognizer,
+    ArcDragEndCallback, ArcDragStartCallback, ArcDragUpdateCallback, ArcJobCallback,
+    ArcScaleEndCallback, ArcScaleStartCallback, ArcScaleUpdateCallback, DoubleTapGestureRecognizer,
+    DragCallbacks, DragEndDetails, DragStartDetails, DragUpdateDetails, GestureRecognizer,
+    GestureRecognizerTeamPolicy, HorizontalDragGestureRecognizer, LongPressGestureRecognizer,
+    PanGestureRecognizer, PointerEvent, PointerEventHandler, ScaleCallbacks, ScaleEndDetails,
+    ScaleGestureRecognizer, ScaleStartDetails, ScaleUpdateDetails, TapGestureRecognizer,
+    VerticalDragGestureRecognizer,
 };
 
+/// Calls back on the gestures of its descendants that it has callbacks for.
+///
+/// All recognizers of a detector compete with each other. For example, a tap is not called when a double tap follows,
+/// and a drag cancels a pending tap or long press once it moves past the slop.
 #[derive(Declarative, TypedBuilder)]
 #[builder(build_method(into=Asc<GestureDetector>))]
 pub struct GestureDetector {
     #[builder(default, setter(transform=|op: impl Fn(&mut JobBuilder) + Send + Sync + 'static| Some(Asc::new(op) as _)))]
     pub on_tap: Option<ArcJobCallback>,
+    #[builder(default, setter(transform=|op: impl Fn(&mut JobBuilder) + Send + Sync + 'static| Some(Asc::new(op) as _)))]
+    pub on_double_tap: Option<ArcJobCallback>,
+    #[builder(default, setter(transform=|op: impl Fn(&mut JobBuilder) + Send + Sync + 'static| Some(Asc::new(op) as _)))]
+    pub on_long_press: Option<ArcJobCallback>,
+    #[builder(default, setter(transform=|op: impl Fn(&DragStartDetails, &mut JobBuilder) + Send + Sync + 'static| Some(Asc::new(op) as _)))]
+    pub on_horizontal_drag_start: Option<ArcDragStartCallback>,
+    #[builder(default, setter(transform=|op: impl Fn(&DragUpdateDetails, &mut JobBuilder) + Send + Sync + 'static| Some(Asc::new(op) as _)))]
+    pub on_horizontal_drag_update: Option<ArcDragUpdateCallback>,
+    #[builder(default, setter(transform=|op: impl Fn(&DragEndDetails, &mut JobBuilder) + Send + Sync + 'static| Some(Asc::new(op) as _)))]
+    pub on_horizontal_drag_end: Option<ArcDragEndCallback>,
+    #[builder(default, setter(transform=|op: impl Fn(&DragStartDetails, &mut JobBuilder) + Send + Sync + 'static| Some(Asc::new(op) as _)))]
+    pub on_vertical_drag_start: Option<ArcDragStartCallback>,
+    #[builder(default, setter(transform=|op: impl Fn(&DragUpdateDetails, &mut JobBuilder) + Send + Sync + 'static| Some(Asc::new(op) as _)))]
+    pub on_vertical_drag_update: Option<ArcDragUpdateCallback>,
+    #[builder(default, setter(transform=|op: impl Fn(&DragEndDetails, &mut JobBuilder) + Send + Sync + 'static| Some(Asc::new(op) as _)))]
+    pub on_vertical_drag_end: Option<ArcDragEndCallback>,
+    #[builder(default, setter(transform=|op: impl Fn(&DragStartDetails, &mut JobBuilder) + Send + Sync + 'static| Some(Asc::new(op) as _)))]
+    pub on_pan_start: Option<ArcDragStartCallback>,
+    #[builder(default, setter(transform=|op: impl Fn(&DragUpdateDetails, &mut JobBuilder) + Send + Sync + 'static| Some(Asc::new(op) as _)))]
+    pub on_pan_update: Option<ArcDragUpdateCallback>,
+    #[builder(default, setter(transform=|op: impl Fn(&DragEndDetails, &mut JobBuilder) + Send + Sync + 'static| Some(Asc::new(op) as _)))]
+    pub on_pan_end: Option<ArcDragEndCallback>,
+    #[builder(default, setter(transform=|op: impl Fn(&ScaleStartDetails, &mut JobBuilder) + Send + Sync + 'static| Some(Asc::new(op) as _)))]
+    pub on_scale_start: Option<ArcScaleStartCallback>,
+    #[builder(default, setter(transform=|op: impl Fn(&ScaleUpdateDetails, &mut JobBuilder) + Send + Sync + 'static| Some(Asc::new(op) as _)))]
+    pub on_scale_update: Option<ArcScaleUpdateCallback>,
+    #[builder(default, setter(transform=|op: impl Fn(&ScaleEndDetails, &mut JobBuilder) + Send + Sync + 'static| Some(Asc::new(op) as _)))]
+    pub on_scale_end: Option<ArcScaleEndCallback>,
     pub child: ArcBoxWidget,
 }
 
@@ -33,6 +70,41 @@ impl std::fmt::Debug for GestureDetector {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("GestureDetector")
             .field("on_tap", &self.on_tap.as_ref().map(|_| ()))
+            .field("on_double_tap", &self.on_double_tap.as_ref().map(|_| ()))
+            .field("on_long_press", &self.on_long_press.as_ref().map(|_| ()))
+            .field(
+                "on_horizontal_drag_start",
+                &self.on_horizontal_drag_start.as_ref().map(|_| ()),
+            )
+            .field(
+                "on_horizontal_drag_update",
+                &self.on_horizontal_drag_update.as_ref().map(|_| ()),
+            )
+            .field(
+                "on_horizontal_drag_end",
+                &self.on_horizontal_drag_end.as_ref().map(|_| ()),
+            )
+            .field(
+                "on_vertical_drag_start",
+                &self.on_vertical_drag_start.as_ref().map(|_| ()),
+            )
+            .field(
+                "on_vertical_drag_update",
+                &self.on_vertical_drag_update.as_ref().map(|_| ()),
+            )
+            .field(
+                "on_vertical_drag_end",
+                &self.on_vertical_drag_end.as_ref().map(|_| ()),
+            )
+            .field("on_pan_start", &self.on_pan_start.as_ref().map(|_| ()))
+            .field("on_pan_update", &self.on_pan_update.as_ref().map(|_| ()))
+            .field("on_pan_end", &self.on_pan_end.as_ref().map(|_| ()))
+            .field("on_scale_start", &self.on_scale_start.as_ref().map(|_| ()))
+            .field(
+                "on_scale_update",
+                &self.on_scale_update.as_ref().map(|_| ()),
+            )
+            .field("on_scale_end", &self.on_scale_end.as_ref().map(|_| ()))
             .field("child", &self.child)
             .finish()
     }
@@ -54,15 +126,72 @@ impl ComponentWidget<BoxProtocol> for GestureDetector {
     fn build(&self, _ctx: &mut BuildContext<'_>) -> ArcBoxWidget {
         let mut recognizer_factories = Vec::new();
         if let Some(on_tap) = &self.on_tap {
-            recognizer_factories.push(GestureRecognizerFactory::new::<TapGestureRecognizer>(
-                {
-                    let on_tap = on_tap.clone();
-                    move || TapGestureRecognizer::new(on_tap.clone())
-                },
-                {
-                    let on_tap = on_tap.clone();
-                    move |recognizer| recognizer.update(on_tap.clone())
-                },
+            recognizer_factories.push(GestureRecognizerFactory::with_callbacks(
+                on_tap.clone(),
+                TapGestureRecognizer::new,
+                TapGestureRecognizer::update,
+            ));
+        }
+        if let Some(on_double_tap) = &self.on_double_tap {
+            recognizer_factories.push(GestureRecognizerFactory::with_callbacks(
+                on_double_tap.clone(),
+                DoubleTapGestureRecognizer::new,
+                DoubleTapGestureRecognizer::update,
+            ));
+        }
+        if let Some(on_long_press) = &self.on_long_press {
+            recognizer_factories.push(GestureRecognizerFactory::with_callbacks(
+                on_long_press.clone(),
+                LongPressGestureRecognizer::new,
+                LongPressGestureRecognizer::update,
+            ));
+        }
+        let horizontal_drag_callbacks = DragCallbacks {
+            on_start: self.on_horizontal_drag_start.clone(),
+            on_update: self.on_horizontal_drag_update.clone(),
+            on_end: self.on_horizontal_drag_end.clone(),
+        };
+        if !horizontal_drag_callbacks.is_empty() {
+            recognizer_factories.push(GestureRecognizerFactory::with_callbacks(
+                horizontal_drag_callbacks,
+                HorizontalDragGestureRecognizer::new,
+                HorizontalDragGestureRecognizer::update,
+            ));
+        }
+        let vertical_drag_callbacks = DragCallbacks {
+            on_start: self.on_vertical_drag_start.clone(),
+            on_update: self.on_vertical_drag_update.clone(),
+            on_end: self.on_vertical_drag_end.clone(),
+        };
+        if !vertical_drag_callbacks.is_empty() {
+            recognizer_factories.push(GestureRecognizerFactory::with_callbacks(
+                vertical_drag_callbacks,
+                VerticalDragGestureRecognizer::new,
+                VerticalDragGestureRecognizer::update,
+            ));
+        }
+        let pan_callbacks = DragCallbacks {
+            on_start: self.on_pan_start.clone(),
+            on_update: self.on_pan_update.clone(),
+            on_end: self.on_pan_end.clone(),
+        };
+        if !pan_callbacks.is_empty() {
+            recognizer_factories.push(GestureRecognizerFactory::with_callbacks(
+                pan_callbacks,
+                PanGestureRecognizer::new,
+                PanGestureRecognizer::update,
+            ));
+        }
+        let scale_callbacks = ScaleCallbacks {
+            on_start: self.on_scale_start.clone(),
+            on_update: self.on_scale_update.clone(),
+            on_end: self.on_scale_end.clone(),
+        };
+        if !scale_callbacks.is_empty() {
+            recognizer_factories.push(GestureRecognizerFactory::with_callbacks(
+                scale_callbacks,
+                ScaleGestureRecognizer::new,
+                ScaleGestureRecognizer::update,
             ));
         }
         Asc::new(RawGestureDetector {
@@ -101,6 +230,20 @@ impl GestureRecognizerFactory {
             }),
         }
     }
+
+    fn with_callbacks<T: GestureRecognizer, C: Clone + Send + Sync + 'static>(
+        callbacks: C,
+        create: fn(C) -> T,
+        update: fn(&T, C),
+    ) -> Self {
+        Self::new::<T>(
+            {
+                let callbacks = callbacks.clone();
+                move || create(callbacks.clone())
+            },
+            move |recognizer| update(recognizer, callbacks.clone()),
+        )
+    }
 }
 
 impl std::fmt::Debug for GestureRecognizerFactory {
@@ -138,7 +281,7 @@ impl BoxSingleChildElement for RawGestureDetectorElement {
         widget: &Self::ArcWidget,
         _ctx: &mut BuildContext<'_>,
         _provider_values: InlinableDwsizeVec<Arc<dyn Provide>>,
-    ) -> Result<ArcBoxWidget, BuildSuspendedError> {
+    ) -> Result<ArcBoxWidget, BuildError> {
         Ok(widget.child.clone())
     }
 
@@ -150,9 +150,14 @@ pub struct PointerPanZoomUpdateData {
 
 #[derive(Clone, Copy, Debug)]
 pub enum PointerSignalData {
-    Scroll { physical_delta: BoxOffset },
+    /// A positive delta scrolls towards the end of the content, i.e. moves the content up or to the left.
+    Scroll {
+        physical_delta: BoxOffset,
+    },
     ScrollInertialCancel,
-    Scale { scale: f32 },
+    Scale {
+        scale: f32,
+    },
 }
 
 bitflags::bitflags! {
@@ -196,12 +201,12 @@ impl PointerEvent {
         }
     }
 
-    // pub fn new_signal(common: PointerEventCommonData, signal: PointerSignalData) -> Self {
-    //     Self {
-    //         common,
-    //         variant: PointerEventVariantData::Signal(signal)
-    //     }
-    // }
+    pub fn new_signal(common: PointerEventCommonData, signal: PointerSignalData) -> Self {
+        Self {
+            common,
+            variant: PointerEventVariantData::Signal(signal),
+        }
+    }
 
     pub fn new_down(
         common: PointerEventCommonData,
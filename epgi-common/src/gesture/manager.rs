@@ -2,11 +2,12 @@ mod arena;
 
 use arena::*;
 
-use std::{any::TypeId, time::Instant};
+use std::{any::TypeId, sync::atomic::Ordering::Release, time::Instant};
 
 use epgi_2d::{Affine2d, ArcBoxRenderObject};
 use epgi_core::{
     foundation::{Arc, AssertExt, SyncMpscReceiver, SyncMpscSender, TransformHitPosition},
+    scheduler::get_current_scheduler,
     tree::HitTestContext,
 };
 use hashbrown::{hash_map::Entry, HashMap};
@@ -14,8 +15,8 @@ use hashbrown::{hash_map::Entry, HashMap};
 // use crate::gesture::PointerEventKind;
 
 use crate::gesture::{
-    PointerEventHandler, PointerEventVariantData, PointerInteractionEvent,
-    PointerInteractionVariantData, RecognitionResult,
+    MouseTracker, PointerDeviceKind, PointerEventHandler, PointerEventVariantData,
+    PointerInteractionEvent, PointerInteractionVariantData, RecognitionResult,
 };
 
 use super::{PointerEvent, PointerInteractionId};
@@ -28,21 +29,32 @@ pub struct PointerGestureManager {
     pointers_in_contact:
         HashMap<PointerInteractionId, Vec<(Affine2d, Arc<dyn PointerEventHandler>)>>,
     arenas: HashMap<PointerInteractionId, GestureArena>,
+    mouse_tracker: MouseTracker,
 }
 
 impl PointerGestureManager {
-    pub fn new(rx: SyncMpscReceiver<PointerEvent>) -> Self {
+    pub fn new(rx: SyncMpscReceiver<PointerEvent>, mouse_tracker: MouseTracker) -> Self {
         Self {
             rx,
             pointers_in_contact: Default::default(),
             arenas: Default::default(),
+            mouse_tracker,
         }
     }
 
     pub fn flush_events(&mut self, root: &ArcBoxRenderObject) {
+        let mut has_mouse_event = false;
         while let Ok(event) = self.rx.try_recv() {
+            if event.common.pointer_kind == PointerDeviceKind::Mouse {
+                has_mouse_event = true;
+                self.mouse_tracker.handle_pointer_event(&event, root);
+            }
             self.handle_pointer_event(event, root.clone())
         }
+        // The last frame may have moved things under the mouse even if the mouse itself has not moved.
+        if !has_mouse_event {
+            self.mouse_tracker.refresh(root);
+        }
     }
 
     pub fn poll_revisit_all(&mut self, current: Instant) {
@@ -50,7 +62,12 @@ impl PointerGestureManager {
         for (&interaction_id, arena) in self.arenas.iter_mut() {
             arena.poll_revisit(interaction_id, current, &mut associated_updates);
         }
+        self.arenas.retain(|_, arena| !arena.is_closed());
         self.process_associated_updates(associated_updates);
+        // Revisits are only polled at the beginning of a frame, so keep the frames coming until they are all done.
+        if self.arenas.values().any(GestureArena::has_pending_revisit) {
+            get_current_scheduler().request_redraw.store(true, Release);
+        }
     }
 
     fn handle_pointer_event(&mut self, event: PointerEvent, root: ArcBoxRenderObject) {
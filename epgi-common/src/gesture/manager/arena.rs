@@ -31,6 +31,20 @@ impl GestureArena {
     pub(super) fn is_closed(&self) -> bool {
         self.state.is_closed()
     }
+
+    /// Whether any member is waiting to be revisited.
+    pub(super) fn has_pending_revisit(&self) -> bool {
+        use GestureArenaState::*;
+        match &self.state {
+            Competing { teams } => teams.iter().any(|team| {
+                team.members
+                    .iter()
+                    .any(|member| member.last_result.is_inconclusive())
+            }),
+            Resolved { winner } => winner.member_handle.last_result.is_inconclusive(),
+            Closed => false,
+        }
+    }
 }
 
 enum GestureArenaState {
@@ -294,17 +308,7 @@ impl GestureArena {
         interaction_id: PointerInteractionId,
         associated_updates: &mut AssociatedUpdates,
     ) {
-        use GestureArenaState::*;
-        let has_inconclusive = match &self.state {
-            Competing { teams } => teams.iter().any(|team| {
-                team.members
-                    .iter()
-                    .any(|member| member.last_result.is_inconclusive())
-            }),
-            Resolved { winner } => winner.member_handle.last_result.is_inconclusive(),
-            Closed => false,
-        };
-        if !has_inconclusive {
+        if !self.has_pending_revisit() {
             self.sweep_immediately(interaction_id, associated_updates)
         }
     }
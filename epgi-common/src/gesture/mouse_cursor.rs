@@ -0,0 +1,27 @@
+/// The shape of the mouse cursor requested by a [`MouseRegion`](crate::MouseRegion).
+///
+/// The names follow the CSS `cursor` property. Embeddings map them to the closest cursor of the platform.
+#[derive(PartialEq, Eq, Clone, Copy, Default, Debug)]
+pub enum CursorIcon {
+    #[default]
+    Default,
+    /// A link or a button.
+    Pointer,
+    Text,
+    Crosshair,
+    Help,
+    Wait,
+    Progress,
+    Move,
+    Grab,
+    Grabbing,
+    NotAllowed,
+    EwResize,
+    NsResize,
+    NeswResize,
+    NwseResize,
+    ColResize,
+    RowResize,
+    ZoomIn,
+    ZoomOut,
+}
@@ -0,0 +1,188 @@
+use std::any::TypeId;
+
+use epgi_2d::{
+    ArcBoxWidget, BoxOffset, BoxProtocol, BoxSingleChildElement, BoxSingleChildElementTemplate,
+    BoxSingleChildRenderElement, BoxSize, Point2d,
+};
+use epgi_core::{
+    foundation::{AnyRawPointer, Arc, Asc, BuildError, InlinableDwsizeVec, Provide},
+    hit_test_interface_query_table,
+    scheduler::{get_current_scheduler, JobBuilder},
+    template::{ImplByTemplate, ProxyRender, ProxyRenderTemplate},
+    tree::{BuildContext, ElementBase, HitTestResult, RenderAction, RenderObject, Widget},
+};
+use epgi_macro::Declarative;
+use typed_builder::TypedBuilder;
+
+use crate::{CursorIcon, MouseTrackerAnnotation, PointerEvent};
+
+#[derive(Clone, Copy, Debug)]
+pub struct MouseRegionEvent {
+    pub global_position: Point2d,
+    /// The position in the coordinate space of the layer enclosing the region.
+    pub local_position: Point2d,
+}
+
+pub type ArcMouseRegionCallback = Asc<dyn Fn(&MouseRegionEvent, &mut JobBuilder) + Send + Sync>;
+
+/// Calls back when the mouse enters, hovers over or exits its child, and requests a cursor while the mouse is over it.
+///
+/// `on_hover` is only called for mouse movements without any button pressed.
+/// Among nested regions, the innermost region with a `cursor` decides the cursor.
+#[derive(Declarative, TypedBuilder)]
+#[builder(build_method(into=Asc<MouseRegion>))]
+pub struct MouseRegion {
+    #[builder(default, setter(transform=|op: impl Fn(&MouseRegionEvent, &mut JobBuilder) + Send + Sync + 'static| Some(Asc::new(op) as _)))]
+    pub on_enter: Option<ArcMouseRegionCallback>,
+    #[builder(default, setter(transform=|op: impl Fn(&MouseRegionEvent, &mut JobBuilder) + Send + Sync + 'static| Some(Asc::new(op) as _)))]
+    pub on_hover: Option<ArcMouseRegionCallback>,
+    #[builder(default, setter(transform=|op: impl Fn(&MouseRegionEvent, &mut JobBuilder) + Send + Sync + 'static| Some(Asc::new(op) as _)))]
+    pub on_exit: Option<ArcMouseRegionCallback>,
+    #[builder(default, setter(strip_option))]
+    pub cursor: Option<CursorIcon>,
+    pub child: ArcBoxWidget,
+}
+
+impl std::fmt::Debug for MouseRegion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MouseRegion")
+            .field("on_enter", &self.on_enter.as_ref().map(|_| ()))
+            .field("on_hover", &self.on_hover.as_ref().map(|_| ()))
+            .field("on_exit", &self.on_exit.as_ref().map(|_| ()))
+            .field("cursor", &self.cursor)
+            .field("child", &self.child)
+            .finish()
+    }
+}
+
+impl Widget for MouseRegion {
+    type ParentProtocol = BoxProtocol;
+
+    type ChildProtocol = BoxProtocol;
+
+    type Element = MouseRegionElement;
+
+    fn into_arc_widget(self: Arc<Self>) -> <Self::Element as ElementBase>::ArcWidget {
+        self
+    }
+}
+
+#[derive(Clone)]
+pub struct MouseRegionElement;
+
+impl ImplByTemplate for MouseRegionElement {
+    type Template = BoxSingleChildElementTemplate<true, false>;
+}
+
+impl BoxSingleChildElement for MouseRegionElement {
+    type ArcWidget = Asc<MouseRegion>;
+
+    fn get_child_widget(
+        _element: Option<&mut Self>,
+        widget: &Self::ArcWidget,
+        _ctx: &mut BuildContext<'_>,
+        _provider_values: InlinableDwsizeVec<Arc<dyn Provide>>,
+    ) -> Result<ArcBoxWidget, BuildError> {
+        Ok(widget.child.clone())
+    }
+
+    fn create_element(_widget: &Self::ArcWidget) -> Self {
+        Self
+    }
+}
+
+impl BoxSingleChildRenderElement for MouseRegionElement {
+    type Render = RenderMouseRegion;
+
+    fn create_render(&self, widget: &Self::ArcWidget) -> Self::Render {
+        RenderMouseRegion {
+            widget: widget.clone(),
+        }
+    }
+
+    fn update_render(render: &mut Self::Render, widget: &Self::ArcWidget) -> Option<RenderAction> {
+        render.widget = widget.clone();
+        None
+    }
+}
+
+pub struct RenderMouseRegion {
+    widget: Asc<MouseRegion>,
+}
+
+impl ImplByTemplate for RenderMouseRegion {
+    type Template = ProxyRenderTemplate;
+}
+
+impl ProxyRender for RenderMouseRegion {
+    type Protocol = BoxProtocol;
+
+    fn hit_test_self(
+        &self,
+        _position: &Point2d,
+        _size: &BoxSize,
+        _offset: &BoxOffset,
+    ) -> HitTestResult {
+        HitTestResult::Hit
+    }
+
+    fn all_hit_test_interfaces() -> &'static [(TypeId, fn(*mut RenderObject<Self>) -> AnyRawPointer)]
+    {
+        MOUSE_REGION_HIT_TEST_INTERFACE_TABLE.as_slice()
+    }
+}
+
+hit_test_interface_query_table!(
+    MOUSE_REGION_HIT_TEST_INTERFACE_TABLE,
+    RenderMouseRegion,
+    dyn MouseTrackerAnnotation,
+);
+
+fn call_back(
+    render_object: &RenderObject<RenderMouseRegion>,
+    callback: impl FnOnce(&MouseRegion) -> Option<ArcMouseRegionCallback>,
+    transformed_position: Point2d,
+    event: &PointerEvent,
+) {
+    let Some(callback) = render_object.update(|render, _| callback(&render.widget)) else {
+        return;
+    };
+    let region_event = MouseRegionEvent {
+        global_position: event.common.position,
+        local_position: transformed_position,
+    };
+    get_current_scheduler().create_sync_job(|job_builder| callback(&region_event, job_builder));
+}
+
+impl MouseTrackerAnnotation for RenderObject<RenderMouseRegion> {
+    fn handle_mouse_enter(&self, transformed_position: Point2d, event: &PointerEvent) {
+        call_back(
+            self,
+            |widget| widget.on_enter.clone(),
+            transformed_position,
+            event,
+        )
+    }
+
+    fn handle_mouse_hover(&self, transformed_position: Point2d, event: &PointerEvent) {
+        call_back(
+            self,
+            |widget| widget.on_hover.clone(),
+            transformed_position,
+            event,
+        )
+    }
+
+    fn handle_mouse_exit(&self, transformed_position: Point2d, event: &PointerEvent) {
+        call_back(
+            self,
+            |widget| widget.on_exit.clone(),
+            transformed_position,
+            event,
+        )
+    }
+
+    fn cursor(&self) -> Option<CursorIcon> {
+        self.update(|render, _| render.widget.cursor)
+    }
+}
@@ -0,0 +1,152 @@
+use std::any::TypeId;
+
+use epgi_2d::{Affine2d, ArcBoxRenderObject, Point2d};
+use epgi_core::{
+    foundation::{Arc, Aweak, PtrEq, SyncMutex, TransformHitPosition},
+    tree::HitTestContext,
+};
+
+use super::{CursorIcon, PointerDeviceKind, PointerEvent, PointerEventVariantData};
+
+/// Render objects that want to know when the mouse enters, hovers over or exits them.
+///
+/// The [`MouseTracker`] finds them by hit-testing the mouse position with this interface.
+pub trait MouseTrackerAnnotation: Send + Sync {
+    fn handle_mouse_enter(&self, transformed_position: Point2d, event: &PointerEvent);
+
+    fn handle_mouse_hover(&self, transformed_position: Point2d, event: &PointerEvent);
+
+    fn handle_mouse_exit(&self, transformed_position: Point2d, event: &PointerEvent);
+
+    /// The cursor requested while the mouse is over this object, if any.
+    fn cursor(&self) -> Option<CursorIcon>;
+}
+
+/// Tracks which [`MouseTrackerAnnotation`]s are under the mouse, and which cursor they request.
+///
+/// Annotations are compared between two hit tests, which happen on every mouse event,
+/// and once more at the beginning of every frame to catch annotations moving under a stationary mouse.
+/// The embedding applies [`MouseTracker::cursor`] to its window after every frame.
+#[derive(Clone)]
+pub struct MouseTracker {
+    inner: Arc<SyncMutex<MouseTrackerInner>>,
+}
+
+struct MouseTrackerInner {
+    /// The latest mouse event, or none if the mouse is not over the window.
+    last_event: Option<PointerEvent>,
+    /// The annotations under the mouse with their transforms, from the innermost to the outermost.
+    hovered: Vec<(Affine2d, Aweak<dyn MouseTrackerAnnotation>)>,
+    cursor: CursorIcon,
+}
+
+impl MouseTracker {
+    pub fn new() -> Self {
+        Self {
+            inner: Arc::new(SyncMutex::new(MouseTrackerInner {
+                last_event: None,
+                hovered: Vec::new(),
+                cursor: CursorIcon::Default,
+            })),
+        }
+    }
+
+    /// The cursor requested by the innermost annotation under the mouse.
+    pub fn cursor(&self) -> CursorIcon {
+        self.inner.lock().cursor
+    }
+
+    /// Update the annotations under the mouse with a new mouse event. Events from other kinds of pointers are ignored.
+    pub fn handle_pointer_event(&self, event: &PointerEvent, root: &ArcBoxRenderObject) {
+        if event.common.pointer_kind != PointerDeviceKind::Mouse {
+            return;
+        }
+        let mut inner = self.inner.lock();
+        if let PointerEventVariantData::Removed = event.variant {
+            inner.last_event = None;
+            inner.update_hovered(Vec::new(), event);
+            return;
+        }
+        inner.last_event = Some(event.clone());
+        let hovered = hit_test(event.common.position, root);
+        inner.update_hovered(hovered, event);
+        if let PointerEventVariantData::Hover(_) = event.variant {
+            for (transform, annotation) in inner.hovered.iter() {
+                if let Some(annotation) = annotation.upgrade() {
+                    annotation
+                        .handle_mouse_hover(transform.transform(&event.common.position), event);
+                }
+            }
+        }
+    }
+
+    /// Hit-test the last mouse position again, in case the annotations have moved since the last mouse event.
+    pub fn refresh(&self, root: &ArcBoxRenderObject) {
+        let mut inner = self.inner.lock();
+        let Some(last_event) = inner.last_event.clone() else {
+            return;
+        };
+        let hovered = hit_test(last_event.common.position, root);
+        inner.update_hovered(hovered, &last_event);
+    }
+}
+
+impl Default for MouseTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MouseTrackerInner {
+    fn update_hovered(
+        &mut self,
+        hovered: Vec<(Affine2d, Aweak<dyn MouseTrackerAnnotation>)>,
+        event: &PointerEvent,
+    ) {
+        let position = event.common.position;
+        let contains = |list: &[(Affine2d, Aweak<dyn MouseTrackerAnnotation>)],
+                        annotation: &Aweak<dyn MouseTrackerAnnotation>| {
+            list.iter()
+                .any(|(_, other)| PtrEq(other) == PtrEq(annotation))
+        };
+        // Exit from the innermost, and enter from the outermost.
+        for (transform, annotation) in self.hovered.iter() {
+            if !contains(&hovered, annotation) {
+                if let Some(annotation) = annotation.upgrade() {
+                    annotation.handle_mouse_exit(transform.transform(&position), event);
+                }
+            }
+        }
+        for (transform, annotation) in hovered.iter().rev() {
+            if !contains(&self.hovered, annotation) {
+                if let Some(annotation) = annotation.upgrade() {
+                    annotation.handle_mouse_enter(transform.transform(&position), event);
+                }
+            }
+        }
+        self.cursor = hovered
+            .iter()
+            .find_map(|(_, annotation)| annotation.upgrade()?.cursor())
+            .unwrap_or_default();
+        self.hovered = hovered;
+    }
+}
+
+fn hit_test(
+    position: Point2d,
+    root: &ArcBoxRenderObject,
+) -> Vec<(Affine2d, Aweak<dyn MouseTrackerAnnotation>)> {
+    let mut results = HitTestContext::new(position, TypeId::of::<dyn MouseTrackerAnnotation>());
+    root.clone().hit_test_with(&mut results);
+    results
+        .targets
+        .into_iter()
+        .map(|(transform, render_object)| {
+            let annotation = render_object
+                .query_interface_arc::<dyn MouseTrackerAnnotation>()
+                .ok()
+                .expect("Hit test should only return render objects with the requested interface");
+            (transform, Arc::downgrade(&annotation))
+        })
+        .collect()
+}
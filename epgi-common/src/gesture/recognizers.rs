@@ -1,2 +1,30 @@
+mod constants;
+pub use constants::*;
+
+mod double_tap;
+pub use double_tap::*;
+
+mod drag;
+pub use drag::*;
+
+mod long_press;
+pub use long_press::*;
+
+mod scale;
+pub use scale::*;
+
 mod tap;
 pub use tap::*;
+
+mod velocity_tracker;
+pub use velocity_tracker::*;
+
+use crate::{PointerButtons, PointerInteractionVariantData};
+
+fn is_primary_button_down(variant: &PointerInteractionVariantData) -> bool {
+    matches!(
+        variant,
+        PointerInteractionVariantData::Down(contact)
+            if contact.buttons.contains(PointerButtons::PRIMARY_BUTTON)
+    )
+}
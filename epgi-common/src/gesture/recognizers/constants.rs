@@ -0,0 +1,22 @@
+use std::time::Duration;
+
+/// How far a pointer can move before a tap, a long press or a double tap is cancelled, and a drag starts.
+pub const TOUCH_SLOP: f32 = 18.0;
+
+/// How far a pointer has to move before a pan starts.
+pub const PAN_SLOP: f32 = 2.0 * TOUCH_SLOP;
+
+/// How long a pointer has to stay down to be recognized as a long press.
+pub const LONG_PRESS_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// How long after the first tap is released the second tap of a double tap has to come down.
+pub const DOUBLE_TAP_TIMEOUT: Duration = Duration::from_millis(300);
+
+/// How far apart the two taps of a double tap can be.
+pub const DOUBLE_TAP_SLOP: f32 = 100.0;
+
+/// How far a trackpad pinch has to change the scale before it is recognized as a scale gesture.
+pub const SCALE_SLOP: f32 = 0.05;
+
+/// How far a trackpad gesture has to rotate, in radians, before it is recognized as a scale gesture.
+pub const ROTATION_SLOP: f32 = 0.1;
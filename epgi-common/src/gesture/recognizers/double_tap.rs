@@ -0,0 +1,311 @@
+use std::{any::TypeId, time::Instant};
+
+use epgi_2d::Point2d;
+use epgi_core::{foundation::SyncMutex, scheduler::get_current_scheduler};
+
+use crate::{
+    ArcJobCallback, GestureRecognizer, PointerInteractionEvent, PointerInteractionId,
+    PointerInteractionVariantData, RecognizerResponse,
+};
+
+use super::{
+    is_primary_button_down, DOUBLE_TAP_SLOP, DOUBLE_TAP_TIMEOUT, LONG_PRESS_TIMEOUT, TOUCH_SLOP,
+};
+
+/// Recognizes two taps in quick succession at about the same place.
+///
+/// The first tap keeps its arena inconclusive for [`DOUBLE_TAP_TIMEOUT`] after it is released, so that a competing
+/// tap is not called before it is clear that no second tap follows. Once the second tap is released, the double tap
+/// claims both arenas.
+pub struct DoubleTapGestureRecognizer {
+    inner: SyncMutex<DoubleTapGestureRecognizerInner>,
+}
+
+struct DoubleTapGestureRecognizerInner {
+    on_double_tap: ArcJobCallback,
+    state: DoubleTapState,
+}
+
+#[derive(Clone, Copy)]
+enum DoubleTapState {
+    Idle,
+    FirstDown {
+        interaction_id: PointerInteractionId,
+        position: Point2d,
+    },
+    WaitingForSecondDown {
+        first_interaction_id: PointerInteractionId,
+        position: Point2d,
+        deadline: Instant,
+    },
+    SecondDown {
+        first_interaction_id: PointerInteractionId,
+        interaction_id: PointerInteractionId,
+        position: Point2d,
+        /// When the second tap has to be released.
+        deadline: Instant,
+        /// The revisit time last reported to the arena of the first tap.
+        reported_deadline: Instant,
+    },
+    Recognized {
+        first_interaction_id: PointerInteractionId,
+    },
+}
+
+impl DoubleTapGestureRecognizer {
+    pub fn new(on_double_tap: ArcJobCallback) -> Self {
+        Self {
+            inner: SyncMutex::new(DoubleTapGestureRecognizerInner {
+                on_double_tap,
+                state: DoubleTapState::Idle,
+            }),
+        }
+    }
+
+    pub fn update(&self, on_double_tap: ArcJobCallback) {
+        self.inner.lock().on_double_tap = on_double_tap;
+    }
+}
+
+impl DoubleTapGestureRecognizerInner {
+    /// The state of this recognizer in the arena of `interaction_id`.
+    ///
+    /// `revisit_due` tells whether the arena is asking because the revisit time last reported to it has come.
+    fn recognition_state(
+        &mut self,
+        interaction_id: PointerInteractionId,
+        revisit_due: bool,
+    ) -> RecognizerResponse {
+        use DoubleTapState::*;
+        match &mut self.state {
+            FirstDown {
+                interaction_id: id, ..
+            } if *id == interaction_id => RecognizerResponse::possible(),
+            WaitingForSecondDown {
+                first_interaction_id,
+                deadline,
+                ..
+            } if *first_interaction_id == interaction_id => {
+                if revisit_due {
+                    self.state = Idle;
+                    RecognizerResponse::impossible()
+                } else {
+                    RecognizerResponse::inconclusive(*deadline)
+                }
+            }
+            SecondDown {
+                first_interaction_id,
+                interaction_id: second_interaction_id,
+                deadline,
+                reported_deadline,
+                ..
+            } if *first_interaction_id == interaction_id => {
+                if revisit_due && *reported_deadline == *deadline {
+                    let second_interaction_id = *second_interaction_id;
+                    self.state = Idle;
+                    RecognizerResponse {
+                        associated_arenas: vec![second_interaction_id],
+                        ..RecognizerResponse::impossible()
+                    }
+                } else {
+                    *reported_deadline = *deadline;
+                    RecognizerResponse::inconclusive(*deadline)
+                }
+            }
+            SecondDown {
+                interaction_id: id, ..
+            } if *id == interaction_id => RecognizerResponse::possible(),
+            Recognized {
+                first_interaction_id,
+            } if *first_interaction_id == interaction_id => RecognizerResponse::certain(1.0),
+            _ => RecognizerResponse::impossible(),
+        }
+    }
+
+    /// Give up the current sequence, and let the arena of the other tap, if any, know about it.
+    fn reset(&mut self, interaction_id: PointerInteractionId) -> RecognizerResponse {
+        use DoubleTapState::*;
+        let other_interaction_id = match self.state {
+            SecondDown {
+                first_interaction_id,
+                interaction_id: second_interaction_id,
+                ..
+            } => {
+                if first_interaction_id == interaction_id {
+                    Some(second_interaction_id)
+                } else {
+                    Some(first_interaction_id)
+                }
+            }
+            _ => None,
+        };
+        self.state = Idle;
+        RecognizerResponse {
+            associated_arenas: other_interaction_id.into_iter().collect(),
+            ..RecognizerResponse::impossible()
+        }
+    }
+
+    fn is_tracking(&self, interaction_id: PointerInteractionId) -> bool {
+        use DoubleTapState::*;
+        match self.state {
+            Idle => false,
+            FirstDown {
+                interaction_id: id, ..
+            }
+            | WaitingForSecondDown {
+                first_interaction_id: id,
+                ..
+            }
+            | Recognized {
+                first_interaction_id: id,
+            } => id == interaction_id,
+            SecondDown {
+                first_interaction_id,
+                interaction_id: id,
+                ..
+            } => first_interaction_id == interaction_id || id == interaction_id,
+        }
+    }
+}
+
+impl GestureRecognizer for DoubleTapGestureRecognizer {
+    fn handle_event(
+        &self,
+        _transformed_position: &Point2d,
+        event: &PointerInteractionEvent,
+    ) -> RecognizerResponse {
+        use DoubleTapState::*;
+        use PointerInteractionVariantData::*;
+        let mut inner = self.inner.lock();
+        let interaction_id = event.interaction_id;
+        let event_position = event.common.position;
+        let time = event.common.time_stamp;
+        if is_primary_button_down(&event.variant) {
+            return match inner.state {
+                Idle => {
+                    inner.state = FirstDown {
+                        interaction_id,
+                        position: event_position,
+                    };
+                    RecognizerResponse::possible()
+                }
+                WaitingForSecondDown {
+                    first_interaction_id,
+                    position,
+                    deadline,
+                } => {
+                    inner.state = if time <= deadline
+                        && (event_position - position).distance() <= DOUBLE_TAP_SLOP
+                    {
+                        SecondDown {
+                            first_interaction_id,
+                            interaction_id,
+                            position: event_position,
+                            deadline: time + LONG_PRESS_TIMEOUT,
+                            reported_deadline: deadline,
+                        }
+                    } else {
+                        // Too late or too far away. This tap may be the first of another double tap.
+                        FirstDown {
+                            interaction_id,
+                            position: event_position,
+                        }
+                    };
+                    // Either way, the arena of the first tap should hear about it.
+                    RecognizerResponse {
+                        associated_arenas: vec![first_interaction_id],
+                        ..RecognizerResponse::possible()
+                    }
+                }
+                // Another pointer is in the middle of a double tap.
+                _ => RecognizerResponse::impossible(),
+            };
+        }
+        match (inner.state, &event.variant) {
+            (
+                FirstDown {
+                    interaction_id: id,
+                    position,
+                }
+                | SecondDown {
+                    interaction_id: id,
+                    position,
+                    ..
+                },
+                Move(_),
+            ) if id == interaction_id => {
+                if (event_position - position).distance() <= TOUCH_SLOP {
+                    RecognizerResponse::possible()
+                } else {
+                    inner.reset(interaction_id)
+                }
+            }
+            (
+                FirstDown {
+                    interaction_id: id,
+                    position,
+                },
+                Up(_),
+            ) if id == interaction_id && (event_position - position).distance() <= TOUCH_SLOP => {
+                let deadline = time + DOUBLE_TAP_TIMEOUT;
+                inner.state = WaitingForSecondDown {
+                    first_interaction_id: id,
+                    position,
+                    deadline,
+                };
+                RecognizerResponse::inconclusive(deadline)
+            }
+            (
+                SecondDown {
+                    first_interaction_id,
+                    interaction_id: id,
+                    position,
+                    ..
+                },
+                Up(_),
+            ) if id == interaction_id && (event_position - position).distance() <= TOUCH_SLOP => {
+                inner.state = Recognized {
+                    first_interaction_id,
+                };
+                let on_double_tap = inner.on_double_tap.clone();
+                get_current_scheduler().create_sync_job(|job_builder| {
+                    on_double_tap(job_builder);
+                });
+                RecognizerResponse {
+                    associated_arenas: vec![first_interaction_id],
+                    ..RecognizerResponse::certain(1.0)
+                }
+            }
+            _ if inner.is_tracking(interaction_id) => inner.reset(interaction_id),
+            _ => RecognizerResponse::impossible(),
+        }
+    }
+
+    /// The arena of the first tap only queries when the revisit time it was given has come,
+    /// or when the double tap has asked it to through associated arenas.
+    fn query_recognition_state(&self, interaction_id: PointerInteractionId) -> RecognizerResponse {
+        self.inner.lock().recognition_state(interaction_id, true)
+    }
+
+    fn handle_arena_victory(&self, interaction_id: PointerInteractionId) -> RecognizerResponse {
+        self.inner.lock().recognition_state(interaction_id, false)
+    }
+
+    fn handle_arena_evict(&self, interaction_id: PointerInteractionId) -> RecognizerResponse {
+        let mut inner = self.inner.lock();
+        // The arena of the second tap is swept after a double tap has been recognized, which should not reset it.
+        if !inner.is_tracking(interaction_id) {
+            return RecognizerResponse::impossible();
+        }
+        inner.reset(interaction_id)
+    }
+
+    fn on_detach(&self) {
+        self.inner.lock().state = DoubleTapState::Idle;
+    }
+
+    fn recognizer_type_id(&self) -> TypeId {
+        TypeId::of::<Self>()
+    }
+}
@@ -0,0 +1,295 @@
+use std::{any::TypeId, marker::PhantomData};
+
+use epgi_2d::{BoxOffset, Point2d};
+use epgi_core::{
+    foundation::{Asc, SyncMutex},
+    scheduler::{get_current_scheduler, JobBuilder},
+};
+use hashbrown::HashMap;
+
+use crate::{
+    GestureRecognizer, PointerInteractionEvent, PointerInteractionId,
+    PointerInteractionVariantData, RecognizerResponse,
+};
+
+use super::{is_primary_button_down, VelocityTracker, PAN_SLOP, TOUCH_SLOP};
+
+#[derive(Clone, Copy, Debug)]
+pub struct DragStartDetails {
+    pub global_position: Point2d,
+    pub local_position: Point2d,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct DragUpdateDetails {
+    /// The movement since the last update, along the direction of the drag.
+    pub delta: BoxOffset,
+    pub global_position: Point2d,
+    pub local_position: Point2d,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct DragEndDetails {
+    /// The velocity in logical pixels per second when the pointer was released, along the direction of the drag.
+    pub velocity: BoxOffset,
+}
+
+pub type ArcDragStartCallback = Asc<dyn Fn(&DragStartDetails, &mut JobBuilder) + Send + Sync>;
+pub type ArcDragUpdateCallback = Asc<dyn Fn(&DragUpdateDetails, &mut JobBuilder) + Send + Sync>;
+pub type ArcDragEndCallback = Asc<dyn Fn(&DragEndDetails, &mut JobBuilder) + Send + Sync>;
+
+#[derive(Clone, Default)]
+pub struct DragCallbacks {
+    pub on_start: Option<ArcDragStartCallback>,
+    pub on_update: Option<ArcDragUpdateCallback>,
+    pub on_end: Option<ArcDragEndCallback>,
+}
+
+impl DragCallbacks {
+    pub fn is_empty(&self) -> bool {
+        self.on_start.is_none() && self.on_update.is_none() && self.on_end.is_none()
+    }
+}
+
+/// The direction a [`DragGestureRecognizer`] follows.
+pub trait DragDirection: Send + Sync + 'static {
+    /// How far the pointer has to move along the direction before the drag is recognized.
+    const SLOP: f32;
+
+    /// The part of a movement that is along the direction.
+    fn project(offset: BoxOffset) -> BoxOffset;
+}
+
+pub struct HorizontalDrag;
+
+impl DragDirection for HorizontalDrag {
+    const SLOP: f32 = TOUCH_SLOP;
+
+    fn project(offset: BoxOffset) -> BoxOffset {
+        BoxOffset {
+            x: offset.x,
+            y: 0.0,
+        }
+    }
+}
+
+pub struct VerticalDrag;
+
+impl DragDirection for VerticalDrag {
+    const SLOP: f32 = TOUCH_SLOP;
+
+    fn project(offset: BoxOffset) -> BoxOffset {
+        BoxOffset {
+            x: 0.0,
+            y: offset.y,
+        }
+    }
+}
+
+/// Drags in any direction.
+pub struct Pan;
+
+impl DragDirection for Pan {
+    const SLOP: f32 = PAN_SLOP;
+
+    fn project(offset: BoxOffset) -> BoxOffset {
+        offset
+    }
+}
+
+pub type HorizontalDragGestureRecognizer = DragGestureRecognizer<HorizontalDrag>;
+pub type VerticalDragGestureRecognizer = DragGestureRecognizer<VerticalDrag>;
+pub type PanGestureRecognizer = DragGestureRecognizer<Pan>;
+
+/// Recognizes a primary button press that moves further than [`DragDirection::SLOP`] along the direction.
+///
+/// The drag claims the arena as soon as it passes the slop. If it wins the arena before that, e.g. as the only
+/// recognizer, it starts right away.
+pub struct DragGestureRecognizer<D: DragDirection> {
+    inner: SyncMutex<DragGestureRecognizerInner>,
+    phantom: PhantomData<D>,
+}
+
+struct DragGestureRecognizerInner {
+    callbacks: DragCallbacks,
+    drags: HashMap<PointerInteractionId, DragState>,
+}
+
+struct DragState {
+    initial_position: Point2d,
+    initial_local_position: Point2d,
+    last_position: Point2d,
+    last_local_position: Point2d,
+    /// The movement along the direction that has not been reported, because the drag has not won the arena yet.
+    pending_delta: BoxOffset,
+    velocity_tracker: VelocityTracker,
+    has_won: bool,
+}
+
+impl<D: DragDirection> DragGestureRecognizer<D> {
+    pub fn new(callbacks: DragCallbacks) -> Self {
+        Self {
+            inner: SyncMutex::new(DragGestureRecognizerInner {
+                callbacks,
+                drags: Default::default(),
+            }),
+            phantom: PhantomData,
+        }
+    }
+
+    pub fn update(&self, callbacks: DragCallbacks) {
+        self.inner.lock().callbacks = callbacks;
+    }
+
+    fn end(callbacks: &DragCallbacks, velocity: BoxOffset) {
+        let Some(on_end) = callbacks.on_end.clone() else {
+            return;
+        };
+        let details = DragEndDetails { velocity };
+        get_current_scheduler().create_sync_job(|job_builder| on_end(&details, job_builder));
+    }
+}
+
+impl<D: DragDirection> GestureRecognizer for DragGestureRecognizer<D> {
+    fn handle_event(
+        &self,
+        transformed_position: &Point2d,
+        event: &PointerInteractionEvent,
+    ) -> RecognizerResponse {
+        use PointerInteractionVariantData::*;
+        let mut inner = self.inner.lock();
+        let interaction_id = event.interaction_id;
+        let position = event.common.position;
+        let time = event.common.time_stamp;
+        if is_primary_button_down(&event.variant) {
+            let mut velocity_tracker = VelocityTracker::new();
+            velocity_tracker.add_position(time, position);
+            inner.drags.insert(
+                interaction_id,
+                DragState {
+                    initial_position: position,
+                    initial_local_position: *transformed_position,
+                    last_position: position,
+                    last_local_position: *transformed_position,
+                    pending_delta: BoxOffset::ZERO,
+                    velocity_tracker,
+                    has_won: false,
+                },
+            );
+            return RecognizerResponse::possible();
+        }
+        let inner = &mut *inner;
+        let Some(drag) = inner.drags.get_mut(&interaction_id) else {
+            return RecognizerResponse::impossible();
+        };
+        match &event.variant {
+            Move(_) => {
+                let delta = D::project(position - drag.last_position);
+                drag.last_position = position;
+                drag.last_local_position = *transformed_position;
+                drag.velocity_tracker.add_position(time, position);
+                if drag.has_won {
+                    if let (Some(on_update), true) =
+                        (inner.callbacks.on_update.clone(), delta != BoxOffset::ZERO)
+                    {
+                        let details = DragUpdateDetails {
+                            delta,
+                            global_position: position,
+                            local_position: *transformed_position,
+                        };
+                        get_current_scheduler()
+                            .create_sync_job(|job_builder| on_update(&details, job_builder));
+                    }
+                    return RecognizerResponse::certain(1.0);
+                }
+                drag.pending_delta = drag.pending_delta + delta;
+                if D::project(position - drag.initial_position).distance() > D::SLOP {
+                    RecognizerResponse::certain(1.0)
+                } else {
+                    RecognizerResponse::possible()
+                }
+            }
+            Up(_) => {
+                let drag = inner
+                    .drags
+                    .remove(&interaction_id)
+                    .expect("Impossible to fail");
+                if drag.has_won {
+                    let velocity = D::project(drag.velocity_tracker.velocity(time));
+                    Self::end(&inner.callbacks, velocity);
+                }
+                RecognizerResponse::impossible()
+            }
+            _ => {
+                let drag = inner
+                    .drags
+                    .remove(&interaction_id)
+                    .expect("Impossible to fail");
+                if drag.has_won {
+                    // A cancelled drag does not fling.
+                    Self::end(&inner.callbacks, BoxOffset::ZERO);
+                }
+                RecognizerResponse::impossible()
+            }
+        }
+    }
+
+    fn query_recognition_state(&self, interaction_id: PointerInteractionId) -> RecognizerResponse {
+        if self.inner.lock().drags.contains_key(&interaction_id) {
+            RecognizerResponse::possible()
+        } else {
+            RecognizerResponse::impossible()
+        }
+    }
+
+    fn handle_arena_victory(&self, interaction_id: PointerInteractionId) -> RecognizerResponse {
+        let mut inner = self.inner.lock();
+        let inner = &mut *inner;
+        let Some(drag) = inner.drags.get_mut(&interaction_id) else {
+            return RecognizerResponse::impossible();
+        };
+        drag.has_won = true;
+        let start_details = DragStartDetails {
+            global_position: drag.initial_position,
+            local_position: drag.initial_local_position,
+        };
+        let update_details = (drag.pending_delta != BoxOffset::ZERO).then_some(DragUpdateDetails {
+            delta: drag.pending_delta,
+            global_position: drag.last_position,
+            local_position: drag.last_local_position,
+        });
+        drag.pending_delta = BoxOffset::ZERO;
+        let DragCallbacks {
+            on_start,
+            on_update,
+            ..
+        } = inner.callbacks.clone();
+        get_current_scheduler().create_sync_job(|job_builder| {
+            if let Some(on_start) = on_start {
+                on_start(&start_details, job_builder);
+            }
+            if let (Some(on_update), Some(update_details)) = (on_update, update_details) {
+                on_update(&update_details, job_builder);
+            }
+        });
+        RecognizerResponse::certain(1.0)
+    }
+
+    fn handle_arena_evict(&self, interaction_id: PointerInteractionId) -> RecognizerResponse {
+        let mut inner = self.inner.lock();
+        if let Some(drag) = inner.drags.remove(&interaction_id) {
+            if drag.has_won {
+                Self::end(&inner.callbacks, BoxOffset::ZERO);
+            }
+        }
+        RecognizerResponse::impossible()
+    }
+
+    fn on_detach(&self) {
+        self.inner.lock().drags.clear();
+    }
+
+    fn recognizer_type_id(&self) -> TypeId {
+        TypeId::of::<Self>()
+    }
+}
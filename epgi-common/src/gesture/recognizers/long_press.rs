@@ -0,0 +1,136 @@
+use std::{any::TypeId, time::Instant};
+
+use epgi_2d::Point2d;
+use epgi_core::{foundation::SyncMutex, scheduler::get_current_scheduler};
+use hashbrown::HashMap;
+
+use crate::{
+    ArcJobCallback, GestureRecognizer, PointerInteractionEvent, PointerInteractionId,
+    PointerInteractionVariantData, RecognizerResponse,
+};
+
+use super::{is_primary_button_down, LONG_PRESS_TIMEOUT, TOUCH_SLOP};
+
+/// Recognizes a primary button press that stays within [`TOUCH_SLOP`] for [`LONG_PRESS_TIMEOUT`].
+///
+/// The press keeps the arena inconclusive until the timeout, and claims it afterwards.
+/// The callback is called as soon as the press has both timed out and won the arena, without waiting for the release.
+pub struct LongPressGestureRecognizer {
+    inner: SyncMutex<LongPressGestureRecognizerInner>,
+}
+
+struct LongPressGestureRecognizerInner {
+    on_long_press: ArcJobCallback,
+    presses: HashMap<PointerInteractionId, LongPressState>,
+}
+
+struct LongPressState {
+    initial_position: Point2d,
+    deadline: Instant,
+    has_expired: bool,
+    has_won: bool,
+}
+
+impl LongPressGestureRecognizer {
+    pub fn new(on_long_press: ArcJobCallback) -> Self {
+        Self {
+            inner: SyncMutex::new(LongPressGestureRecognizerInner {
+                on_long_press,
+                presses: Default::default(),
+            }),
+        }
+    }
+
+    pub fn update(&self, on_long_press: ArcJobCallback) {
+        self.inner.lock().on_long_press = on_long_press;
+    }
+
+    fn fire(inner: &LongPressGestureRecognizerInner) {
+        let on_long_press = inner.on_long_press.clone();
+        get_current_scheduler().create_sync_job(|job_builder| {
+            on_long_press(job_builder);
+        });
+    }
+}
+
+impl GestureRecognizer for LongPressGestureRecognizer {
+    fn handle_event(
+        &self,
+        _transformed_position: &Point2d,
+        event: &PointerInteractionEvent,
+    ) -> RecognizerResponse {
+        use PointerInteractionVariantData::*;
+        let mut inner = self.inner.lock();
+        let interaction_id = event.interaction_id;
+        if is_primary_button_down(&event.variant) {
+            let deadline = event.common.time_stamp + LONG_PRESS_TIMEOUT;
+            inner.presses.insert(
+                interaction_id,
+                LongPressState {
+                    initial_position: event.common.position,
+                    deadline,
+                    has_expired: false,
+                    has_won: false,
+                },
+            );
+            return RecognizerResponse::inconclusive(deadline);
+        }
+        let Some(press) = inner.presses.get(&interaction_id) else {
+            return RecognizerResponse::impossible();
+        };
+        match &event.variant {
+            // Once recognized, the press may move freely until it is released.
+            Move(_) if press.has_expired => RecognizerResponse::certain(1.0),
+            Move(_)
+                if (event.common.position - press.initial_position).distance() <= TOUCH_SLOP =>
+            {
+                RecognizerResponse::inconclusive(press.deadline)
+            }
+            _ => {
+                inner.presses.remove(&interaction_id);
+                RecognizerResponse::impossible()
+            }
+        }
+    }
+
+    /// The arena only queries a press when the revisit time it asked for has come, i.e. when the press has timed out.
+    fn query_recognition_state(&self, interaction_id: PointerInteractionId) -> RecognizerResponse {
+        let mut inner = self.inner.lock();
+        let Some(press) = inner.presses.get_mut(&interaction_id) else {
+            return RecognizerResponse::impossible();
+        };
+        if !press.has_expired {
+            press.has_expired = true;
+            if press.has_won {
+                Self::fire(&inner);
+            }
+        }
+        RecognizerResponse::certain(1.0)
+    }
+
+    fn handle_arena_victory(&self, interaction_id: PointerInteractionId) -> RecognizerResponse {
+        let mut inner = self.inner.lock();
+        let Some(press) = inner.presses.get_mut(&interaction_id) else {
+            return RecognizerResponse::impossible();
+        };
+        press.has_won = true;
+        if !press.has_expired {
+            return RecognizerResponse::inconclusive(press.deadline);
+        }
+        Self::fire(&inner);
+        RecognizerResponse::certain(1.0)
+    }
+
+    fn handle_arena_evict(&self, interaction_id: PointerInteractionId) -> RecognizerResponse {
+        self.inner.lock().presses.remove(&interaction_id);
+        RecognizerResponse::impossible()
+    }
+
+    fn on_detach(&self) {
+        self.inner.lock().presses.clear();
+    }
+
+    fn recognizer_type_id(&self) -> TypeId {
+        TypeId::of::<Self>()
+    }
+}
@@ -0,0 +1,238 @@
+use std::any::TypeId;
+
+use epgi_2d::{BoxOffset, Point2d};
+use epgi_core::{
+    foundation::{Asc, SyncMutex},
+    scheduler::{get_current_scheduler, JobBuilder},
+};
+use hashbrown::HashMap;
+
+use crate::{
+    GestureRecognizer, PointerInteractionEvent, PointerInteractionId,
+    PointerInteractionVariantData, RecognizerResponse,
+};
+
+use super::{VelocityTracker, PAN_SLOP, ROTATION_SLOP, SCALE_SLOP};
+
+#[derive(Clone, Copy, Debug)]
+pub struct ScaleStartDetails {
+    pub focal_point: Point2d,
+    pub local_focal_point: Point2d,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ScaleUpdateDetails {
+    pub focal_point: Point2d,
+    pub local_focal_point: Point2d,
+    /// The movement of the focal point since the last update.
+    pub focal_point_delta: BoxOffset,
+    /// The scale since the gesture started.
+    pub scale: f32,
+    /// The clockwise rotation in radians since the gesture started.
+    pub rotation: f32,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ScaleEndDetails {
+    /// The velocity of the focal point in logical pixels per second when the gesture ended.
+    pub velocity: BoxOffset,
+}
+
+pub type ArcScaleStartCallback = Asc<dyn Fn(&ScaleStartDetails, &mut JobBuilder) + Send + Sync>;
+pub type ArcScaleUpdateCallback = Asc<dyn Fn(&ScaleUpdateDetails, &mut JobBuilder) + Send + Sync>;
+pub type ArcScaleEndCallback = Asc<dyn Fn(&ScaleEndDetails, &mut JobBuilder) + Send + Sync>;
+
+#[derive(Clone, Default)]
+pub struct ScaleCallbacks {
+    pub on_start: Option<ArcScaleStartCallback>,
+    pub on_update: Option<ArcScaleUpdateCallback>,
+    pub on_end: Option<ArcScaleEndCallback>,
+}
+
+impl ScaleCallbacks {
+    pub fn is_empty(&self) -> bool {
+        self.on_start.is_none() && self.on_update.is_none() && self.on_end.is_none()
+    }
+}
+
+/// Recognizes trackpad pan and pinch gestures.
+///
+/// The gesture claims the arena once it has scaled more than [`SCALE_SLOP`], rotated more than [`ROTATION_SLOP`]
+/// or panned further than [`PAN_SLOP`].
+pub struct ScaleGestureRecognizer {
+    inner: SyncMutex<ScaleGestureRecognizerInner>,
+}
+
+struct ScaleGestureRecognizerInner {
+    callbacks: ScaleCallbacks,
+    gestures: HashMap<PointerInteractionId, ScaleState>,
+}
+
+struct ScaleState {
+    initial_focal_point: Point2d,
+    initial_local_focal_point: Point2d,
+    last_update: ScaleUpdateDetails,
+    velocity_tracker: VelocityTracker,
+    has_won: bool,
+}
+
+impl ScaleGestureRecognizer {
+    pub fn new(callbacks: ScaleCallbacks) -> Self {
+        Self {
+            inner: SyncMutex::new(ScaleGestureRecognizerInner {
+                callbacks,
+                gestures: Default::default(),
+            }),
+        }
+    }
+
+    pub fn update(&self, callbacks: ScaleCallbacks) {
+        self.inner.lock().callbacks = callbacks;
+    }
+}
+
+impl GestureRecognizer for ScaleGestureRecognizer {
+    fn handle_event(
+        &self,
+        transformed_position: &Point2d,
+        event: &PointerInteractionEvent,
+    ) -> RecognizerResponse {
+        use PointerInteractionVariantData::*;
+        let mut inner = self.inner.lock();
+        let inner = &mut *inner;
+        let interaction_id = event.interaction_id;
+        let time = event.common.time_stamp;
+        match &event.variant {
+            PanZoomStart => {
+                let focal_point = event.common.position;
+                let mut velocity_tracker = VelocityTracker::new();
+                velocity_tracker.add_position(time, focal_point);
+                inner.gestures.insert(
+                    interaction_id,
+                    ScaleState {
+                        initial_focal_point: focal_point,
+                        initial_local_focal_point: *transformed_position,
+                        last_update: ScaleUpdateDetails {
+                            focal_point,
+                            local_focal_point: *transformed_position,
+                            focal_point_delta: BoxOffset::ZERO,
+                            scale: 1.0,
+                            rotation: 0.0,
+                        },
+                        velocity_tracker,
+                        has_won: false,
+                    },
+                );
+                RecognizerResponse::possible()
+            }
+            PanZoomUpdate(data) => {
+                let Some(gesture) = inner.gestures.get_mut(&interaction_id) else {
+                    return RecognizerResponse::impossible();
+                };
+                let focal_point = event.common.position + data.pan;
+                gesture.velocity_tracker.add_position(time, focal_point);
+                let details = ScaleUpdateDetails {
+                    focal_point,
+                    local_focal_point: *transformed_position + data.pan,
+                    focal_point_delta: focal_point - gesture.last_update.focal_point,
+                    scale: data.scale,
+                    rotation: data.rotation,
+                };
+                gesture.last_update = details;
+                if gesture.has_won {
+                    if let Some(on_update) = inner.callbacks.on_update.clone() {
+                        get_current_scheduler()
+                            .create_sync_job(|job_builder| on_update(&details, job_builder));
+                    }
+                    return RecognizerResponse::certain(1.0);
+                }
+                if (data.scale - 1.0).abs() > SCALE_SLOP
+                    || data.rotation.abs() > ROTATION_SLOP
+                    || (focal_point - gesture.initial_focal_point).distance() > PAN_SLOP
+                {
+                    RecognizerResponse::certain(1.0)
+                } else {
+                    RecognizerResponse::possible()
+                }
+            }
+            _ => {
+                let Some(gesture) = inner.gestures.remove(&interaction_id) else {
+                    return RecognizerResponse::impossible();
+                };
+                if let (true, Some(on_end)) = (gesture.has_won, inner.callbacks.on_end.clone()) {
+                    let details = ScaleEndDetails {
+                        velocity: gesture.velocity_tracker.velocity(time),
+                    };
+                    get_current_scheduler()
+                        .create_sync_job(|job_builder| on_end(&details, job_builder));
+                }
+                RecognizerResponse::impossible()
+            }
+        }
+    }
+
+    fn query_recognition_state(&self, interaction_id: PointerInteractionId) -> RecognizerResponse {
+        if self.inner.lock().gestures.contains_key(&interaction_id) {
+            RecognizerResponse::possible()
+        } else {
+            RecognizerResponse::impossible()
+        }
+    }
+
+    fn handle_arena_victory(&self, interaction_id: PointerInteractionId) -> RecognizerResponse {
+        let mut inner = self.inner.lock();
+        let inner = &mut *inner;
+        let Some(gesture) = inner.gestures.get_mut(&interaction_id) else {
+            return RecognizerResponse::impossible();
+        };
+        gesture.has_won = true;
+        let start_details = ScaleStartDetails {
+            focal_point: gesture.initial_focal_point,
+            local_focal_point: gesture.initial_local_focal_point,
+        };
+        // Report what happened before the gesture won the arena as a single update.
+        let update_details = (gesture.last_update.focal_point != gesture.initial_focal_point
+            || gesture.last_update.scale != 1.0
+            || gesture.last_update.rotation != 0.0)
+            .then_some(ScaleUpdateDetails {
+                focal_point_delta: gesture.last_update.focal_point - gesture.initial_focal_point,
+                ..gesture.last_update
+            });
+        let ScaleCallbacks {
+            on_start,
+            on_update,
+            ..
+        } = inner.callbacks.clone();
+        get_current_scheduler().create_sync_job(|job_builder| {
+            if let Some(on_start) = on_start {
+                on_start(&start_details, job_builder);
+            }
+            if let (Some(on_update), Some(update_details)) = (on_update, update_details) {
+                on_update(&update_details, job_builder);
+            }
+        });
+        RecognizerResponse::certain(1.0)
+    }
+
+    fn handle_arena_evict(&self, interaction_id: PointerInteractionId) -> RecognizerResponse {
+        let mut inner = self.inner.lock();
+        if let Some(gesture) = inner.gestures.remove(&interaction_id) {
+            if let (true, Some(on_end)) = (gesture.has_won, inner.callbacks.on_end.clone()) {
+                let details = ScaleEndDetails {
+                    velocity: BoxOffset::ZERO,
+                };
+                get_current_scheduler()
+                    .create_sync_job(|job_builder| on_end(&details, job_builder));
+            }
+        }
+        RecognizerResponse::impossible()
+    }
+
+    fn on_detach(&self) {
+        self.inner.lock().gestures.clear();
+    }
+
+    fn recognizer_type_id(&self) -> TypeId {
+        TypeId::of::<Self>()
+    }
+}
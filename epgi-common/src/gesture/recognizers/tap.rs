@@ -2,24 +2,41 @@ use std::any::TypeId;
 
 use epgi_2d::Point2d;
 use epgi_core::{foundation::SyncMutex, scheduler::get_current_scheduler};
+use hashbrown::HashMap;
 
 use crate::{
     ArcJobCallback, GestureRecognizer, PointerInteractionEvent, PointerInteractionId,
-    RecognizerResponse,
+    PointerInteractionVariantData, RecognizerResponse,
 };
 
+use super::{is_primary_button_down, TOUCH_SLOP};
+
+/// Recognizes a primary button press that is released without moving further than [`TOUCH_SLOP`].
+///
+/// A tap never claims the arena by itself. It is called once the pointer is released and no other gesture,
+/// e.g. a double tap waiting for its second tap, has claimed the arena.
 pub struct TapGestureRecognizer {
     inner: SyncMutex<TapGestureRecognizerInner>,
 }
 
 struct TapGestureRecognizerInner {
-    pub on_tap: ArcJobCallback,
+    on_tap: ArcJobCallback,
+    taps: HashMap<PointerInteractionId, TapState>,
+}
+
+struct TapState {
+    initial_position: Point2d,
+    is_up: bool,
+    has_won: bool,
 }
 
 impl TapGestureRecognizer {
     pub fn new(on_tap: ArcJobCallback) -> Self {
         Self {
-            inner: SyncMutex::new(TapGestureRecognizerInner { on_tap }),
+            inner: SyncMutex::new(TapGestureRecognizerInner {
+                on_tap,
+                taps: Default::default(),
+            }),
         }
     }
 
@@ -27,35 +44,85 @@ impl TapGestureRecognizer {
         let mut inner = self.inner.lock();
         inner.on_tap = on_tap;
     }
+
+    fn fire(inner: &mut TapGestureRecognizerInner, interaction_id: PointerInteractionId) {
+        inner.taps.remove(&interaction_id);
+        let on_tap = inner.on_tap.clone();
+        get_current_scheduler().create_sync_job(|job_builder| {
+            on_tap(job_builder);
+        });
+    }
 }
 
 impl GestureRecognizer for TapGestureRecognizer {
     fn handle_event(
         &self,
-        position: &Point2d,
+        _transformed_position: &Point2d,
         event: &PointerInteractionEvent,
     ) -> RecognizerResponse {
-        RecognizerResponse::possible()
+        use PointerInteractionVariantData::*;
+        let mut inner = self.inner.lock();
+        let interaction_id = event.interaction_id;
+        if is_primary_button_down(&event.variant) {
+            inner.taps.insert(
+                interaction_id,
+                TapState {
+                    initial_position: event.common.position,
+                    is_up: false,
+                    has_won: false,
+                },
+            );
+            return RecognizerResponse::possible();
+        }
+        let Some(tap) = inner.taps.get_mut(&interaction_id) else {
+            return RecognizerResponse::impossible();
+        };
+        let within_slop = (event.common.position - tap.initial_position).distance() <= TOUCH_SLOP;
+        match &event.variant {
+            Move(_) if within_slop => RecognizerResponse::possible(),
+            Up(_) if within_slop => {
+                if tap.has_won {
+                    Self::fire(&mut inner, interaction_id);
+                    return RecognizerResponse::certain(1.0);
+                }
+                tap.is_up = true;
+                RecognizerResponse::possible()
+            }
+            _ => {
+                inner.taps.remove(&interaction_id);
+                RecognizerResponse::impossible()
+            }
+        }
     }
 
     fn query_recognition_state(&self, interaction_id: PointerInteractionId) -> RecognizerResponse {
-        RecognizerResponse::possible()
+        if self.inner.lock().taps.contains_key(&interaction_id) {
+            RecognizerResponse::possible()
+        } else {
+            RecognizerResponse::impossible()
+        }
     }
 
     fn handle_arena_victory(&self, interaction_id: PointerInteractionId) -> RecognizerResponse {
-        let on_tap = self.inner.lock().on_tap.clone();
-        get_current_scheduler().create_sync_job(|job_builder| {
-            on_tap(job_builder);
-        });
+        let mut inner = self.inner.lock();
+        let Some(tap) = inner.taps.get_mut(&interaction_id) else {
+            return RecognizerResponse::impossible();
+        };
+        if tap.is_up {
+            Self::fire(&mut inner, interaction_id);
+        } else {
+            tap.has_won = true;
+        }
         RecognizerResponse::certain(1.0)
     }
 
     fn handle_arena_evict(&self, interaction_id: PointerInteractionId) -> RecognizerResponse {
+        self.inner.lock().taps.remove(&interaction_id);
         RecognizerResponse::impossible()
     }
 
     fn on_detach(&self) {
-        todo!()
+        self.inner.lock().taps.clear();
     }
 
     fn recognizer_type_id(&self) -> std::any::TypeId {
@@ -0,0 +1,45 @@
+use std::time::{Duration, Instant};
+
+use epgi_2d::{BoxOffset, Point2d};
+
+/// Estimates the velocity of a pointer from its recent positions.
+#[derive(Clone, Debug, Default)]
+pub struct VelocityTracker {
+    samples: Vec<(Instant, Point2d)>,
+}
+
+impl VelocityTracker {
+    /// Only the positions within this window before the latest one are taken into account.
+    pub const SAMPLE_WINDOW: Duration = Duration::from_millis(100);
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_position(&mut self, time: Instant, position: Point2d) {
+        self.samples.retain(|(sample_time, _)| {
+            time.saturating_duration_since(*sample_time) <= Self::SAMPLE_WINDOW
+        });
+        self.samples.push((time, position));
+    }
+
+    /// The velocity in logical pixels per second. Zero if the pointer has rested longer than the sample window.
+    pub fn velocity(&self, now: Instant) -> BoxOffset {
+        let recent = self
+            .samples
+            .iter()
+            .filter(|(sample_time, _)| {
+                now.saturating_duration_since(*sample_time) <= Self::SAMPLE_WINDOW
+            })
+            .collect::<Vec<_>>();
+        match (recent.first(), recent.last()) {
+            (Some((first_time, first_position)), Some((last_time, last_position)))
+                if last_time > first_time =>
+            {
+                (*last_position - *first_position)
+                    / last_time.duration_since(*first_time).as_secs_f32()
+            }
+            _ => BoxOffset::ZERO,
+        }
+    }
+}
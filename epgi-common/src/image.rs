@@ -0,0 +1,14 @@
+mod box_fit;
+pub use box_fit::*;
+
+mod cache;
+pub use cache::*;
+
+mod provider;
+pub use provider::*;
+
+mod raw_image;
+pub use raw_image::*;
+
+mod widget;
+pub use widget::*;
@@ -0,0 +1,123 @@
+use epgi_2d::BoxSize;
+
+/// How a box of one size is inscribed into a box of another size.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum BoxFit {
+    /// Stretch the source to fill the destination, distorting the aspect ratio.
+    Fill,
+    /// Scale the source as large as possible while still fitting entirely within the destination.
+    #[default]
+    Contain,
+    /// Scale the source as small as possible while still covering the entire destination.
+    Cover,
+    /// Scale the source so that its width matches the destination.
+    FitWidth,
+    /// Scale the source so that its height matches the destination.
+    FitHeight,
+    /// Do not scale the source.
+    None,
+    /// Like [`BoxFit::Contain`], but never scales the source up.
+    ScaleDown,
+}
+
+/// The part of the source to be drawn, and the size it should be drawn at.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct FittedSizes {
+    pub source: BoxSize,
+    pub destination: BoxSize,
+}
+
+impl BoxFit {
+    pub fn apply(self, input: BoxSize, output: BoxSize) -> FittedSizes {
+        if input.width <= 0.0 || input.height <= 0.0 || output.width <= 0.0 || output.height <= 0.0
+        {
+            return FittedSizes {
+                source: BoxSize::ZERO,
+                destination: BoxSize::ZERO,
+            };
+        }
+        let input_aspect = input.width / input.height;
+        let output_aspect = output.width / output.height;
+        let (source, destination) = match self {
+            BoxFit::Fill => (input, output),
+            BoxFit::Contain => {
+                let destination = if output_aspect > input_aspect {
+                    BoxSize {
+                        width: input.width * output.height / input.height,
+                        height: output.height,
+                    }
+                } else {
+                    BoxSize {
+                        width: output.width,
+                        height: input.height * output.width / input.width,
+                    }
+                };
+                (input, destination)
+            }
+            BoxFit::Cover => {
+                let source = if output_aspect > input_aspect {
+                    BoxSize {
+                        width: input.width,
+                        height: input.width / output_aspect,
+                    }
+                } else {
+                    BoxSize {
+                        width: input.height * output_aspect,
+                        height: input.height,
+                    }
+                };
+                (source, output)
+            }
+            BoxFit::FitWidth => {
+                if output_aspect > input_aspect {
+                    let source = BoxSize {
+                        width: input.width,
+                        height: input.width / output_aspect,
+                    };
+                    (source, output)
+                } else {
+                    let destination = BoxSize {
+                        width: output.width,
+                        height: input.height * output.width / input.width,
+                    };
+                    (input, destination)
+                }
+            }
+            BoxFit::FitHeight => {
+                if output_aspect > input_aspect {
+                    let destination = BoxSize {
+                        width: input.width * output.height / input.height,
+                        height: output.height,
+                    };
+                    (input, destination)
+                } else {
+                    let source = BoxSize {
+                        width: input.height * output_aspect,
+                        height: input.height,
+                    };
+                    (source, output)
+                }
+            }
+            BoxFit::None => {
+                let source = BoxSize {
+                    width: input.width.min(output.width),
+                    height: input.height.min(output.height),
+                };
+                (source, source)
+            }
+            BoxFit::ScaleDown => {
+                let FittedSizes { destination, .. } = BoxFit::Contain.apply(input, output);
+                let destination = if destination.height > input.height {
+                    input
+                } else {
+                    destination
+                };
+                (input, destination)
+            }
+        };
+        FittedSizes {
+            source,
+            destination,
+        }
+    }
+}
@@ -0,0 +1,249 @@
+use std::collections::BTreeMap;
+
+use epgi_2d::Image;
+use epgi_core::foundation::{Asc, SyncMutex};
+use hashbrown::HashMap;
+
+use crate::{ImageFuture, ImageProvider};
+
+lazy_static::lazy_static! {
+    /// The image cache shared by all [`Image`](crate::Image) widgets.
+    pub static ref IMAGE_CACHE: ImageCache = ImageCache::new(ImageCache::DEFAULT_MAX_BYTES);
+}
+
+/// A cache of decoded images, keyed by the identity of their [`ImageProvider`].
+///
+/// Decoded images are kept until their total size exceeds the byte budget,
+/// at which point the least recently used images are evicted.
+/// Concurrent loads of the same provider share a single decode.
+///
+/// Cloning the cache returns a handle to the same cache. Handles are equal if they refer to the same cache.
+#[derive(Clone)]
+pub struct ImageCache {
+    inner: Asc<SyncMutex<ImageCacheInner>>,
+}
+
+impl PartialEq for ImageCache {
+    fn eq(&self, other: &Self) -> bool {
+        Asc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
+impl std::fmt::Debug for ImageCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ImageCache")
+            .field("stats", &self.stats())
+            .finish()
+    }
+}
+
+struct ImageCacheInner {
+    max_bytes: usize,
+    current_bytes: usize,
+    next_tick: u64,
+    entries: HashMap<ImageProvider, CachedImage>,
+    /// Cached providers ordered from the least to the most recently used.
+    recency: BTreeMap<u64, ImageProvider>,
+    next_load_id: u64,
+    pending: HashMap<ImageProvider, PendingImage>,
+    stats: ImageCacheStats,
+}
+
+struct CachedImage {
+    image: Image,
+    size_bytes: usize,
+    last_used: u64,
+}
+
+struct PendingImage {
+    load_id: u64,
+    future: ImageFuture,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct ImageCacheStats {
+    /// Loads served by an already decoded image.
+    pub hits: u64,
+    /// Loads that had to start a new decode.
+    pub misses: u64,
+    /// Loads that joined a decode already in flight.
+    pub joined: u64,
+    /// Images evicted to stay within the byte budget.
+    pub evictions: u64,
+    /// Total size of the evicted images.
+    pub evicted_bytes: u64,
+    /// Number of images currently cached.
+    pub entries: usize,
+    /// Total size of the images currently cached.
+    pub current_bytes: usize,
+}
+
+impl ImageCache {
+    pub const DEFAULT_MAX_BYTES: usize = 100 << 20;
+
+    pub fn new(max_bytes: usize) -> Self {
+        Self {
+            inner: Asc::new(SyncMutex::new(ImageCacheInner {
+                max_bytes,
+                current_bytes: 0,
+                next_tick: 0,
+                entries: HashMap::new(),
+                recency: BTreeMap::new(),
+                next_load_id: 0,
+                pending: HashMap::new(),
+                stats: ImageCacheStats::default(),
+            })),
+        }
+    }
+
+    /// Load the image through the cache.
+    ///
+    /// Returns a ready future if the image is cached, joins the decode if one is already in flight,
+    /// or starts a new decode otherwise. Failed loads are not cached.
+    pub fn load(&self, provider: &ImageProvider) -> ImageFuture {
+        let mut inner = self.inner.lock();
+        if let Some(image) = inner.get(provider) {
+            inner.stats.hits += 1;
+            return ImageFuture::ready(Ok(image));
+        }
+        if let Some(pending) = inner.pending.get(provider) {
+            let future = pending.future.clone();
+            inner.stats.joined += 1;
+            return future;
+        }
+        inner.stats.misses += 1;
+        let load_id = inner.next_load_id;
+        inner.next_load_id += 1;
+        let cache = self.clone();
+        let key = provider.clone();
+        let future = provider.load_then(move |result| {
+            let mut inner = cache.inner.lock();
+            if !matches!(inner.pending.get(&key), Some(pending) if pending.load_id == load_id) {
+                // The cache has been cleared since this load started.
+                return;
+            }
+            inner.pending.remove(&key);
+            if let Ok(image) = result {
+                inner.insert(key, image.clone());
+            }
+        });
+        inner.pending.insert(
+            provider.clone(),
+            PendingImage {
+                load_id,
+                future: future.clone(),
+            },
+        );
+        future
+    }
+
+    /// Get a cached image, marking it as recently used.
+    pub fn get(&self, provider: &ImageProvider) -> Option<Image> {
+        self.inner.lock().get(provider)
+    }
+
+    /// Put an already decoded image into the cache.
+    ///
+    /// Images larger than the whole byte budget are not cached.
+    pub fn insert(&self, provider: ImageProvider, image: Image) {
+        self.inner.lock().insert(provider, image)
+    }
+
+    /// Remove an image from the cache. Returns whether it was cached.
+    ///
+    /// Explicit removals are not counted as evictions.
+    pub fn remove(&self, provider: &ImageProvider) -> bool {
+        self.inner.lock().remove(provider).is_some()
+    }
+
+    /// Remove all cached images and forget all decodes in flight.
+    ///
+    /// Decodes in flight still resolve for the widgets waiting on them, but are not cached.
+    pub fn clear(&self) {
+        let mut inner = self.inner.lock();
+        inner.entries.clear();
+        inner.recency.clear();
+        inner.pending.clear();
+        inner.current_bytes = 0;
+    }
+
+    pub fn max_bytes(&self) -> usize {
+        self.inner.lock().max_bytes
+    }
+
+    /// Change the byte budget, evicting images right away if the cache no longer fits.
+    pub fn set_max_bytes(&self, max_bytes: usize) {
+        let mut inner = self.inner.lock();
+        inner.max_bytes = max_bytes;
+        inner.evict_to_fit();
+    }
+
+    pub fn stats(&self) -> ImageCacheStats {
+        let inner = self.inner.lock();
+        ImageCacheStats {
+            entries: inner.entries.len(),
+            current_bytes: inner.current_bytes,
+            ..inner.stats
+        }
+    }
+}
+
+impl ImageCacheInner {
+    fn tick(&mut self) -> u64 {
+        let tick = self.next_tick;
+        self.next_tick += 1;
+        tick
+    }
+
+    fn get(&mut self, provider: &ImageProvider) -> Option<Image> {
+        let tick = self.tick();
+        let entry = self.entries.get_mut(provider)?;
+        let last_used = std::mem::replace(&mut entry.last_used, tick);
+        let image = entry.image.clone();
+        self.recency.remove(&last_used);
+        self.recency.insert(tick, provider.clone());
+        Some(image)
+    }
+
+    fn insert(&mut self, provider: ImageProvider, image: Image) {
+        self.remove(&provider);
+        let size_bytes = image.data.data().len();
+        if size_bytes > self.max_bytes {
+            return;
+        }
+        let tick = self.tick();
+        self.current_bytes += size_bytes;
+        self.recency.insert(tick, provider.clone());
+        self.entries.insert(
+            provider,
+            CachedImage {
+                image,
+                size_bytes,
+                last_used: tick,
+            },
+        );
+        self.evict_to_fit();
+    }
+
+    fn remove(&mut self, provider: &ImageProvider) -> Option<CachedImage> {
+        let entry = self.entries.remove(provider)?;
+        self.recency.remove(&entry.last_used);
+        self.current_bytes -= entry.size_bytes;
+        Some(entry)
+    }
+
+    fn evict_to_fit(&mut self) {
+        while self.current_bytes > self.max_bytes {
+            let Some((_, provider)) = self.recency.pop_first() else {
+                break;
+            };
+            let entry = self
+                .entries
+                .remove(&provider)
+                .expect("Every provider in the recency order should be cached");
+            self.current_bytes -= entry.size_bytes;
+            self.stats.evictions += 1;
+            self.stats.evicted_bytes += entry.size_bytes as u64;
+        }
+    }
+}
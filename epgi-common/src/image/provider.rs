@@ -0,0 +1,204 @@
+use std::{
+    future::Future,
+    hash::{Hash, Hasher},
+    path::PathBuf,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use epgi_2d::{Blob, Format, Image};
+use epgi_core::scheduler::get_current_scheduler;
+use futures::{channel::oneshot, future::Shared, FutureExt};
+
+/// Where the encoded bytes of an image come from.
+///
+/// Two providers are equal if they load the same image:
+/// file providers compare by path, memory providers compare by the identity of their bytes,
+/// and custom providers compare by the identity of their loader.
+#[derive(Clone)]
+pub enum ImageProvider {
+    File(PathBuf),
+    Memory(Arc<[u8]>),
+    /// Load the image with a custom function, e.g. from an asset bundle or the network.
+    Custom(Arc<dyn Fn() -> Result<Image, ImageError> + Send + Sync>),
+}
+
+impl ImageProvider {
+    pub fn file(path: impl Into<PathBuf>) -> Self {
+        Self::File(path.into())
+    }
+
+    pub fn memory(bytes: impl Into<Arc<[u8]>>) -> Self {
+        Self::Memory(bytes.into())
+    }
+
+    /// The loader is called on the async threadpool, once per decode.
+    pub fn custom(load: impl Fn() -> Result<Image, ImageError> + Send + Sync + 'static) -> Self {
+        Self::Custom(Arc::new(load))
+    }
+
+    /// Read and decode the image on the async threadpool.
+    ///
+    /// The returned future is meant to be passed to [`BuildContext::use_future`](epgi_core::tree::BuildContext::use_future),
+    /// which suspends the build until the image is decoded.
+    /// Use [`ImageCache::load`](crate::ImageCache::load) instead to share decoded images across widgets.
+    pub fn load(&self) -> ImageFuture {
+        self.load_then(|_| {})
+    }
+
+    /// Like [`ImageProvider::load`], but runs `on_loaded` on the threadpool before the future resolves.
+    pub(crate) fn load_then(
+        &self,
+        on_loaded: impl FnOnce(&Result<Image, ImageError>) + Send + 'static,
+    ) -> ImageFuture {
+        let (sender, receiver) = oneshot::channel();
+        let provider = self.clone();
+        get_current_scheduler().async_threadpool.spawn(move || {
+            let result = provider.load_sync();
+            on_loaded(&result);
+            let _ = sender.send(result);
+        });
+        ImageFuture(ImageFutureState::Loading(
+            ImageReceiver { receiver }.shared(),
+        ))
+    }
+
+    /// Read and decode the image on the current thread.
+    pub fn load_sync(&self) -> Result<Image, ImageError> {
+        match self {
+            ImageProvider::File(path) => {
+                let bytes = std::fs::read(path).map_err(|e| ImageError::Io(Arc::new(e)))?;
+                decode_image(&bytes)
+            }
+            ImageProvider::Memory(bytes) => decode_image(bytes),
+            ImageProvider::Custom(load) => load(),
+        }
+    }
+}
+
+impl std::fmt::Debug for ImageProvider {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ImageProvider::File(path) => f.debug_tuple("File").field(path).finish(),
+            ImageProvider::Memory(bytes) => f.debug_tuple("Memory").field(&bytes.len()).finish(),
+            ImageProvider::Custom(load) => {
+                f.debug_tuple("Custom").field(&Arc::as_ptr(load)).finish()
+            }
+        }
+    }
+}
+
+impl PartialEq for ImageProvider {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (ImageProvider::File(path), ImageProvider::File(other_path)) => path == other_path,
+            (ImageProvider::Memory(bytes), ImageProvider::Memory(other_bytes)) => {
+                Arc::ptr_eq(bytes, other_bytes)
+            }
+            (ImageProvider::Custom(load), ImageProvider::Custom(other_load)) => {
+                Arc::ptr_eq(load, other_load)
+            }
+            _ => false,
+        }
+    }
+}
+
+impl Eq for ImageProvider {}
+
+impl Hash for ImageProvider {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        match self {
+            ImageProvider::File(path) => path.hash(state),
+            ImageProvider::Memory(bytes) => bytes.as_ptr().hash(state),
+            ImageProvider::Custom(load) => Arc::as_ptr(load).cast::<()>().hash(state),
+        }
+    }
+}
+
+/// Decode an encoded PNG or JPEG image into unpremultiplied RGBA8 pixels.
+pub fn decode_image(bytes: &[u8]) -> Result<Image, ImageError> {
+    let image = image::load_from_memory(bytes)
+        .map_err(|e| ImageError::Decode(Arc::new(e)))?
+        .into_rgba8();
+    let (width, height) = image.dimensions();
+    Ok(Image::new(
+        Blob::new(Arc::new(image.into_raw())),
+        Format::Rgba8,
+        width,
+        height,
+    ))
+}
+
+/// The future returned by [`ImageProvider::load`].
+///
+/// Clones of the future resolve to the same decoded image.
+#[derive(Clone)]
+pub struct ImageFuture(ImageFutureState);
+
+#[derive(Clone)]
+enum ImageFutureState {
+    Ready(Result<Image, ImageError>),
+    Loading(Shared<ImageReceiver>),
+}
+
+impl ImageFuture {
+    /// A future that resolves immediately to an already loaded image.
+    pub fn ready(result: Result<Image, ImageError>) -> Self {
+        Self(ImageFutureState::Ready(result))
+    }
+}
+
+impl Future for ImageFuture {
+    type Output = Result<Image, ImageError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match &mut self.0 {
+            ImageFutureState::Ready(result) => Poll::Ready(result.clone()),
+            ImageFutureState::Loading(shared) => shared.poll_unpin(cx),
+        }
+    }
+}
+
+struct ImageReceiver {
+    receiver: oneshot::Receiver<Result<Image, ImageError>>,
+}
+
+impl Future for ImageReceiver {
+    type Output = Result<Image, ImageError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.receiver
+            .poll_unpin(cx)
+            .map(|result| result.unwrap_or(Err(ImageError::Canceled)))
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum ImageError {
+    Io(Arc<std::io::Error>),
+    Decode(Arc<image::ImageError>),
+    /// The loading task was dropped before it finished.
+    Canceled,
+}
+
+impl std::fmt::Display for ImageError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ImageError::Io(e) => write!(f, "Failed to read image: {}", e),
+            ImageError::Decode(e) => write!(f, "Failed to decode image: {}", e),
+            ImageError::Canceled => write!(f, "Image loading was canceled"),
+        }
+    }
+}
+
+impl std::error::Error for ImageError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ImageError::Io(e) => Some(e.as_ref()),
+            ImageError::Decode(e) => Some(e.as_ref()),
+            ImageError::Canceled => None,
+        }
+    }
+}
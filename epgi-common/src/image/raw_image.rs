@@ -0,0 +1,216 @@
+use std::sync::Arc;
+
+use epgi_2d::{
+    Affine2dCanvas, Affine2dPaintContextExt, BoxConstraints, BoxIntrinsics, BoxOffset, BoxProtocol,
+    BoxSize, FilterQuality, Image, Rect,
+};
+use epgi_core::{
+    foundation::{set_if_changed, Asc, BuildError, InlinableDwsizeVec, PaintContext, Provide},
+    template::{ImplByTemplate, LeafElement, LeafElementTemplate, LeafRender, LeafRenderTemplate},
+    tree::{BuildContext, RenderAction, Widget},
+};
+use epgi_macro::Declarative;
+use typed_builder::TypedBuilder;
+
+use crate::{Alignment, BoxFit};
+
+/// Paints an already decoded image.
+///
+/// Use [`Image`](crate::Image) to load and decode an image from an [`ImageProvider`](crate::ImageProvider).
+#[derive(Debug, Declarative, TypedBuilder)]
+#[builder(build_method(into=Asc<RawImage>))]
+pub struct RawImage {
+    pub image: Image,
+    /// If set, the width is forced upon the image, otherwise the image is sized to preserve its aspect ratio.
+    #[builder(default, setter(strip_option))]
+    pub width: Option<f32>,
+    /// If set, the height is forced upon the image, otherwise the image is sized to preserve its aspect ratio.
+    #[builder(default, setter(strip_option))]
+    pub height: Option<f32>,
+    #[builder(default)]
+    pub fit: BoxFit,
+    #[builder(default = Alignment::CENTER)]
+    pub alignment: Alignment,
+    #[builder(default)]
+    pub filter_quality: FilterQuality,
+    #[builder(default = 1.0)]
+    pub opacity: f32,
+}
+
+impl Widget for RawImage {
+    type ParentProtocol = BoxProtocol;
+    type ChildProtocol = BoxProtocol;
+    type Element = RawImageElement;
+
+    fn into_arc_widget(self: Asc<Self>) -> Asc<Self> {
+        self
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct RawImageElement;
+
+impl ImplByTemplate for RawImageElement {
+    type Template = LeafElementTemplate;
+}
+
+impl LeafElement for RawImageElement {
+    type Protocol = BoxProtocol;
+    type ArcWidget = Asc<RawImage>;
+    type Render = RenderRawImage;
+
+    fn create_element(
+        _widget: &Self::ArcWidget,
+        _ctx: &mut BuildContext,
+        _provider_values: InlinableDwsizeVec<Arc<dyn Provide>>,
+    ) -> Result<Self, BuildError> {
+        Ok(Self)
+    }
+
+    fn create_render(&self, widget: &Self::ArcWidget) -> Self::Render {
+        RenderRawImage {
+            image: widget.image.clone(),
+            width: widget.width,
+            height: widget.height,
+            fit: widget.fit,
+            alignment: widget.alignment,
+            filter_quality: widget.filter_quality,
+            opacity: widget.opacity,
+        }
+    }
+
+    fn update_render(render: &mut Self::Render, widget: &Self::ArcWidget) -> Option<RenderAction> {
+        let image_size_changed =
+            render.image.width != widget.image.width || render.image.height != widget.image.height;
+        let image_changed = set_if_changed(&mut render.image, widget.image.clone());
+        let width_changed = set_if_changed(&mut render.width, widget.width);
+        let height_changed = set_if_changed(&mut render.height, widget.height);
+        let fit_changed = set_if_changed(&mut render.fit, widget.fit);
+        let alignment_changed = set_if_changed(&mut render.alignment, widget.alignment);
+        let filter_quality_changed =
+            set_if_changed(&mut render.filter_quality, widget.filter_quality);
+        let opacity_changed = set_if_changed(&mut render.opacity, widget.opacity);
+        if image_size_changed || width_changed || height_changed {
+            Some(RenderAction::Relayout)
+        } else if image_changed
+            || fit_changed
+            || alignment_changed
+            || filter_quality_changed
+            || opacity_changed
+        {
+            Some(RenderAction::Repaint)
+        } else {
+            None
+        }
+    }
+}
+
+pub struct RenderRawImage {
+    image: Image,
+    width: Option<f32>,
+    height: Option<f32>,
+    fit: BoxFit,
+    alignment: Alignment,
+    filter_quality: FilterQuality,
+    opacity: f32,
+}
+
+impl RenderRawImage {
+    fn image_size(&self) -> BoxSize {
+        BoxSize {
+            width: self.image.width as f32,
+            height: self.image.height as f32,
+        }
+    }
+
+    /// Force the explicit width and height upon the constraints,
+    /// then fit the image size into them while attempting to preserve its aspect ratio.
+    fn size_for_constraints(&self, constraints: &BoxConstraints) -> BoxSize {
+        let constraints =
+            BoxConstraints::new_tight_for(self.width, self.height).enforce(constraints);
+        if let Some(size) = constraints.is_tight() {
+            return size;
+        }
+        let image_size = self.image_size();
+        if image_size.width <= 0.0 || image_size.height <= 0.0 {
+            return constraints.constrain(image_size);
+        }
+        let aspect_ratio = image_size.width / image_size.height;
+        let BoxSize {
+            mut width,
+            mut height,
+        } = image_size;
+        if width > constraints.max_width {
+            width = constraints.max_width;
+            height = width / aspect_ratio;
+        }
+        if height > constraints.max_height {
+            height = constraints.max_height;
+            width = height * aspect_ratio;
+        }
+        if width < constraints.min_width {
+            width = constraints.min_width;
+            height = width / aspect_ratio;
+        }
+        if height < constraints.min_height {
+            height = constraints.min_height;
+            width = height * aspect_ratio;
+        }
+        constraints.constrain(BoxSize { width, height })
+    }
+}
+
+impl ImplByTemplate for RenderRawImage {
+    type Template = LeafRenderTemplate;
+}
+
+impl LeafRender for RenderRawImage {
+    type Protocol = BoxProtocol;
+
+    fn perform_layout(&mut self, constraints: &BoxConstraints) -> BoxSize {
+        self.size_for_constraints(constraints)
+    }
+
+    fn perform_paint(
+        &self,
+        size: &BoxSize,
+        offset: &BoxOffset,
+        paint_ctx: &mut impl PaintContext<Canvas = Affine2dCanvas>,
+    ) {
+        let image_size = self.image_size();
+        let fitted = self.fit.apply(image_size, *size);
+        if fitted.destination.width <= 0.0 || fitted.destination.height <= 0.0 {
+            return;
+        }
+        let dst_offset = self.alignment.along_offset(BoxOffset {
+            x: size.width - fitted.destination.width,
+            y: size.height - fitted.destination.height,
+        });
+        let src_offset = self.alignment.along_offset(BoxOffset {
+            x: image_size.width - fitted.source.width,
+            y: image_size.height - fitted.source.height,
+        });
+        paint_ctx.draw_image(
+            &self.image,
+            Rect::new_point_size(src_offset, fitted.source),
+            Rect::new_point_size(*offset + dst_offset, fitted.destination),
+            self.filter_quality,
+            self.opacity,
+        );
+    }
+
+    fn compute_intrinsics(render: &mut Self, intrinsics: &mut BoxIntrinsics) {
+        use BoxIntrinsics::*;
+        let finite = |extent: f32| Some(extent).filter(|extent| extent.is_finite());
+        match intrinsics {
+            MinWidth { height, res } | MaxWidth { height, res } => {
+                let constraints = BoxConstraints::new_tight_for(None, finite(*height));
+                *res = Some(render.size_for_constraints(&constraints).width)
+            }
+            MinHeight { width, res } | MaxHeight { width, res } => {
+                let constraints = BoxConstraints::new_tight_for(finite(*width), None);
+                *res = Some(render.size_for_constraints(&constraints).height)
+            }
+        }
+    }
+}
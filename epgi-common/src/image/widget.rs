@@ -0,0 +1,66 @@
+use epgi_2d::{ArcBoxWidget, BoxProtocol, FilterQuality};
+use epgi_core::{
+    foundation::{Asc, BuildError, Error},
+    nodes::{SuspendableComponentElement, SuspendableComponentWidget},
+    tree::{BuildContext, Widget},
+};
+use epgi_macro::Declarative;
+use typed_builder::TypedBuilder;
+
+use crate::{Alignment, BoxFit, ImageCache, ImageProvider, RawImage, IMAGE_CACHE};
+
+/// Loads, decodes and paints an image.
+///
+/// Decoded images are shared with other widgets through the `cache`, which defaults to [`IMAGE_CACHE`].
+/// The build suspends while the image is being decoded, so it should be placed under a `Suspense`.
+/// If the image fails to load, the error is propagated to the nearest `ErrorBoundary`.
+#[derive(Debug, Declarative, TypedBuilder)]
+#[builder(build_method(into=Asc<Image>))]
+pub struct Image {
+    pub image: ImageProvider,
+    #[builder(default, setter(strip_option))]
+    pub width: Option<f32>,
+    #[builder(default, setter(strip_option))]
+    pub height: Option<f32>,
+    #[builder(default)]
+    pub fit: BoxFit,
+    #[builder(default = Alignment::CENTER)]
+    pub alignment: Alignment,
+    #[builder(default)]
+    pub filter_quality: FilterQuality,
+    #[builder(default = 1.0)]
+    pub opacity: f32,
+    #[builder(default = IMAGE_CACHE.clone())]
+    pub cache: ImageCache,
+}
+
+impl Widget for Image {
+    type ParentProtocol = BoxProtocol;
+    type ChildProtocol = BoxProtocol;
+    type Element = SuspendableComponentElement<BoxProtocol>;
+
+    fn into_arc_widget(self: Asc<Self>) -> Asc<dyn SuspendableComponentWidget<BoxProtocol>> {
+        self
+    }
+}
+
+impl SuspendableComponentWidget<BoxProtocol> for Image {
+    fn build(&self, ctx: &mut BuildContext<'_>) -> Result<ArcBoxWidget, BuildError> {
+        let image = ctx
+            .use_future_2(
+                |provider: ImageProvider, cache: ImageCache| cache.load(&provider),
+                self.image.clone(),
+                self.cache.clone(),
+            )?
+            .map_err(|e| Error::Custom(Box::new(e)))?;
+        Ok(Asc::new(RawImage {
+            image,
+            width: self.width,
+            height: self.height,
+            fit: self.fit,
+            alignment: self.alignment,
+            filter_quality: self.filter_quality,
+            opacity: self.opacity,
+        }))
+    }
+}
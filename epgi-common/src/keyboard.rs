@@ -0,0 +1,11 @@
+mod event;
+pub use event::*;
+
+mod ime;
+pub use ime::*;
+
+mod manager;
+pub use manager::*;
+
+mod listener;
+pub use listener::*;
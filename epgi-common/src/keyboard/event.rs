@@ -0,0 +1,348 @@
+use std::time::Instant;
+
+#[derive(Clone, Debug)]
+pub struct KeyEvent {
+    pub time_stamp: Instant,
+    /// The key on the keyboard regardless of the keyboard layout. Use it for layout-independent bindings such as WASD.
+    pub physical_key: PhysicalKey,
+    /// The meaning of the key under the current keyboard layout and modifiers. Use it for shortcuts.
+    pub logical_key: LogicalKey,
+    /// The text produced by this key press, if any.
+    pub text: Option<String>,
+    pub location: KeyLocation,
+    pub state: KeyState,
+    /// Whether this is a press generated by holding the key down.
+    pub repeat: bool,
+    /// The modifiers that were held down when this event was generated.
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyEvent {
+    pub fn new_down(
+        time_stamp: Instant,
+        physical_key: PhysicalKey,
+        logical_key: LogicalKey,
+        modifiers: KeyModifiers,
+    ) -> Self {
+        let text = match &logical_key {
+            LogicalKey::Character(character) => Some(character.clone()),
+            LogicalKey::Named(NamedKey::Space) => Some(" ".to_owned()),
+            _ => None,
+        };
+        Self {
+            time_stamp,
+            physical_key,
+            logical_key,
+            text,
+            location: KeyLocation::Standard,
+            state: KeyState::Pressed,
+            repeat: false,
+            modifiers,
+        }
+    }
+
+    pub fn new_up(
+        time_stamp: Instant,
+        physical_key: PhysicalKey,
+        logical_key: LogicalKey,
+        modifiers: KeyModifiers,
+    ) -> Self {
+        Self {
+            time_stamp,
+            physical_key,
+            logical_key,
+            text: None,
+            location: KeyLocation::Standard,
+            state: KeyState::Released,
+            repeat: false,
+            modifiers,
+        }
+    }
+
+    pub fn is_pressed(&self) -> bool {
+        self.state == KeyState::Pressed
+    }
+
+    /// Whether this is a fresh press of the given logical key with exactly the given modifiers.
+    ///
+    /// Character keys are compared case-insensitively, since shift changes the case of the produced character.
+    pub fn is_shortcut(&self, logical_key: &LogicalKey, modifiers: KeyModifiers) -> bool {
+        if !self.is_pressed() || self.repeat || self.modifiers != modifiers {
+            return false;
+        }
+        match (&self.logical_key, logical_key) {
+            (LogicalKey::Character(this), LogicalKey::Character(other)) => {
+                this.to_lowercase() == other.to_lowercase()
+            }
+            (this, other) => this == other,
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum KeyState {
+    Pressed,
+    Released,
+}
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum KeyLocation {
+    Standard,
+    Left,
+    Right,
+    Numpad,
+}
+
+bitflags::bitflags! {
+    #[derive(PartialEq, Eq, Hash, Clone, Copy, Debug, Default)]
+    pub struct KeyModifiers: u32 {
+        const SHIFT = 1;
+        const CONTROL = 1 << 1;
+        const ALT = 1 << 2;
+        /// The Windows key, or the Command key on macOS.
+        const SUPER = 1 << 3;
+    }
+}
+
+impl KeyModifiers {
+    /// The modifier used for common shortcuts on the current platform, i.e. Command on macOS and Control elsewhere.
+    pub const PRIMARY: Self = if cfg!(target_os = "macos") {
+        Self::SUPER
+    } else {
+        Self::CONTROL
+    };
+}
+
+/// The meaning of a key under the current keyboard layout.
+#[derive(PartialEq, Eq, Hash, Clone, Debug)]
+pub enum LogicalKey {
+    Named(NamedKey),
+    /// The character typed by the key, taking into account the keyboard layout and the modifiers.
+    Character(String),
+    /// A dead key used to compose the next character, with the diacritic if known.
+    Dead(Option<char>),
+    Unidentified,
+}
+
+impl LogicalKey {
+    pub fn character(character: impl Into<String>) -> Self {
+        Self::Character(character.into())
+    }
+}
+
+impl From<NamedKey> for LogicalKey {
+    fn from(value: NamedKey) -> Self {
+        Self::Named(value)
+    }
+}
+
+/// Keys that do not produce a character. Names follow the W3C UI Events KeyboardEvent key values.
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub enum NamedKey {
+    Alt,
+    AltGraph,
+    CapsLock,
+    Control,
+    Fn,
+    NumLock,
+    ScrollLock,
+    Shift,
+    Super,
+    Meta,
+    Enter,
+    Tab,
+    Space,
+    ArrowDown,
+    ArrowLeft,
+    ArrowRight,
+    ArrowUp,
+    End,
+    Home,
+    PageDown,
+    PageUp,
+    Backspace,
+    Clear,
+    Copy,
+    Cut,
+    Delete,
+    Insert,
+    Paste,
+    Redo,
+    Undo,
+    Escape,
+    ContextMenu,
+    Pause,
+    PrintScreen,
+    AudioVolumeDown,
+    AudioVolumeUp,
+    AudioVolumeMute,
+    MediaPlayPause,
+    MediaStop,
+    MediaTrackNext,
+    MediaTrackPrevious,
+    BrowserBack,
+    BrowserForward,
+    F1,
+    F2,
+    F3,
+    F4,
+    F5,
+    F6,
+    F7,
+    F8,
+    F9,
+    F10,
+    F11,
+    F12,
+    F13,
+    F14,
+    F15,
+    F16,
+    F17,
+    F18,
+    F19,
+    F20,
+    F21,
+    F22,
+    F23,
+    F24,
+}
+
+/// The position of a key on the keyboard, regardless of the keyboard layout.
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub enum PhysicalKey {
+    Code(KeyCode),
+    Unidentified,
+}
+
+impl From<KeyCode> for PhysicalKey {
+    fn from(value: KeyCode) -> Self {
+        Self::Code(value)
+    }
+}
+
+/// Physical key codes. Names follow the W3C UI Events KeyboardEvent code values, and are named after a US layout.
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub enum KeyCode {
+    Backquote,
+    Backslash,
+    BracketLeft,
+    BracketRight,
+    Comma,
+    Digit0,
+    Digit1,
+    Digit2,
+    Digit3,
+    Digit4,
+    Digit5,
+    Digit6,
+    Digit7,
+    Digit8,
+    Digit9,
+    Equal,
+    IntlBackslash,
+    IntlRo,
+    IntlYen,
+    KeyA,
+    KeyB,
+    KeyC,
+    KeyD,
+    KeyE,
+    KeyF,
+    KeyG,
+    KeyH,
+    KeyI,
+    KeyJ,
+    KeyK,
+    KeyL,
+    KeyM,
+    KeyN,
+    KeyO,
+    KeyP,
+    KeyQ,
+    KeyR,
+    KeyS,
+    KeyT,
+    KeyU,
+    KeyV,
+    KeyW,
+    KeyX,
+    KeyY,
+    KeyZ,
+    Minus,
+    Period,
+    Quote,
+    Semicolon,
+    Slash,
+    AltLeft,
+    AltRight,
+    Backspace,
+    CapsLock,
+    ContextMenu,
+    ControlLeft,
+    ControlRight,
+    Enter,
+    SuperLeft,
+    SuperRight,
+    ShiftLeft,
+    ShiftRight,
+    Space,
+    Tab,
+    Delete,
+    End,
+    Home,
+    Insert,
+    PageDown,
+    PageUp,
+    ArrowDown,
+    ArrowLeft,
+    ArrowRight,
+    ArrowUp,
+    NumLock,
+    Numpad0,
+    Numpad1,
+    Numpad2,
+    Numpad3,
+    Numpad4,
+    Numpad5,
+    Numpad6,
+    Numpad7,
+    Numpad8,
+    Numpad9,
+    NumpadAdd,
+    NumpadDecimal,
+    NumpadDivide,
+    NumpadEnter,
+    NumpadEqual,
+    NumpadMultiply,
+    NumpadSubtract,
+    Escape,
+    Fn,
+    PrintScreen,
+    ScrollLock,
+    Pause,
+    F1,
+    F2,
+    F3,
+    F4,
+    F5,
+    F6,
+    F7,
+    F8,
+    F9,
+    F10,
+    F11,
+    F12,
+    F13,
+    F14,
+    F15,
+    F16,
+    F17,
+    F18,
+    F19,
+    F20,
+    F21,
+    F22,
+    F23,
+    F24,
+}
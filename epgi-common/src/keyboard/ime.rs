@@ -0,0 +1,24 @@
+use epgi_core::{foundation::Asc, scheduler::JobBuilder};
+
+use super::KeyEventResult;
+
+pub type ArcImeEventCallback =
+    Asc<dyn Fn(&ImeEvent, &mut JobBuilder) -> KeyEventResult + Send + Sync>;
+
+/// An event from the platform input method editor (IME).
+///
+/// Input methods compose text that does not map to single key presses, such as CJK characters or accented letters.
+/// While composing, the IME reports the preedit text, which is finally replaced by the committed text.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum ImeEvent {
+    Enabled,
+    /// The text being composed. An empty text clears the composition.
+    Preedit {
+        text: String,
+        /// The byte range of the cursor within the preedit text, if it should be shown.
+        cursor: Option<(usize, usize)>,
+    },
+    /// The composition is finished and the text should be inserted.
+    Commit(String),
+    Disabled,
+}
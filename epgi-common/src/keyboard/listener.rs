@@ -0,0 +1,107 @@
+use epgi_2d::{ArcBoxWidget, BoxProtocol};
+use epgi_core::{
+    foundation::{
+        Arc, Asc, AscProvideExt, InlinableDwsizeVec, Provide, SmallVecExt, SyncMutex, TypeKey,
+    },
+    nodes::{ConsumerElement, ConsumerWidget},
+    read_providers,
+    scheduler::{get_current_scheduler, JobBuilder},
+    tree::{BuildContext, ElementBase, Widget},
+};
+use epgi_macro::Declarative;
+use typed_builder::TypedBuilder;
+
+use super::{KeyEvent, KeyEventHandler, KeyEventHandlerRegistry, KeyEventResult};
+
+pub type ArcKeyEventCallback =
+    Asc<dyn Fn(&KeyEvent, &mut JobBuilder) -> KeyEventResult + Send + Sync>;
+
+/// Calls [`KeyboardListener::on_key_event`] for every key event received by the app.
+///
+/// Listeners deeper in the tree are not guaranteed to be visited first.
+/// Returning [`KeyEventResult::Handled`] stops the event from reaching the remaining listeners.
+#[derive(Declarative, TypedBuilder)]
+#[builder(build_method(into=Asc<KeyboardListener>))]
+pub struct KeyboardListener {
+    #[builder(setter(transform=|op: impl Fn(&KeyEvent, &mut JobBuilder) -> KeyEventResult + Send + Sync + 'static| Asc::new(op) as _))]
+    pub on_key_event: ArcKeyEventCallback,
+    pub child: ArcBoxWidget,
+}
+
+impl std::fmt::Debug for KeyboardListener {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("KeyboardListener")
+            .field("child", &self.child)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Widget for KeyboardListener {
+    type ParentProtocol = BoxProtocol;
+    type ChildProtocol = BoxProtocol;
+    type Element = ConsumerElement<BoxProtocol>;
+
+    fn into_arc_widget(self: Asc<Self>) -> <Self::Element as ElementBase>::ArcWidget {
+        self
+    }
+}
+
+lazy_static::lazy_static! {
+    static ref KEYBOARD_LISTENER_CONSUMED_TYPES: [TypeKey; 1] = [
+        TypeKey::of::<KeyEventHandlerRegistry>(),
+    ];
+}
+
+impl ConsumerWidget<BoxProtocol> for KeyboardListener {
+    fn get_consumed_types(&self) -> &[TypeKey] {
+        KEYBOARD_LISTENER_CONSUMED_TYPES.as_ref()
+    }
+
+    fn build(
+        &self,
+        ctx: &mut BuildContext,
+        provider_values: InlinableDwsizeVec<Arc<dyn Provide>>,
+    ) -> ArcBoxWidget {
+        let registry = read_providers!(provider_values, KeyEventHandlerRegistry);
+        // The handler is registered once and keeps pointing to the latest callback.
+        let handler = ctx.use_memo(
+            |_| {
+                Arc::new(KeyboardListenerHandler {
+                    on_key_event: SyncMutex::new(self.on_key_event.clone()),
+                })
+            },
+            (),
+        );
+        *handler.on_key_event.lock() = self.on_key_event.clone();
+        ctx.use_effect(
+            move |registry: KeyEventHandlerRegistry| {
+                let registration = registry.register(handler);
+                move || drop(registration)
+            },
+            registry.as_ref().clone(),
+        );
+        self.child.clone()
+    }
+}
+
+struct KeyboardListenerHandler {
+    on_key_event: SyncMutex<ArcKeyEventCallback>,
+}
+
+impl std::fmt::Debug for KeyboardListenerHandler {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("KeyboardListenerHandler")
+            .finish_non_exhaustive()
+    }
+}
+
+impl KeyEventHandler for KeyboardListenerHandler {
+    fn handle_key_event(&self, event: &KeyEvent) -> KeyEventResult {
+        let on_key_event = self.on_key_event.lock().clone();
+        let mut result = KeyEventResult::Ignored;
+        get_current_scheduler().create_sync_job(|job_builder| {
+            result = on_key_event(event, job_builder);
+        });
+        result
+    }
+}
@@ -0,0 +1,161 @@
+use epgi_core::foundation::{Arc, SyncMpscReceiver, SyncMutex};
+use hashbrown::HashMap;
+
+use super::{ImeEvent, KeyEvent, KeyModifiers, KeyState, LogicalKey, PhysicalKey};
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum KeyEventResult {
+    Handled,
+    Ignored,
+}
+
+pub trait KeyEventHandler: Send + Sync + 'static {
+    fn handle_key_event(&self, event: &KeyEvent) -> KeyEventResult;
+}
+
+/// The set of key event handlers that the [`KeyboardManager`] dispatches to.
+///
+/// The embedding provides the registry to the whole tree, so that widgets can register their handlers.
+#[derive(Clone, Default)]
+pub struct KeyEventHandlerRegistry {
+    inner: Arc<SyncMutex<KeyEventHandlerRegistryInner>>,
+}
+
+#[derive(Default)]
+struct KeyEventHandlerRegistryInner {
+    next_id: u64,
+    handlers: Vec<(u64, Arc<dyn KeyEventHandler>)>,
+}
+
+impl KeyEventHandlerRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The handler stays registered until the returned registration is dropped.
+    #[must_use]
+    pub fn register(&self, handler: Arc<dyn KeyEventHandler>) -> KeyEventHandlerRegistration {
+        let mut inner = self.inner.lock();
+        let id = inner.next_id;
+        inner.next_id += 1;
+        inner.handlers.push((id, handler));
+        KeyEventHandlerRegistration {
+            registry: Arc::downgrade(&self.inner),
+            id,
+        }
+    }
+
+    /// Handlers are visited from the most recently registered one, until one of them handles the event.
+    pub fn dispatch(&self, event: &KeyEvent) -> KeyEventResult {
+        // Handlers may register or unregister other handlers, so we must not hold the lock while dispatching.
+        let handlers = self
+            .inner
+            .lock()
+            .handlers
+            .iter()
+            .map(|(_, handler)| handler.clone())
+            .collect::<Vec<_>>();
+        for handler in handlers.iter().rev() {
+            if handler.handle_key_event(event) == KeyEventResult::Handled {
+                return KeyEventResult::Handled;
+            }
+        }
+        KeyEventResult::Ignored
+    }
+}
+
+impl PartialEq for KeyEventHandlerRegistry {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
+impl std::fmt::Debug for KeyEventHandlerRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("KeyEventHandlerRegistry")
+            .field("handlers", &self.inner.lock().handlers.len())
+            .finish()
+    }
+}
+
+pub struct KeyEventHandlerRegistration {
+    registry: std::sync::Weak<SyncMutex<KeyEventHandlerRegistryInner>>,
+    id: u64,
+}
+
+impl Drop for KeyEventHandlerRegistration {
+    fn drop(&mut self) {
+        if let Some(registry) = self.registry.upgrade() {
+            registry.lock().handlers.retain(|(id, _)| *id != self.id);
+        }
+    }
+}
+
+/// Receives key events from the embedding, tracks the keyboard state and dispatches the events to the handlers.
+///
+/// Input method events are received on a separate channel and are not seen by the key event handlers.
+/// Their order relative to the key events received within the same frame is not preserved.
+pub struct KeyboardManager {
+    rx: SyncMpscReceiver<KeyEvent>,
+    ime_rx: SyncMpscReceiver<ImeEvent>,
+    registry: KeyEventHandlerRegistry,
+    pressed_keys: HashMap<PhysicalKey, LogicalKey>,
+    modifiers: KeyModifiers,
+}
+
+impl KeyboardManager {
+    pub fn new(rx: SyncMpscReceiver<KeyEvent>, ime_rx: SyncMpscReceiver<ImeEvent>) -> Self {
+        Self {
+            rx,
+            ime_rx,
+            registry: KeyEventHandlerRegistry::new(),
+            pressed_keys: Default::default(),
+            modifiers: KeyModifiers::empty(),
+        }
+    }
+
+    pub fn registry(&self) -> &KeyEventHandlerRegistry {
+        &self.registry
+    }
+
+    /// Dispatch the received key events to the registered handlers.
+    ///
+    /// Events ignored by every handler are passed on to `on_ignored`, which is usually [`FocusManager::handle_key_event`](crate::FocusManager::handle_key_event).
+    pub fn flush_events(&mut self, mut on_ignored: impl FnMut(&KeyEvent) -> KeyEventResult) {
+        while let Ok(event) = self.rx.try_recv() {
+            if self.handle_key_event(&event) == KeyEventResult::Ignored {
+                on_ignored(&event);
+            }
+        }
+    }
+
+    /// Pass the received input method events on to `on_event`, which is usually [`FocusManager::handle_ime_event`](crate::FocusManager::handle_ime_event).
+    pub fn flush_ime_events(&mut self, mut on_event: impl FnMut(&ImeEvent) -> KeyEventResult) {
+        while let Ok(event) = self.ime_rx.try_recv() {
+            on_event(&event);
+        }
+    }
+
+    /// The keys that are currently held down, and the logical keys they produced when pressed.
+    pub fn pressed_keys(&self) -> &HashMap<PhysicalKey, LogicalKey> {
+        &self.pressed_keys
+    }
+
+    pub fn modifiers(&self) -> KeyModifiers {
+        self.modifiers
+    }
+
+    fn handle_key_event(&mut self, event: &KeyEvent) -> KeyEventResult {
+        self.modifiers = event.modifiers;
+        match event.state {
+            KeyState::Pressed => {
+                self.pressed_keys
+                    .insert(event.physical_key, event.logical_key.clone());
+            }
+            KeyState::Released => {
+                self.pressed_keys.remove(&event.physical_key);
+            }
+        }
+        self.registry.dispatch(event)
+    }
+}
@@ -7,12 +7,24 @@ pub use basic::*;
 mod frame_info;
 pub use frame_info::*;
 
+mod focus;
+pub use focus::*;
+
 pub mod gesture;
 pub use gesture::*;
 
+mod image;
+pub use image::*;
+
+pub mod keyboard;
+pub use keyboard::*;
+
 mod physics;
 pub use physics::*;
 
+mod scroll;
+pub use scroll::*;
+
 mod utils;
 pub use utils::*;
 
@@ -1,2 +1,8 @@
+mod friction_simulation;
+pub use friction_simulation::*;
+
 mod simulation;
 pub use simulation::*;
+
+mod spring_simulation;
+pub use spring_simulation::*;
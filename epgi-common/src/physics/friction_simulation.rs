@@ -0,0 +1,78 @@
+use std::time::Duration;
+
+use crate::Simulation;
+
+/// A particle slowed down by a drag proportional to its velocity.
+///
+/// `drag` is the fraction of the velocity that is kept after one second.
+#[derive(Clone, Debug)]
+pub struct FrictionSimulation {
+    drag: f32,
+    drag_log: f32,
+    position: f32,
+    velocity: f32,
+    /// The simulation completes once the speed drops below this value.
+    velocity_tolerance: f32,
+}
+
+impl FrictionSimulation {
+    pub const DEFAULT_VELOCITY_TOLERANCE: f32 = 1.0;
+
+    pub fn new(drag: f32, position: f32, velocity: f32) -> Self {
+        debug_assert!(
+            drag > 0.0 && drag < 1.0,
+            "Drag of a friction simulation should be between 0 and 1"
+        );
+        Self {
+            drag,
+            drag_log: drag.ln(),
+            position,
+            velocity,
+            velocity_tolerance: Self::DEFAULT_VELOCITY_TOLERANCE,
+        }
+    }
+
+    pub fn with_velocity_tolerance(mut self, velocity_tolerance: f32) -> Self {
+        self.velocity_tolerance = velocity_tolerance;
+        self
+    }
+
+    /// The position the particle comes to rest at.
+    pub fn final_x(&self) -> f32 {
+        self.position - self.velocity / self.drag_log
+    }
+
+    /// The time at which the particle passes `x`, or `None` if it never does.
+    pub fn time_at_x(&self, x: f32) -> Option<Duration> {
+        if x == self.position {
+            return Some(Duration::ZERO);
+        }
+        if self.velocity == 0.0 {
+            return None;
+        }
+        let decay = 1.0 + (x - self.position) * self.drag_log / self.velocity;
+        if decay <= 0.0 || decay > 1.0 {
+            return None;
+        }
+        Some(Duration::from_secs_f32(decay.ln() / self.drag_log))
+    }
+}
+
+impl Simulation for FrictionSimulation {
+    fn x(&self, time: Duration) -> f32 {
+        let decay = self.drag.powf(time.as_secs_f32());
+        self.position + self.velocity * (decay - 1.0) / self.drag_log
+    }
+
+    fn dx(&self, time: Duration) -> f32 {
+        self.velocity * self.drag.powf(time.as_secs_f32())
+    }
+
+    fn completed(&self, time: Duration) -> bool {
+        self.dx(time).abs() < self.velocity_tolerance
+    }
+
+    fn clone_box(&self) -> Box<dyn Simulation> {
+        Box::new(self.clone())
+    }
+}
@@ -0,0 +1,63 @@
+use std::time::Duration;
+
+use crate::Simulation;
+
+/// A critically damped spring of unit mass, pulling a particle towards `end`.
+///
+/// A critically damped spring settles as fast as possible without oscillating,
+/// though a particle that starts moving away from `end` overshoots once before coming back.
+#[derive(Clone, Debug)]
+pub struct SpringSimulation {
+    end: f32,
+    /// The angular frequency of the spring, i.e. the square root of its stiffness.
+    omega: f32,
+    c1: f32,
+    c2: f32,
+    /// The simulation completes once the particle is this close to `end` and this slow.
+    tolerance: f32,
+}
+
+impl SpringSimulation {
+    pub const DEFAULT_TOLERANCE: f32 = 0.5;
+
+    pub fn new(stiffness: f32, start: f32, end: f32, velocity: f32) -> Self {
+        debug_assert!(
+            stiffness > 0.0,
+            "Stiffness of a spring simulation should be positive"
+        );
+        let omega = stiffness.sqrt();
+        let c1 = start - end;
+        Self {
+            end,
+            omega,
+            c1,
+            c2: velocity + omega * c1,
+            tolerance: Self::DEFAULT_TOLERANCE,
+        }
+    }
+
+    pub fn with_tolerance(mut self, tolerance: f32) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+}
+
+impl Simulation for SpringSimulation {
+    fn x(&self, time: Duration) -> f32 {
+        let t = time.as_secs_f32();
+        self.end + (self.c1 + self.c2 * t) * (-self.omega * t).exp()
+    }
+
+    fn dx(&self, time: Duration) -> f32 {
+        let t = time.as_secs_f32();
+        (self.c2 - self.omega * (self.c1 + self.c2 * t)) * (-self.omega * t).exp()
+    }
+
+    fn completed(&self, time: Duration) -> bool {
+        (self.x(time) - self.end).abs() < self.tolerance && self.dx(time).abs() < self.tolerance
+    }
+
+    fn clone_box(&self) -> Box<dyn Simulation> {
+        Box::new(self.clone())
+    }
+}
@@ -0,0 +1,23 @@
+mod controller;
+pub use controller::*;
+
+mod list_view;
+pub use list_view::*;
+
+mod scrollable;
+pub use scrollable::*;
+
+mod simulation;
+pub use simulation::*;
+
+mod single_child_scroll_view;
+pub use single_child_scroll_view::*;
+
+mod sliver_fixed_extent_list;
+pub use sliver_fixed_extent_list::*;
+
+mod sliver_list;
+pub use sliver_list::*;
+
+mod viewport;
+pub use viewport::*;
@@ -0,0 +1,291 @@
+use std::time::{Duration, Instant};
+
+use epgi_core::{
+    foundation::{Arc, SyncMutex, SyncMutexGuard},
+    hooks::SetState,
+    scheduler::JobBuilder,
+    tree::BuildContext,
+};
+
+use crate::Simulation;
+
+use super::BouncingScrollSimulation;
+
+/// Pan velocities below this are not flung, in logical pixels per second.
+pub const MIN_FLING_VELOCITY: f32 = 50.0;
+
+/// Past the scroll extents, a trackpad pan only moves the content by this fraction of the pan distance.
+pub const OVERSCROLL_RESISTANCE: f32 = 0.5;
+
+/// The fling velocity is estimated from the pan updates within this window before the pan ends.
+const VELOCITY_SAMPLE_WINDOW: Duration = Duration::from_millis(100);
+
+pub trait BuildContextScrollControllerExt {
+    /// A scroll controller that lives as long as the calling element.
+    ///
+    /// Pass it to a [`Scrollable`](crate::Scrollable) or a [`SingleChildScrollView`](crate::SingleChildScrollView)
+    /// to read and change its scroll offset from outside.
+    fn use_scroll_controller(&mut self) -> ScrollController;
+}
+
+impl BuildContextScrollControllerExt for BuildContext<'_> {
+    fn use_scroll_controller(&mut self) -> ScrollController {
+        let (controller, _) = self.use_state_with(ScrollController::new);
+        controller
+    }
+}
+
+/// Holds the scroll offset of a [`Scrollable`](crate::Scrollable), and lets the app read and change it.
+///
+/// The offset is in logical pixels along the scroll axis, and is zero when the start of the content is shown.
+/// The scroll extents are only known after the scrollable has been laid out.
+/// A controller should be attached to at most one scrollable at a time.
+#[derive(Clone)]
+pub struct ScrollController {
+    inner: Arc<SyncMutex<ScrollPosition>>,
+}
+
+struct ScrollPosition {
+    pixels: f32,
+    max_scroll_extent: f32,
+    viewport_extent: f32,
+    activity: ScrollActivity,
+    /// Rebuilds the attached scrollable, telling it whether the offset is animating.
+    set_is_animating: Option<SetState<bool>>,
+}
+
+enum ScrollActivity {
+    Idle,
+    /// A trackpad pan is in progress.
+    Drag {
+        last_pan: f32,
+        /// Recent offsets along with the time they were reached, to estimate the fling velocity.
+        samples: Vec<(Instant, f32)>,
+    },
+    /// The offset is animated by a simulation, e.g. after a fling.
+    Ballistic {
+        start_time: Instant,
+        simulation: Box<dyn Simulation>,
+    },
+}
+
+impl ScrollController {
+    pub fn new() -> Self {
+        Self::new_with_initial_offset(0.0)
+    }
+
+    pub fn new_with_initial_offset(initial_offset: f32) -> Self {
+        Self {
+            inner: Arc::new(SyncMutex::new(ScrollPosition {
+                pixels: initial_offset,
+                max_scroll_extent: f32::INFINITY,
+                viewport_extent: 0.0,
+                activity: ScrollActivity::Idle,
+                set_is_animating: None,
+            })),
+        }
+    }
+
+    pub fn offset(&self) -> f32 {
+        self.inner.lock().pixels
+    }
+
+    /// The largest offset, at which the end of the content is shown. Infinite until the scrollable is laid out.
+    pub fn max_scroll_extent(&self) -> f32 {
+        self.inner.lock().max_scroll_extent
+    }
+
+    /// The extent of the scrollable along the scroll axis, as of the last layout.
+    pub fn viewport_extent(&self) -> f32 {
+        self.inner.lock().viewport_extent
+    }
+
+    pub fn is_animating(&self) -> bool {
+        matches!(self.inner.lock().activity, ScrollActivity::Ballistic { .. })
+    }
+
+    /// Stop any ongoing scroll, and move to `offset` clamped to the scroll extents.
+    pub fn jump_to(&self, offset: f32, job_builder: &mut JobBuilder) {
+        let mut position = self.inner.lock();
+        position.pixels = offset.clamp(0.0, position.max_scroll_extent.max(0.0));
+        position.activity = ScrollActivity::Idle;
+        Self::notify(position, job_builder);
+    }
+
+    pub(crate) fn attach(&self, set_is_animating: SetState<bool>) {
+        self.inner.lock().set_is_animating = Some(set_is_animating);
+    }
+
+    pub(crate) fn detach(&self, set_is_animating: &SetState<bool>) {
+        let mut position = self.inner.lock();
+        if position.set_is_animating.as_ref() == Some(set_is_animating) {
+            position.set_is_animating = None;
+        }
+    }
+
+    /// Scroll by `delta` within the scroll extents, as a mouse wheel does.
+    pub(crate) fn scroll_by(&self, delta: f32, job_builder: &mut JobBuilder) {
+        let mut position = self.inner.lock();
+        position.pixels = (position.pixels + delta).clamp(0.0, position.max_scroll_extent.max(0.0));
+        position.activity = ScrollActivity::Idle;
+        Self::notify(position, job_builder);
+    }
+
+    /// Stop a fling in place.
+    pub(crate) fn stop(&self, job_builder: &mut JobBuilder) {
+        let mut position = self.inner.lock();
+        if !matches!(position.activity, ScrollActivity::Ballistic { .. }) {
+            return;
+        }
+        position.activity = ScrollActivity::Idle;
+        Self::notify(position, job_builder);
+    }
+
+    pub(crate) fn begin_drag(&self, time: Instant, job_builder: &mut JobBuilder) {
+        let mut position = self.inner.lock();
+        let pixels = position.pixels;
+        let was_animating = matches!(position.activity, ScrollActivity::Ballistic { .. });
+        position.activity = ScrollActivity::Drag {
+            last_pan: 0.0,
+            samples: vec![(time, pixels)],
+        };
+        if was_animating {
+            Self::notify(position, job_builder);
+        }
+    }
+
+    /// Follow a trackpad pan. `pan` is the distance panned since the pan started,
+    /// where a positive pan moves the content forward and reveals its start.
+    pub(crate) fn update_drag(&self, pan: f32, time: Instant, job_builder: &mut JobBuilder) {
+        let mut position = self.inner.lock();
+        let max_scroll_extent = position.max_scroll_extent.max(0.0);
+        let pixels = position.pixels;
+        let ScrollActivity::Drag { last_pan, samples } = &mut position.activity else {
+            return;
+        };
+        let delta = *last_pan - pan;
+        *last_pan = pan;
+        let overscrolling =
+            (pixels <= 0.0 && delta < 0.0) || (pixels >= max_scroll_extent && delta > 0.0);
+        let pixels = if overscrolling {
+            pixels + delta * OVERSCROLL_RESISTANCE
+        } else {
+            pixels + delta
+        };
+        samples.retain(|(sample_time, _)| {
+            time.saturating_duration_since(*sample_time) <= VELOCITY_SAMPLE_WINDOW
+        });
+        samples.push((time, pixels));
+        position.pixels = pixels;
+        Self::notify(position, job_builder);
+    }
+
+    /// Release a trackpad pan. The offset keeps moving with the velocity of the pan,
+    /// and springs back if it has been pulled past the scroll extents.
+    pub(crate) fn end_drag(&self, time: Instant, job_builder: &mut JobBuilder) {
+        let mut position = self.inner.lock();
+        let ScrollActivity::Drag { mut samples, .. } =
+            std::mem::replace(&mut position.activity, ScrollActivity::Idle)
+        else {
+            return;
+        };
+        // A pan that rests before it is released does not fling.
+        samples.retain(|(sample_time, _)| {
+            time.saturating_duration_since(*sample_time) <= VELOCITY_SAMPLE_WINDOW
+        });
+        let velocity = match (samples.first(), samples.last()) {
+            (Some((first_time, first_pixels)), Some((last_time, last_pixels)))
+                if last_time > first_time =>
+            {
+                (last_pixels - first_pixels) / last_time.duration_since(*first_time).as_secs_f32()
+            }
+            _ => 0.0,
+        };
+        let max_scroll_extent = position.max_scroll_extent.max(0.0);
+        let pixels = position.pixels;
+        let out_of_range = pixels < 0.0 || pixels > max_scroll_extent;
+        if out_of_range || velocity.abs() >= MIN_FLING_VELOCITY {
+            position.activity = ScrollActivity::Ballistic {
+                start_time: time,
+                simulation: Box::new(BouncingScrollSimulation::new(
+                    pixels,
+                    velocity,
+                    0.0,
+                    max_scroll_extent,
+                )),
+            };
+        }
+        Self::notify(position, job_builder);
+    }
+
+    /// Advance the ongoing simulation to `time`. Returns whether the offset is still animating.
+    pub(crate) fn tick(&self, time: Instant) -> bool {
+        let mut position = self.inner.lock();
+        let ScrollActivity::Ballistic {
+            start_time,
+            simulation,
+        } = &position.activity
+        else {
+            return false;
+        };
+        let elapsed = time.saturating_duration_since(*start_time);
+        let pixels = simulation.x(elapsed);
+        if simulation.completed(elapsed) {
+            position.pixels = pixels.clamp(0.0, position.max_scroll_extent.max(0.0));
+            position.activity = ScrollActivity::Idle;
+            return false;
+        }
+        position.pixels = pixels;
+        true
+    }
+
+    /// Record the extents measured by the viewport, and return the offset to paint with.
+    ///
+    /// When the content shrinks, an idle offset is clamped to the new extents.
+    pub(crate) fn apply_dimensions(&self, viewport_extent: f32, content_extent: f32) -> f32 {
+        let mut position = self.inner.lock();
+        position.viewport_extent = viewport_extent;
+        position.max_scroll_extent = (content_extent - viewport_extent).max(0.0);
+        if let ScrollActivity::Idle = position.activity {
+            position.pixels = position.pixels.clamp(0.0, position.max_scroll_extent);
+        }
+        position.pixels
+    }
+
+    /// Rebuild the attached scrollable, e.g. after the offset has been advanced by [`ScrollController::tick`].
+    pub(crate) fn notify_attached(&self, job_builder: &mut JobBuilder) {
+        Self::notify(self.inner.lock(), job_builder)
+    }
+
+    fn notify(position: SyncMutexGuard<'_, ScrollPosition>, job_builder: &mut JobBuilder) {
+        let is_animating = matches!(position.activity, ScrollActivity::Ballistic { .. });
+        let set_is_animating = position.set_is_animating.clone();
+        drop(position);
+        if let Some(set_is_animating) = set_is_animating {
+            set_is_animating.set(is_animating, job_builder);
+        }
+    }
+}
+
+impl Default for ScrollController {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PartialEq for ScrollController {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
+impl std::fmt::Debug for ScrollController {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let position = self.inner.lock();
+        f.debug_struct("ScrollController")
+            .field("offset", &position.pixels)
+            .field("max_scroll_extent", &position.max_scroll_extent)
+            .field("viewport_extent", &position.viewport_extent)
+            .finish()
+    }
+}
@@ -0,0 +1,127 @@
+use epgi_2d::{ArcBoxWidget, ArcSliverWidget, Axis, BoxProtocol};
+use epgi_core::{
+    foundation::Asc,
+    nodes::{ComponentElement, ComponentWidget, KeyedSubtree},
+    tree::{BuildContext, ElementBase, Widget},
+};
+use epgi_macro::Declarative;
+use typed_builder::TypedBuilder;
+
+use super::{
+    ScrollController, Scrollable, SliverFixedExtentList, SliverList, SliverListExtents, Viewport,
+};
+
+pub type ArcIndexedWidgetBuilder = Asc<dyn Fn(usize) -> ArcBoxWidget + Send + Sync>;
+
+/// A scrollable list of `item_count` items built by `item_builder`, where only the items scrolled into view are built.
+///
+/// Items pick their own extents along the axis. The visible items are found while the list is laid out,
+/// and the items that turn out to be missing are built before the frame is painted.
+/// The extents of the items that have never been laid out are estimated, see [`SliverListExtents`].
+/// If every item is `item_extent` long, setting it lets the visible items be found from the scroll offset alone,
+/// and makes the scroll extent exact.
+///
+/// Each item is keyed by its index, so an item keeps its element and state while it stays in view.
+#[derive(Declarative, TypedBuilder)]
+#[builder(build_method(into=Asc<ListView>))]
+pub struct ListView {
+    #[builder(default = Axis::Vertical)]
+    pub axis: Axis,
+    /// The controller holding the offset. If not provided, the widget manages its own controller.
+    #[builder(default, setter(strip_option))]
+    pub controller: Option<ScrollController>,
+    pub item_count: usize,
+    #[builder(default, setter(strip_option))]
+    pub item_extent: Option<f32>,
+    #[builder(setter(transform=|op: impl Fn(usize) -> ArcBoxWidget + Send + Sync + 'static| Asc::new(op) as _))]
+    pub item_builder: ArcIndexedWidgetBuilder,
+}
+
+impl std::fmt::Debug for ListView {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ListView")
+            .field("axis", &self.axis)
+            .field("controller", &self.controller)
+            .field("item_count", &self.item_count)
+            .field("item_extent", &self.item_extent)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Widget for ListView {
+    type ParentProtocol = BoxProtocol;
+    type ChildProtocol = BoxProtocol;
+    type Element = ComponentElement<BoxProtocol>;
+
+    fn into_arc_widget(self: Asc<Self>) -> <Self::Element as ElementBase>::ArcWidget {
+        self
+    }
+}
+
+impl ComponentWidget<BoxProtocol> for ListView {
+    fn build(&self, ctx: &mut BuildContext<'_>) -> ArcBoxWidget {
+        let (extents, _) = ctx.use_state_with(SliverListExtents::new);
+        let axis = self.axis;
+        let item_count = self.item_count;
+        let item_extent = self.item_extent;
+        let item_builder = self.item_builder.clone();
+        Asc::new(Scrollable {
+            axis,
+            controller: self.controller.clone(),
+            viewport_builder: Asc::new(move |controller, offset| {
+                let viewport_extent = controller.viewport_extent();
+                let (first_index, last_index) = match item_extent {
+                    Some(item_extent) => {
+                        fixed_extent_visible_range(offset, viewport_extent, item_extent, item_count)
+                    }
+                    None => extents.visible_range(offset, viewport_extent, item_count),
+                };
+                let children = (first_index..last_index)
+                    .map(|index| {
+                        Asc::new(KeyedSubtree {
+                            key: Box::new(index),
+                            child: item_builder(index),
+                        }) as _
+                    })
+                    .collect();
+                let sliver: ArcSliverWidget = match item_extent {
+                    Some(item_extent) => Asc::new(SliverFixedExtentList {
+                        item_count,
+                        item_extent,
+                        first_index,
+                        children,
+                    }),
+                    None => Asc::new(SliverList {
+                        item_count,
+                        first_index,
+                        extents: extents.clone(),
+                        controller: controller.clone(),
+                        children,
+                    }),
+                };
+                Asc::new(Viewport {
+                    axis,
+                    controller: controller.clone(),
+                    offset,
+                    sliver,
+                })
+            }),
+        })
+    }
+}
+
+/// The indices of the items that overlap the viewport, as a half-open range.
+fn fixed_extent_visible_range(
+    offset: f32,
+    viewport_extent: f32,
+    item_extent: f32,
+    item_count: usize,
+) -> (usize, usize) {
+    if item_extent <= 0.0 {
+        return (0, 0);
+    }
+    let first_index = ((offset.max(0.0) / item_extent).floor() as usize).min(item_count);
+    let last_index = (((offset + viewport_extent) / item_extent).ceil().max(0.0) as usize)
+        .clamp(first_index, item_count);
+    (first_index, last_index)
+}
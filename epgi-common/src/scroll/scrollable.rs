@@ -0,0 +1,299 @@
+use std::any::TypeId;
+
+use epgi_2d::{
+    ArcBoxWidget, BoxOffset, BoxProtocol, BoxSingleChildElement, BoxSingleChildElementTemplate,
+    BoxSingleChildRenderElement, BoxSize, Point2d,
+};
+use epgi_core::{
+    foundation::{
+        AnyRawPointer, Arc, Asc, AscProvideExt, BuildError, InlinableDwsizeVec, Provide,
+        SmallVecExt, TypeKey,
+    },
+    hit_test_interface_query_table,
+    nodes::{ComponentElement, ComponentWidget, ConsumerElement, ConsumerWidget},
+    read_providers,
+    scheduler::get_current_scheduler,
+    template::{ImplByTemplate, ProxyRender, ProxyRenderTemplate},
+    tree::{BuildContext, ElementBase, HitTestResult, RenderAction, RenderObject, Widget},
+};
+use epgi_macro::Declarative;
+use typed_builder::TypedBuilder;
+
+use crate::{
+    AnimationFrame, Axis, PointerEvent, PointerEventHandler, PointerEventVariantData,
+    PointerInteractionVariantData, PointerSignalData,
+};
+
+use super::{BuildContextScrollControllerExt, ScrollController};
+
+pub type ArcScrollViewportBuilder =
+    Asc<dyn Fn(&ScrollController, f32) -> ArcBoxWidget + Send + Sync>;
+
+/// Scrolls the viewport built by `viewport_builder` in response to mouse wheels and trackpad pans.
+///
+/// The viewport is built with the controller and the current scroll offset, and is expected to report
+/// its extents back to the controller during layout, as [`ScrollViewport`](crate::ScrollViewport) does.
+///
+/// A mouse wheel scrolls within the scroll extents. A trackpad pan can pull the content past the extents,
+/// and the content keeps moving after the pan is released, springing back if needed.
+/// Animating the offset requires an [`AnimationFrame`] provider above, e.g. from `MaterialApp`.
+#[derive(Declarative, TypedBuilder)]
+#[builder(build_method(into=Asc<Scrollable>))]
+pub struct Scrollable {
+    #[builder(default = Axis::Vertical)]
+    pub axis: Axis,
+    /// The controller holding the offset. If not provided, the widget manages its own controller.
+    #[builder(default, setter(strip_option))]
+    pub controller: Option<ScrollController>,
+    #[builder(setter(transform=|op: impl Fn(&ScrollController, f32) -> ArcBoxWidget + Send + Sync + 'static| Asc::new(op) as _))]
+    pub viewport_builder: ArcScrollViewportBuilder,
+}
+
+impl std::fmt::Debug for Scrollable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Scrollable")
+            .field("axis", &self.axis)
+            .field("controller", &self.controller)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Widget for Scrollable {
+    type ParentProtocol = BoxProtocol;
+    type ChildProtocol = BoxProtocol;
+    type Element = ComponentElement<BoxProtocol>;
+
+    fn into_arc_widget(self: Asc<Self>) -> <Self::Element as ElementBase>::ArcWidget {
+        self
+    }
+}
+
+impl ComponentWidget<BoxProtocol> for Scrollable {
+    fn build(&self, ctx: &mut BuildContext<'_>) -> ArcBoxWidget {
+        let own_controller = ctx.use_scroll_controller();
+        let controller = self.controller.clone().unwrap_or(own_controller);
+        // The state only schedules the rebuilds. The controller holds the latest offset.
+        let (is_animating, set_is_animating) = ctx.use_state_with(|| controller.is_animating());
+        ctx.use_effect(
+            move |controller: ScrollController| {
+                controller.attach(set_is_animating.clone());
+                move || controller.detach(&set_is_animating)
+            },
+            controller.clone(),
+        );
+        Asc::new(ScrollTicker {
+            controller,
+            axis: self.axis,
+            is_animating,
+            viewport_builder: self.viewport_builder.clone(),
+        })
+    }
+}
+
+/// Advances the animated offset every frame, while subscribing to the animation frame only when animating.
+struct ScrollTicker {
+    controller: ScrollController,
+    axis: Axis,
+    is_animating: bool,
+    viewport_builder: ArcScrollViewportBuilder,
+}
+
+impl std::fmt::Debug for ScrollTicker {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ScrollTicker")
+            .field("controller", &self.controller)
+            .field("axis", &self.axis)
+            .field("is_animating", &self.is_animating)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Widget for ScrollTicker {
+    type ParentProtocol = BoxProtocol;
+    type ChildProtocol = BoxProtocol;
+    type Element = ConsumerElement<BoxProtocol>;
+
+    fn into_arc_widget(self: Asc<Self>) -> <Self::Element as ElementBase>::ArcWidget {
+        self
+    }
+}
+
+lazy_static::lazy_static! {
+    static ref SCROLL_TICKER_CONSUMED_TYPES_ANIMATING: [TypeKey; 1] = [
+        TypeKey::of::<AnimationFrame>(),
+    ];
+}
+
+impl ConsumerWidget<BoxProtocol> for ScrollTicker {
+    fn get_consumed_types(&self) -> &[TypeKey] {
+        if self.is_animating {
+            SCROLL_TICKER_CONSUMED_TYPES_ANIMATING.as_ref()
+        } else {
+            &[]
+        }
+    }
+
+    fn build(
+        &self,
+        ctx: &mut BuildContext,
+        provider_values: InlinableDwsizeVec<Arc<dyn Provide>>,
+    ) -> ArcBoxWidget {
+        let (frame_time, still_animating) = if self.is_animating {
+            let frame = read_providers!(provider_values, AnimationFrame);
+            (Some(frame.time), self.controller.tick(frame.time))
+        } else {
+            (None, false)
+        };
+        // Request the next frame while animating, and unsubscribe from the animation frame once the simulation has completed.
+        let controller = self.controller.clone();
+        ctx.use_effect(
+            move |(frame_time, _still_animating)| {
+                if frame_time.is_some() {
+                    get_current_scheduler().create_sync_job(|job_builder| {
+                        controller.notify_attached(job_builder);
+                    });
+                }
+            },
+            (frame_time, still_animating),
+        );
+
+        let child = (self.viewport_builder)(&self.controller, self.controller.offset());
+        Asc::new(ScrollInputListener {
+            controller: self.controller.clone(),
+            axis: self.axis,
+            child,
+        })
+    }
+}
+
+#[derive(Debug)]
+struct ScrollInputListener {
+    controller: ScrollController,
+    axis: Axis,
+    child: ArcBoxWidget,
+}
+
+impl Widget for ScrollInputListener {
+    type ParentProtocol = BoxProtocol;
+    type ChildProtocol = BoxProtocol;
+    type Element = ScrollInputListenerElement;
+
+    fn into_arc_widget(self: Asc<Self>) -> <Self::Element as ElementBase>::ArcWidget {
+        self
+    }
+}
+
+#[derive(Clone)]
+struct ScrollInputListenerElement;
+
+impl ImplByTemplate for ScrollInputListenerElement {
+    type Template = BoxSingleChildElementTemplate<true, false>;
+}
+
+impl BoxSingleChildElement for ScrollInputListenerElement {
+    type ArcWidget = Asc<ScrollInputListener>;
+
+    fn get_child_widget(
+        _element: Option<&mut Self>,
+        widget: &Self::ArcWidget,
+        _ctx: &mut BuildContext<'_>,
+        _provider_values: InlinableDwsizeVec<Arc<dyn Provide>>,
+    ) -> Result<ArcBoxWidget, BuildError> {
+        Ok(widget.child.clone())
+    }
+
+    fn create_element(_widget: &Self::ArcWidget) -> Self {
+        Self
+    }
+}
+
+impl BoxSingleChildRenderElement for ScrollInputListenerElement {
+    type Render = RenderScrollInputListener;
+
+    fn create_render(&self, widget: &Self::ArcWidget) -> Self::Render {
+        RenderScrollInputListener {
+            controller: widget.controller.clone(),
+            axis: widget.axis,
+        }
+    }
+
+    fn update_render(render: &mut Self::Render, widget: &Self::ArcWidget) -> Option<RenderAction> {
+        render.controller = widget.controller.clone();
+        render.axis = widget.axis;
+        None
+    }
+}
+
+struct RenderScrollInputListener {
+    controller: ScrollController,
+    axis: Axis,
+}
+
+impl ImplByTemplate for RenderScrollInputListener {
+    type Template = ProxyRenderTemplate;
+}
+
+impl ProxyRender for RenderScrollInputListener {
+    type Protocol = BoxProtocol;
+
+    fn hit_test_self(
+        &self,
+        _position: &Point2d,
+        _size: &BoxSize,
+        _offset: &BoxOffset,
+    ) -> HitTestResult {
+        HitTestResult::Hit
+    }
+
+    fn all_hit_test_interfaces() -> &'static [(TypeId, fn(*mut RenderObject<Self>) -> AnyRawPointer)]
+    {
+        SCROLL_INPUT_LISTENER_HIT_TEST_INTERFACE_TABLE.as_slice()
+    }
+
+    const NOOP_DETACH: bool = true;
+}
+
+hit_test_interface_query_table!(
+    SCROLL_INPUT_LISTENER_HIT_TEST_INTERFACE_TABLE,
+    RenderScrollInputListener,
+    dyn PointerEventHandler,
+);
+
+impl PointerEventHandler for RenderObject<RenderScrollInputListener> {
+    fn handle_pointer_event(&self, _transformed_position: Point2d, event: &PointerEvent) {
+        use PointerEventVariantData::*;
+        use PointerInteractionVariantData::*;
+        let is_scroll_input = matches!(
+            &event.variant,
+            Signal(PointerSignalData::Scroll { .. } | PointerSignalData::ScrollInertialCancel)
+                | Interaction {
+                    variant: PanZoomStart | PanZoomUpdate(_) | PanZoomEnd,
+                    ..
+                }
+        );
+        if !is_scroll_input {
+            return;
+        }
+        let (controller, axis) = self.update(|render, _| (render.controller.clone(), render.axis));
+        let main_axis = |offset: &BoxOffset| match axis {
+            Axis::Horizontal => offset.x,
+            Axis::Vertical => offset.y,
+        };
+        let time = event.common.time_stamp;
+        get_current_scheduler().create_sync_job(|job_builder| match &event.variant {
+            Signal(PointerSignalData::Scroll { physical_delta }) => {
+                controller.scroll_by(main_axis(physical_delta), job_builder)
+            }
+            Signal(PointerSignalData::ScrollInertialCancel) => controller.stop(job_builder),
+            Interaction { variant, .. } => match variant {
+                PanZoomStart => controller.begin_drag(time, job_builder),
+                PanZoomUpdate(update) => {
+                    controller.update_drag(main_axis(&update.pan), time, job_builder)
+                }
+                PanZoomEnd => controller.end_drag(time, job_builder),
+                _ => {}
+            },
+            _ => {}
+        });
+    }
+}
@@ -0,0 +1,91 @@
+use std::time::Duration;
+
+use crate::{FrictionSimulation, Simulation, SpringSimulation};
+
+/// The motion of a scroll position after the user lets go of it.
+///
+/// Within the scroll extents, the position coasts to a stop under friction.
+/// Once it is past one of the extents, whether it started there or coasted there,
+/// a spring pulls it back to that extent.
+#[derive(Clone, Debug)]
+pub struct BouncingScrollSimulation {
+    friction: Option<FrictionSimulation>,
+    spring: Option<SpringSimulation>,
+    /// The time at which the spring takes over from the friction.
+    spring_time: Duration,
+}
+
+impl BouncingScrollSimulation {
+    /// The fraction of the velocity that is kept after one second of coasting.
+    pub const DRAG: f32 = 0.135;
+
+    pub const SPRING_STIFFNESS: f32 = 200.0;
+
+    pub fn new(position: f32, velocity: f32, min_extent: f32, max_extent: f32) -> Self {
+        let spring = |start: f32, end: f32, velocity: f32| {
+            SpringSimulation::new(Self::SPRING_STIFFNESS, start, end, velocity)
+        };
+        if position < min_extent || position > max_extent {
+            let end = position.clamp(min_extent, max_extent);
+            return Self {
+                friction: None,
+                spring: Some(spring(position, end, velocity)),
+                spring_time: Duration::ZERO,
+            };
+        }
+
+        let friction = FrictionSimulation::new(Self::DRAG, position, velocity);
+        let final_x = friction.final_x();
+        let boundary = if final_x > max_extent {
+            Some(max_extent)
+        } else if final_x < min_extent {
+            Some(min_extent)
+        } else {
+            None
+        };
+        let Some(boundary) = boundary else {
+            return Self {
+                friction: Some(friction),
+                spring: None,
+                spring_time: Duration::ZERO,
+            };
+        };
+        // Hand the velocity over to the spring at the moment the position crosses the extent.
+        let spring_time = friction.time_at_x(boundary).unwrap_or(Duration::ZERO);
+        Self {
+            spring: Some(spring(boundary, boundary, friction.dx(spring_time))),
+            friction: Some(friction),
+            spring_time,
+        }
+    }
+
+    fn current(&self, time: Duration) -> (&dyn Simulation, Duration) {
+        match (&self.friction, &self.spring) {
+            (Some(friction), Some(_)) if time < self.spring_time => (friction, time),
+            (_, Some(spring)) => (spring, time.saturating_sub(self.spring_time)),
+            (Some(friction), None) => (friction, time),
+            (None, None) => unreachable!("A scroll simulation should have at least one phase"),
+        }
+    }
+}
+
+impl Simulation for BouncingScrollSimulation {
+    fn x(&self, time: Duration) -> f32 {
+        let (simulation, time) = self.current(time);
+        simulation.x(time)
+    }
+
+    fn dx(&self, time: Duration) -> f32 {
+        let (simulation, time) = self.current(time);
+        simulation.dx(time)
+    }
+
+    fn completed(&self, time: Duration) -> bool {
+        let (simulation, time) = self.current(time);
+        simulation.completed(time)
+    }
+
+    fn clone_box(&self) -> Box<dyn Simulation> {
+        Box::new(self.clone())
+    }
+}
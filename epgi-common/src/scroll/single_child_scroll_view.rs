@@ -0,0 +1,233 @@
+use epgi_2d::{
+    Affine2d, Affine2dCanvas, Affine2dPaintContextExt, ArcBoxRenderObject, ArcBoxWidget, BlendMode,
+    BoxConstraints, BoxIntrinsics, BoxOffset, BoxProtocol, BoxSingleChildElement,
+    BoxSingleChildElementTemplate, BoxSingleChildRenderElement, BoxSize, ShiftedBoxRender,
+    ShiftedBoxRenderTemplate,
+};
+use epgi_core::{
+    foundation::{Arc, Asc, BuildError, InlinableDwsizeVec, PaintContext, Provide},
+    nodes::{ComponentElement, ComponentWidget},
+    template::ImplByTemplate,
+    tree::{BuildContext, ElementBase, HitTestContext, RenderAction, Widget},
+};
+use epgi_macro::Declarative;
+use typed_builder::TypedBuilder;
+
+use crate::Axis;
+
+use super::{ScrollController, Scrollable};
+
+/// Scrolls a single child that may be larger than the space available to it.
+///
+/// The child is laid out without a limit along the scroll axis, and is clipped to the bounds of this widget.
+/// See [`Scrollable`] for the input that scrolls it.
+#[derive(Debug, Declarative, TypedBuilder)]
+#[builder(build_method(into=Asc<SingleChildScrollView>))]
+pub struct SingleChildScrollView {
+    #[builder(default = Axis::Vertical)]
+    pub axis: Axis,
+    /// The controller holding the offset. If not provided, the widget manages its own controller.
+    #[builder(default, setter(strip_option))]
+    pub controller: Option<ScrollController>,
+    pub child: ArcBoxWidget,
+}
+
+impl Widget for SingleChildScrollView {
+    type ParentProtocol = BoxProtocol;
+    type ChildProtocol = BoxProtocol;
+    type Element = ComponentElement<BoxProtocol>;
+
+    fn into_arc_widget(self: Asc<Self>) -> <Self::Element as ElementBase>::ArcWidget {
+        self
+    }
+}
+
+impl ComponentWidget<BoxProtocol> for SingleChildScrollView {
+    fn build(&self, _ctx: &mut BuildContext<'_>) -> ArcBoxWidget {
+        let axis = self.axis;
+        let child = self.child.clone();
+        Asc::new(Scrollable {
+            axis,
+            controller: self.controller.clone(),
+            viewport_builder: Asc::new(move |controller, offset| {
+                Asc::new(ScrollViewport {
+                    axis,
+                    controller: controller.clone(),
+                    offset,
+                    child: child.clone(),
+                })
+            }),
+        })
+    }
+}
+
+/// Shows the part of its child that starts at `offset` along the axis, and reports its extents to the controller.
+///
+/// The child is shifted with a transform rather than laid out again, so scrolling only repaints.
+#[derive(Debug, Declarative, TypedBuilder)]
+#[builder(build_method(into=Asc<ScrollViewport>))]
+pub struct ScrollViewport {
+    #[builder(default = Axis::Vertical)]
+    pub axis: Axis,
+    pub controller: ScrollController,
+    pub offset: f32,
+    pub child: ArcBoxWidget,
+}
+
+impl Widget for ScrollViewport {
+    type ParentProtocol = BoxProtocol;
+    type ChildProtocol = BoxProtocol;
+    type Element = ScrollViewportElement;
+
+    fn into_arc_widget(self: Asc<Self>) -> Asc<Self> {
+        self
+    }
+}
+
+#[derive(Clone)]
+pub struct ScrollViewportElement {}
+
+impl ImplByTemplate for ScrollViewportElement {
+    type Template = BoxSingleChildElementTemplate<true, false>;
+}
+
+impl BoxSingleChildElement for ScrollViewportElement {
+    type ArcWidget = Asc<ScrollViewport>;
+
+    fn get_child_widget(
+        _element: Option<&mut Self>,
+        widget: &Self::ArcWidget,
+        _ctx: &mut BuildContext<'_>,
+        _provider_values: InlinableDwsizeVec<Arc<dyn Provide>>,
+    ) -> Result<ArcBoxWidget, BuildError> {
+        Ok(widget.child.clone())
+    }
+
+    fn create_element(_widget: &Self::ArcWidget) -> Self {
+        Self {}
+    }
+}
+
+impl BoxSingleChildRenderElement for ScrollViewportElement {
+    type Render = RenderScrollViewport;
+
+    fn create_render(&self, widget: &Self::ArcWidget) -> Self::Render {
+        RenderScrollViewport {
+            axis: widget.axis,
+            controller: widget.controller.clone(),
+            offset: widget.offset,
+        }
+    }
+
+    fn update_render(render: &mut Self::Render, widget: &Self::ArcWidget) -> Option<RenderAction> {
+        // The extents have to be reported to a new controller.
+        let needs_relayout = render.axis != widget.axis || render.controller != widget.controller;
+        let needs_repaint = render.offset != widget.offset;
+        render.axis = widget.axis;
+        render.controller = widget.controller.clone();
+        render.offset = widget.offset;
+        if needs_relayout {
+            Some(RenderAction::Relayout)
+        } else if needs_repaint {
+            Some(RenderAction::Repaint)
+        } else {
+            None
+        }
+    }
+}
+
+pub struct RenderScrollViewport {
+    axis: Axis,
+    controller: ScrollController,
+    offset: f32,
+}
+
+impl RenderScrollViewport {
+    /// The transform that shifts the child by the scroll offset.
+    fn scroll_transform(&self) -> Affine2d {
+        Affine2d::from_translation(&match self.axis {
+            Axis::Horizontal => BoxOffset {
+                x: -self.offset,
+                y: 0.0,
+            },
+            Axis::Vertical => BoxOffset {
+                x: 0.0,
+                y: -self.offset,
+            },
+        })
+    }
+}
+
+impl ImplByTemplate for RenderScrollViewport {
+    type Template = ShiftedBoxRenderTemplate;
+}
+
+impl ShiftedBoxRender for RenderScrollViewport {
+    type LayoutMemo = ();
+
+    /// The child is placed at the origin of the viewport. The scroll offset is applied as a transform when painting.
+    fn get_child_offset(&self, _size: &BoxSize, offset: &BoxOffset, _memo: &()) -> BoxOffset {
+        *offset
+    }
+
+    fn perform_layout(
+        &mut self,
+        constraints: &BoxConstraints,
+        child: &ArcBoxRenderObject,
+    ) -> (BoxSize, Self::LayoutMemo) {
+        let child_constraints = match self.axis {
+            Axis::Horizontal => BoxConstraints {
+                min_width: 0.0,
+                max_width: f32::INFINITY,
+                ..*constraints
+            },
+            Axis::Vertical => BoxConstraints {
+                min_height: 0.0,
+                max_height: f32::INFINITY,
+                ..*constraints
+            },
+        };
+        let child_size = child.layout_use_size(&child_constraints);
+        let size = constraints.constrain(child_size);
+        let (viewport_extent, content_extent) = match self.axis {
+            Axis::Horizontal => (size.width, child_size.width),
+            Axis::Vertical => (size.height, child_size.height),
+        };
+        self.offset = self
+            .controller
+            .apply_dimensions(viewport_extent, content_extent);
+        (size, ())
+    }
+
+    fn perform_paint(
+        &self,
+        size: &BoxSize,
+        offset: &BoxOffset,
+        _memo: &(),
+        child: &ArcBoxRenderObject,
+        paint_ctx: &mut impl PaintContext<Canvas = Affine2dCanvas>,
+    ) {
+        paint_ctx.clip_rect(*offset & *size, BlendMode::default(), 1.0, |paint_ctx| {
+            paint_ctx.with_transform(self.scroll_transform(), |paint_ctx| {
+                paint_ctx.paint(child, offset)
+            })
+        });
+    }
+
+    fn hit_test_child(
+        &self,
+        ctx: &mut HitTestContext<Affine2dCanvas>,
+        _size: &BoxSize,
+        _offset: &BoxOffset,
+        _memo: &(),
+        child: &ArcBoxRenderObject,
+    ) -> bool {
+        ctx.hit_test_with_paint_transform(child.clone(), &self.scroll_transform())
+    }
+
+    fn compute_intrinsics(&mut self, child: &ArcBoxRenderObject, intrinsics: &mut BoxIntrinsics) {
+        child.get_intrinsics(intrinsics)
+    }
+
+    const NOOP_DETACH: bool = true;
+}
@@ -0,0 +1,155 @@
+use epgi_2d::{
+    Affine2dCanvas, ArcBoxRenderObject, ArcBoxWidget, BoxOffset, BoxProtocol, SliverConstraints,
+    SliverGeometry, SliverProtocol,
+};
+use epgi_core::{
+    foundation::{Arc, Asc, BuildError, InlinableDwsizeVec, PaintContext, Provide},
+    template::{
+        ImplByTemplate, MultiChildElement, MultiChildElementTemplate, MultiChildHitTest,
+        MultiChildLayout, MultiChildPaint, MultiChildRender, MultiChildRenderTemplate,
+    },
+    tree::{BuildContext, RenderAction, Widget},
+};
+use epgi_macro::Declarative;
+use typed_builder::TypedBuilder;
+
+/// A sliver of `item_count` items that are all `item_extent` long along the axis.
+///
+/// Only the items starting from `first_index` are present as `children`, which is expected to cover
+/// the visible part of the sliver. The items before and after them still take up their space.
+/// See [`ListView`](crate::ListView), which builds the visible items only.
+#[derive(Debug, Declarative, TypedBuilder)]
+#[builder(build_method(into=Asc<SliverFixedExtentList>))]
+pub struct SliverFixedExtentList {
+    pub item_count: usize,
+    pub item_extent: f32,
+    #[builder(default)]
+    pub first_index: usize,
+    pub children: Vec<ArcBoxWidget>,
+}
+
+impl Widget for SliverFixedExtentList {
+    type ParentProtocol = SliverProtocol;
+    type ChildProtocol = BoxProtocol;
+    type Element = SliverFixedExtentListElement;
+
+    fn into_arc_widget(self: Asc<Self>) -> Asc<Self> {
+        self
+    }
+}
+
+#[derive(Clone)]
+pub struct SliverFixedExtentListElement {}
+
+impl ImplByTemplate for SliverFixedExtentListElement {
+    type Template = MultiChildElementTemplate<false>;
+}
+
+impl MultiChildElement for SliverFixedExtentListElement {
+    type ParentProtocol = SliverProtocol;
+    type ChildProtocol = BoxProtocol;
+    type ArcWidget = Asc<SliverFixedExtentList>;
+    type Render = RenderSliverFixedExtentList;
+
+    fn get_child_widgets(
+        _element: &mut Self,
+        widget: &Self::ArcWidget,
+        _ctx: &mut BuildContext<'_>,
+        _provider_values: InlinableDwsizeVec<Arc<dyn Provide>>,
+    ) -> Result<Vec<ArcBoxWidget>, BuildError> {
+        Ok(widget.children.clone())
+    }
+
+    fn create_element(_widget: &Self::ArcWidget) -> Self {
+        Self {}
+    }
+
+    fn create_render(&self, widget: &Self::ArcWidget) -> Self::Render {
+        RenderSliverFixedExtentList {
+            item_count: widget.item_count,
+            item_extent: widget.item_extent,
+            first_index: widget.first_index,
+        }
+    }
+
+    fn update_render(render: &mut Self::Render, widget: &Self::ArcWidget) -> Option<RenderAction> {
+        let needs_relayout = render.item_count != widget.item_count
+            || render.item_extent != widget.item_extent
+            || render.first_index != widget.first_index;
+        render.item_count = widget.item_count;
+        render.item_extent = widget.item_extent;
+        render.first_index = widget.first_index;
+        needs_relayout.then_some(RenderAction::Relayout)
+    }
+}
+
+pub struct RenderSliverFixedExtentList {
+    item_count: usize,
+    item_extent: f32,
+    first_index: usize,
+}
+
+impl ImplByTemplate for RenderSliverFixedExtentList {
+    type Template = MultiChildRenderTemplate<false, false, false, false>;
+}
+
+impl MultiChildRender for RenderSliverFixedExtentList {
+    type ParentProtocol = SliverProtocol;
+    type ChildProtocol = BoxProtocol;
+    /// The offsets of the children, relative to where the visible part of the sliver is painted.
+    type LayoutMemo = Vec<BoxOffset>;
+
+    fn compute_intrinsics(&mut self, _children: &Vec<ArcBoxRenderObject>, _intrinsics: &mut ()) {}
+
+    const NOOP_DETACH: bool = true;
+}
+
+impl MultiChildLayout for RenderSliverFixedExtentList {
+    fn perform_layout(
+        &mut self,
+        constraints: &SliverConstraints,
+        children: &Vec<ArcBoxRenderObject>,
+    ) -> (SliverGeometry, Vec<BoxOffset>) {
+        let child_constraints = constraints.as_box_constraints(self.item_extent);
+        let offsets = children
+            .iter()
+            .enumerate()
+            .map(|(index, child)| {
+                child.layout(&child_constraints);
+                let leading = (self.first_index + index) as f32 * self.item_extent;
+                constraints
+                    .axis
+                    .offset(leading - constraints.scroll_offset, 0.0)
+            })
+            .collect();
+        let scroll_extent = self.item_count as f32 * self.item_extent;
+        let paint_extent = (scroll_extent - constraints.scroll_offset)
+            .clamp(0.0, constraints.remaining_paint_extent);
+        (
+            SliverGeometry {
+                axis: constraints.axis,
+                scroll_extent,
+                paint_extent,
+                cross_axis_extent: constraints.cross_axis_extent,
+            },
+            offsets,
+        )
+    }
+}
+
+impl MultiChildPaint for RenderSliverFixedExtentList {
+    fn perform_paint(
+        &self,
+        _geometry: &SliverGeometry,
+        offset: &BoxOffset,
+        memo: &Vec<BoxOffset>,
+        children: &Vec<ArcBoxRenderObject>,
+        paint_ctx: &mut impl PaintContext<Canvas = Affine2dCanvas>,
+    ) {
+        for (child, child_offset) in std::iter::zip(children, memo) {
+            paint_ctx.paint(child, &(*offset + *child_offset));
+        }
+    }
+}
+
+impl MultiChildHitTest for RenderSliverFixedExtentList {}
@@ -0,0 +1,318 @@
+use std::collections::BTreeMap;
+
+use epgi_2d::{
+    Affine2dCanvas, ArcBoxRenderObject, ArcBoxWidget, Axis, BoxOffset, BoxProtocol,
+    SliverConstraints, SliverGeometry, SliverProtocol,
+};
+use epgi_core::{
+    foundation::{Arc, Asc, BuildError, InlinableDwsizeVec, PaintContext, Provide, SyncMutex},
+    scheduler::get_current_scheduler,
+    template::{
+        ImplByTemplate, MultiChildElement, MultiChildElementTemplate, MultiChildHitTest,
+        MultiChildLayout, MultiChildPaint, MultiChildRender, MultiChildRenderTemplate,
+    },
+    tree::{BuildContext, RenderAction, Widget},
+};
+use epgi_macro::Declarative;
+use typed_builder::TypedBuilder;
+
+use super::ScrollController;
+
+/// The extent assumed for the items of a list before any of them has been laid out.
+pub const DEFAULT_ESTIMATED_ITEM_EXTENT: f32 = 50.0;
+
+/// The extents of the items of a [`SliverList`] that have been laid out so far,
+/// shared between the list and the widget that builds its items.
+///
+/// The items that have not been laid out yet are estimated to be as long as the average of the items laid out
+/// while the list was scrolled from its start. The estimate is kept once items away from the start have been laid out,
+/// e.g. after a jump, so that the items around them stay in place while more items are laid out.
+/// Still, the scroll extent may change, and the items after a jump may shift as the items before them are laid out.
+#[derive(Clone, Default)]
+pub struct SliverListExtents {
+    inner: Arc<SyncMutex<MeasuredExtents>>,
+}
+
+#[derive(Default)]
+struct MeasuredExtents {
+    extents: BTreeMap<usize, f32>,
+    total: f32,
+    estimated_extent: Option<f32>,
+}
+
+impl SliverListExtents {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The indices of the items that overlap the viewport, as a half-open range.
+    ///
+    /// At least one item is included while the list is not empty, so that an item can be measured
+    /// before the extent of the viewport is known.
+    pub fn visible_range(
+        &self,
+        offset: f32,
+        viewport_extent: f32,
+        item_count: usize,
+    ) -> (usize, usize) {
+        self.inner
+            .lock()
+            .visible_range(offset, viewport_extent, item_count)
+    }
+}
+
+impl std::fmt::Debug for SliverListExtents {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let extents = self.inner.lock();
+        f.debug_struct("SliverListExtents")
+            .field("measured_count", &extents.extents.len())
+            .field("estimated_extent", &extents.estimated_extent())
+            .finish()
+    }
+}
+
+impl PartialEq for SliverListExtents {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
+impl MeasuredExtents {
+    fn estimated_extent(&self) -> f32 {
+        self.estimated_extent
+            .unwrap_or(DEFAULT_ESTIMATED_ITEM_EXTENT)
+    }
+
+    fn visible_range(
+        &self,
+        offset: f32,
+        viewport_extent: f32,
+        item_count: usize,
+    ) -> (usize, usize) {
+        if item_count == 0 {
+            return (0, 0);
+        }
+        let first_index = self.index_at(offset, item_count);
+        let last_index = self.index_at(offset + viewport_extent, item_count) + 1;
+        (first_index, last_index)
+    }
+
+    fn insert(&mut self, index: usize, extent: f32) {
+        let old_extent = self.extents.insert(index, extent);
+        self.total += extent - old_extent.unwrap_or(0.0);
+        // While the measured items start the list, the estimate only places the items after them.
+        let is_prefix = self
+            .extents
+            .last_key_value()
+            .is_some_and(|(&last_index, _)| last_index + 1 == self.extents.len());
+        let average = self.total / self.extents.len() as f32;
+        if is_prefix && average > 0.0 {
+            self.estimated_extent = Some(average);
+        }
+    }
+
+    /// Where the item at `index` starts along the axis.
+    fn leading(&self, index: usize) -> f32 {
+        let (measured_count, measured_total) = self
+            .extents
+            .range(..index)
+            .fold((0, 0.0), |(count, total), (_, extent)| {
+                (count + 1, total + extent)
+            });
+        measured_total + (index - measured_count) as f32 * self.estimated_extent()
+    }
+
+    /// The index of the item at `offset` along the axis, clamped to the items of the list.
+    fn index_at(&self, offset: f32, item_count: usize) -> usize {
+        let estimated_extent = self.estimated_extent();
+        let mut index = 0;
+        let mut leading = 0.0;
+        for (&measured_index, &extent) in self.extents.range(..item_count) {
+            let unmeasured_extent = (measured_index - index) as f32 * estimated_extent;
+            if offset < leading + unmeasured_extent {
+                break;
+            }
+            leading += unmeasured_extent;
+            index = measured_index;
+            if offset < leading + extent {
+                return index;
+            }
+            leading += extent;
+            index += 1;
+        }
+        let unmeasured_index = ((offset - leading).max(0.0) / estimated_extent) as usize;
+        (index + unmeasured_index).min(item_count - 1)
+    }
+}
+
+/// A sliver of `item_count` items that pick their own extents along the axis.
+///
+/// Only the items starting from `first_index` are present as `children`. The items before and after them
+/// still take up the extents recorded in `extents`, or estimated from it.
+/// If the children turn out not to be the visible items once they have been measured,
+/// the scrollable holding `controller` is rebuilt before the frame is painted, so that it can build the right items.
+/// See [`ListView`](crate::ListView), which builds the visible items only.
+#[derive(Debug, Declarative, TypedBuilder)]
+#[builder(build_method(into=Asc<SliverList>))]
+pub struct SliverList {
+    pub item_count: usize,
+    #[builder(default)]
+    pub first_index: usize,
+    pub extents: SliverListExtents,
+    pub controller: ScrollController,
+    pub children: Vec<ArcBoxWidget>,
+}
+
+impl Widget for SliverList {
+    type ParentProtocol = SliverProtocol;
+    type ChildProtocol = BoxProtocol;
+    type Element = SliverListElement;
+
+    fn into_arc_widget(self: Asc<Self>) -> Asc<Self> {
+        self
+    }
+}
+
+#[derive(Clone)]
+pub struct SliverListElement {}
+
+impl ImplByTemplate for SliverListElement {
+    type Template = MultiChildElementTemplate<false>;
+}
+
+impl MultiChildElement for SliverListElement {
+    type ParentProtocol = SliverProtocol;
+    type ChildProtocol = BoxProtocol;
+    type ArcWidget = Asc<SliverList>;
+    type Render = RenderSliverList;
+
+    fn get_child_widgets(
+        _element: &mut Self,
+        widget: &Self::ArcWidget,
+        _ctx: &mut BuildContext<'_>,
+        _provider_values: InlinableDwsizeVec<Arc<dyn Provide>>,
+    ) -> Result<Vec<ArcBoxWidget>, BuildError> {
+        Ok(widget.children.clone())
+    }
+
+    fn create_element(_widget: &Self::ArcWidget) -> Self {
+        Self {}
+    }
+
+    fn create_render(&self, widget: &Self::ArcWidget) -> Self::Render {
+        RenderSliverList {
+            item_count: widget.item_count,
+            first_index: widget.first_index,
+            extents: widget.extents.clone(),
+            controller: widget.controller.clone(),
+        }
+    }
+
+    fn update_render(render: &mut Self::Render, widget: &Self::ArcWidget) -> Option<RenderAction> {
+        let needs_relayout = render.item_count != widget.item_count
+            || render.first_index != widget.first_index
+            || render.extents != widget.extents
+            || render.controller != widget.controller;
+        render.item_count = widget.item_count;
+        render.first_index = widget.first_index;
+        render.extents = widget.extents.clone();
+        render.controller = widget.controller.clone();
+        needs_relayout.then_some(RenderAction::Relayout)
+    }
+}
+
+pub struct RenderSliverList {
+    item_count: usize,
+    first_index: usize,
+    extents: SliverListExtents,
+    controller: ScrollController,
+}
+
+impl ImplByTemplate for RenderSliverList {
+    type Template = MultiChildRenderTemplate<false, false, false, false>;
+}
+
+impl MultiChildRender for RenderSliverList {
+    type ParentProtocol = SliverProtocol;
+    type ChildProtocol = BoxProtocol;
+    /// The offsets of the children, relative to where the visible part of the sliver is painted.
+    type LayoutMemo = Vec<BoxOffset>;
+
+    fn compute_intrinsics(&mut self, _children: &Vec<ArcBoxRenderObject>, _intrinsics: &mut ()) {}
+
+    const NOOP_DETACH: bool = true;
+}
+
+impl MultiChildLayout for RenderSliverList {
+    fn perform_layout(
+        &mut self,
+        constraints: &SliverConstraints,
+        children: &Vec<ArcBoxRenderObject>,
+    ) -> (SliverGeometry, Vec<BoxOffset>) {
+        let child_constraints = constraints.as_unbounded_box_constraints();
+        let mut extents = self.extents.inner.lock();
+        // Measure every child before placing any, so that the estimated extent of the items before them is up to date.
+        for (index, child) in children.iter().enumerate() {
+            let size = child.layout_use_size(&child_constraints);
+            let extent = match constraints.axis {
+                Axis::Horizontal => size.width,
+                Axis::Vertical => size.height,
+            };
+            extents.insert(self.first_index + index, extent);
+        }
+        let mut leading = extents.leading(self.first_index);
+        let offsets = (self.first_index..self.first_index + children.len())
+            .map(|index| {
+                let offset = constraints
+                    .axis
+                    .offset(leading - constraints.scroll_offset, 0.0);
+                leading += extents.extents[&index];
+                offset
+            })
+            .collect();
+        let scroll_extent = extents.leading(self.item_count);
+        let visible_range = extents.visible_range(
+            constraints.scroll_offset,
+            constraints.remaining_paint_extent,
+            self.item_count,
+        );
+        drop(extents);
+
+        // Measuring the children may have shown that other items are visible, which are built in another pass.
+        if visible_range != (self.first_index, self.first_index + children.len()) {
+            let controller = self.controller.clone();
+            get_current_scheduler().create_sync_job_in_layout(|job_builder| {
+                controller.notify_attached(job_builder);
+            });
+        }
+
+        let paint_extent = (scroll_extent - constraints.scroll_offset)
+            .clamp(0.0, constraints.remaining_paint_extent);
+        (
+            SliverGeometry {
+                axis: constraints.axis,
+                scroll_extent,
+                paint_extent,
+                cross_axis_extent: constraints.cross_axis_extent,
+            },
+            offsets,
+        )
+    }
+}
+
+impl MultiChildPaint for RenderSliverList {
+    fn perform_paint(
+        &self,
+        _geometry: &SliverGeometry,
+        offset: &BoxOffset,
+        memo: &Vec<BoxOffset>,
+        children: &Vec<ArcBoxRenderObject>,
+        paint_ctx: &mut impl PaintContext<Canvas = Affine2dCanvas>,
+    ) {
+        for (child, child_offset) in std::iter::zip(children, memo) {
+            paint_ctx.paint(child, &(*offset + *child_offset));
+        }
+    }
+}
+
+impl MultiChildHitTest for RenderSliverList {}
@@ -0,0 +1,195 @@
+use epgi_2d::{
+    Affine2dCanvas, Affine2dPaintContextExt, ArcSliverRenderObject, ArcSliverWidget, Axis,
+    BlendMode, BoxConstraints, BoxIntrinsics, BoxOffset, BoxProtocol, BoxSize, SliverConstraints,
+    SliverGeometry, SliverProtocol,
+};
+use epgi_core::{
+    foundation::{Arc, Asc, BuildError, InlinableDwsizeVec, PaintContext, Provide},
+    scheduler::get_current_scheduler,
+    template::{
+        AdapterRender, AdapterRenderTemplate, ImplByTemplate, SingleChildElement,
+        SingleChildElementTemplate, SingleChildRenderElement,
+    },
+    tree::{BuildContext, RenderAction, Widget},
+};
+use epgi_macro::Declarative;
+use typed_builder::TypedBuilder;
+
+use super::ScrollController;
+
+/// Shows the part of a sliver that is scrolled into view at `offset`, and reports the extents of the sliver to the controller.
+///
+/// The viewport fills the space available to it, which has to be bounded along the axis.
+/// Whenever its extent along the axis changes, or the controller clamps the offset, the scrollable
+/// holding the controller is rebuilt before the frame is painted, so that the sliver can be built
+/// for the part that is actually visible.
+#[derive(Debug, Declarative, TypedBuilder)]
+#[builder(build_method(into=Asc<Viewport>))]
+pub struct Viewport {
+    #[builder(default = Axis::Vertical)]
+    pub axis: Axis,
+    pub controller: ScrollController,
+    pub offset: f32,
+    pub sliver: ArcSliverWidget,
+}
+
+impl Widget for Viewport {
+    type ParentProtocol = BoxProtocol;
+    type ChildProtocol = SliverProtocol;
+    type Element = ViewportElement;
+
+    fn into_arc_widget(self: Asc<Self>) -> Asc<Self> {
+        self
+    }
+}
+
+#[derive(Clone)]
+pub struct ViewportElement {}
+
+impl ImplByTemplate for ViewportElement {
+    type Template = SingleChildElementTemplate<true, false>;
+}
+
+impl SingleChildElement for ViewportElement {
+    type ParentProtocol = BoxProtocol;
+    type ChildProtocol = SliverProtocol;
+    type ArcWidget = Asc<Viewport>;
+
+    fn get_child_widget(
+        _element: Option<&mut Self>,
+        widget: &Self::ArcWidget,
+        _ctx: &mut BuildContext<'_>,
+        _provider_values: InlinableDwsizeVec<Arc<dyn Provide>>,
+    ) -> Result<ArcSliverWidget, BuildError> {
+        Ok(widget.sliver.clone())
+    }
+
+    fn create_element(_widget: &Self::ArcWidget) -> Self {
+        Self {}
+    }
+}
+
+impl SingleChildRenderElement for ViewportElement {
+    type Render = RenderViewport;
+
+    fn create_render(&self, widget: &Self::ArcWidget) -> Self::Render {
+        RenderViewport {
+            axis: widget.axis,
+            controller: widget.controller.clone(),
+            offset: widget.offset,
+        }
+    }
+
+    fn update_render(render: &mut Self::Render, widget: &Self::ArcWidget) -> Option<RenderAction> {
+        let needs_relayout = render.axis != widget.axis
+            || render.controller != widget.controller
+            || render.offset != widget.offset;
+        render.axis = widget.axis;
+        render.controller = widget.controller.clone();
+        render.offset = widget.offset;
+        needs_relayout.then_some(RenderAction::Relayout)
+    }
+}
+
+pub struct RenderViewport {
+    axis: Axis,
+    controller: ScrollController,
+    offset: f32,
+}
+
+impl RenderViewport {
+    fn layout_sliver(
+        &self,
+        size: &BoxSize,
+        child: &ArcSliverRenderObject,
+    ) -> (SliverGeometry, BoxOffset) {
+        let (main_axis_extent, cross_axis_extent) = match self.axis {
+            Axis::Horizontal => (size.width, size.height),
+            Axis::Vertical => (size.height, size.width),
+        };
+        // When pulled past its start, the sliver is painted away from the leading edge.
+        let overscroll = (-self.offset).max(0.0);
+        let geometry = child.layout_use_size(&SliverConstraints {
+            axis: self.axis,
+            scroll_offset: self.offset.max(0.0),
+            remaining_paint_extent: (main_axis_extent - overscroll).max(0.0),
+            cross_axis_extent,
+            viewport_main_axis_extent: main_axis_extent,
+        });
+        (geometry, self.axis.offset(overscroll, 0.0))
+    }
+}
+
+impl ImplByTemplate for RenderViewport {
+    type Template = AdapterRenderTemplate;
+}
+
+impl AdapterRender for RenderViewport {
+    type ParentProtocol = BoxProtocol;
+    type ChildProtocol = SliverProtocol;
+    /// Where the sliver is painted, relative to the viewport.
+    type LayoutMemo = BoxOffset;
+
+    fn perform_layout(
+        &mut self,
+        constraints: &BoxConstraints,
+        child: &ArcSliverRenderObject,
+    ) -> (BoxSize, BoxOffset) {
+        let size = constraints.biggest();
+        debug_assert!(
+            size.is_finite(),
+            "A viewport should be given bounded constraints"
+        );
+        let main_axis_extent = match self.axis {
+            Axis::Horizontal => size.width,
+            Axis::Vertical => size.height,
+        };
+        let viewport_resized = self.controller.viewport_extent() != main_axis_extent;
+        let (geometry, mut sliver_offset) = self.layout_sliver(&size, child);
+        let offset = self
+            .controller
+            .apply_dimensions(main_axis_extent, geometry.scroll_extent);
+        let offset_clamped = offset != self.offset;
+        if offset_clamped {
+            self.offset = offset;
+            (_, sliver_offset) = self.layout_sliver(&size, child);
+        }
+        if viewport_resized || offset_clamped {
+            let controller = self.controller.clone();
+            get_current_scheduler().create_sync_job_in_layout(|job_builder| {
+                controller.notify_attached(job_builder);
+            });
+        }
+        (size, sliver_offset)
+    }
+
+    fn perform_paint(
+        &self,
+        size: &BoxSize,
+        offset: &BoxOffset,
+        sliver_offset: &BoxOffset,
+        child: &ArcSliverRenderObject,
+        paint_ctx: &mut impl PaintContext<Canvas = Affine2dCanvas>,
+    ) {
+        paint_ctx.clip_rect(*offset & *size, BlendMode::default(), 1.0, |paint_ctx| {
+            paint_ctx.paint(child, &(*offset + *sliver_offset))
+        });
+    }
+
+    /// A viewport fills the space available to it regardless of its content.
+    fn compute_intrinsics(
+        &mut self,
+        _child: &ArcSliverRenderObject,
+        intrinsics: &mut BoxIntrinsics,
+    ) {
+        use BoxIntrinsics::*;
+        match intrinsics {
+            MinWidth { res, .. }
+            | MaxWidth { res, .. }
+            | MinHeight { res, .. }
+            | MaxHeight { res, .. } => *res = Some(0.0),
+        }
+    }
+
+    const NOOP_DETACH: bool = true;
+}
@@ -4,6 +4,9 @@ pub use box_adapter_multi_line::*;
 mod multi_line_adapter_box;
 pub use multi_line_adapter_box::*;
 
+mod editable_text;
+pub use editable_text::*;
+
 mod multi_line;
 pub use multi_line::*;
 
@@ -12,3 +15,12 @@ pub use rich_text::*;
 
 mod text;
 pub use text::*;
+
+mod text_editing;
+pub use text_editing::*;
+
+mod text_field;
+pub use text_field::*;
+
+mod widget_span;
+pub use widget_span::*;
@@ -1,14 +1,18 @@
 use epgi_2d::{
     Affine2d, Affine2dCanvas, BoxConstraints, BoxIntrinsics, BoxOffset, BoxProtocol, BoxSize,
-    MultiLineConstraints, MultiLineOffset, MultiLineProtocol, SingleLineOffset,
+    MultiLineConstraints, MultiLineIntrinsics, MultiLineOffset, MultiLineProtocol,
+    SingleLineOffset, SingleLineSize, TextBaseline,
 };
 use epgi_core::{
-    foundation::{Arc, Asc, BuildSuspendedError, InlinableDwsizeVec, PaintContext, Provide},
+    foundation::{Arc, Asc, BuildError, InlinableDwsizeVec, PaintContext, Provide},
     template::{
         AdapterRender, AdapterRenderTemplate, ImplByTemplate, SingleChildElement,
         SingleChildElementTemplate, SingleChildRenderElement,
     },
-    tree::{ArcChildRenderObject, ArcChildWidget, BuildContext, ElementBase, RenderAction, Widget},
+    tree::{
+        ArcChildRenderObject, ArcChildWidget, BuildContext, ElementBase, HitTestContext,
+        RenderAction, Widget,
+    },
 };
 use epgi_macro::Declarative;
 use typed_builder::TypedBuilder;
@@ -46,7 +50,7 @@ impl SingleChildElement for BoxAdapterMultiLineElement {
         widget: &Self::ArcWidget,
         _ctx: &mut BuildContext<'_>,
         _provider_values: InlinableDwsizeVec<Arc<dyn Provide>>,
-    ) -> Result<ArcChildWidget<MultiLineProtocol>, BuildSuspendedError> {
+    ) -> Result<ArcChildWidget<MultiLineProtocol>, BuildError> {
         Ok(widget.child.clone())
     }
 
@@ -79,19 +83,21 @@ impl ImplByTemplate for RenderBoxAdapterMultiLine {
 impl AdapterRender for RenderBoxAdapterMultiLine {
     type ParentProtocol = BoxProtocol;
     type ChildProtocol = MultiLineProtocol;
-    type LayoutMemo = MultiLineOffset;
+    /// The offset of every line, and the size of the first line if there is one.
+    type LayoutMemo = (MultiLineOffset, Option<SingleLineSize>);
 
     fn perform_layout(
         &mut self,
         constraints: &BoxConstraints,
         child: &ArcChildRenderObject<MultiLineProtocol>,
-    ) -> (BoxSize, MultiLineOffset) {
+    ) -> (BoxSize, Self::LayoutMemo) {
         let multi_line_size = child.layout_use_size(&MultiLineConstraints {
             first_line_existing_advance: 0.0,
             max_width: constraints.max_width,
             last_line_append_advance: 0.0,
             max_height: constraints.max_height,
         });
+        let first_line_size = multi_line_size.sizes.first().copied();
         let mut y = 0.0f32;
         let mut max_width = 0.0f32;
         let offsets = multi_line_size
@@ -112,7 +118,7 @@ impl AdapterRender for RenderBoxAdapterMultiLine {
                 width: max_width,
                 height: y,
             },
-            MultiLineOffset { offsets },
+            (MultiLineOffset { offsets }, first_line_size),
         )
     }
 
@@ -120,21 +126,71 @@ impl AdapterRender for RenderBoxAdapterMultiLine {
         &self,
         _size: &BoxSize,
         offset: &BoxOffset,
-        memo: &MultiLineOffset,
+        (line_offsets, _): &Self::LayoutMemo,
         child: &ArcChildRenderObject<MultiLineProtocol>,
         paint_ctx: &mut impl PaintContext<Canvas = Affine2dCanvas>,
     ) {
         paint_ctx.with_transform(Affine2d::from_translation(offset), |paint_ctx| {
-            paint_ctx.paint(child, memo)
+            paint_ctx.paint(child, line_offsets)
         });
     }
 
+    fn hit_test_child(
+        &self,
+        ctx: &mut HitTestContext<Affine2dCanvas>,
+        _size: &BoxSize,
+        offset: &BoxOffset,
+        _memo: &Self::LayoutMemo,
+        child: &ArcChildRenderObject<MultiLineProtocol>,
+    ) -> bool {
+        // The lines are painted relative to the box.
+        ctx.hit_test_with_paint_transform(child.clone(), &Affine2d::from_translation(offset))
+    }
+
+    fn compute_distance_to_baseline(
+        &self,
+        _size: &BoxSize,
+        (_, first_line_size): &Self::LayoutMemo,
+        _child: &ArcChildRenderObject<MultiLineProtocol>,
+        baseline: &TextBaseline,
+    ) -> Option<f32> {
+        // The first line starts at the top.
+        first_line_size.map(|size| size.distance_to_baseline(*baseline))
+    }
+
     fn compute_intrinsics(
         &mut self,
         child: &ArcChildRenderObject<MultiLineProtocol>,
         intrinsics: &mut BoxIntrinsics,
     ) {
-        unimplemented!()
+        let mut child_intrinsics = match *intrinsics {
+            BoxIntrinsics::MinWidth { height, .. } => {
+                MultiLineIntrinsics::MinWidth { height, res: None }
+            }
+            BoxIntrinsics::MaxWidth { height, .. } => {
+                MultiLineIntrinsics::MaxWidth { height, res: None }
+            }
+            BoxIntrinsics::MinHeight { width, .. } => {
+                MultiLineIntrinsics::MinHeight { width, res: None }
+            }
+            BoxIntrinsics::MaxHeight { width, .. } => {
+                MultiLineIntrinsics::MaxHeight { width, res: None }
+            }
+        };
+        child.get_intrinsics(&mut child_intrinsics);
+        match (intrinsics, child_intrinsics) {
+            (BoxIntrinsics::MinWidth { res, .. }, MultiLineIntrinsics::MinWidth { res: x, .. })
+            | (BoxIntrinsics::MaxWidth { res, .. }, MultiLineIntrinsics::MaxWidth { res: x, .. })
+            | (
+                BoxIntrinsics::MinHeight { res, .. },
+                MultiLineIntrinsics::MinHeight { res: x, .. },
+            )
+            | (
+                BoxIntrinsics::MaxHeight { res, .. },
+                MultiLineIntrinsics::MaxHeight { res: x, .. },
+            ) => *res = x,
+            _ => panic!("Child returns a wrong intrinsics type."),
+        }
     }
 
     const NOOP_DETACH: bool = true;
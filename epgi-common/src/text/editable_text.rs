@@ -0,0 +1,383 @@
+use std::any::TypeId;
+
+use epgi_2d::{
+    Affine2dCanvas, Affine2dPaintContextExt, BoxConstraints, BoxIntrinsics, BoxOffset, BoxProtocol,
+    BoxSize, Brush, Color, Fill, FillPainter, Painter, Paragraph, Point2d, Rect, SingleLineOffset,
+    SingleLineSize, TextAlign, TextBaseline, TextDirection, TextSpan, TextStyle,
+};
+use epgi_core::{
+    foundation::{AnyRawPointer, Arc, Asc, BuildError, InlinableDwsizeVec, PaintContext, Provide},
+    hit_test_interface_query_table,
+    scheduler::{get_current_scheduler, JobBuilder},
+    template::{ImplByTemplate, LeafElement, LeafElementTemplate, LeafRender, LeafRenderTemplate},
+    tree::{BuildContext, ChildRenderObject, HitTestResult, RenderAction, RenderObject, Widget},
+};
+use epgi_macro::Declarative;
+use typed_builder::TypedBuilder;
+
+use crate::{
+    PointerEvent, PointerEventHandler, PointerEventVariantData, PointerInteractionVariantData,
+    TextEditingValue,
+};
+
+pub type ArcTextPositionCallback = Asc<dyn Fn(usize, &mut JobBuilder) + Send + Sync>;
+
+pub const CARET_WIDTH: f32 = 1.5;
+
+pub const DEFAULT_SELECTION_COLOR: Color = Color::rgba8(0x33, 0x99, 0xFF, 0x66);
+
+/// Displays a [`TextEditingValue`] with its selection, its composing range and a caret.
+///
+/// This widget only paints and hit-tests the text. [`TextField`](crate::TextField) builds on it to handle focus and input.
+#[derive(Declarative, TypedBuilder)]
+#[builder(build_method(into=Asc<EditableText>))]
+pub struct EditableText {
+    pub value: TextEditingValue,
+    pub style: TextStyle,
+    /// The text starts at the right edge for [`TextDirection::Rtl`].
+    #[builder(default = TextDirection::Ltr)]
+    pub text_direction: TextDirection,
+    /// Whether the text wraps at the maximum width. Otherwise, the text is laid out in a single line per line break.
+    #[builder(default = false)]
+    pub multi_line: bool,
+    /// Whether to paint the caret. The caret is only painted when the selection is collapsed.
+    #[builder(default = false)]
+    pub show_caret: bool,
+    /// Defaults to the color of the text.
+    #[builder(default, setter(strip_option))]
+    pub caret_color: Option<Color>,
+    #[builder(default = DEFAULT_SELECTION_COLOR)]
+    pub selection_color: Color,
+    /// Called with the text position under the pointer when a pointer goes down on the text.
+    #[builder(default, setter(transform=|op: impl Fn(usize, &mut JobBuilder) + Send + Sync + 'static| Some(Asc::new(op) as _)))]
+    pub on_pointer_down: Option<ArcTextPositionCallback>,
+    /// Called with the text position under the pointer when a pointer that went down on the text moves.
+    #[builder(default, setter(transform=|op: impl Fn(usize, &mut JobBuilder) + Send + Sync + 'static| Some(Asc::new(op) as _)))]
+    pub on_pointer_drag: Option<ArcTextPositionCallback>,
+}
+
+impl std::fmt::Debug for EditableText {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EditableText")
+            .field("value", &self.value)
+            .field("style", &self.style)
+            .field("text_direction", &self.text_direction)
+            .field("multi_line", &self.multi_line)
+            .field("show_caret", &self.show_caret)
+            .field("caret_color", &self.caret_color)
+            .field("selection_color", &self.selection_color)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Widget for EditableText {
+    type ParentProtocol = BoxProtocol;
+    type ChildProtocol = BoxProtocol;
+    type Element = EditableTextElement;
+
+    fn into_arc_widget(self: Asc<Self>) -> Asc<Self> {
+        self
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct EditableTextElement {}
+
+impl ImplByTemplate for EditableTextElement {
+    type Template = LeafElementTemplate;
+}
+
+impl LeafElement for EditableTextElement {
+    type Protocol = BoxProtocol;
+    type ArcWidget = Asc<EditableText>;
+    type Render = RenderEditableText;
+
+    fn create_element(
+        _widget: &Self::ArcWidget,
+        _ctx: &mut BuildContext<'_>,
+        _provider_values: InlinableDwsizeVec<Arc<dyn Provide>>,
+    ) -> Result<Self, BuildError> {
+        Ok(Self {})
+    }
+
+    fn create_render(&self, widget: &Self::ArcWidget) -> Self::Render {
+        RenderEditableText {
+            paragraph: create_paragraph(widget),
+            line_offsets: Vec::new(),
+            first_line_size: None,
+            paragraph_width: 0.0,
+            widget: widget.clone(),
+        }
+    }
+
+    fn update_render(render: &mut Self::Render, widget: &Self::ArcWidget) -> Option<RenderAction> {
+        let needs_relayout = render.widget.value.text != widget.value.text
+            || render.widget.style != widget.style
+            || render.widget.text_direction != widget.text_direction
+            || render.widget.multi_line != widget.multi_line;
+        if needs_relayout {
+            render.paragraph = create_paragraph(widget);
+        }
+        render.widget = widget.clone();
+        Some(if needs_relayout {
+            RenderAction::Relayout
+        } else {
+            RenderAction::Repaint
+        })
+    }
+}
+
+fn create_paragraph(widget: &EditableText) -> Paragraph {
+    Paragraph::new(
+        &[TextSpan {
+            text: widget.value.text.clone().into(),
+            style: None,
+        }],
+        &widget.style,
+        widget.text_direction,
+    )
+}
+
+pub struct RenderEditableText {
+    paragraph: Paragraph,
+    /// The offset of every line relative to the top left corner, as of the last layout.
+    line_offsets: Vec<SingleLineOffset>,
+    /// The size of the first line as of the last layout, or `None` if the paragraph is empty.
+    first_line_size: Option<SingleLineSize>,
+    /// The width of the widest line as of the last layout.
+    paragraph_width: f32,
+    widget: Asc<EditableText>,
+}
+
+impl RenderEditableText {
+    /// The horizontal offset of the paragraph from the left edge.
+    ///
+    /// Right-to-left text is aligned to the right edge, and leaves room for the caret on its left instead.
+    fn paragraph_left(&self, size: &BoxSize) -> f32 {
+        if self.widget.text_direction.is_rtl() {
+            size.width - self.paragraph_width
+        } else {
+            0.0
+        }
+    }
+
+    /// The caret at the text position, relative to the top left corner of the paragraph.
+    ///
+    /// The caret is painted after the left edge of the rect for left-to-right text, and before it for right-to-left text.
+    fn caret_rect(&self, position: usize) -> Rect {
+        self.paragraph.caret_rect(position).unwrap_or_else(|| {
+            // An empty paragraph has no lines to measure, so the caret spans a line of the text style.
+            let style = &self.widget.style;
+            Rect::new_ltrb(0.0, 0.0, 0.0, style.font_size * style.height)
+        })
+    }
+
+    fn text_position_at(&self, position: Point2d, size: &BoxSize, offset: &BoxOffset) -> usize {
+        self.paragraph.hit_test_text_position(Point2d {
+            x: position.x - offset.x - self.paragraph_left(size),
+            y: position.y - offset.y,
+        })
+    }
+}
+
+impl ImplByTemplate for RenderEditableText {
+    type Template = LeafRenderTemplate;
+}
+
+impl LeafRender for RenderEditableText {
+    type Protocol = BoxProtocol;
+
+    fn perform_layout(&mut self, constraints: &BoxConstraints) -> BoxSize {
+        let max_width = if self.widget.multi_line && constraints.max_width.is_finite() {
+            Some((constraints.max_width - CARET_WIDTH).max(0.0))
+        } else {
+            None
+        };
+        let sizes = self.paragraph.layout(max_width, TextAlign::Start);
+        self.first_line_size = sizes.first().copied();
+        self.line_offsets.clear();
+        let mut width: f32 = 0.0;
+        let mut height = 0.0;
+        for size in sizes {
+            self.line_offsets.push(SingleLineOffset {
+                advance: 0.0,
+                baseline: height + size.above,
+            });
+            width = width.max(size.advance);
+            height += size.above + size.below;
+        }
+        self.paragraph_width = width;
+        let caret_rect = self.caret_rect(0);
+        height = height.max(caret_rect.b - caret_rect.t);
+        // Leave room for the caret after the last character.
+        constraints.constrain(BoxSize {
+            width: width + CARET_WIDTH,
+            height,
+        })
+    }
+
+    fn perform_paint(
+        &self,
+        size: &BoxSize,
+        offset: &BoxOffset,
+        paint_ctx: &mut impl PaintContext<Canvas = Affine2dCanvas>,
+    ) {
+        let value = &self.widget.value;
+        let paragraph_left = offset.x + self.paragraph_left(size);
+        let fill = |color: Color| {
+            Painter::Fill(FillPainter {
+                fill: Fill::NonZero,
+                brush: Brush::Solid(color),
+                transform: None,
+            })
+        };
+        let translate = |rect: Rect| {
+            Rect::new_ltrb(
+                rect.l + paragraph_left,
+                rect.t + offset.y,
+                rect.r + paragraph_left,
+                rect.b + offset.y,
+            )
+        };
+
+        for rect in self.paragraph.selection_rects(value.selection.range()) {
+            paint_ctx.draw_rect(translate(rect), fill(self.widget.selection_color));
+        }
+
+        let line_offsets = self
+            .line_offsets
+            .iter()
+            .map(|line_offset| SingleLineOffset {
+                advance: line_offset.advance + paragraph_left,
+                baseline: line_offset.baseline + offset.y,
+            })
+            .collect::<Vec<_>>();
+        paint_ctx.draw_paragraph(&self.paragraph, &line_offsets);
+
+        if let Some(composing) = &value.composing {
+            // Underline the text that is still being composed by the input method.
+            for rect in self.paragraph.selection_rects(composing.clone()) {
+                let rect = translate(rect);
+                paint_ctx.draw_rect(
+                    Rect::new_ltrb(rect.l, rect.b - 1.0, rect.r, rect.b),
+                    fill(self.widget.style.color),
+                );
+            }
+        }
+
+        if self.widget.show_caret && value.selection.is_collapsed() {
+            let caret_rect = translate(self.caret_rect(value.selection.extent));
+            let caret_left = if self.widget.text_direction.is_rtl() {
+                caret_rect.l - CARET_WIDTH
+            } else {
+                caret_rect.l
+            };
+            paint_ctx.draw_rect(
+                Rect::new_ltrb(
+                    caret_left,
+                    caret_rect.t,
+                    caret_left + CARET_WIDTH,
+                    caret_rect.b,
+                ),
+                fill(self.widget.caret_color.unwrap_or(self.widget.style.color)),
+            );
+        }
+    }
+
+    fn compute_intrinsics(render: &mut Self, intrinsics: &mut BoxIntrinsics) {
+        let paragraph = &render.paragraph;
+        let multi_line = render.widget.multi_line;
+        match intrinsics {
+            BoxIntrinsics::MinWidth { res, .. } => {
+                let width = if multi_line {
+                    paragraph.min_intrinsic_width()
+                } else {
+                    paragraph.max_intrinsic_width()
+                };
+                *res = Some(width + CARET_WIDTH)
+            }
+            BoxIntrinsics::MaxWidth { res, .. } => {
+                *res = Some(paragraph.max_intrinsic_width() + CARET_WIDTH)
+            }
+            BoxIntrinsics::MinHeight { width, res } | BoxIntrinsics::MaxHeight { width, res } => {
+                let max_width = if multi_line {
+                    (*width - CARET_WIDTH).max(0.0)
+                } else {
+                    f32::INFINITY
+                };
+                // An empty paragraph has no lines, but still leaves room for the caret.
+                let style = &render.widget.style;
+                *res = Some(
+                    paragraph
+                        .intrinsic_height(max_width, None)
+                        .max(style.font_size * style.height),
+                )
+            }
+        }
+    }
+
+    fn compute_distance_to_baseline(
+        &self,
+        _size: &BoxSize,
+        baseline: &TextBaseline,
+    ) -> Option<f32> {
+        // The first line starts at the top.
+        self.first_line_size
+            .map(|size| size.distance_to_baseline(*baseline))
+    }
+
+    fn hit_test_self(
+        &self,
+        position: &Point2d,
+        size: &BoxSize,
+        offset: &BoxOffset,
+    ) -> HitTestResult {
+        if Rect::new_point_size(*offset, *size).contains(position) {
+            HitTestResult::Hit
+        } else {
+            HitTestResult::NotHit
+        }
+    }
+
+    fn all_hit_test_interfaces() -> &'static [(TypeId, fn(*mut RenderObject<Self>) -> AnyRawPointer)]
+    {
+        EDITABLE_TEXT_HIT_TEST_INTERFACE_TABLE.as_slice()
+    }
+}
+
+hit_test_interface_query_table!(
+    EDITABLE_TEXT_HIT_TEST_INTERFACE_TABLE,
+    RenderEditableText,
+    dyn PointerEventHandler,
+);
+
+impl PointerEventHandler for RenderObject<RenderEditableText> {
+    fn handle_pointer_event(&self, transformed_position: Point2d, event: &PointerEvent) {
+        use PointerInteractionVariantData::*;
+        let PointerEventVariantData::Interaction {
+            variant: variant @ (Down(_) | Move(_)),
+            ..
+        } = &event.variant
+        else {
+            return;
+        };
+        let Some((size, offset)) = self.last_size_and_paint_offset() else {
+            return;
+        };
+        let (callback, text_position) = self.update(|render, _| {
+            let callback = match variant {
+                Down(_) => render.widget.on_pointer_down.clone(),
+                _ => render.widget.on_pointer_drag.clone(),
+            };
+            (
+                callback,
+                render.text_position_at(transformed_position, &size, &offset),
+            )
+        });
+        if let Some(callback) = callback {
+            get_current_scheduler().create_sync_job(|job_builder| {
+                callback(text_position, job_builder);
+            });
+        }
+    }
+}
@@ -1,10 +1,11 @@
 use epgi_2d::{
     Affine2dCanvas, Affine2dMultiChildHitTest, Affine2dMultiChildLayout, Affine2dMultiChildPaint,
     Affine2dMultiChildRender, Affine2dMultiChildRenderTemplate, MultiLineConstraints,
-    MultiLineIntrinsics, MultiLineOffset, MultiLineProtocol, MultiLineSize, SingleLineSize,
+    MultiLineIntrinsics, MultiLineOffset, MultiLineProtocol, MultiLineRenderObjectIntrinsicsExt,
+    MultiLineSize, SingleLineSize,
 };
 use epgi_core::{
-    foundation::{Arc, Asc, BuildSuspendedError, InlinableDwsizeVec, PaintContext, Provide},
+    foundation::{Arc, Asc, BuildError, InlinableDwsizeVec, PaintContext, Provide},
     template::{ImplByTemplate, MultiChildElement, MultiChildElementTemplate},
     tree::{ArcChildRenderObject, ArcChildWidget, BuildContext, ElementBase, RenderAction, Widget},
 };
@@ -41,11 +42,11 @@ impl MultiChildElement for MultiLineElement {
     type Render = RenderMultiLine;
 
     fn get_child_widgets(
-        _element: Option<&mut Self>,
+        _element: &mut Self,
         widget: &Self::ArcWidget,
         _ctx: &mut BuildContext<'_>,
         _provider_values: InlinableDwsizeVec<Arc<dyn Provide>>,
-    ) -> Result<Vec<ArcChildWidget<MultiLineProtocol>>, BuildSuspendedError> {
+    ) -> Result<Vec<ArcChildWidget<MultiLineProtocol>>, BuildError> {
         Ok(widget.children.clone())
     }
 
@@ -81,7 +82,50 @@ impl Affine2dMultiChildRender for RenderMultiLine {
         children: &Vec<ArcChildRenderObject<MultiLineProtocol>>,
         intrinsics: &mut MultiLineIntrinsics,
     ) {
-        unimplemented!()
+        use MultiLineIntrinsics::*;
+        match intrinsics {
+            // Lines can break between children, so no line has to be wider than the widest child.
+            MinWidth { height, res } => {
+                *res = children.iter().try_fold(0.0f32, |width, child| {
+                    Some(width.max(child.get_min_intrinsic_width(*height)?))
+                })
+            }
+            // Children share a line until one of them ends with a line break.
+            MaxWidth { height, res } => {
+                *res = children
+                    .iter()
+                    .try_fold((0.0f32, 0.0f32), |(widest, line), child| {
+                        let line = line + child.get_max_intrinsic_width(*height)?;
+                        Some(if child.get_end_with_break()? {
+                            (widest.max(line), 0.0)
+                        } else {
+                            (widest, line)
+                        })
+                    })
+                    .map(|(widest, line)| widest.max(line))
+            }
+            // Children sharing a line are stacked instead, so the height is an upper bound.
+            MinHeight { width, res } => {
+                *res = children.iter().try_fold(0.0f32, |height, child| {
+                    Some(height + child.get_min_intrinsic_height(*width)?)
+                })
+            }
+            MaxHeight { width, res } => {
+                *res = children.iter().try_fold(0.0f32, |height, child| {
+                    Some(height + child.get_max_intrinsic_height(*width)?)
+                })
+            }
+            AdvanceBeforeFirstBreak { res } => {
+                *res = children
+                    .first()
+                    .map_or(Some(0.0), |child| child.get_advance_before_first_break())
+            }
+            EndWithBreak { res } => {
+                *res = children
+                    .last()
+                    .map_or(Some(false), |child| child.get_end_with_break())
+            }
+        }
     }
 }
 
@@ -90,6 +134,9 @@ pub struct _RenderMultiLineLayoutMemo {
 }
 
 struct MultiLineChildLayoutResult {
+    /// Whether the child was moved to a new line, since its start did not fit on the line before it.
+    breaks_at_start: bool,
+    /// The advance of the child on its first line, from the start of this render object on that line.
     first_line_advance: f32,
     line_count: u32,
 }
@@ -100,7 +147,10 @@ impl Affine2dMultiChildLayout for RenderMultiLine {
         constraints: &MultiLineConstraints,
         children: &Vec<ArcChildRenderObject<MultiLineProtocol>>,
     ) -> (MultiLineSize, Self::LayoutMemo) {
-        let mut advance = constraints.first_line_existing_advance;
+        // Advances are measured from where this render object starts on the current line,
+        // which is after the existing advance on the first line, and at the start of the line afterwards.
+        let mut line_start = constraints.first_line_existing_advance;
+        let mut advance = 0.0f32;
         let mut above = 0.0f32;
         let mut below = 0.0f32;
         let mut height = 0.0f32;
@@ -111,8 +161,29 @@ impl Affine2dMultiChildLayout for RenderMultiLine {
         while let Some(child) = it.next() {
             let is_last = it.peek().is_none();
 
+            // Break at the start: if the child can not break before its first break opportunity
+            // and that does not fit after the content already on the line, it starts on a new line instead.
+            let breaks_at_start = line_start + advance > 0.0
+                && child
+                    .get_advance_before_first_break()
+                    .is_some_and(|unbreakable| {
+                        line_start + advance + unbreakable > constraints.max_width
+                    });
+            if breaks_at_start {
+                sizes.push(SingleLineSize {
+                    advance,
+                    above,
+                    below,
+                });
+                height += above + below;
+                line_start = 0.0;
+                advance = 0.0;
+                above = 0.0;
+                below = 0.0;
+            }
+
             let size = child.layout_use_size(&MultiLineConstraints {
-                first_line_existing_advance: advance,
+                first_line_existing_advance: line_start + advance,
                 max_width: constraints.max_width,
                 last_line_append_advance: if is_last {
                     constraints.last_line_append_advance
@@ -122,9 +193,8 @@ impl Affine2dMultiChildLayout for RenderMultiLine {
                 max_height: constraints.max_height - height,
             });
 
-            // TODO: Prototype algorithm. We haven't consider about "Break at the start" case for child render objects.
-            // To properly impl the algorithm, we need to impl multi-line protocol intrinsics.
             memo.push(MultiLineChildLayoutResult {
+                breaks_at_start,
                 first_line_advance: advance,
                 line_count: size.sizes.len() as u32,
             });
@@ -152,6 +222,7 @@ impl Affine2dMultiChildLayout for RenderMultiLine {
                         size
                     }));
 
+                    line_start = 0.0;
                     above = last_size.above;
                     below = last_size.below;
                     advance = last_size.advance;
@@ -185,9 +256,14 @@ impl Affine2dMultiChildPaint for RenderMultiLine {
 
         for (child, result) in std::iter::zip(children, memo.results.iter()) {
             let MultiLineChildLayoutResult {
+                breaks_at_start,
                 first_line_advance,
                 line_count,
             } = *result;
+            if breaks_at_start {
+                // The line before the child has been finished by its previous siblings.
+                lines.next();
+            }
             let line_count = line_count as usize;
             let child_offset = if line_count > 0 {
                 let mut offsets = Vec::with_capacity(line_count);
@@ -207,7 +283,7 @@ impl Affine2dMultiChildPaint for RenderMultiLine {
                         .1,
                 );
                 debug_assert_eq!(offsets.len(), line_count, "Impossible to fail");
-                offsets[0].advance = first_line_advance;
+                offsets[0].advance += first_line_advance;
                 MultiLineOffset { offsets }
             } else {
                 MultiLineOffset {
@@ -1,10 +1,10 @@
 use epgi_2d::{
     Affine2dCanvas, ArcBoxRenderObject, ArcBoxWidget, BoxConstraints, BoxOffset, BoxProtocol,
-    MultiLineConstraints, MultiLineIntrinsics, MultiLineOffset, MultiLineProtocol, MultiLineSize,
-    SingleLineSize,
+    BoxRenderObjectIntrinsicsExt, MultiLineConstraints, MultiLineIntrinsics, MultiLineOffset,
+    MultiLineProtocol, MultiLineSize, SingleLineSize,
 };
 use epgi_core::{
-    foundation::{Arc, Asc, BuildSuspendedError, InlinableDwsizeVec, PaintContext, Provide},
+    foundation::{set_if_changed, Arc, Asc, BuildError, InlinableDwsizeVec, PaintContext, Provide},
     template::{
         AdapterRender, AdapterRenderTemplate, ImplByTemplate, SingleChildElement,
         SingleChildElementTemplate, SingleChildRenderElement,
@@ -14,10 +14,14 @@ use epgi_core::{
 use epgi_macro::Declarative;
 use typed_builder::TypedBuilder;
 
+use crate::PlaceholderAlignment;
+
 #[derive(Debug, Declarative, TypedBuilder)]
 #[builder(build_method(into=Asc<MultiLineAdapterBox>))]
 pub struct MultiLineAdapterBox {
     child: ArcBoxWidget,
+    #[builder(default)]
+    alignment: PlaceholderAlignment,
 }
 
 impl Widget for MultiLineAdapterBox {
@@ -47,7 +51,7 @@ impl SingleChildElement for MultiLineAdapterBoxElement {
         widget: &Self::ArcWidget,
         _ctx: &mut BuildContext<'_>,
         _provider_values: InlinableDwsizeVec<Arc<dyn Provide>>,
-    ) -> Result<ArcBoxWidget, BuildSuspendedError> {
+    ) -> Result<ArcBoxWidget, BuildError> {
         Ok(widget.child.clone())
     }
 
@@ -59,19 +63,20 @@ impl SingleChildElement for MultiLineAdapterBoxElement {
 impl SingleChildRenderElement for MultiLineAdapterBoxElement {
     type Render = RenderMultiLineBAdapterBox;
 
-    fn create_render(&self, _widget: &Self::ArcWidget) -> Self::Render {
-        RenderMultiLineBAdapterBox {}
+    fn create_render(&self, widget: &Self::ArcWidget) -> Self::Render {
+        RenderMultiLineBAdapterBox {
+            alignment: widget.alignment,
+        }
     }
 
-    fn update_render(
-        _render: &mut Self::Render,
-        _widget: &Self::ArcWidget,
-    ) -> Option<RenderAction> {
-        None
+    fn update_render(render: &mut Self::Render, widget: &Self::ArcWidget) -> Option<RenderAction> {
+        set_if_changed(&mut render.alignment, widget.alignment).then_some(RenderAction::Relayout)
     }
 }
 
-pub struct RenderMultiLineBAdapterBox {}
+pub struct RenderMultiLineBAdapterBox {
+    alignment: PlaceholderAlignment,
+}
 
 impl ImplByTemplate for RenderMultiLineBAdapterBox {
     type Template = AdapterRenderTemplate;
@@ -93,10 +98,17 @@ impl AdapterRender for RenderMultiLineBAdapterBox {
             min_height: 0.0,
             max_height: constraints.max_height,
         });
+        let above = match self.alignment {
+            PlaceholderAlignment::Baseline(baseline) => child
+                .get_distance_to_baseline(&baseline)
+                .unwrap_or(size.height),
+            PlaceholderAlignment::AboveBaseline => size.height,
+            PlaceholderAlignment::BelowBaseline => 0.0,
+        };
         let single_line_size = SingleLineSize {
             advance: size.width,
-            above: size.height,
-            below: 0.0,
+            above,
+            below: size.height - above,
         };
         (
             MultiLineSize {
@@ -134,7 +146,16 @@ impl AdapterRender for RenderMultiLineBAdapterBox {
         child: &ArcBoxRenderObject,
         intrinsics: &mut MultiLineIntrinsics,
     ) {
-        unimplemented!()
+        use MultiLineIntrinsics::*;
+        match intrinsics {
+            MinWidth { height, res } => *res = child.get_min_intrinsic_width(*height),
+            MaxWidth { height, res } => *res = child.get_max_intrinsic_width(*height),
+            MinHeight { width, res } => *res = child.get_min_intrinsic_height(*width),
+            MaxHeight { width, res } => *res = child.get_max_intrinsic_height(*width),
+            // The box occupies a single line and can not be broken.
+            AdvanceBeforeFirstBreak { res } => *res = child.get_max_intrinsic_width(f32::INFINITY),
+            EndWithBreak { res } => *res = Some(false),
+        }
     }
 
     const NOOP_DETACH: bool = true;
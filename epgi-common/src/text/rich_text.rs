@@ -1,35 +1,81 @@
 use std::sync::Arc;
 
 use epgi_2d::{
-    Affine2dCanvas, Affine2dPaintContextExt, MultiLineConstraints, MultiLineIntrinsics,
-    MultiLineOffset, MultiLineProtocol, MultiLineSize, Paragraph, TextAlign, TextSpan, TextStyle,
+    Affine2dCanvas, Affine2dMultiChildHitTest, Affine2dMultiChildLayout, Affine2dMultiChildPaint,
+    Affine2dMultiChildRender, Affine2dMultiChildRenderTemplate, Affine2dPaintContextExt,
+    ArcBoxRenderObject, ArcBoxWidget, BoxConstraints, BoxOffset, BoxProtocol,
+    BoxRenderObjectIntrinsicsExt, MultiLineConstraints, MultiLineIntrinsics, MultiLineOffset,
+    MultiLineProtocol, MultiLineSize, Paragraph, Placeholder, TextAlign, TextDirection, TextSpan,
+    TextStyle,
 };
 use epgi_core::{
-    foundation::{Asc, BuildSuspendedError, InlinableDwsizeVec, PaintContext, Provide},
-    template::{ImplByTemplate, LeafElement, LeafElementTemplate, LeafRender, LeafRenderTemplate},
-    tree::{BuildContext, RenderAction, Widget},
+    foundation::{Asc, BuildError, InlinableDwsizeVec, PaintContext, Provide},
+    template::{ImplByTemplate, MultiChildElement, MultiChildElementTemplate},
+    tree::{
+        ArcChildRenderObject, BuildContext, HitTestContext, RecordedChildLayer, RenderAction,
+        Widget,
+    },
 };
 use epgi_macro::Declarative;
 use typed_builder::TypedBuilder;
 
+use crate::{InlineSpan, PlaceholderAlignment};
+
 #[derive(Clone, Debug, Declarative, TypedBuilder)]
 #[builder(build_method(into=Asc<RichText>))]
 pub struct RichText {
-    /// Single item optimization. If `text` is filled, then `text_spans` will be ignored
+    /// Single item optimization. If `text` is filled, then `text_spans` and `children` will be ignored
     #[builder(default, setter(strip_option))]
     pub text: Option<TextSpan>,
     /// If `text` is filled, then `text_spans` will be ignored
     #[builder(default)]
     pub text_spans: Vec<TextSpan>,
+    /// Spans that may hold inline widgets, laid out after `text_spans`.
+    #[builder(default)]
+    pub children: Vec<InlineSpan>,
     pub style: TextStyle,
     #[builder(default = TextAlign::Start)]
     pub text_align: TextAlign,
+    /// The base direction of the paragraph, which decides the order of its bidirectional runs.
+    #[builder(default = TextDirection::Ltr)]
+    pub text_direction: TextDirection,
+    /// Lines beyond this count are hidden, and the last visible line ends as `style.overflow` asks.
+    ///
+    /// Inline widgets on the hidden lines, or covered by the end of the last visible line, are neither painted nor hit.
+    #[builder(default, setter(strip_option))]
+    pub max_lines: Option<usize>,
+}
+
+impl RichText {
+    /// The spans that are laid out, ignoring `text_spans` and `children` if `text` is filled.
+    fn spans(&self) -> impl Iterator<Item = InlineSpanRef<'_>> {
+        let text_spans = match &self.text {
+            Some(text) => std::slice::from_ref(text),
+            None => self.text_spans.as_slice(),
+        };
+        let children = match &self.text {
+            Some(_) => [].as_slice(),
+            None => self.children.as_slice(),
+        };
+        text_spans
+            .iter()
+            .map(InlineSpanRef::Text)
+            .chain(children.iter().map(|span| match span {
+                InlineSpan::Text(span) => InlineSpanRef::Text(span),
+                InlineSpan::Widget(span) => InlineSpanRef::Widget(&span.child, span.alignment),
+            }))
+    }
+}
+
+enum InlineSpanRef<'a> {
+    Text(&'a TextSpan),
+    Widget(&'a ArcBoxWidget, PlaceholderAlignment),
 }
 
 impl Widget for RichText {
     type ParentProtocol = MultiLineProtocol;
 
-    type ChildProtocol = MultiLineProtocol;
+    type ChildProtocol = BoxProtocol;
 
     type Element = RichTextElement;
 
@@ -42,77 +88,246 @@ impl Widget for RichText {
 pub struct RichTextElement {}
 
 impl ImplByTemplate for RichTextElement {
-    type Template = LeafElementTemplate;
+    type Template = MultiChildElementTemplate<false>;
 }
 
-impl LeafElement for RichTextElement {
-    type Render = RenderRichText;
-
-    type Protocol = MultiLineProtocol;
-
+impl MultiChildElement for RichTextElement {
+    type ParentProtocol = MultiLineProtocol;
+    type ChildProtocol = BoxProtocol;
     type ArcWidget = Asc<RichText>;
+    type Render = RenderRichText;
 
-    fn create_element(
-        _widget: &Self::ArcWidget,
+    fn get_child_widgets(
+        _element: &mut Self,
+        widget: &Self::ArcWidget,
         _ctx: &mut BuildContext<'_>,
         _provider_values: InlinableDwsizeVec<Arc<dyn Provide>>,
-    ) -> Result<Self, BuildSuspendedError> {
-        Ok(Self {})
+    ) -> Result<Vec<ArcBoxWidget>, BuildError> {
+        Ok(widget
+            .spans()
+            .filter_map(|span| match span {
+                InlineSpanRef::Text(_) => None,
+                InlineSpanRef::Widget(child, _) => Some(child.clone()),
+            })
+            .collect())
+    }
+
+    fn create_element(_widget: &Self::ArcWidget) -> Self {
+        Self {}
     }
 
     fn create_render(&self, widget: &Self::ArcWidget) -> Self::Render {
-        let spans = widget
-            .text
-            .as_ref()
-            .map(std::slice::from_ref)
-            .unwrap_or(widget.text_spans.as_slice());
+        let mut text_spans = Vec::new();
+        let mut widget_spans = Vec::new();
+        collect_spans(widget, &mut text_spans, &mut widget_spans);
         RenderRichText {
-            paragraph: Paragraph::new(spans, &widget.style),
+            paragraph: Paragraph::new(&text_spans, &widget.style, widget.text_direction),
+            text_spans,
+            widget_spans,
+            style: widget.style.clone(),
             text_align: widget.text_align,
+            max_lines: widget.max_lines,
         }
     }
 
     fn update_render(render: &mut Self::Render, widget: &Self::ArcWidget) -> Option<RenderAction> {
-        let spans = widget
-            .text
-            .as_ref()
-            .map(std::slice::from_ref)
-            .unwrap_or(widget.text_spans.as_slice());
-        render.paragraph = Paragraph::new(spans, &widget.style);
+        render.text_spans.clear();
+        render.widget_spans.clear();
+        collect_spans(widget, &mut render.text_spans, &mut render.widget_spans);
+        render.paragraph = Paragraph::new(&render.text_spans, &widget.style, widget.text_direction);
+        render.style = widget.style.clone();
         render.text_align = widget.text_align;
+        render.max_lines = widget.max_lines;
         Some(RenderAction::Relayout)
     }
 }
 
+/// Split the spans into their text, and where each inline widget sits in it.
+fn collect_spans(
+    widget: &RichText,
+    text_spans: &mut Vec<TextSpan>,
+    widget_spans: &mut Vec<(usize, PlaceholderAlignment)>,
+) {
+    let mut position = 0;
+    for span in widget.spans() {
+        match span {
+            InlineSpanRef::Text(span) => {
+                position += span.text.len();
+                text_spans.push(span.clone());
+            }
+            InlineSpanRef::Widget(_, alignment) => widget_spans.push((position, alignment)),
+        }
+    }
+}
+
 pub struct RenderRichText {
+    /// Shaped with room for the inline widgets as measured by the last layout, or without it before the first one.
     paragraph: Paragraph,
+    text_spans: Vec<TextSpan>,
+    /// The text position of each inline widget, and how it sits on its line.
+    widget_spans: Vec<(usize, PlaceholderAlignment)>,
+    style: TextStyle,
     text_align: TextAlign,
+    max_lines: Option<usize>,
+}
+
+impl RenderRichText {
+    /// The text shaped with room left for the inline widgets, if there are any.
+    fn paragraph_with(&self, placeholders: &[Placeholder]) -> Option<Paragraph> {
+        if placeholders.is_empty() {
+            return None;
+        }
+        Some(Paragraph::new_with_placeholders(
+            &self.text_spans,
+            placeholders,
+            &self.style,
+            self.paragraph.text_direction(),
+        ))
+    }
 }
 
 impl ImplByTemplate for RenderRichText {
-    type Template = LeafRenderTemplate;
+    type Template = Affine2dMultiChildRenderTemplate<false, false, false, false>;
+}
+
+impl Affine2dMultiChildRender for RenderRichText {
+    type ParentProtocol = MultiLineProtocol;
+    type ChildProtocol = BoxProtocol;
+    type LayoutMemo = _RenderRichTextLayoutMemo;
+
+    fn compute_intrinsics(
+        &mut self,
+        children: &Vec<ArcBoxRenderObject>,
+        intrinsics: &mut MultiLineIntrinsics,
+    ) {
+        use MultiLineIntrinsics::*;
+        // Inline widgets are measured at their max intrinsic sizes, and sit on the baseline unless they hang below it.
+        let Some(placeholders) = std::iter::zip(children, &self.widget_spans)
+            .map(|(child, &(position, alignment))| {
+                let width = child.get_max_intrinsic_width(f32::INFINITY)?;
+                let height = child.get_max_intrinsic_height(width)?;
+                let above = match alignment {
+                    PlaceholderAlignment::BelowBaseline => 0.0,
+                    _ => height,
+                };
+                Some(Placeholder {
+                    position,
+                    width,
+                    above,
+                    below: height - above,
+                })
+            })
+            .collect::<Option<Vec<_>>>()
+        else {
+            return;
+        };
+        let paragraph_with_widgets = self.paragraph_with(&placeholders);
+        let paragraph = paragraph_with_widgets.as_ref().unwrap_or(&self.paragraph);
+        match intrinsics {
+            MinWidth { res, .. } => *res = Some(paragraph.min_intrinsic_width()),
+            MaxWidth { res, .. } => *res = Some(paragraph.max_intrinsic_width()),
+            MinHeight { width, res } | MaxHeight { width, res } => {
+                *res = Some(paragraph.intrinsic_height(*width, self.max_lines))
+            }
+            AdvanceBeforeFirstBreak { res } => *res = Some(paragraph.advance_before_first_break()),
+            EndWithBreak { res } => *res = Some(paragraph.ends_with_break()),
+        }
+    }
 }
 
-impl LeafRender for RenderRichText {
-    type Protocol = MultiLineProtocol;
+pub struct _RenderRichTextLayoutMemo {
+    /// Where each inline widget was placed: the index of its line, its left from the left of the paragraph,
+    /// and how far it extends above the baseline. Widgets hidden by truncation are not placed.
+    widget_positions: Vec<Option<(usize, f32, f32)>>,
+}
 
-    fn perform_layout(&mut self, constraints: &MultiLineConstraints) -> MultiLineSize {
+impl Affine2dMultiChildLayout for RenderRichText {
+    fn perform_layout(
+        &mut self,
+        constraints: &MultiLineConstraints,
+        children: &Vec<ArcBoxRenderObject>,
+    ) -> (MultiLineSize, Self::LayoutMemo) {
+        // An inline widget that does not fit on the rest of its line starts a new one, so it may take the full width.
+        let placeholders = std::iter::zip(children, &self.widget_spans)
+            .map(|(child, &(position, alignment))| {
+                let size = child.layout_use_size(&BoxConstraints {
+                    min_width: 0.0,
+                    max_width: constraints.max_width,
+                    min_height: 0.0,
+                    max_height: constraints.max_height,
+                });
+                let above = match alignment {
+                    PlaceholderAlignment::Baseline(baseline) => child
+                        .get_distance_to_baseline(&baseline)
+                        .unwrap_or(size.height),
+                    PlaceholderAlignment::AboveBaseline => size.height,
+                    PlaceholderAlignment::BelowBaseline => 0.0,
+                };
+                Placeholder {
+                    position,
+                    width: size.width,
+                    above,
+                    below: size.height - above,
+                }
+            })
+            .collect::<Vec<_>>();
+        if let Some(paragraph) = self.paragraph_with(&placeholders) {
+            self.paragraph = paragraph;
+        }
         let sizes = self
             .paragraph
-            .layout_multi_line(constraints, self.text_align);
-        MultiLineSize { sizes }
+            .layout_multi_line(constraints, self.text_align, self.max_lines);
+        let widget_positions = std::iter::zip(self.paragraph.placeholder_positions(), placeholders)
+            .map(|(position, placeholder)| {
+                position.map(|(line, left)| (line, left, placeholder.above))
+            })
+            .collect();
+        (
+            MultiLineSize { sizes },
+            _RenderRichTextLayoutMemo { widget_positions },
+        )
     }
+}
 
+impl Affine2dMultiChildPaint for RenderRichText {
     fn perform_paint(
         &self,
         _size: &MultiLineSize,
         offset: &MultiLineOffset,
+        memo: &Self::LayoutMemo,
+        children: &Vec<ArcBoxRenderObject>,
         paint_ctx: &mut impl PaintContext<Canvas = Affine2dCanvas>,
     ) {
-        paint_ctx.draw_paragraph(&self.paragraph, &offset.offsets)
+        paint_ctx.draw_paragraph(&self.paragraph, &offset.offsets);
+        for (child, position) in std::iter::zip(children, &memo.widget_positions) {
+            if let Some((line, left, above)) = *position {
+                let line_offset = offset.offsets[line];
+                paint_ctx.paint(
+                    child,
+                    &BoxOffset {
+                        x: line_offset.advance + left,
+                        y: line_offset.baseline - above,
+                    },
+                );
+            }
+        }
     }
+}
 
-    fn compute_intrinsics(render: &mut Self, intrinsics: &mut MultiLineIntrinsics) {
-        unimplemented!()
+impl Affine2dMultiChildHitTest for RenderRichText {
+    fn hit_test_children(
+        &self,
+        ctx: &mut HitTestContext<Affine2dCanvas>,
+        _size: &MultiLineSize,
+        _offset: &MultiLineOffset,
+        memo: &Self::LayoutMemo,
+        children: &Vec<ArcChildRenderObject<BoxProtocol>>,
+        _adopted_children: &[RecordedChildLayer<Affine2dCanvas>],
+    ) -> bool {
+        // Widgets hidden by truncation have not been painted.
+        std::iter::zip(children, &memo.widget_positions)
+            .rev()
+            .filter(|(_, position)| position.is_some())
+            .any(|(child, _)| ctx.hit_test(child.clone()))
     }
 }
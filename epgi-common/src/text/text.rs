@@ -1,7 +1,8 @@
 use std::{borrow::Cow, sync::Arc};
 
 use epgi_2d::{
-    ArcBoxWidget, BoxProtocol, LocalTextStyle, MultiLineProtocol, TextAlign, TextSpan, TextStyle,
+    ArcBoxWidget, BoxProtocol, LocalTextStyle, MultiLineProtocol, TextAlign, TextDirection,
+    TextSpan, TextStyle,
 };
 use epgi_core::{
     foundation::{Asc, AscProvideExt, InlinableDwsizeVec, Provide, SmallVecExt, TypeKey},
@@ -12,7 +13,7 @@ use epgi_core::{
 use epgi_macro::Declarative;
 use typed_builder::TypedBuilder;
 
-use crate::{BoxAdapterMultiLine, RichText};
+use crate::{BoxAdapterMultiLine, InlineSpan, RichText};
 
 #[derive(Clone, Debug, Declarative, TypedBuilder)]
 #[builder(build_method(into=Asc<Text>))]
@@ -23,10 +24,19 @@ pub struct Text {
     /// If `text` is filled, then `text_spans` will be ignored
     #[builder(default)]
     pub text_spans: Vec<TextSpan>,
+    /// Spans that may hold inline widgets, laid out after `text_spans`. If `text` is filled, then `children` will be ignored
+    #[builder(default)]
+    pub children: Vec<InlineSpan>,
     #[builder(default, setter(strip_option))]
     pub style: Option<LocalTextStyle>,
     #[builder(default, setter(strip_option))]
     pub text_align: Option<TextAlign>,
+    /// Defaults to the ambient [`TextDirection`] of the [`Directionality`](crate::Directionality).
+    #[builder(default, setter(strip_option))]
+    pub text_direction: Option<TextDirection>,
+    /// Lines beyond this count are hidden, and the last visible line ends as the overflow of the style asks.
+    #[builder(default, setter(strip_option))]
+    pub max_lines: Option<usize>,
 }
 
 impl Widget for Text {
@@ -45,8 +55,11 @@ impl ComponentWidget<BoxProtocol> for Text {
             child = Asc::new(MultiLineText {
                 text: self.text.clone(),
                 text_spans: self.text_spans.clone(),
+                children: self.children.clone(),
                 style: self.style.clone(),
                 text_align: self.text_align,
+                text_direction: self.text_direction,
+                max_lines: self.max_lines,
             })
         )
     }
@@ -61,10 +74,19 @@ pub struct MultiLineText {
     /// If `text` is filled, then `text_spans` will be ignored
     #[builder(default)]
     pub text_spans: Vec<TextSpan>,
+    /// Spans that may hold inline widgets, laid out after `text_spans`. If `text` is filled, then `children` will be ignored
+    #[builder(default)]
+    pub children: Vec<InlineSpan>,
     #[builder(default, setter(strip_option))]
     pub style: Option<LocalTextStyle>,
     #[builder(default, setter(strip_option))]
     pub text_align: Option<TextAlign>,
+    /// Defaults to the ambient [`TextDirection`] of the [`Directionality`](crate::Directionality).
+    #[builder(default, setter(strip_option))]
+    pub text_direction: Option<TextDirection>,
+    /// Lines beyond this count are hidden, and the last visible line ends as the overflow of the style asks.
+    #[builder(default, setter(strip_option))]
+    pub max_lines: Option<usize>,
 }
 
 impl Widget for MultiLineText {
@@ -78,14 +100,22 @@ impl Widget for MultiLineText {
 }
 
 lazy_static::lazy_static! {
-    static ref MULTI_LINE_TEXT_CONSUMED_TYPES: [TypeKey; 1] = [
+    static ref MULTI_LINE_TEXT_CONSUMED_TYPES: [TypeKey; 2] = [
+        TypeKey::of::<TextStyle>(),
+        TypeKey::of_or_default::<TextDirection>(),
+    ];
+    static ref MULTI_LINE_TEXT_WITH_DIRECTION_CONSUMED_TYPES: [TypeKey; 1] = [
         TypeKey::of::<TextStyle>(),
     ];
 }
 
 impl ConsumerWidget<MultiLineProtocol> for MultiLineText {
     fn get_consumed_types(&self) -> &[TypeKey] {
-        MULTI_LINE_TEXT_CONSUMED_TYPES.as_ref()
+        if self.text_direction.is_some() {
+            MULTI_LINE_TEXT_WITH_DIRECTION_CONSUMED_TYPES.as_ref()
+        } else {
+            MULTI_LINE_TEXT_CONSUMED_TYPES.as_ref()
+        }
     }
 
     fn build(
@@ -93,7 +123,14 @@ impl ConsumerWidget<MultiLineProtocol> for MultiLineText {
         _ctx: &mut BuildContext,
         provider_values: InlinableDwsizeVec<Arc<dyn Provide>>,
     ) -> ArcChildWidget<MultiLineProtocol> {
-        let default_text_style = read_providers!(provider_values, TextStyle);
+        let (default_text_style, text_direction) = match self.text_direction {
+            Some(text_direction) => (read_providers!(provider_values, TextStyle), text_direction),
+            None => {
+                let (default_text_style, text_direction) =
+                    read_providers!(provider_values, TextStyle, TextDirection);
+                (default_text_style, *text_direction)
+            }
+        };
         let mut effective_text_style = default_text_style.as_ref().clone();
         if let Some(style) = self.style.as_ref() {
             effective_text_style = effective_text_style.merge(style.clone())
@@ -108,8 +145,11 @@ impl ConsumerWidget<MultiLineProtocol> for MultiLineText {
                 style: None,
             }),
             text_spans: self.text_spans.clone(),
+            children: self.children.clone(),
             style: effective_text_style,
             text_align: TextAlign::Start,
+            text_direction,
+            max_lines: self.max_lines,
         })
     }
 }
@@ -0,0 +1,336 @@
+use std::ops::Range;
+
+use epgi_core::foundation::{Arc, SyncMutex};
+use unicode_segmentation::{GraphemeCursor, UnicodeSegmentation};
+
+/// A range of selected text, given in byte offsets into the text.
+///
+/// The base stays in place while the selection is extended, and the extent is where the caret is.
+/// A collapsed selection, with the base equal to the extent, is just a caret.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub struct TextSelection {
+    pub base: usize,
+    pub extent: usize,
+}
+
+impl TextSelection {
+    pub fn new(base: usize, extent: usize) -> Self {
+        Self { base, extent }
+    }
+
+    pub fn collapsed(offset: usize) -> Self {
+        Self {
+            base: offset,
+            extent: offset,
+        }
+    }
+
+    pub fn is_collapsed(&self) -> bool {
+        self.base == self.extent
+    }
+
+    pub fn start(&self) -> usize {
+        self.base.min(self.extent)
+    }
+
+    pub fn end(&self) -> usize {
+        self.base.max(self.extent)
+    }
+
+    pub fn range(&self) -> Range<usize> {
+        self.start()..self.end()
+    }
+}
+
+/// How a caret moves through the text in response to navigation keys.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum CaretMovement {
+    Left,
+    Right,
+    WordLeft,
+    WordRight,
+    /// To the start of the current line, as delimited by line breaks in the text.
+    LineStart,
+    /// To the end of the current line, as delimited by line breaks in the text.
+    LineEnd,
+    TextStart,
+    TextEnd,
+}
+
+/// The text of an editable text field, along with its selection and its IME composing range.
+///
+/// All offsets are byte offsets into the text, and are kept on char boundaries by the editing operations.
+/// Moving the caret and deleting around it go by grapheme clusters, so that a character is never split
+/// from its combining marks, nor an emoji sequence broken apart.
+#[derive(PartialEq, Eq, Clone, Debug, Default)]
+pub struct TextEditingValue {
+    pub text: String,
+    pub selection: TextSelection,
+    /// The range of text that is still being composed by the input method.
+    pub composing: Option<Range<usize>>,
+}
+
+impl TextEditingValue {
+    /// A value with the caret placed at the end of the text.
+    pub fn new(text: impl Into<String>) -> Self {
+        let text = text.into();
+        let selection = TextSelection::collapsed(text.len());
+        Self {
+            text,
+            selection,
+            composing: None,
+        }
+    }
+
+    pub fn selected_text(&self) -> &str {
+        &self.text[self.selection.range()]
+    }
+
+    /// Set the selection, clamping both ends to the text and to char boundaries.
+    pub fn set_selection(&mut self, selection: TextSelection) {
+        self.selection = TextSelection {
+            base: self.clamp_offset(selection.base),
+            extent: self.clamp_offset(selection.extent),
+        };
+    }
+
+    /// Move the extent of the selection to `offset`, keeping its base.
+    pub fn extend_selection(&mut self, offset: usize) {
+        self.selection.extent = self.clamp_offset(offset);
+    }
+
+    pub fn select_all(&mut self) {
+        self.selection = TextSelection::new(0, self.text.len());
+    }
+
+    /// Replace the selected text with `replacement`, and place the caret after it.
+    pub fn replace_selection(&mut self, replacement: &str) {
+        self.replace_range(self.selection.range(), replacement);
+        self.composing = None;
+    }
+
+    /// Delete the selected text, or the grapheme cluster in front of the caret if the selection is collapsed.
+    pub fn delete_backward(&mut self) {
+        if self.selection.is_collapsed() {
+            let offset = self.selection.extent;
+            if offset == 0 {
+                return;
+            }
+            self.replace_range(self.previous_grapheme_boundary(offset)..offset, "");
+        } else {
+            self.replace_range(self.selection.range(), "");
+        }
+        self.composing = None;
+    }
+
+    /// Delete the selected text, or the grapheme cluster after the caret if the selection is collapsed.
+    pub fn delete_forward(&mut self) {
+        if self.selection.is_collapsed() {
+            let offset = self.selection.extent;
+            if offset == self.text.len() {
+                return;
+            }
+            self.replace_range(offset..self.next_grapheme_boundary(offset), "");
+        } else {
+            self.replace_range(self.selection.range(), "");
+        }
+        self.composing = None;
+    }
+
+    /// Move the caret. If `extend_selection` is set, the base of the selection stays in place.
+    ///
+    /// Moving left or right without extending collapses a non-empty selection to its respective end.
+    pub fn move_caret(&mut self, movement: CaretMovement, extend_selection: bool) {
+        use CaretMovement::*;
+        if !extend_selection && !self.selection.is_collapsed() {
+            match movement {
+                Left => {
+                    self.selection = TextSelection::collapsed(self.selection.start());
+                    return;
+                }
+                Right => {
+                    self.selection = TextSelection::collapsed(self.selection.end());
+                    return;
+                }
+                _ => {}
+            }
+        }
+        let offset = self.selection.extent;
+        let target = match movement {
+            Left if offset == 0 => 0,
+            Left => self.previous_grapheme_boundary(offset),
+            Right if offset == self.text.len() => offset,
+            Right => self.next_grapheme_boundary(offset),
+            WordLeft => self.previous_word_boundary(offset),
+            WordRight => self.next_word_boundary(offset),
+            LineStart => self.text[..offset].rfind('\n').map_or(0, |index| index + 1),
+            LineEnd => self.text[offset..]
+                .find('\n')
+                .map_or(self.text.len(), |index| offset + index),
+            TextStart => 0,
+            TextEnd => self.text.len(),
+        };
+        if extend_selection {
+            self.selection.extent = target;
+        } else {
+            self.selection = TextSelection::collapsed(target);
+        }
+    }
+
+    /// Replace the composing text, or the selection if nothing is being composed, with the preedit text of the input method.
+    ///
+    /// `cursor` is a byte range within `text`. If it is not provided, the caret is placed after the preedit text.
+    /// An empty `text` removes the composing text.
+    pub fn set_composing(&mut self, text: &str, cursor: Option<(usize, usize)>) {
+        let range = self
+            .composing
+            .take()
+            .unwrap_or_else(|| self.selection.range());
+        let start = range.start;
+        self.replace_range(range, text);
+        if text.is_empty() {
+            return;
+        }
+        self.composing = Some(start..start + text.len());
+        if let Some((cursor_start, cursor_end)) = cursor {
+            self.set_selection(TextSelection::new(start + cursor_start, start + cursor_end));
+        }
+    }
+
+    /// Replace the composing text, or the selection if nothing is being composed, with the text committed by the input method.
+    pub fn commit(&mut self, text: &str) {
+        let range = self
+            .composing
+            .take()
+            .unwrap_or_else(|| self.selection.range());
+        self.replace_range(range, text);
+    }
+
+    fn replace_range(&mut self, range: Range<usize>, replacement: &str) {
+        let start = range.start;
+        self.text.replace_range(range, replacement);
+        self.selection = TextSelection::collapsed(start + replacement.len());
+    }
+
+    fn clamp_offset(&self, offset: usize) -> usize {
+        let mut offset = offset.min(self.text.len());
+        while !self.text.is_char_boundary(offset) {
+            offset -= 1;
+        }
+        offset
+    }
+
+    fn previous_grapheme_boundary(&self, offset: usize) -> usize {
+        GraphemeCursor::new(offset, self.text.len(), true)
+            .prev_boundary(&self.text, 0)
+            .ok()
+            .flatten()
+            .unwrap_or(0)
+    }
+
+    fn next_grapheme_boundary(&self, offset: usize) -> usize {
+        GraphemeCursor::new(offset, self.text.len(), true)
+            .next_boundary(&self.text, 0)
+            .ok()
+            .flatten()
+            .unwrap_or(self.text.len())
+    }
+
+    fn previous_word_boundary(&self, offset: usize) -> usize {
+        let mut graphemes = self.text[..offset].grapheme_indices(true).rev().peekable();
+        // Skip the separators in front of the caret, then the word before them.
+        while graphemes
+            .next_if(|(_, grapheme)| !is_word_grapheme(grapheme))
+            .is_some()
+        {}
+        let mut boundary = graphemes.peek().map_or(0, |(index, _)| *index);
+        while let Some((index, _)) = graphemes.next_if(|(_, grapheme)| is_word_grapheme(grapheme)) {
+            boundary = index;
+        }
+        boundary
+    }
+
+    fn next_word_boundary(&self, offset: usize) -> usize {
+        let mut graphemes = self.text[offset..].grapheme_indices(true).peekable();
+        while graphemes
+            .next_if(|(_, grapheme)| !is_word_grapheme(grapheme))
+            .is_some()
+        {}
+        while graphemes
+            .next_if(|(_, grapheme)| is_word_grapheme(grapheme))
+            .is_some()
+        {}
+        graphemes
+            .peek()
+            .map_or(self.text.len(), |(index, _)| offset + index)
+    }
+}
+
+/// Whether a grapheme cluster is part of a word, judged by its base character.
+fn is_word_grapheme(grapheme: &str) -> bool {
+    grapheme.chars().next().is_some_and(char::is_alphanumeric)
+}
+
+/// Holds the [`TextEditingValue`] of a [`TextField`](crate::TextField), and lets the app read and replace it.
+///
+/// The field edits the controller as soon as it receives input, so the controller always holds the latest value.
+/// A value set by the app is shown the next time the field is built.
+#[derive(Clone)]
+pub struct TextEditingController {
+    inner: Arc<SyncMutex<TextEditingValue>>,
+}
+
+impl TextEditingController {
+    pub fn new(text: impl Into<String>) -> Self {
+        Self::new_with_value(TextEditingValue::new(text))
+    }
+
+    pub fn new_with_value(value: TextEditingValue) -> Self {
+        Self {
+            inner: Arc::new(SyncMutex::new(value)),
+        }
+    }
+
+    pub fn value(&self) -> TextEditingValue {
+        self.inner.lock().clone()
+    }
+
+    pub fn text(&self) -> String {
+        self.inner.lock().text.clone()
+    }
+
+    pub fn selection(&self) -> TextSelection {
+        self.inner.lock().selection
+    }
+
+    pub fn set_value(&self, value: TextEditingValue) {
+        *self.inner.lock() = value;
+    }
+
+    /// Edit the value in place, and return the resulting value.
+    pub fn update(&self, op: impl FnOnce(&mut TextEditingValue)) -> TextEditingValue {
+        let mut value = self.inner.lock();
+        op(&mut value);
+        value.clone()
+    }
+}
+
+impl Default for TextEditingController {
+    fn default() -> Self {
+        Self::new(String::new())
+    }
+}
+
+impl PartialEq for TextEditingController {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
+impl std::fmt::Debug for TextEditingController {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TextEditingController")
+            .field("value", &*self.inner.lock())
+            .finish()
+    }
+}
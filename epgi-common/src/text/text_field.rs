@@ -0,0 +1,273 @@
+use std::borrow::Cow;
+
+use epgi_2d::{ArcBoxWidget, BoxProtocol, Color, LocalTextStyle, TextDirection, TextStyle};
+use epgi_core::{
+    foundation::{Arc, Asc, AscProvideExt, InlinableDwsizeVec, Provide, SmallVecExt, TypeKey},
+    hooks::SetState,
+    nodes::{ConsumerElement, ConsumerWidget},
+    read_providers,
+    scheduler::JobBuilder,
+    tree::{BuildContext, ElementBase, Widget},
+};
+use epgi_macro::Declarative;
+use typed_builder::TypedBuilder;
+
+use crate::{
+    BuildContextFocusExt, CaretMovement, EditableText, Focus, FocusNode, ImeEvent, KeyEvent,
+    KeyEventResult, KeyModifiers, LogicalKey, NamedKey, TextEditingController, TextEditingValue,
+    TextSelection, DEFAULT_SELECTION_COLOR,
+};
+
+pub type ArcTextCallback = Asc<dyn Fn(&str, &mut JobBuilder) + Send + Sync>;
+
+/// An editable text field.
+///
+/// The field takes the focus when tapped, and then edits its text in response to key presses and input method events.
+/// Tapping or dragging on the text places the caret or selects text. Shift extends the selection with the navigation keys.
+#[derive(Declarative, TypedBuilder)]
+#[builder(build_method(into=Asc<TextField>))]
+pub struct TextField {
+    /// The controller holding the value. If not provided, the widget manages its own controller.
+    #[builder(default, setter(strip_option))]
+    pub controller: Option<TextEditingController>,
+    /// The initial text of the controller managed by the widget. Ignored if `controller` is provided.
+    #[builder(default, setter(into))]
+    pub initial_text: Cow<'static, str>,
+    /// The focus node to attach. If not provided, the widget manages its own node.
+    #[builder(default, setter(strip_option))]
+    pub focus_node: Option<FocusNode>,
+    #[builder(default = false)]
+    pub autofocus: bool,
+    #[builder(default, setter(strip_option))]
+    pub style: Option<LocalTextStyle>,
+    /// Whether the text wraps, and Enter inserts a line break instead of submitting.
+    #[builder(default = false)]
+    pub multi_line: bool,
+    #[builder(default, setter(strip_option))]
+    pub caret_color: Option<Color>,
+    /// Called with the new text whenever an edit changes the text.
+    #[builder(default, setter(transform=|op: impl Fn(&str, &mut JobBuilder) + Send + Sync + 'static| Some(Asc::new(op) as _)))]
+    pub on_changed: Option<ArcTextCallback>,
+    /// Called with the text when Enter is pressed in a single-line field.
+    #[builder(default, setter(transform=|op: impl Fn(&str, &mut JobBuilder) + Send + Sync + 'static| Some(Asc::new(op) as _)))]
+    pub on_submitted: Option<ArcTextCallback>,
+}
+
+impl std::fmt::Debug for TextField {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TextField")
+            .field("controller", &self.controller)
+            .field("initial_text", &self.initial_text)
+            .field("focus_node", &self.focus_node)
+            .field("autofocus", &self.autofocus)
+            .field("style", &self.style)
+            .field("multi_line", &self.multi_line)
+            .field("caret_color", &self.caret_color)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Widget for TextField {
+    type ParentProtocol = BoxProtocol;
+    type ChildProtocol = BoxProtocol;
+    type Element = ConsumerElement<BoxProtocol>;
+
+    fn into_arc_widget(self: Asc<Self>) -> <Self::Element as ElementBase>::ArcWidget {
+        self
+    }
+}
+
+lazy_static::lazy_static! {
+    static ref TEXT_FIELD_CONSUMED_TYPES: [TypeKey; 2] = [
+        TypeKey::of::<TextStyle>(),
+        TypeKey::of_or_default::<TextDirection>(),
+    ];
+}
+
+impl ConsumerWidget<BoxProtocol> for TextField {
+    fn get_consumed_types(&self) -> &[TypeKey] {
+        TEXT_FIELD_CONSUMED_TYPES.as_ref()
+    }
+
+    fn build(
+        &self,
+        ctx: &mut BuildContext,
+        provider_values: InlinableDwsizeVec<Arc<dyn Provide>>,
+    ) -> ArcBoxWidget {
+        let (default_text_style, text_direction) =
+            read_providers!(provider_values, TextStyle, TextDirection);
+        let mut style = default_text_style.as_ref().clone();
+        if let Some(local_style) = self.style.as_ref() {
+            style = style.merge(local_style.clone())
+        }
+
+        let (own_controller, _) =
+            ctx.use_state_with(|| TextEditingController::new(self.initial_text.as_ref()));
+        let controller = self.controller.clone().unwrap_or(own_controller);
+        let own_focus_node = ctx.use_focus_node();
+        let focus_node = self.focus_node.clone().unwrap_or(own_focus_node);
+        let (has_focus, set_has_focus) = ctx.use_state_with(|| focus_node.has_focus());
+        // The state only schedules the rebuilds. The controller holds the latest value,
+        // since several edits may arrive within a single frame.
+        let (_, set_value) = ctx.use_state_with(|| controller.value());
+
+        let editor = TextFieldEditor {
+            controller: controller.clone(),
+            set_value,
+            on_changed: self.on_changed.clone(),
+        };
+        let on_key_event = {
+            let editor = editor.clone();
+            let multi_line = self.multi_line;
+            let on_submitted = self.on_submitted.clone();
+            move |event: &KeyEvent, job_builder: &mut JobBuilder| {
+                editor.handle_key_event(event, multi_line, on_submitted.as_ref(), job_builder)
+            }
+        };
+        let on_ime_event = {
+            let editor = editor.clone();
+            move |event: &ImeEvent, job_builder: &mut JobBuilder| {
+                editor.handle_ime_event(event, job_builder)
+            }
+        };
+        let on_pointer_down = {
+            let editor = editor.clone();
+            let focus_node = focus_node.clone();
+            move |position, job_builder: &mut JobBuilder| {
+                focus_node.request_focus();
+                editor.edit(job_builder, |value| {
+                    value.set_selection(TextSelection::collapsed(position))
+                });
+            }
+        };
+        let on_pointer_drag = move |position, job_builder: &mut JobBuilder| {
+            editor.edit(job_builder, |value| value.extend_selection(position));
+        };
+
+        let editable_text = Asc::new(EditableText {
+            value: controller.value(),
+            style,
+            text_direction: *text_direction,
+            multi_line: self.multi_line,
+            show_caret: has_focus,
+            caret_color: self.caret_color,
+            selection_color: DEFAULT_SELECTION_COLOR,
+            on_pointer_down: Some(Asc::new(on_pointer_down)),
+            on_pointer_drag: Some(Asc::new(on_pointer_drag)),
+        });
+
+        Focus!(
+            focus_node,
+            autofocus = self.autofocus,
+            on_key_event,
+            on_ime_event,
+            on_focus_change = move |has_focus, job_builder| {
+                set_has_focus.set(has_focus, job_builder);
+            },
+            child = editable_text
+        )
+    }
+}
+
+#[derive(Clone)]
+struct TextFieldEditor {
+    controller: TextEditingController,
+    set_value: SetState<TextEditingValue>,
+    on_changed: Option<ArcTextCallback>,
+}
+
+impl TextFieldEditor {
+    fn edit(&self, job_builder: &mut JobBuilder, op: impl FnOnce(&mut TextEditingValue)) {
+        let mut text_changed = false;
+        let value = self.controller.update(|value| {
+            let old_text = value.text.clone();
+            op(value);
+            text_changed = value.text != old_text;
+        });
+        if text_changed {
+            if let Some(on_changed) = &self.on_changed {
+                on_changed(&value.text, job_builder);
+            }
+        }
+        self.set_value.set(value, job_builder);
+    }
+
+    fn handle_key_event(
+        &self,
+        event: &KeyEvent,
+        multi_line: bool,
+        on_submitted: Option<&ArcTextCallback>,
+        job_builder: &mut JobBuilder,
+    ) -> KeyEventResult {
+        use CaretMovement::*;
+        if !event.is_pressed() {
+            return KeyEventResult::Ignored;
+        }
+        let shift = event.modifiers.contains(KeyModifiers::SHIFT);
+        let primary = event.modifiers.contains(KeyModifiers::PRIMARY);
+        let movement = match &event.logical_key {
+            LogicalKey::Named(NamedKey::ArrowLeft) if primary => Some(WordLeft),
+            LogicalKey::Named(NamedKey::ArrowLeft) => Some(Left),
+            LogicalKey::Named(NamedKey::ArrowRight) if primary => Some(WordRight),
+            LogicalKey::Named(NamedKey::ArrowRight) => Some(Right),
+            LogicalKey::Named(NamedKey::Home) if primary => Some(TextStart),
+            LogicalKey::Named(NamedKey::Home) => Some(LineStart),
+            LogicalKey::Named(NamedKey::End) if primary => Some(TextEnd),
+            LogicalKey::Named(NamedKey::End) => Some(LineEnd),
+            _ => None,
+        };
+        if let Some(movement) = movement {
+            self.edit(job_builder, |value| value.move_caret(movement, shift));
+            return KeyEventResult::Handled;
+        }
+        match &event.logical_key {
+            LogicalKey::Named(NamedKey::Backspace) => {
+                self.edit(job_builder, TextEditingValue::delete_backward)
+            }
+            LogicalKey::Named(NamedKey::Delete) => {
+                self.edit(job_builder, TextEditingValue::delete_forward)
+            }
+            LogicalKey::Named(NamedKey::Enter) if multi_line => {
+                self.edit(job_builder, |value| value.replace_selection("\n"))
+            }
+            LogicalKey::Named(NamedKey::Enter) => {
+                if let Some(on_submitted) = on_submitted {
+                    on_submitted(&self.controller.text(), job_builder);
+                }
+            }
+            LogicalKey::Character(character) if primary && character.eq_ignore_ascii_case("a") => {
+                self.edit(job_builder, TextEditingValue::select_all)
+            }
+            _ => {
+                // Shortcuts are left to the ancestors.
+                let Some(text) = event.text.as_ref().filter(|text| {
+                    !event
+                        .modifiers
+                        .intersects(KeyModifiers::CONTROL | KeyModifiers::SUPER)
+                        && !text.chars().any(char::is_control)
+                }) else {
+                    return KeyEventResult::Ignored;
+                };
+                self.edit(job_builder, |value| value.replace_selection(text))
+            }
+        }
+        KeyEventResult::Handled
+    }
+
+    fn handle_ime_event(&self, event: &ImeEvent, job_builder: &mut JobBuilder) -> KeyEventResult {
+        match event {
+            ImeEvent::Enabled => return KeyEventResult::Ignored,
+            ImeEvent::Preedit { text, cursor } => {
+                self.edit(job_builder, |value| value.set_composing(text, *cursor))
+            }
+            ImeEvent::Commit(text) => self.edit(job_builder, |value| value.commit(text)),
+            // Drop whatever is left of an unfinished composition.
+            ImeEvent::Disabled => self.edit(job_builder, |value| {
+                if value.composing.is_some() {
+                    value.set_composing("", None)
+                }
+            }),
+        }
+        KeyEventResult::Handled
+    }
+}
@@ -0,0 +1,53 @@
+use epgi_2d::{ArcBoxWidget, TextBaseline, TextSpan};
+
+/// How an inline widget is placed vertically against the line it sits on.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub enum PlaceholderAlignment {
+    /// Align the baseline of the widget with the baseline of the line.
+    ///
+    /// A widget without a baseline sits on the baseline, as with [PlaceholderAlignment::AboveBaseline].
+    Baseline(TextBaseline),
+    /// Place the bottom edge of the widget on the baseline of the line.
+    #[default]
+    AboveBaseline,
+    /// Place the top edge of the widget on the baseline of the line.
+    BelowBaseline,
+}
+
+/// A box widget that flows inline with the text around it, such as an icon, a chip or an avatar.
+///
+/// The widget is laid out with at most the width of the paragraph, and can not be broken across lines.
+/// If it does not fit after the text before it, it starts on a new line.
+#[derive(Clone, Debug)]
+pub struct WidgetSpan {
+    pub child: ArcBoxWidget,
+    pub alignment: PlaceholderAlignment,
+}
+
+/// A span of a rich text, either some text or an inline widget.
+#[derive(Clone, Debug)]
+pub enum InlineSpan {
+    Text(TextSpan),
+    Widget(WidgetSpan),
+}
+
+impl InlineSpan {
+    pub fn as_text_span(&self) -> Option<&TextSpan> {
+        match self {
+            InlineSpan::Text(span) => Some(span),
+            InlineSpan::Widget(_) => None,
+        }
+    }
+}
+
+impl From<TextSpan> for InlineSpan {
+    fn from(value: TextSpan) -> Self {
+        InlineSpan::Text(value)
+    }
+}
+
+impl From<WidgetSpan> for InlineSpan {
+    fn from(value: WidgetSpan) -> Self {
+        InlineSpan::Widget(value)
+    }
+}
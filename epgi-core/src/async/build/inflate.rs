@@ -2,7 +2,8 @@ use std::borrow::Cow;
 
 use crate::{
     foundation::{
-        Arc, Asc, Container, InlinableDwsizeVec, Protocol, Provide, 
+        catch_build_panic, Arc, Asc, BuildError, Container, Error, InlinableDwsizeVec, Protocol,
+        Provide,
     },
     scheduler::get_current_scheduler,
     sync::{CommitBarrier, ImplCommitRenderObject},
@@ -164,7 +165,7 @@ impl<E: FullElement> ElementNode<E> {
         self: &Arc<Self>,
         widget: &E::ArcWidget,
         suspended_hooks: Option<HooksWithEffects>,
-        provider_values: InlinableDwsizeVec<Arc<dyn Provide>>,
+        provider_values: Result<InlinableDwsizeVec<Arc<dyn Provide>>, Error>,
         child_work_context: Asc<WorkContext>,
         handle: WorkHandle,
         barrier: CommitBarrier,
@@ -181,7 +182,12 @@ impl<E: FullElement> ElementNode<E> {
             element_context: &self.context,
             hook_context: HookContext::new_async(&mut hooks, hook_mode),
         };
-        let result = E::perform_inflate_element(&widget, &mut ctx, provider_values);
+        let result = provider_values
+            .map_err(BuildError::from)
+            .and_then(|provider_values| {
+                catch_build_panic(|| E::perform_inflate_element(widget, &mut ctx, provider_values))
+                    .unwrap_or_else(|error| Err(error.into()))
+            });
 
         let lane_pos = child_work_context.lane_pos;
 
@@ -216,10 +222,15 @@ impl<E: FullElement> ElementNode<E> {
                 );
                 AsyncOutput::Completed(BuildResults::new_inflate(hooks, element, children))
             }
-            Err(err) => AsyncOutput::Suspended {
-                suspended_results: Some(BuildSuspendResults::new(hooks, err.waker)),
-                barrier: (!allow_commit_suspend).then_some(barrier),
-            },
+            Err(err) => {
+                let (waker, error) = err.into_parts(&self.context, lane_pos);
+                // A failed build will never resolve, so it must not hold back the batch.
+                let barrier = (!allow_commit_suspend && error.is_none()).then_some(barrier);
+                AsyncOutput::Suspended {
+                    suspended_results: Some(BuildSuspendResults::new(hooks, waker, error)),
+                    barrier,
+                }
+            }
         };
 
         self.write_back_build_results::<IS_NEW_INFLATE>(output, lane_pos, &handle);
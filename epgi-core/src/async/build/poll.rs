@@ -141,7 +141,7 @@ impl<E: FullElement> ElementNode<E> {
             .take()
             .expect("Async polling should not witness another polling taken the results");
 
-        let BuildSuspendResults { hooks, waker } = suspended_results;
+        let BuildSuspendResults { hooks, waker, .. } = suspended_results;
 
         waker.abort();
 
@@ -231,7 +231,7 @@ impl<E: FullElement> ElementNode<E> {
             .take()
             .expect("Async polling should not witness another polling taken the results");
 
-        let BuildSuspendResults { hooks, waker } = suspended_results;
+        let BuildSuspendResults { hooks, waker, .. } = suspended_results;
 
         waker.abort();
 
@@ -3,7 +3,9 @@ use std::borrow::Cow;
 use hashbrown::HashMap;
 
 use crate::{
-    foundation::{Arc, Asc, InlinableDwsizeVec, InlinableUsizeVec, Provide, TypeKey},
+    foundation::{
+        Arc, Asc, Error, ErrorKind, InlinableDwsizeVec, InlinableUsizeVec, Provide, TypeKey,
+    },
     sync::CommitBarrier,
     tree::{
         ArcElementContextNode, ElementLockHeldToken, ElementNode, FullElement, ProviderElementMap,
@@ -22,15 +24,11 @@ impl<E: FullElement> ElementNode<E> {
         if is_old_consumed_types {
             return Default::default();
         }
+        // Providers that are not found are never subscribed to
         let remove = old_consumed_types
             .iter()
             .filter(|consumed_type| !new_consumed_types.contains(consumed_type))
-            .map(|consumed_type| {
-                provider_map
-                    .get(consumed_type)
-                    .expect("Requested provider should exist")
-                    .clone()
-            })
+            .filter_map(|consumed_type| provider_map.get(consumed_type).cloned())
             .collect();
         let mut register = InlinableUsizeVec::<ArcElementContextNode>::default();
         let mut reserve = InlinableUsizeVec::<ArcElementContextNode>::default();
@@ -39,10 +37,9 @@ impl<E: FullElement> ElementNode<E> {
         for consumed_type in new_consumed_types.iter() {
             let is_old = old_consumed_types.contains(consumed_type);
             if !is_old {
-                let subscription = provider_map
-                    .get(consumed_type)
-                    .expect("Requested provider should exist")
-                    .clone();
+                let Some(subscription) = provider_map.get(consumed_type).cloned() else {
+                    continue;
+                };
                 if reserved_provider_values.contains_key(consumed_type) {
                     register.push(subscription);
                 } else {
@@ -74,21 +71,26 @@ impl<E: FullElement> ElementNode<E> {
         work_context: &mut Cow<'_, WorkContext>,
         barrier: &CommitBarrier,
         element_lock_held: &ElementLockHeldToken,
-    ) -> InlinableDwsizeVec<Arc<dyn Provide>> {
+    ) -> Result<InlinableDwsizeVec<Arc<dyn Provide>>, Error> {
         let is_old_consumed_types = std::ptr::eq(new_consumed_types, old_consumed_types);
 
+        // Keep reserving the rest even if a provider is not found, so that the reservations stay consistent with the SubscriptionDiff.
+        let mut not_found = None;
         let consumed_values = new_consumed_types
             .iter()
-            .map(|consumed_type| {
+            .filter_map(|consumed_type| {
                 if let Some(value) = work_context.recorded_provider_values.get(consumed_type) {
-                    return value.clone();
+                    return Some(value.clone());
                 }
 
-                let providing_element_context = self
-                    .context
-                    .provider_map
-                    .get(consumed_type)
-                    .expect("The context node of the requested provider should exist");
+                let Some(providing_element_context) = self.context.provider_map.get(consumed_type)
+                else {
+                    let default_value = consumed_type.default_value();
+                    if default_value.is_none() {
+                        not_found.get_or_insert(*consumed_type);
+                    }
+                    return default_value;
+                };
                 let value = if is_old_consumed_types || old_consumed_types.contains(consumed_type) {
                     providing_element_context
                         .provider_object
@@ -109,9 +111,12 @@ impl<E: FullElement> ElementNode<E> {
                     .to_mut()
                     .recorded_provider_values
                     .insert(consumed_type.clone(), value.clone());
-                value
+                Some(value)
             })
             .collect();
-        return consumed_values;
+        match not_found {
+            Some(type_key) => Err(ErrorKind::ProviderNotFound { type_key }.into()),
+            None => Ok(consumed_values),
+        }
     }
 }
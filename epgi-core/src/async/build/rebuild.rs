@@ -1,5 +1,8 @@
 use crate::{
-    foundation::{Arc, Asc, Container, ContainerOf, InlinableDwsizeVec, Protocol, Provide},
+    foundation::{
+        catch_build_panic, Arc, Asc, BuildError, Container, ContainerOf, Error, InlinableDwsizeVec,
+        Protocol, Provide,
+    },
     scheduler::get_current_scheduler,
     sync::{CommitBarrier, ImplCommitRenderObject},
     tree::{
@@ -60,7 +63,7 @@ impl<E: FullElement> ElementNode<E> {
         mut element: E,
         mut hooks: HooksWithEffects,
         children: ContainerOf<E::ChildContainer, ArcChildElementNode<E::ChildProtocol>>,
-        provider_values: InlinableDwsizeVec<Arc<dyn Provide>>,
+        provider_values: Result<InlinableDwsizeVec<Arc<dyn Provide>>, Error>,
         child_work_context: Asc<WorkContext>,
         handle: WorkHandle,
         barrier: CommitBarrier,
@@ -71,14 +74,26 @@ impl<E: FullElement> ElementNode<E> {
             element_context: &self.context,
             hook_context: HookContext::new_async(&mut hooks, HookContextMode::Rebuild),
         };
-        let results = E::perform_rebuild_element(
-            &mut element,
-            &widget,
-            &mut ctx,
-            provider_values,
-            children,
-            &mut nodes_needing_unmount,
-        );
+        let results = provider_values
+            .map_err(BuildError::from)
+            .and_then(|provider_values| {
+                catch_build_panic(|| {
+                    E::perform_rebuild_element(
+                        &mut element,
+                        widget,
+                        &mut ctx,
+                        provider_values,
+                        children,
+                        &mut nodes_needing_unmount,
+                    )
+                    // This is a rebuild. As a result, in the current design, we will always wait until the suspended node is resolved.
+                    // When we do commit, the node is guaranteed to be resolved. By then, we have already visited and finished building the new children.
+                    // Therefore, we do not need to visit the mainline children.
+                    // Note, this is different from the sync rebuild, which does need to visit the mainline children.
+                    .map_err(|(_children, err)| err)
+                })
+                .unwrap_or_else(|error| Err(error.into()))
+            });
 
         let lane_pos = child_work_context.lane_pos;
 
@@ -145,15 +160,13 @@ impl<E: FullElement> ElementNode<E> {
                     shuffle,
                 ))
             }
-            Err((children, err)) => {
-                // This is a rebuild. As a result, in the current design, we will always wait until the suspended node is resolved.
-                // When we do commit, the node is guaranteed to be resolved. By then, we have already visited and finished building the new children.
-                // Therefore, we do not need to visit the mainline children.
-                // Note, this is different from the sync rebuild, which does need to visit the mainline children.
-                drop(children);
+            Err(err) => {
+                let (waker, error) = err.into_parts(&self.context, lane_pos);
+                // A failed build will never resolve, so it must not hold back the batch.
+                let barrier = error.is_none().then_some(barrier);
                 AsyncOutput::Suspended {
-                    suspended_results: Some(BuildSuspendResults::new(hooks, err.waker)),
-                    barrier: Some(barrier),
+                    suspended_results: Some(BuildSuspendResults::new(hooks, waker, error)),
+                    barrier,
                 }
             }
         };
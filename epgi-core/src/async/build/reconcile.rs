@@ -3,7 +3,7 @@ use std::borrow::Cow;
 use futures::stream::Aborted;
 
 use crate::{
-    foundation::{Arc, Asc, ContainerOf, HktContainer, InlinableDwsizeVec, Provide},
+    foundation::{Arc, Asc, ContainerOf, Error, HktContainer, InlinableDwsizeVec, Provide},
     scheduler::{get_current_scheduler, LanePos},
     sync::CommitBarrier,
     tree::{
@@ -62,7 +62,7 @@ pub(crate) struct AsyncReconcile<E: ElementBase> {
     pub(super) handle: WorkHandle,
     pub(super) barrier: CommitBarrier,
     pub(super) old_widget: E::ArcWidget,
-    pub(super) provider_values: InlinableDwsizeVec<Arc<dyn Provide>>,
+    pub(super) provider_values: Result<InlinableDwsizeVec<Arc<dyn Provide>>, Error>,
     pub(super) variant: AsyncReconcileVariant<E>,
 }
 
@@ -1,7 +1,15 @@
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
 use event_listener::EventListener;
 
-use crate::tree::ArcSuspendWaker;
+use crate::{
+    scheduler::LanePos,
+    tree::{ArcElementContextNode, ArcSuspendWaker, SuspendWaker},
+};
+
+use super::{Arc, TypeKey};
 
+#[derive(Debug)]
 pub enum Error {
     Suspended,
     BuildError,
@@ -10,22 +18,158 @@ pub enum Error {
     Custom(Box<dyn std::error::Error + 'static + Send + Sync>),
 }
 
+#[derive(Debug)]
 pub enum ErrorKind {
     Suspended { listener: EventListener },
     BuildError,
     HookError,
 
-    ProviderNotFound,
+    ProviderNotFound { type_key: TypeKey },
     ProviderImmutable,
     ProviderTypeMismatch,
 }
 
-struct TracedError {
-    kind: ErrorKind,
-    payload: Option<Box<dyn std::error::Error + 'static + Send + Sync>>,
-    previous: Vec<TracedError>,
+/// An error from a failed build, traced through the elements it has passed on its way to an error boundary.
+#[derive(Debug)]
+pub struct TracedError {
+    pub error: Error,
+    trace: Vec<&'static str>,
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Suspended => f.write_str("build suspended"),
+            Error::BuildError => f.write_str("build failed"),
+            Error::HookError => f.write_str("hook failed"),
+            Error::RawError(kind) => kind.fmt(f),
+            Error::Custom(error) => error.fmt(f),
+        }
+    }
+}
+
+impl std::fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ErrorKind::Suspended { .. } => f.write_str("build suspended"),
+            ErrorKind::BuildError => f.write_str("build failed"),
+            ErrorKind::HookError => f.write_str("hook failed"),
+            ErrorKind::ProviderNotFound { type_key } => {
+                write!(f, "provider not found: {}", type_key.name())
+            }
+            ErrorKind::ProviderImmutable => f.write_str("provider is immutable"),
+            ErrorKind::ProviderTypeMismatch => f.write_str("provider type mismatch"),
+        }
+    }
+}
+
+impl From<ErrorKind> for Error {
+    fn from(kind: ErrorKind) -> Self {
+        Error::RawError(kind)
+    }
+}
+
+impl TracedError {
+    pub fn new(error: Error) -> Self {
+        Self {
+            error,
+            trace: Vec::new(),
+        }
+    }
+
+    /// Widget type names of the elements the error has passed through,
+    /// from the element that failed up to the boundary that caught it.
+    pub fn trace(&self) -> &[&'static str] {
+        &self.trace
+    }
+
+    pub(crate) fn push_trace(&mut self, widget_type_name: &'static str) {
+        self.trace.push(widget_type_name)
+    }
+}
+
+impl std::fmt::Display for TracedError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.error.fmt(f)
+    }
+}
+
+impl From<Error> for TracedError {
+    fn from(error: Error) -> Self {
+        Self::new(error)
+    }
+}
+
+/// A panic caught while building an element, carried by [`Error::Custom`].
+#[derive(Debug)]
+pub struct BuildPanic {
+    pub message: String,
+}
+
+impl std::fmt::Display for BuildPanic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "build panicked: {}", self.message)
+    }
+}
+
+impl std::error::Error for BuildPanic {}
+
+/// Run a build function, turning a panic inside into an [`Error`].
+///
+/// The caller is responsible for not touching any state the build function may have left half-updated.
+pub(crate) fn catch_build_panic<T>(build: impl FnOnce() -> T) -> Result<T, Error> {
+    catch_unwind(AssertUnwindSafe(build)).map_err(|payload| {
+        let message = if let Some(message) = payload.downcast_ref::<&str>() {
+            message.to_string()
+        } else if let Some(message) = payload.downcast_ref::<String>() {
+            message.clone()
+        } else {
+            "Box<dyn Any>".to_string()
+        };
+        Error::Custom(Box::new(BuildPanic { message }))
+    })
 }
 
-pub struct BuildSuspendedError {
-    pub(crate) waker: ArcSuspendWaker,
+/// The reason a build has returned without producing its children.
+pub enum BuildError {
+    /// The build is waiting on a future, and will be polled again once the future wakes it.
+    Suspended(ArcSuspendWaker),
+    /// The build has failed.
+    /// The subtree will be replaced by the fallback of the nearest [`ErrorBoundary`](crate::nodes::ErrorBoundary).
+    Failed(TracedError),
+}
+
+impl From<Error> for BuildError {
+    fn from(error: Error) -> Self {
+        BuildError::Failed(TracedError::new(error))
+    }
+}
+
+impl From<ErrorKind> for BuildError {
+    fn from(kind: ErrorKind) -> Self {
+        Error::from(kind).into()
+    }
+}
+
+impl BuildError {
+    pub(crate) fn is_failed(&self) -> bool {
+        matches!(self, BuildError::Failed(_))
+    }
+
+    /// Split into the waker to park the element with, and the error to report if the build has failed.
+    ///
+    /// A failed element is parked with a waker that never wakes, until the error boundary unmounts it.
+    pub(crate) fn into_parts(
+        self,
+        element_context: &ArcElementContextNode,
+        lane_pos: LanePos,
+    ) -> (ArcSuspendWaker, Option<TracedError>) {
+        match self {
+            BuildError::Suspended(waker) => (waker, None),
+            BuildError::Failed(error) => (
+                SuspendWaker::new_aborted(Arc::downgrade(element_context), lane_pos),
+                Some(error),
+            ),
+        }
+    }
 }
@@ -2,6 +2,7 @@ use std::{
     any::Any,
     fmt::Debug,
     hash::{Hash, Hasher},
+    sync::atomic::{AtomicUsize, Ordering::*},
 };
 
 pub trait Key: Any + Debug + Send + Sync {
@@ -54,11 +55,35 @@ pub struct ValueKey<T: Clone + Debug + PartialEq + Eq + Hash> {
     pub value: T,
 }
 
+/// A key that is unique across the entire tree.
+///
+/// When a widget with a global key is removed from one place and inserted into another in the same sync build,
+/// its element subtree is moved to the new place instead of being unmounted and inflated again,
+/// which preserves its hooks state and render objects.
+/// Moving an element under ancestors that provide a different set of values still remounts it.
+///
+/// Async builds, such as the ones started by transitions, never move elements.
+/// Moving a global key to a new place in an async build panics when the build commits.
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct GlobalKey {
     id: usize,
 }
 
+impl GlobalKey {
+    pub fn new() -> Self {
+        static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+        Self {
+            id: NEXT_ID.fetch_add(1, Relaxed),
+        }
+    }
+}
+
+impl Default for GlobalKey {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct UniqueKey {
     id: usize,
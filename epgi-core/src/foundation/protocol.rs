@@ -16,6 +16,10 @@ pub trait Protocol: std::fmt::Debug + Copy + Clone + Send + Sync + 'static {
     type Size: Clone + Debug + Send + Sync + 'static;
     // We cannot use reference to return intrinsic results, because we would still need to cache the result before returning.
     type Intrinsics: Intrinsics;
+    /// The baselines a parent can ask its laid-out children for.
+    ///
+    /// Protocols without baselines can use `()` and never report one.
+    type Baseline: Clone + Debug + Send + Sync;
     type Canvas: Canvas;
 
     fn position_in_shape(
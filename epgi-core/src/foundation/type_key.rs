@@ -3,10 +3,13 @@ use std::{
     hash::Hash,
 };
 
+use super::{Asc, Provide};
+
 #[derive(Clone, Copy, Debug, Eq)]
 pub struct TypeKey {
     id: TypeId,
     name: &'static str,
+    default: Option<fn() -> Asc<dyn Provide>>,
 }
 
 impl TypeKey {
@@ -14,12 +17,29 @@ impl TypeKey {
         Self {
             id: TypeId::of::<T>(),
             name: type_name::<T>(),
+            default: None,
+        }
+    }
+
+    /// A consumed type that does not need a provider.
+    ///
+    /// If no ancestor provides the type, the consumer reads its default value
+    /// instead of failing with [`ErrorKind::ProviderNotFound`](super::ErrorKind::ProviderNotFound).
+    pub fn of_or_default<T: Provide + Default>() -> Self {
+        Self {
+            default: Some(|| Asc::new(T::default())),
+            ..Self::of::<T>()
         }
     }
 
     pub fn name(&self) -> &'static str {
         self.name
     }
+
+    /// The value to read when no ancestor provides the type, if the type does not need a provider.
+    pub fn default_value(&self) -> Option<Asc<dyn Provide>> {
+        self.default.map(|default| default())
+    }
 }
 
 impl Hash for TypeKey {
@@ -3,7 +3,7 @@ use std::future::Future;
 use futures::FutureExt;
 
 use crate::{
-    foundation::{Arc, BuildSuspendedError, DependencyKey},
+    foundation::{Arc, BuildError, DependencyKey},
     tree::{ArcSuspendWaker, BuildContext, Effect, Hook, HookState, SuspendWaker},
 };
 
@@ -18,7 +18,7 @@ impl<'a> BuildContext<'a> {
         &mut self,
         compute_future: impl FnOnce(D) -> Fut,
         dependencies: D,
-    ) -> Result<&T, BuildSuspendedError> {
+    ) -> Result<&T, BuildError> {
         let waker = SuspendWaker::new(Arc::downgrade(self.element_context), self.lane_pos);
         let (hook_state, _index) = self.use_hook(FutureHook {
             dependencies,
@@ -35,7 +35,7 @@ impl<'a> BuildContext<'a> {
         &mut self,
         compute_future: impl FnOnce(D) -> Fut,
         dependencies: D,
-    ) -> Result<T, BuildSuspendedError> {
+    ) -> Result<T, BuildError> {
         self.use_future_ref(compute_future, dependencies)
             .map(Clone::clone)
     }
@@ -51,7 +51,7 @@ macro_rules! impl_use_future {
             &mut self,
             compute_future: impl FnOnce($($input_type),*) -> Fut,
             $($input: $input_type),*
-        ) -> Result<T, BuildSuspendedError> {
+        ) -> Result<T, BuildError> {
             self.use_future(|($($input),*)| compute_future($($input),*), ($($input),*))
         }
     };
@@ -134,7 +134,7 @@ where
     Fut: Future<Output = T> + Unpin + Send + Sync + 'static,
     T: State,
 {
-    fn poll(&mut self, waker: ArcSuspendWaker) -> Result<&T, BuildSuspendedError> {
+    fn poll(&mut self, waker: ArcSuspendWaker) -> Result<&T, BuildError> {
         let maybe_done = &mut self.maybe_done;
         match maybe_done {
             MaybeDone::Done(value) => Ok(value),
@@ -151,7 +151,7 @@ where
                         waker.abort();
                         Ok(value)
                     }
-                    std::task::Poll::Pending => Err(BuildSuspendedError { waker }),
+                    std::task::Poll::Pending => Err(BuildError::Suspended(waker)),
                 }
             }
         }
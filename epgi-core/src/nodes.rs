@@ -1,6 +1,12 @@
 mod component;
 pub use component::*;
 
+mod error_boundary;
+pub use error_boundary::*;
+
+mod keyed_subtree;
+pub use keyed_subtree::*;
+
 mod provider;
 pub use provider::*;
 
@@ -4,7 +4,7 @@ use epgi_macro::Declarative;
 use typed_builder::TypedBuilder;
 
 use crate::{
-    foundation::{Arc, Asc, BuildSuspendedError, InlinableDwsizeVec, Key, Protocol, Provide},
+    foundation::{Arc, Asc, BuildError, InlinableDwsizeVec, Key, Protocol, Provide},
     template::{ImplByTemplate, SingleChildElement, SingleChildElementTemplate},
     tree::{ArcAnyWidget, ArcChildWidget, ArcWidget, BuildContext, ElementBase, Widget, WidgetExt},
 };
@@ -16,6 +16,11 @@ use crate::{
 pub trait ComponentWidget<P: Protocol>:
     Widget<Element = ComponentElement<P>, ParentProtocol = P, ChildProtocol = P> + WidgetExt
 {
+    /// A component build can not fail with a typed error.
+    /// A panic inside it reaches the nearest [`ErrorBoundary`](crate::nodes::ErrorBoundary)
+    /// as a [`BuildPanic`](crate::foundation::BuildPanic) carrying only the panic message.
+    /// Widgets that need to fail with their own error should be a [`SuspendableComponentWidget`] instead,
+    /// and return [`BuildError::Failed`].
     fn build(&self, ctx: &mut BuildContext<'_>) -> ArcChildWidget<P>;
 }
 
@@ -56,7 +61,7 @@ impl<P: Protocol> SingleChildElement for ComponentElement<P> {
         widget: &Self::ArcWidget,
         ctx: &mut BuildContext<'_>,
         _provider_values: InlinableDwsizeVec<Arc<dyn Provide>>,
-    ) -> Result<ArcChildWidget<P>, BuildSuspendedError> {
+    ) -> Result<ArcChildWidget<P>, BuildError> {
         Ok(widget.build(ctx))
     }
 
@@ -68,7 +73,7 @@ impl<P: Protocol> SingleChildElement for ComponentElement<P> {
 pub trait SuspendableComponentWidget<P: Protocol>:
     Widget<Element = SuspendableComponentElement<P>, ParentProtocol = P, ChildProtocol = P> + WidgetExt
 {
-    fn build(&self, ctx: &mut BuildContext<'_>) -> Result<ArcChildWidget<P>, BuildSuspendedError>;
+    fn build(&self, ctx: &mut BuildContext<'_>) -> Result<ArcChildWidget<P>, BuildError>;
 }
 
 impl<P: Protocol> ArcWidget for Asc<dyn SuspendableComponentWidget<P>> {
@@ -108,7 +113,7 @@ impl<P: Protocol> SingleChildElement for SuspendableComponentElement<P> {
         widget: &Self::ArcWidget,
         ctx: &mut BuildContext<'_>,
         _provider_values: InlinableDwsizeVec<Arc<dyn Provide>>,
-    ) -> Result<ArcChildWidget<P>, BuildSuspendedError> {
+    ) -> Result<ArcChildWidget<P>, BuildError> {
         widget.build(ctx)
     }
 
@@ -163,7 +168,7 @@ where
 #[derive(Declarative, TypedBuilder)]
 #[builder(build_method(into=Asc<SuspendableBuilder<F, P>>))]
 pub struct SuspendableBuilder<
-    F: Fn(&mut BuildContext) -> Result<ArcChildWidget<P>, BuildSuspendedError> + Send + Sync + 'static,
+    F: Fn(&mut BuildContext) -> Result<ArcChildWidget<P>, BuildError> + Send + Sync + 'static,
     P: Protocol,
 > {
     pub builder: F,
@@ -172,10 +177,7 @@ pub struct SuspendableBuilder<
 impl<F, P> std::fmt::Debug for SuspendableBuilder<F, P>
 where
     P: Protocol,
-    F: Fn(&mut BuildContext) -> Result<ArcChildWidget<P>, BuildSuspendedError>
-        + Send
-        + Sync
-        + 'static,
+    F: Fn(&mut BuildContext) -> Result<ArcChildWidget<P>, BuildError> + Send + Sync + 'static,
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_tuple("Function").finish()
@@ -185,10 +187,7 @@ where
 impl<F, P> Widget for SuspendableBuilder<F, P>
 where
     P: Protocol,
-    F: Fn(&mut BuildContext) -> Result<ArcChildWidget<P>, BuildSuspendedError>
-        + Send
-        + Sync
-        + 'static,
+    F: Fn(&mut BuildContext) -> Result<ArcChildWidget<P>, BuildError> + Send + Sync + 'static,
 {
     type ParentProtocol = P;
     type ChildProtocol = P;
@@ -202,12 +201,9 @@ where
 impl<F, P> SuspendableComponentWidget<P> for SuspendableBuilder<F, P>
 where
     P: Protocol,
-    F: Fn(&mut BuildContext) -> Result<ArcChildWidget<P>, BuildSuspendedError>
-        + Send
-        + Sync
-        + 'static,
+    F: Fn(&mut BuildContext) -> Result<ArcChildWidget<P>, BuildError> + Send + Sync + 'static,
 {
-    fn build(&self, ctx: &mut BuildContext<'_>) -> Result<ArcChildWidget<P>, BuildSuspendedError> {
+    fn build(&self, ctx: &mut BuildContext<'_>) -> Result<ArcChildWidget<P>, BuildError> {
         (self.builder)(ctx)
     }
 }
@@ -5,8 +5,7 @@ use typed_builder::TypedBuilder;
 
 use crate::{
     foundation::{
-        Arc, Asc, AscProvideExt, BuildSuspendedError, InlinableDwsizeVec, Key, Protocol, Provide,
-        TypeKey,
+        Arc, Asc, AscProvideExt, BuildError, InlinableDwsizeVec, Key, Protocol, Provide, TypeKey,
     },
     template::{ImplByTemplate, SingleChildElement, SingleChildElementTemplate},
     tree::{ArcAnyWidget, ArcChildWidget, ArcWidget, BuildContext, ElementBase, Widget, WidgetExt},
@@ -66,7 +65,7 @@ impl<P: Protocol> SingleChildElement for ConsumerElement<P> {
         widget: &Self::ArcWidget,
         ctx: &mut BuildContext<'_>,
         provider_values: InlinableDwsizeVec<Arc<dyn Provide>>,
-    ) -> Result<ArcChildWidget<Self::ChildProtocol>, BuildSuspendedError> {
+    ) -> Result<ArcChildWidget<Self::ChildProtocol>, BuildError> {
         Ok(widget.build(ctx, provider_values))
     }
 
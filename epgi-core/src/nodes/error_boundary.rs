@@ -0,0 +1,190 @@
+use std::marker::PhantomData;
+
+use epgi_macro::Declarative;
+use typed_builder::TypedBuilder;
+
+use crate::{
+    foundation::{
+        Arc, ArrayContainer, Asc, BuildError, InlinableDwsizeVec, Key, Protocol, Provide,
+        TracedError,
+    },
+    hooks::SetState,
+    scheduler::JobBuilder,
+    template::{ImplByTemplate, ProxyRender, ProxyRenderTemplate},
+    tree::{
+        ArcChildElementNode, ArcChildWidget, BuildContext, ChildRenderObjectsUpdateCallback,
+        Element, ElementBase, ElementImpl, ElementReconcileItem, Widget,
+    },
+};
+
+pub type ErrorBoundaryFallback<P> =
+    Asc<dyn Fn(&TracedError, ErrorBoundaryReset) -> ArcChildWidget<P> + Send + Sync>;
+
+/// Catches failed builds in its subtree and shows a fallback instead.
+///
+/// A build fails when it panics, or when it returns [`BuildError::Failed`],
+/// which is also what a missing provider or an [`Error`](crate::foundation::Error) turns into.
+/// The failed subtree is unmounted, and the fallback is built from the error.
+/// The child is inflated again from scratch after the fallback calls [`ErrorBoundaryReset::reset`].
+///
+/// Failures inside the fallback are caught by the next boundary up.
+#[derive(Declarative, TypedBuilder)]
+#[builder(build_method(into=Asc<ErrorBoundary<P>>))]
+pub struct ErrorBoundary<P: Protocol> {
+    pub child: ArcChildWidget<P>,
+    #[builder(setter(transform=|op: impl Fn(&TracedError, ErrorBoundaryReset) -> ArcChildWidget<P> + Send + Sync + 'static| Asc::new(op) as _))]
+    pub fallback: ErrorBoundaryFallback<P>,
+    #[builder(default, setter(transform=|key: impl Key| Some(Box::new(key) as _)))]
+    pub key: Option<Box<dyn Key>>,
+}
+
+impl<P: Protocol> std::fmt::Debug for ErrorBoundary<P> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ErrorBoundary")
+            .field("child", &self.child)
+            .field("key", &self.key)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<P: Protocol> Widget for ErrorBoundary<P> {
+    type ParentProtocol = P;
+    type ChildProtocol = P;
+    type Element = ErrorBoundaryElement<P>;
+
+    fn key(&self) -> Option<&dyn Key> {
+        self.key.as_deref()
+    }
+
+    fn into_arc_widget(self: Asc<Self>) -> Asc<Self> {
+        self
+    }
+}
+
+/// Handed to the fallback of an [`ErrorBoundary`] to retry the failed child.
+#[derive(Clone, Debug)]
+pub struct ErrorBoundaryReset {
+    generation: usize,
+    set_generation: SetState<usize>,
+}
+
+impl ErrorBoundaryReset {
+    pub fn reset(&self, job_builder: &mut JobBuilder) {
+        self.set_generation.set(self.generation + 1, job_builder);
+    }
+}
+
+#[derive(Clone)]
+pub struct ErrorBoundaryElement<P: Protocol> {
+    generation: usize,
+    set_generation: SetState<usize>,
+    _phantom: PhantomData<P>,
+}
+
+impl<P: Protocol> ErrorBoundaryElement<P> {
+    pub(crate) fn reset_handle(&self) -> ErrorBoundaryReset {
+        ErrorBoundaryReset {
+            generation: self.generation,
+            set_generation: self.set_generation.clone(),
+        }
+    }
+}
+
+impl<P: Protocol> ElementBase for ErrorBoundaryElement<P> {
+    type ArcWidget = Asc<ErrorBoundary<P>>;
+
+    type ParentProtocol = P;
+    type ChildProtocol = P;
+    type ChildContainer = ArrayContainer<1>;
+
+    fn perform_rebuild_element(
+        &mut self,
+        widget: &Self::ArcWidget,
+        ctx: &mut BuildContext<'_>,
+        _provider_values: InlinableDwsizeVec<Arc<dyn Provide>>,
+        [child]: [ArcChildElementNode<P>; 1],
+        nodes_needing_unmount: &mut InlinableDwsizeVec<ArcChildElementNode<P>>,
+    ) -> Result<
+        (
+            [ElementReconcileItem<P>; 1],
+            Option<ChildRenderObjectsUpdateCallback<Self::ChildContainer, Self::ChildProtocol>>,
+        ),
+        ([ArcChildElementNode<P>; 1], BuildError),
+    > {
+        let (generation, set_generation) = ctx.use_state(0usize);
+        self.set_generation = set_generation;
+        let error_catcher = ctx
+            .element_context
+            .error_catcher
+            .as_ref()
+            .expect("An ErrorBoundary should have an error catcher in its element context");
+
+        if generation != self.generation {
+            self.generation = generation;
+            if error_catcher.handled().is_some() {
+                // Retry with a fresh child, even if the fallback could be updated into the child.
+                error_catcher.reset();
+                nodes_needing_unmount.push(child);
+                return Ok((
+                    [ElementReconcileItem::new_inflate(widget.child.clone())],
+                    None,
+                ));
+            }
+        }
+
+        let child_widget = match error_catcher.handled() {
+            Some(error) => (widget.fallback)(&error, self.reset_handle()),
+            None => widget.child.clone(),
+        };
+        let item = match child.can_rebuild_with(child_widget) {
+            Ok(pair) => pair,
+            Err((child, child_widget)) => {
+                nodes_needing_unmount.push(child);
+                ElementReconcileItem::new_inflate(child_widget)
+            }
+        };
+        Ok(([item], None))
+    }
+
+    fn perform_inflate_element(
+        widget: &Self::ArcWidget,
+        ctx: &mut BuildContext<'_>,
+        _provider_values: InlinableDwsizeVec<Arc<dyn Provide>>,
+    ) -> Result<(Self, [ArcChildWidget<P>; 1]), BuildError> {
+        let (generation, set_generation) = ctx.use_state(0usize);
+        Ok((
+            Self {
+                generation,
+                set_generation,
+                _phantom: PhantomData,
+            },
+            [widget.child.clone()],
+        ))
+    }
+}
+
+impl<P: Protocol> Element for ErrorBoundaryElement<P> {
+    type Impl = ElementImpl<true, false>;
+}
+
+pub struct RenderErrorBoundary<P: Protocol> {
+    phantom_data: PhantomData<P>,
+}
+
+impl<P: Protocol> RenderErrorBoundary<P> {
+    pub(crate) fn new() -> Self {
+        Self {
+            phantom_data: PhantomData,
+        }
+    }
+}
+
+impl<P: Protocol> ImplByTemplate for RenderErrorBoundary<P> {
+    type Template = ProxyRenderTemplate;
+}
+
+impl<P: Protocol> ProxyRender for RenderErrorBoundary<P> {
+    type Protocol = P;
+
+    const NOOP_DETACH: bool = true;
+}
@@ -0,0 +1,38 @@
+use epgi_macro::Declarative;
+use typed_builder::TypedBuilder;
+
+use crate::{
+    foundation::{Asc, Key, Protocol},
+    tree::{ArcChildWidget, BuildContext, ElementBase, Widget},
+};
+
+use super::{ComponentElement, ComponentWidget};
+
+/// Attach a key to a subtree whose root widget does not carry a key by itself.
+#[derive(Debug, Declarative, TypedBuilder)]
+#[builder(build_method(into=Asc<KeyedSubtree<P>>))]
+pub struct KeyedSubtree<P: Protocol> {
+    #[builder(setter(transform = |key: impl Key| Box::new(key) as Box<dyn Key>))]
+    pub key: Box<dyn Key>,
+    pub child: ArcChildWidget<P>,
+}
+
+impl<P: Protocol> Widget for KeyedSubtree<P> {
+    type ParentProtocol = P;
+    type ChildProtocol = P;
+    type Element = ComponentElement<P>;
+
+    fn key(&self) -> Option<&dyn Key> {
+        Some(self.key.as_ref())
+    }
+
+    fn into_arc_widget(self: Asc<Self>) -> <Self::Element as ElementBase>::ArcWidget {
+        self
+    }
+}
+
+impl<P: Protocol> ComponentWidget<P> for KeyedSubtree<P> {
+    fn build(&self, _ctx: &mut BuildContext<'_>) -> ArcChildWidget<P> {
+        self.child.clone()
+    }
+}
@@ -4,7 +4,7 @@ use epgi_macro::Declarative;
 use typed_builder::TypedBuilder;
 
 use crate::{
-    foundation::{Arc, Asc, BuildSuspendedError, InlinableDwsizeVec, Protocol, Provide},
+    foundation::{Arc, Asc, BuildError, InlinableDwsizeVec, Protocol, Provide},
     template::{
         ImplByTemplate, SingleChildElement, SingleChildElementTemplate, SingleChildProvideElement,
     },
@@ -71,7 +71,7 @@ impl<T: Provide, P: Protocol> SingleChildElement for ProviderElement<T, P> {
         widget: &Self::ArcWidget,
         _ctx: &mut BuildContext<'_>,
         _provider_values: InlinableDwsizeVec<Arc<dyn Provide>>,
-    ) -> Result<ArcChildWidget<P>, BuildSuspendedError> {
+    ) -> Result<ArcChildWidget<P>, BuildError> {
         Ok(widget.child.clone())
     }
 
@@ -1,303 +1,244 @@
-// use crate::foundation::{
-//     Arc, Aweak, BuildSuspendedError, Canvas, InlinableDwsizeVec, LayerProtocol, Never,
-//     PaintContext, Protocol, Provide, SyncMutex,
-// };
-
-// use crate::template::{ImplByTemplate, ProxyElement, ProxyElementTemplate};
-// use crate::tree::{
-//     ArcChildElementNode, ArcChildRenderObject, ArcChildWidget, ArcElementContextNode, BuildContext,
-//     ComposableChildLayer, Element, ElementReconcileItem, LayerCompositionConfig, LayerPaint,
-//     PaintResults, Render, RenderObject, Widget,
-// };
-
-// #[derive(Debug)]
-// pub struct RepaintBoundary<P: LayerProtocol> {
-//     child: ArcChildWidget<P>,
-// }
-
-// impl<P: LayerProtocol> Widget for RepaintBoundary<P> {
-//     type ParentProtocol = P;
-//     type ChildProtocol = P;
-//     type Element = RepaintBoundaryElement<P>;
-
-//     fn into_arc_widget(self: Arc<Self>) -> Self::ArcWidget {
-//         self
-//     }
-// }
-
-// #[derive(Clone)]
-// pub struct RepaintBoundaryElement<P: LayerProtocol> {
-//     child: ArcChildElementNode<P>,
-// }
-
-// impl<P: LayerProtocol> ImplByTemplate for RepaintBoundaryElement<P> {
-//     type Template = ProxyElementTemplate<Self, true, false>;
-// }
-
-// impl<P: LayerProtocol> ProxyElement for RepaintBoundaryElement<P> {
-//     type Protocol = P;
-
-//     type ArcWidget = Arc<RepaintBoundary<P>>;
-
-//     fn perform_rebuild_element(
-//         &mut self,
-//         widget: &Self::ArcWidget,
-//         ctx: BuildContext<'_>,
-//         provider_values: InlinableDwsizeVec<Arc<dyn Provide>>,
-//         child: ArcChildElementNode<P>,
-//         nodes_needing_unmount: &mut InlinableDwsizeVec<ArcChildElementNode<P>>,
-//     ) -> Result<ElementReconcileItem<P>, (ArcChildElementNode<P>, BuildSuspendedError)> {
-//         todo!()
-//     }
-
-//     fn perform_inflate_element(
-//         widget: &Self::ArcWidget,
-//         ctx: BuildContext<'_>,
-//         provider_values: InlinableDwsizeVec<Arc<dyn Provide>>,
-//     ) -> Result<(Self, ArcChildWidget<P>), BuildSuspendedError> {
-//         todo!()
-//     }
-// }
-
-// pub struct RenderRepaintBoundary<P: LayerProtocol> {
-//     layer: Arc<RepaintBoundaryLayer<P>>,
-//     pub(crate) child: ArcChildRenderObject<P>,
-// }
-
-// impl<P: LayerProtocol> ImplByTemplate for RenderRepaintBoundary<P> {
-//     type Template = Proxy;
-// }
-
-// impl<P> Render for RenderRepaintBoundary<P>
-// where
-//     P: LayerProtocol,
-// {
-//     type Element = RepaintBoundaryElement<P>;
-
-//     type ChildIter = [ArcChildRenderObject<P>; 1];
-
-//     fn children(&self) -> Self::ChildIter {
-//         todo!()
-//     }
-
-//     fn try_create_render_object_from_element(
-//         element: &Self::Element,
-//         _widget: &Self::ArcWidget,
-//         context: &AscRenderContextNode,
-//     ) -> Option<Self> {
-//         let child = element.child.get_current_subtree_render_object()?;
-//         assert!(
-//             context.is_repaint_boundary(),
-//             concat!(
-//                 "Repaint boundaries must be registered in its RenderContextNode. \n",
-//                 "If this assertion failed, you have encountered a framework bug."
-//             )
-//         );
-//         let layer = Arc::new(RepaintBoundaryLayer::new(
-//             context.nearest_repaint_boundary.clone(),
-//             child.clone(),
-//         ));
-//         Some(Self { layer, child })
-//     }
-
-//     fn update_render_object(&mut self, _widget: &Self::ArcWidget) -> RenderObjectUpdateResult {
-//         RenderObjectUpdateResult::None
-//     }
-
-//     fn try_update_render_object_children(&mut self, element: &Self::Element) -> Result<(), ()> {
-//         let Some(child) = element.child.get_current_subtree_render_object() else {
-//             return Err(());
-//         };
-//         self.layer.update_child_render_object(child.clone());
-//         self.child = child;
-//         Ok(())
-//     }
-
-//     const NOOP_DETACH: bool = true;
-
-//     type LayoutMemo = ();
-
-//     fn perform_layout<'a, 'layout>(
-//         &'a self,
-//         constraints: &'a <Self::ParentProtocol as Protocol>::Constraints,
-//     ) -> (<Self::ParentProtocol as Protocol>::Size, Self::LayoutMemo) {
-//         todo!()
-//     }
-
-//     fn perform_paint(
-//         &self,
-//         size: &<Self::ParentProtocol as Protocol>::Size,
-//         transform: &<Self::ParentProtocol as Protocol>::Transform,
-//         memo: &Self::LayoutMemo,
-//         paint_ctx: &mut impl PaintContext<Canvas = <Self::ParentProtocol as Protocol>::Canvas>,
-//     ) {
-//         todo!()
-//     }
-// }
-
-// impl<P: Protocol> LayerPaint for RenderRepaintBoundary<P>
-// where
-//     P: LayerProtocol,
-// {
-//     fn get_layer(&self) -> ArcLayerOf<Self> {
-//         self.layer.clone() as _
-//         // if let Some(layer) = &self.layer {
-//         //     return layer.clone() as _;
-//         // }
-
-//         // assert!(
-//         //     node.context.is_repaint_boundary(),
-//         //     concat!(
-//         //         "Repaint boundaries must be registered in its RenderContextNode. \n",
-//         //         "If this assertion failed, you have encountered a framework bug."
-//         //     )
-//         // );
-//         // let layer = Arc::new(RepaintBoundaryLayer {
-//         //     render_object: Arc::downgrade(node),
-//         //     context: node.context.nearest_repaint_boundary.clone(),
-//         //     inner: SyncMutex::new(RepaintBoundaryLayerInner { paint_cache: None }),
-//         // });
-//         // self.layer = Some(layer.clone());
-//         // return layer;
-//     }
-
-//     fn get_canvas_transform_ref(
-//         transform: &<Self::ParentProtocol as Protocol>::Transform,
-//     ) -> &<<Self::ParentProtocol as Protocol>::Canvas as Canvas>::Transform {
-//         transform
-//     }
-
-//     fn get_canvas_transform(
-//         transform: <Self::ParentProtocol as Protocol>::Transform,
-//     ) -> <<Self::ParentProtocol as Protocol>::Canvas as Canvas>::Transform {
-//         transform
-//     }
-// }
-
-// pub struct RepaintBoundaryLayer<P: LayerProtocol> {
-//     pub context: AscLayerContextNode,
-//     pub inner: SyncMutex<RepaintBoundaryLayerInner<P>>,
-// }
-
-// pub struct RepaintBoundaryLayerInner<P: LayerProtocol> {
-//     /// This field is nullable because we temporarily share implementation with RootLayer
-//     child_render_object: ArcChildRenderObject<P>,
-//     paint_cache: Option<PaintResults<P::Canvas>>,
-// }
-
-// impl<P> RepaintBoundaryLayer<P>
-// where
-//     P: LayerProtocol,
-// {
-//     pub fn new(context: AscLayerContextNode, child_render_object: ArcChildRenderObject<P>) -> Self {
-//         Self {
-//             context,
-//             inner: SyncMutex::new(RepaintBoundaryLayerInner {
-//                 child_render_object,
-//                 paint_cache: None,
-//             }),
-//         }
-//     }
-
-//     pub fn update_child_render_object(&self, child_render_object: ArcChildRenderObject<P>) {
-//         let mut inner = self.inner.lock();
-//         inner.child_render_object = child_render_object;
-//         inner.paint_cache = None;
-//     }
-// }
-
-// impl<P> Layer for RepaintBoundaryLayer<P>
-// where
-//     P: LayerProtocol,
-// {
-//     type ParentCanvas = P::Canvas;
-
-//     type ChildCanvas = P::Canvas;
-
-//     fn context(&self) -> &AscLayerContextNode {
-//         &self.context
-//     }
-
-//     fn composite(
-//         &self,
-//         encoding: &mut <Self::ParentCanvas as Canvas>::Encoding,
-//         composition_config: &LayerCompositionConfig<Self::ParentCanvas>,
-//     ) {
-//         let inner = self.inner.lock();
-//         let paint_cache = inner
-//             .paint_cache
-//             .as_ref()
-//             .expect("A layer can only be composited after it has finished painting");
-
-//         paint_cache.composite_to(encoding, composition_config)
-//     }
-
-//     fn repaint(&self) {
-//         let mut inner = self.inner.lock();
-//         if !self.context.needs_paint() && inner.paint_cache.is_some() {
-//             return;
-//         }
-//         inner.paint_cache = Some(P::Canvas::paint_render_object(
-//             inner.child_render_object.as_ref(),
-//         ));
-//     }
-
-//     fn painted_children(&self) -> Option<Vec<ArcAnyLayer>> {
-//         let inner = self.inner.lock();
-//         let paint_cache = inner.paint_cache.as_ref()?;
-//         let children = paint_cache
-//             .structured_children
-//             .iter()
-//             .filter_map(|child| match child {
-//                 ChildLayerOrFragment::Fragment(_) => None,
-//                 ChildLayerOrFragment::Layer(ComposableChildLayer { layer, .. }) => {
-//                     Some(layer.clone().as_arc_any_layer())
-//                 }
-//             })
-//             .chain(
-//                 paint_cache
-//                     .detached_children
-//                     .iter()
-//                     .map(|child| child.layer.clone()),
-//             )
-//             .collect::<Vec<_>>();
-//         Some(children)
-//     }
-
-//     fn composited_children(&self) -> Option<Vec<ComposableChildLayer<Self::ChildCanvas>>> {
-//         let inner = self.inner.lock();
-//         let paint_cache = inner.paint_cache.as_ref()?;
-//         let composition_results = paint_cache.composition_results.as_ref()?;
-//         let children = paint_cache
-//             .structured_children
-//             .iter()
-//             .filter_map(|child| match child {
-//                 ChildLayerOrFragment::Fragment(_) => None,
-//                 ChildLayerOrFragment::Layer(layer) => Some(layer.clone().as_arc_any_layer()),
-//             })
-//             .chain(
-//                 paint_cache
-//                     .detached_children
-//                     .iter()
-//                     .map(|child| child.layer.clone()),
-//             )
-//             .collect::<Vec<_>>();
-//         Some(children)
-//     }
-
-//     fn as_arc_child_layer(
-//         self: Arc<Self>,
-//     ) -> Arc<dyn ChildLayer<ParentCanvas = Self::ParentCanvas>> {
-//         self
-//     }
-
-//     fn as_arc_parent_layer(
-//         self: Arc<Self>,
-//     ) -> Arc<dyn ParentLayer<ChildCanvas = Self::ChildCanvas>> {
-//         self
-//     }
-
-//     fn as_arc_any_layer(self: Arc<Self>) -> Arc<dyn AnyLayer> {
-//         self
-//     }
-// }
+use std::{
+    marker::PhantomData,
+    sync::atomic::{AtomicUsize, Ordering::*},
+};
+
+use epgi_macro::Declarative;
+use typed_builder::TypedBuilder;
+
+use crate::{
+    foundation::{
+        Arc, ArrayContainer, Asc, BuildError, Canvas, InlinableDwsizeVec, Key, LayerProtocol,
+        Provide, Transform,
+    },
+    template::{
+        ImplByTemplate, SingleChildElement, SingleChildElementTemplate, SingleChildRenderElement,
+    },
+    tree::{
+        ArcChildRenderObject, ArcChildWidget, BuildContext, CachedComposite,
+        ChildLayerProducingIterator, HitTest, HitTestContext, LayerCompositionConfig, LayerPaint,
+        Layout, PaintResults, RecordedChildLayer, Render, RenderAction, RenderBase, RenderImpl,
+        Widget,
+    },
+};
+
+/// Paints its subtree into a separate layer.
+///
+/// A repaint inside the subtree stops at the boundary. Ancestors only recomposite the cached layer
+/// instead of repainting. A repaint of an ancestor also reuses the cached layer.
+///
+/// Pass a [`RepaintCounter`] to find out how often the subtree actually repainted.
+#[derive(Declarative, TypedBuilder)]
+#[builder(build_method(into=Asc<RepaintBoundary<P>>))]
+pub struct RepaintBoundary<P: LayerProtocol> {
+    pub child: ArcChildWidget<P>,
+    #[builder(default, setter(strip_option))]
+    pub counter: Option<RepaintCounter>,
+    #[builder(default, setter(transform=|key: impl Key| Some(Box::new(key) as _)))]
+    pub key: Option<Box<dyn Key>>,
+}
+
+impl<P: LayerProtocol> std::fmt::Debug for RepaintBoundary<P> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RepaintBoundary")
+            .field("child", &self.child)
+            .field("counter", &self.counter)
+            .field("key", &self.key)
+            .finish()
+    }
+}
+
+impl<P: LayerProtocol> Widget for RepaintBoundary<P> {
+    type ParentProtocol = P;
+    type ChildProtocol = P;
+    type Element = RepaintBoundaryElement<P>;
+
+    fn key(&self) -> Option<&dyn Key> {
+        self.key.as_deref()
+    }
+
+    fn into_arc_widget(self: Asc<Self>) -> Asc<Self> {
+        self
+    }
+}
+
+/// Counts how many times a [`RepaintBoundary`] has repainted its subtree.
+///
+/// Clones share the same count, so a handle kept outside the tree can be read between frames.
+#[derive(Clone, Default, Debug)]
+pub struct RepaintCounter(Arc<AtomicUsize>);
+
+impl RepaintCounter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn count(&self) -> usize {
+        self.0.load(Relaxed)
+    }
+
+    fn increment(&self) {
+        self.0.fetch_add(1, Relaxed);
+    }
+}
+
+#[derive(Clone)]
+pub struct RepaintBoundaryElement<P: LayerProtocol> {
+    _phantom: PhantomData<P>,
+}
+
+impl<P: LayerProtocol> ImplByTemplate for RepaintBoundaryElement<P> {
+    type Template = SingleChildElementTemplate<true, false>;
+}
+
+impl<P: LayerProtocol> SingleChildElement for RepaintBoundaryElement<P> {
+    type ParentProtocol = P;
+    type ChildProtocol = P;
+    type ArcWidget = Asc<RepaintBoundary<P>>;
+
+    fn get_child_widget(
+        _element: Option<&mut Self>,
+        widget: &Self::ArcWidget,
+        _ctx: &mut BuildContext<'_>,
+        _provider_values: InlinableDwsizeVec<Arc<dyn Provide>>,
+    ) -> Result<ArcChildWidget<P>, BuildError> {
+        Ok(widget.child.clone())
+    }
+
+    fn create_element(_widget: &Self::ArcWidget) -> Self {
+        Self {
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<P: LayerProtocol> SingleChildRenderElement for RepaintBoundaryElement<P> {
+    type Render = RenderRepaintBoundary<P>;
+
+    fn create_render(&self, widget: &Self::ArcWidget) -> Self::Render {
+        RenderRepaintBoundary {
+            counter: widget.counter.clone(),
+            _phantom: PhantomData,
+        }
+    }
+
+    fn update_render(render: &mut Self::Render, widget: &Self::ArcWidget) -> Option<RenderAction> {
+        render.counter = widget.counter.clone();
+        None
+    }
+}
+
+pub struct RenderRepaintBoundary<P: LayerProtocol> {
+    counter: Option<RepaintCounter>,
+    _phantom: PhantomData<P>,
+}
+
+impl<P: LayerProtocol> RenderBase for RenderRepaintBoundary<P> {
+    type ParentProtocol = P;
+    type ChildProtocol = P;
+    type ChildContainer = ArrayContainer<1>;
+
+    type LayoutMemo = ();
+
+    const NOOP_DETACH: bool = true;
+
+    fn compute_intrinsics(
+        &mut self,
+        [child]: &[ArcChildRenderObject<P>; 1],
+        intrinsics: &mut P::Intrinsics,
+    ) {
+        child.get_intrinsics(intrinsics)
+    }
+
+    fn compute_distance_to_baseline(
+        &self,
+        _size: &P::Size,
+        _memo: &(),
+        [child]: &[ArcChildRenderObject<P>; 1],
+        baseline: &P::Baseline,
+    ) -> Option<f32> {
+        child.get_distance_to_baseline(baseline)
+    }
+}
+
+impl<P: LayerProtocol> Render for RenderRepaintBoundary<P> {
+    type Impl = RenderImpl<false, true, true, false>;
+}
+
+impl<P: LayerProtocol> Layout for RenderRepaintBoundary<P> {
+    fn perform_layout(
+        &mut self,
+        constraints: &P::Constraints,
+        [child]: &[ArcChildRenderObject<P>; 1],
+    ) -> (P::Size, ()) {
+        (child.layout_use_size(constraints), ())
+    }
+}
+
+impl<P: LayerProtocol> LayerPaint for RenderRepaintBoundary<P> {
+    fn paint_layer(&self, children: &[ArcChildRenderObject<P>; 1]) -> PaintResults<P::Canvas> {
+        if let Some(counter) = &self.counter {
+            counter.increment();
+        }
+        P::Canvas::paint_render_objects(children.clone())
+    }
+
+    fn transform_config(
+        self_config: &LayerCompositionConfig<P::Canvas>,
+        child_config: &LayerCompositionConfig<P::Canvas>,
+    ) -> LayerCompositionConfig<P::Canvas> {
+        LayerCompositionConfig {
+            transform: Transform::mul(&self_config.transform, &child_config.transform),
+        }
+    }
+}
+
+impl<P: LayerProtocol> CachedComposite for RenderRepaintBoundary<P> {
+    type CompositionMemo = Arc<<P::Canvas as Canvas>::Encoding>;
+
+    fn composite_into_memo(
+        &self,
+        child_iterator: &mut ChildLayerProducingIterator<P::Canvas>,
+    ) -> Self::CompositionMemo {
+        let mut result = P::Canvas::new_encoding();
+        use crate::tree::ChildLayerOrFragmentRef::*;
+        child_iterator.for_each(|child| match child {
+            Fragment(encoding) => {
+                P::Canvas::composite_encoding(&mut result, encoding, None);
+                Vec::new()
+            }
+            Child(layer) | AdoptedChild(layer) => {
+                layer.layer.composite_to(&mut result, &layer.config)
+            }
+        });
+        Arc::new(result)
+    }
+
+    fn composite_from_cache_to(
+        &self,
+        encoding: &mut <P::Canvas as Canvas>::Encoding,
+        memo: &Self::CompositionMemo,
+        composition_config: &LayerCompositionConfig<P::Canvas>,
+    ) {
+        P::Canvas::composite_encoding(encoding, memo, composition_config.transform())
+    }
+}
+
+impl<P: LayerProtocol> HitTest for RenderRepaintBoundary<P> {
+    fn hit_test_children(
+        &self,
+        ctx: &mut HitTestContext<P::Canvas>,
+        _size: &P::Size,
+        offset: &P::Offset,
+        _memo: &(),
+        [child]: &[ArcChildRenderObject<P>; 1],
+        _adopted_children: &[RecordedChildLayer<P::Canvas>],
+    ) -> bool {
+        // The subtree was painted at the layer origin.
+        let paint_transform = P::offset_layer_transform(
+            offset,
+            &<<P::Canvas as Canvas>::Transform as Transform<_>>::identity(),
+        );
+        ctx.hit_test_with_paint_transform(child.clone(), &paint_transform)
+    }
+}
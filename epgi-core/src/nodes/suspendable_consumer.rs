@@ -5,8 +5,7 @@ use typed_builder::TypedBuilder;
 
 use crate::{
     foundation::{
-        Arc, Asc, AscProvideExt, BuildSuspendedError, InlinableDwsizeVec, Key, Protocol, Provide,
-        TypeKey,
+        Arc, Asc, AscProvideExt, BuildError, InlinableDwsizeVec, Key, Protocol, Provide, TypeKey,
     },
     template::{ImplByTemplate, SingleChildElement, SingleChildElementTemplate},
     tree::{ArcAnyWidget, ArcChildWidget, ArcWidget, BuildContext, ElementBase, Widget, WidgetExt},
@@ -22,7 +21,7 @@ pub trait SuspendableConsumerWidget<P: Protocol>:
         &self,
         ctx: &mut BuildContext,
         provider_values: InlinableDwsizeVec<Arc<dyn Provide>>,
-    ) -> Result<ArcChildWidget<P>, BuildSuspendedError>;
+    ) -> Result<ArcChildWidget<P>, BuildError>;
 }
 
 impl<P: Protocol> ArcWidget for Asc<dyn SuspendableConsumerWidget<P>> {
@@ -66,7 +65,7 @@ impl<P: Protocol> SingleChildElement for SuspendableConsumerElement<P> {
         widget: &Self::ArcWidget,
         ctx: &mut BuildContext<'_>,
         provider_values: InlinableDwsizeVec<Arc<dyn Provide>>,
-    ) -> Result<ArcChildWidget<Self::ChildProtocol>, BuildSuspendedError> {
+    ) -> Result<ArcChildWidget<Self::ChildProtocol>, BuildError> {
         widget.build(ctx, provider_values)
     }
 
@@ -79,10 +78,7 @@ impl<P: Protocol> SingleChildElement for SuspendableConsumerElement<P> {
 #[builder(build_method(into=Asc<SuspendableConsumer<T, F, P>>))]
 pub struct SuspendableConsumer<
     T: Provide,
-    F: Fn(&mut BuildContext, Asc<T>) -> Result<ArcChildWidget<P>, BuildSuspendedError>
-        + Send
-        + Sync
-        + 'static,
+    F: Fn(&mut BuildContext, Asc<T>) -> Result<ArcChildWidget<P>, BuildError> + Send + Sync + 'static,
     P: Protocol,
 > {
     pub builder: F,
@@ -95,7 +91,7 @@ pub struct SuspendableConsumer<
 impl<T, F, P> std::fmt::Debug for SuspendableConsumer<T, F, P>
 where
     T: Provide,
-    F: Fn(&mut BuildContext, Asc<T>) -> Result<ArcChildWidget<P>, BuildSuspendedError>
+    F: Fn(&mut BuildContext, Asc<T>) -> Result<ArcChildWidget<P>, BuildError>
         + Send
         + Sync
         + 'static,
@@ -111,7 +107,7 @@ where
 impl<T, F, P> Widget for SuspendableConsumer<T, F, P>
 where
     T: Provide,
-    F: Fn(&mut BuildContext, Asc<T>) -> Result<ArcChildWidget<P>, BuildSuspendedError>
+    F: Fn(&mut BuildContext, Asc<T>) -> Result<ArcChildWidget<P>, BuildError>
         + Send
         + Sync
         + 'static,
@@ -129,7 +125,7 @@ where
 impl<T, F, P> SuspendableConsumerWidget<P> for SuspendableConsumer<T, F, P>
 where
     T: Provide,
-    F: Fn(&mut BuildContext, Asc<T>) -> Result<ArcChildWidget<P>, BuildSuspendedError>
+    F: Fn(&mut BuildContext, Asc<T>) -> Result<ArcChildWidget<P>, BuildError>
         + Send
         + Sync
         + 'static,
@@ -143,7 +139,7 @@ where
         &self,
         ctx: &mut BuildContext,
         provider_values: InlinableDwsizeVec<Arc<dyn Provide>>,
-    ) -> Result<ArcChildWidget<P>, BuildSuspendedError> {
+    ) -> Result<ArcChildWidget<P>, BuildError> {
         assert_eq!(
             provider_values.len(),
             1,
@@ -167,7 +163,7 @@ macro_rules! impl_multi_suspendable_consumer {
         #[builder(build_method(into=Asc<$name<$($t),*, F, P>>))]
         pub struct $name<
             $($t: Provide),*,
-            F: Fn(&mut BuildContext, $(Asc<$t>),*) -> Result<ArcChildWidget<P>, BuildSuspendedError> + Send + Sync + 'static,
+            F: Fn(&mut BuildContext, $(Asc<$t>),*) -> Result<ArcChildWidget<P>, BuildError> + Send + Sync + 'static,
             P: Protocol,
         > {
             pub builder: F,
@@ -180,7 +176,7 @@ macro_rules! impl_multi_suspendable_consumer {
         impl<$($t),*, F, P> std::fmt::Debug for $name<$($t),*, F, P>
         where
             $($t: Provide),*,
-            F: Fn(&mut BuildContext, $(Asc<$t>),*) -> Result<ArcChildWidget<P>, BuildSuspendedError> + Send + Sync + 'static,
+            F: Fn(&mut BuildContext, $(Asc<$t>),*) -> Result<ArcChildWidget<P>, BuildError> + Send + Sync + 'static,
             P: Protocol,
         {
             fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -193,7 +189,7 @@ macro_rules! impl_multi_suspendable_consumer {
         impl<$($t),*, F, P> Widget for $name<$($t),*, F, P>
         where
             $($t: Provide),*,
-            F: Fn(&mut BuildContext, $(Asc<$t>),*) -> Result<ArcChildWidget<P>, BuildSuspendedError> + Send + Sync + 'static,
+            F: Fn(&mut BuildContext, $(Asc<$t>),*) -> Result<ArcChildWidget<P>, BuildError> + Send + Sync + 'static,
             P: Protocol,
         {
             type ParentProtocol = P;
@@ -210,7 +206,7 @@ macro_rules! impl_multi_suspendable_consumer {
         impl<$($t),*, F, P> SuspendableConsumerWidget<P> for $name<$($t),*, F, P>
         where
             $($t: Provide),*,
-            F: Fn(&mut BuildContext, $(Asc<$t>),*) -> Result<ArcChildWidget<P>, BuildSuspendedError> + Send + Sync + 'static,
+            F: Fn(&mut BuildContext, $(Asc<$t>),*) -> Result<ArcChildWidget<P>, BuildError> + Send + Sync + 'static,
             P: Protocol,
         {
             fn get_consumed_types(&self) -> &[TypeKey] {
@@ -221,7 +217,7 @@ macro_rules! impl_multi_suspendable_consumer {
                 &self,
                 ctx: &mut BuildContext,
                 provider_values: InlinableDwsizeVec<Arc<dyn Provide>>,
-            ) -> Result<ArcChildWidget<P>, BuildSuspendedError> {
+            ) -> Result<ArcChildWidget<P>, BuildError> {
                 assert_eq!(
                     provider_values.len(),
                     $count,
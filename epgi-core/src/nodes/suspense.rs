@@ -6,8 +6,8 @@ use typed_builder::TypedBuilder;
 
 use crate::{
     foundation::{
-        Arc, ArrayContainer, Asc, BuildSuspendedError, EitherContainer, EitherParallel,
-        InlinableDwsizeVec, Key, Protocol, Provide,
+        Arc, ArrayContainer, Asc, BuildError, EitherContainer, EitherParallel, InlinableDwsizeVec,
+        Key, Protocol, Provide,
     },
     template::{ImplByTemplate, ProxyRender, ProxyRenderTemplate},
     tree::{
@@ -67,7 +67,7 @@ impl<P: Protocol> ElementBase for SuspenseElement<P> {
         ),
         (
             EitherParallel<[ArcChildElementNode<P>; 1], [ArcChildElementNode<P>; 2]>,
-            BuildSuspendedError,
+            BuildError,
         ),
     > {
         use Either::*;
@@ -111,7 +111,7 @@ impl<P: Protocol> ElementBase for SuspenseElement<P> {
             Self,
             EitherParallel<[ArcChildWidget<P>; 1], [ArcChildWidget<P>; 2]>,
         ),
-        BuildSuspendedError,
+        BuildError,
     > {
         Ok((
             Self {
@@ -46,6 +46,8 @@ pub struct SchedulerHandle {
     pub async_threadpool: rayon::ThreadPool,
 
     pub request_redraw: AtomicBool,
+    /// Set when layout creates sync jobs that should be built before the frame is painted.
+    pub(super) rebuild_requested_in_layout: AtomicBool,
 
     pub(super) task_rx: SchedulerTaskReceiver,
 
@@ -65,6 +67,7 @@ impl SchedulerHandle {
             sync_threadpool,
             async_threadpool,
             request_redraw: AtomicBool::new(false),
+            rebuild_requested_in_layout: AtomicBool::new(false),
             task_rx: SchedulerTaskReceiver::new(),
             global_sync_job_build_lock: SyncRwLock::new(()),
             job_id_counter: AtomicJobIdCounter::new(),
@@ -82,6 +85,18 @@ impl SchedulerHandle {
     }
 
     pub fn create_sync_job(&self, builder: impl FnOnce(&mut JobBuilder)) {
+        self.push_sync_job(builder, &self.request_redraw)
+    }
+
+    /// Create a sync job from within layout, which is built and laid out again before the current frame is painted.
+    ///
+    /// This is for layouts that decide what has to be built, e.g. a list that only builds the items it finds visible.
+    /// A frame is rebuilt at most [`MAX_LAYOUT_REBUILDS`] times, after which the jobs are left to the next frame.
+    pub fn create_sync_job_in_layout(&self, builder: impl FnOnce(&mut JobBuilder)) {
+        self.push_sync_job(builder, &self.rebuild_requested_in_layout)
+    }
+
+    fn push_sync_job(&self, builder: impl FnOnce(&mut JobBuilder), requested: &AtomicBool) {
         // Note the additional lock compared to the async version.
         // This lock is to ensure the scheduler could not process jobs before all sync jobs create in the previous frame have finished building.
         // Therefore, the scheduler will never see an outdated sync job from previous frames.
@@ -95,7 +110,7 @@ impl SchedulerHandle {
                 .accumulated_jobs
                 .lock()
                 .push(job_builder);
-            self.request_redraw.store(true, Release);
+            requested.store(true, Release);
         }
         drop(guard);
     }
@@ -188,6 +203,14 @@ impl SchedulerHandle {
             .push(SchedulerTask::SchedulerExtensionEvent(event))
     }
 
+    /// Ask the running event loop to exit after its current task.
+    ///
+    /// The request is consumed by the event loop, so the handle can drive a new [`super::Scheduler`] afterwards.
+    pub fn request_shutdown(&self) {
+        self.task_rx.request_shutdown.store(true, Release);
+        self.task_rx.new_task_event.notify(usize::MAX);
+    }
+
     // pub fn schedule_idle_callback
 }
 
@@ -215,6 +238,17 @@ impl SchedulerHandle {
             .collect();
         (accumulated_jobs, point_rebuilds)
     }
+
+    /// Discard all work targeting the tree of a scheduler that has shut down.
+    pub(super) fn clear_pending_work(&self) {
+        let _guard = self.global_sync_job_build_lock.write();
+        self.accumulated_jobs.lock().clear();
+        self.accumulated_wakeups.lock().clear();
+        self.layer_needing_repaint.lock().clear();
+        self.request_redraw.store(false, Release);
+        self.rebuild_requested_in_layout.store(false, Release);
+        while self.task_rx.other_tasks.pop().is_some() {}
+    }
 }
 
 pub(super) struct SchedulerTaskReceiver {
@@ -232,6 +266,7 @@ struct RequestFrame {
     requesters: Vec<SyncMpscSender<FrameResults>>,
 }
 
+#[derive(Clone)]
 pub struct FrameResults {
     pub composited: Asc<dyn Any + Send + Sync>,
     pub id: u64,
@@ -330,7 +365,7 @@ impl SchedulerTaskReceiver {
         }
     }
     pub(super) fn try_recv(&self) -> Option<SchedulerTask> {
-        if self.request_shutdown.load(Acquire) {
+        if self.request_shutdown.swap(false, AcqRel) {
             return Some(SchedulerTask::Shutdown);
         }
         {
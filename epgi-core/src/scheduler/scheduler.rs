@@ -63,6 +63,10 @@ pub struct BuildStates {
 
 pub(crate) static LAYOUT_PASS_ID: AtomicUsize = AtomicUsize::new(0);
 
+/// How many times a frame is built and laid out again for the jobs created by its layout,
+/// see [`SchedulerHandle::create_sync_job_in_layout`].
+pub const MAX_LAYOUT_REBUILDS: usize = 4;
+
 impl BuildStates {
     pub(crate) fn apply_batcher_result(
         &mut self,
@@ -73,6 +77,35 @@ impl BuildStates {
             .apply_batcher_result(result, point_rebuilds, &self.root_element)
     }
 
+    /// Build the jobs created since the last build, and commit the sync batch.
+    fn build_new_jobs(
+        &mut self,
+        handle: &SchedulerHandle,
+        job_batcher: &mut JobBatcher,
+        frame_metrics_builder: &mut FrameMetricsBuilder,
+    ) {
+        self.commit_completed_async_batches(job_batcher);
+        frame_metrics_builder.current_build_start();
+        let (new_jobs, point_rebuilds) = handle.process_new_frame();
+        let updates = job_batcher.update_with_new_jobs(new_jobs);
+        self.apply_batcher_result(
+            updates,
+            point_rebuilds
+                .into_iter()
+                .filter(|waker| !waker.is_aborted())
+                .map(|waker| PtrEq(waker.element_context.clone()))
+                .collect(),
+        );
+        frame_metrics_builder.sync_batch_start();
+        let commited_sync_batch = self.dispatch_sync_batch();
+        frame_metrics_builder.sync_batch_end();
+        self.dispatch_async_batches();
+        if let Some(commited_sync_batch) = commited_sync_batch {
+            job_batcher.remove_commited_batch(&commited_sync_batch);
+        }
+        self.commit_completed_async_batches(job_batcher);
+    }
+
     pub(crate) fn dispatch_sync_batch(&mut self) -> Option<BatchId> {
         self.scheduler.dispatch_sync_batch(&self.root_element)
     }
@@ -169,6 +202,11 @@ where
             extension,
         }
     }
+    /// The build states shared with the event loop, useful for inspecting the tree from outside of the scheduler thread.
+    pub fn build_states(&self) -> &Asc<SyncRwLock<BuildStates>> {
+        &self.build_states
+    }
+
     pub fn start_event_loop(mut self, handle: &SchedulerHandle) {
         // handle.push_layer_render_objects_needing_paint(self.lane_scheduler.roo)
         let tasks = &handle.task_rx;
@@ -185,28 +223,28 @@ where
                     frame_metrics_builder.frame_start();
                     let mut build_states = self.build_states.write();
                     self.extension.on_frame_begin(&build_states);
-                    build_states.commit_completed_async_batches(&mut self.job_batcher);
-                    frame_metrics_builder.current_build_start();
-                    let (new_jobs, point_rebuilds) = handle.process_new_frame();
-                    let updates = self.job_batcher.update_with_new_jobs(new_jobs);
-                    build_states.apply_batcher_result(
-                        updates,
-                        point_rebuilds
-                            .into_iter()
-                            .filter(|waker| !waker.is_aborted())
-                            .map(|waker| PtrEq(waker.element_context.clone()))
-                            .collect(),
+                    build_states.build_new_jobs(
+                        handle,
+                        &mut self.job_batcher,
+                        &mut frame_metrics_builder,
                     );
-                    frame_metrics_builder.sync_batch_start();
-                    let commited_sync_batch = build_states.dispatch_sync_batch();
-                    frame_metrics_builder.sync_batch_end();
-                    build_states.dispatch_async_batches();
-                    if let Some(commited_sync_batch) = commited_sync_batch {
-                        self.job_batcher.remove_commited_batch(&commited_sync_batch);
-                    }
-                    build_states.commit_completed_async_batches(&mut self.job_batcher);
                     frame_metrics_builder.layout_start();
                     build_states.perform_layout();
+                    for _ in 0..MAX_LAYOUT_REBUILDS {
+                        if !handle.rebuild_requested_in_layout.swap(false, AcqRel) {
+                            break;
+                        }
+                        build_states.build_new_jobs(
+                            handle,
+                            &mut self.job_batcher,
+                            &mut FrameMetricsBuilder::new(),
+                        );
+                        build_states.perform_layout();
+                    }
+                    // Out of rebuilds, so leave the remaining jobs to the next frame.
+                    if handle.rebuild_requested_in_layout.swap(false, AcqRel) {
+                        handle.request_redraw.store(true, Release);
+                    }
                     self.extension.on_layout_complete(&build_states);
                     // We don't have RwLock downgrade in std, this is to simulate it by re-reading while blocking the event loop.
                     // TODO: Parking_lot owned downgradable guard
@@ -287,7 +325,10 @@ where
                 SchedulerExtensionEvent(event) => {
                     self.extension.on_extension_event(event);
                 }
-                Shutdown => break,
+                Shutdown => {
+                    handle.clear_pending_work();
+                    break;
+                }
             }
         }
     }
@@ -22,3 +22,6 @@ mod commit_render_object;
 pub use commit_render_object::*;
 
 mod provider;
+
+mod global_key;
+pub(crate) use global_key::*;
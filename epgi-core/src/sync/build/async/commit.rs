@@ -514,6 +514,9 @@ where
                     state: Some(state),
                     async_queue: AsyncWorkQueue::new_empty(),
                 });
+                lane_scheduler
+                    .global_keys
+                    .register_async(self, &snapshot_reborrow.widget);
             }
             ElementSnapshotInner::Mainline(mainline) => {
                 mainline.state = Some(state);
@@ -538,6 +541,10 @@ where
         suspended_results: BuildSuspendResults,
     ) {
         suspended_results.waker.make_sync();
+        if let Some(error) = suspended_results.error {
+            // The failed element stays suspended until the error boundary unmounts it.
+            self.context.report_build_error(error);
+        }
 
         let mut snapshot = self.snapshot.lock();
         let snapshot_reborrow = &mut *snapshot;
@@ -1,4 +1,5 @@
 mod component_element;
+mod error_boundary_element;
 mod render_element;
 mod suspense_element;
 
@@ -131,4 +132,9 @@ pub trait ImplCommitRenderObject<E: Element<Impl = Self>>: ImplElementNode<E> {
     ///
     /// If set to true, then in an async batch, any descendant suspended work won't generate a `CommitBarrier` and thus won't prevent the batch from being committed.
     const ALLOW_ASYNC_COMMIT_INFLATE_SUSPENDED_CHILD: bool = false;
+
+    /// Whether build errors from descendants should be caught by this element, as an [`ErrorBoundary`] does.
+    ///
+    /// If set to true, the element context will carry an error slot, which this element is responsible to check during its commit.
+    const CATCH_BUILD_ERRORS: bool = false;
 }
@@ -0,0 +1,251 @@
+use crate::{
+    foundation::{Arc, Asc, Protocol},
+    nodes::{ErrorBoundary, ErrorBoundaryElement, RenderErrorBoundary},
+    scheduler::get_current_scheduler,
+    sync::{LaneScheduler, RenderObjectCommitResult},
+    tree::{
+        AnyRenderObject, ArcChildElementNode, ArcElementContextNode,
+        ChildRenderObjectsUpdateCallback, ElementImpl, ElementNode, ErrorCatcher, MainlineState,
+        RenderAction, RenderObject,
+    },
+};
+
+use super::ImplCommitRenderObject;
+
+impl<P: Protocol> ImplCommitRenderObject<ErrorBoundaryElement<P>> for ElementImpl<true, false> {
+    fn visit_commit_render_object<'batch>(
+        element_node: &ElementNode<ErrorBoundaryElement<P>>,
+        render_object: Option<Arc<RenderObject<RenderErrorBoundary<P>>>>,
+        [render_object_change]: [RenderObjectCommitResult<P>; 1],
+        lane_scheduler: &'batch LaneScheduler,
+        scope: &rayon::Scope<'batch>,
+        self_rebuild_suspended: bool,
+    ) -> RenderObjectCommitResult<P> {
+        debug_assert!(
+            !self_rebuild_suspended,
+            "ErrorBoundary itself can never suspend"
+        );
+        let error = error_catcher(&element_node.context).take_caught();
+
+        // Happy path without locking
+        if error.is_none() {
+            if let Some(render_object) = &render_object {
+                if !matches!(render_object_change, RenderObjectCommitResult::Suspend) {
+                    return update_attached(render_object, render_object_change);
+                }
+            }
+        }
+
+        // A descendant has failed. Inflate the fallback and unmount the failed child.
+        //
+        // Same as Suspense, we read the widget right from inside the element node,
+        // since this is considered to be a rare case / slow path.
+        let fallback = error.map(|error| {
+            let fallback_widget = {
+                let snapshot = element_node.snapshot.lock();
+                let Some(MainlineState::Ready { element, .. }) = snapshot
+                    .inner
+                    .mainline_ref()
+                    .expect("An unmounted element node should not be reachable by a rebuild!")
+                    .state
+                    .as_ref()
+                else {
+                    panic!("ErrorBoundary should always be in the Ready state")
+                };
+                (snapshot.widget.fallback)(&error, element.reset_handle())
+            };
+            fallback_widget.inflate_sync(Some(element_node.context.clone()), lane_scheduler)
+        });
+
+        let mut snapshot = element_node.snapshot.lock();
+        let state = snapshot
+            .inner
+            .mainline_mut()
+            .expect("An unmounted element node should not be reachable by a rebuild!")
+            .state
+            .as_mut()
+            .expect(
+                "State corrupted. \
+                This node has been previously designated to visit by a sync batch. \
+                However, when the visit returns, \
+                it found the sync state has been occupied.",
+            );
+        let MainlineState::Ready {
+            children: [child],
+            render_object,
+            ..
+        } = state
+        else {
+            panic!("ErrorBoundary should always be in the Ready state")
+        };
+
+        let mut render_object_change = render_object_change;
+        if let Some((fallback, commit_result)) = fallback {
+            let failed_child = std::mem::replace(child, fallback);
+            scope.spawn(|scope| failed_child.unmount(scope, lane_scheduler));
+            render_object_change = commit_result.render_object;
+        }
+        commit_child(
+            render_object,
+            child,
+            render_object_change,
+            &element_node.context,
+        )
+    }
+
+    fn rebuild_success_commit_render_object<'batch>(
+        element: &mut ErrorBoundaryElement<P>,
+        widget: &Asc<ErrorBoundary<P>>,
+        _shuffle: Option<
+            ChildRenderObjectsUpdateCallback<
+                <ErrorBoundaryElement<P> as crate::tree::ElementBase>::ChildContainer,
+                P,
+            >,
+        >,
+        [child]: &mut [ArcChildElementNode<P>; 1],
+        render_object: Option<Option<Arc<RenderObject<RenderErrorBoundary<P>>>>>,
+        [mut render_object_change]: [RenderObjectCommitResult<P>; 1],
+        element_context: &ArcElementContextNode,
+        lane_scheduler: &'batch LaneScheduler,
+        scope: &rayon::Scope<'batch>,
+        _is_new_widget: bool,
+    ) -> (
+        Option<Arc<RenderObject<RenderErrorBoundary<P>>>>,
+        RenderObjectCommitResult<P>,
+    ) {
+        debug_assert!(_shuffle.is_none(), "ErrorBoundary cannot shuffle its child");
+        let mut render_object = render_object.expect("ErrorBoundary itself can never suspend");
+        if let Some(error) = error_catcher(element_context).take_caught() {
+            let (fallback, commit_result) = (widget.fallback)(&error, element.reset_handle())
+                .inflate_sync(Some(element_context.clone()), lane_scheduler);
+            let failed_child = std::mem::replace(child, fallback);
+            scope.spawn(|scope| failed_child.unmount(scope, lane_scheduler));
+            render_object_change = commit_result.render_object;
+        }
+        let change = commit_child(
+            &mut render_object,
+            child,
+            render_object_change,
+            element_context,
+        );
+        (render_object, change)
+    }
+
+    fn rebuild_suspend_commit_render_object(
+        _render_object: Option<Option<Arc<RenderObject<RenderErrorBoundary<P>>>>>,
+    ) -> RenderObjectCommitResult<P> {
+        panic!("ErrorBoundary can not suspend on itself")
+    }
+
+    fn inflate_success_commit_render_object(
+        element: &mut ErrorBoundaryElement<P>,
+        widget: &Asc<ErrorBoundary<P>>,
+        [child]: &mut [ArcChildElementNode<P>; 1],
+        [mut render_object_change]: [RenderObjectCommitResult<P>; 1],
+        element_context: &ArcElementContextNode,
+        lane_scheduler: &LaneScheduler,
+    ) -> (
+        Option<Arc<RenderObject<RenderErrorBoundary<P>>>>,
+        RenderObjectCommitResult<P>,
+    ) {
+        debug_assert!(
+            !render_object_change.is_keep_render_object(),
+            "Fatal logic bug in epgi-core reconcile logic. Please file issue report."
+        );
+        if let Some(error) = error_catcher(element_context).take_caught() {
+            let (fallback, commit_result) = (widget.fallback)(&error, element.reset_handle())
+                .inflate_sync(Some(element_context.clone()), lane_scheduler);
+            let failed_child = std::mem::replace(child, fallback);
+            get_current_scheduler()
+                .sync_threadpool
+                .scope(|scope| failed_child.unmount(scope, lane_scheduler));
+            render_object_change = commit_result.render_object;
+        }
+        let mut render_object = None;
+        let change = commit_child(
+            &mut render_object,
+            child,
+            render_object_change,
+            element_context,
+        );
+        (render_object, change)
+    }
+
+    fn detach_render_object(render_object: &Option<Arc<RenderObject<RenderErrorBoundary<P>>>>) {
+        if let Some(render_object) = render_object {
+            render_object.detach_render_object();
+        }
+    }
+
+    const CATCH_BUILD_ERRORS: bool = true;
+}
+
+fn error_catcher(element_context: &ArcElementContextNode) -> &ErrorCatcher {
+    element_context
+        .error_catcher
+        .as_ref()
+        .expect("An ErrorBoundary should have an error catcher in its element context")
+}
+
+/// Commit the change from the only child, which may suspend or resume the boundary like any other render element.
+fn commit_child<P: Protocol>(
+    render_object: &mut Option<Arc<RenderObject<RenderErrorBoundary<P>>>>,
+    child: &ArcChildElementNode<P>,
+    render_object_change: RenderObjectCommitResult<P>,
+    element_context: &ArcElementContextNode,
+) -> RenderObjectCommitResult<P> {
+    use RenderObjectCommitResult::*;
+    if let Some(attached) = render_object {
+        if let Suspend = render_object_change {
+            attached.detach_render_object();
+            *render_object = None;
+            return Suspend;
+        }
+        return update_attached(attached, render_object_change);
+    }
+    let child_render_object = match render_object_change {
+        New(child_render_object) => Some(child_render_object),
+        Keep { .. } => child.get_current_subtree_render_object(),
+        Suspend => None,
+    };
+    let Some(child_render_object) = child_render_object else {
+        return Suspend;
+    };
+    let new_render_object = RenderObject::new(
+        RenderErrorBoundary::new(),
+        [child_render_object],
+        element_context.clone(),
+    );
+    *render_object = Some(new_render_object.clone());
+    New(new_render_object)
+}
+
+fn update_attached<P: Protocol>(
+    render_object: &Arc<RenderObject<RenderErrorBoundary<P>>>,
+    render_object_change: RenderObjectCommitResult<P>,
+) -> RenderObjectCommitResult<P> {
+    use RenderObjectCommitResult::*;
+    match render_object_change {
+        Keep {
+            propagated_render_action,
+            subtree_has_action,
+        } => {
+            let propagated_render_action =
+                render_object.mark_render_action(propagated_render_action, subtree_has_action);
+            Keep {
+                propagated_render_action,
+                subtree_has_action,
+            }
+        }
+        New(child_render_object) => {
+            render_object.inner.lock().children = [child_render_object];
+            let propagated_render_action = render_object
+                .mark_render_action(Some(RenderAction::Relayout), Some(RenderAction::Relayout));
+            Keep {
+                propagated_render_action,
+                subtree_has_action: Some(RenderAction::Relayout),
+            }
+        }
+        Suspend => panic!("A suspended child should have been handled by the caller"),
+    }
+}
@@ -75,6 +75,7 @@ where
         let was_suspended = render_object.is_none();
         let (new_render_object, change) = if let Some(render_object) = render_object.flatten() {
             rebuild_success_process_attached(
+                element,
                 widget,
                 shuffle,
                 render_object,
@@ -158,11 +159,11 @@ where
             }
         });
 
-        let new_render_object = Arc::new(RenderObject::<E::Render>::new(
+        let new_render_object = RenderObject::<E::Render>::new(
             E::create_render(&element, &widget), //TODO: This could panic
             child_render_objects,
             element_context.clone(),
-        ));
+        );
 
         if let Some(layer_render_object) =
             RenderObject::<E::Render>::try_as_aweak_any_layer_render_object(&new_render_object)
@@ -388,6 +389,7 @@ where
 
 #[inline(always)]
 fn rebuild_success_process_attached<E, const PROVIDE_ELEMENT: bool>(
+    element: &E,
     widget: &E::ArcWidget,
     shuffle: Option<ChildRenderObjectsUpdateCallback<E::ChildContainer, E::ChildProtocol>>,
     render_object: Arc<RenderObject<E::Render>>,
@@ -420,11 +422,18 @@ where
     if shuffle.is_some()
         || !render_object_change_summary.is_keep_all()
         || (is_new_widget && !E::NOOP_UPDATE_RENDER_OBJECT)
+        || !E::NOOP_UPDATE_RENDER_FROM_ELEMENT
     {
         render_object.update(|render, children| {
             if is_new_widget && !E::NOOP_UPDATE_RENDER_OBJECT {
                 self_render_action = E::update_render(render, widget);
             }
+            if !E::NOOP_UPDATE_RENDER_FROM_ELEMENT {
+                self_render_action = std::cmp::max(
+                    self_render_action,
+                    element.update_render_from_element(render),
+                );
+            }
             update_children::<E::Render>(
                 children,
                 shuffle,
@@ -499,11 +508,11 @@ where
     } else {
         let new_render_children =
             option_child_render_objects.map_collect(|child| child.expect("Impossible to fail"));
-        let new_render_object = Arc::new(RenderObject::<E::Render>::new(
+        let new_render_object = RenderObject::<E::Render>::new(
             E::create_render(&element, &widget), //TODO: This could panic
             new_render_children,
             element_context.clone(),
-        ));
+        );
         Some(new_render_object)
     }
 }
@@ -281,11 +281,11 @@ impl<P: Protocol> ImplCommitRenderObject<SuspenseElement<P>> for ElementImpl<tru
             (true, fallback_render_object)
         };
 
-        let new_render_object = Arc::new(RenderObject::new(
+        let new_render_object = RenderObject::new(
             RenderSuspense::new(is_suspended),
             [child_render_object],
             element_context.clone(),
-        ));
+        );
         return (Some(new_render_object.clone()), New(new_render_object));
     }
 
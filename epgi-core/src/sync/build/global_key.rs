@@ -0,0 +1,238 @@
+use std::any::{Any, TypeId};
+
+use hashbrown::HashMap;
+
+use crate::{
+    foundation::{Arc, Aweak, GlobalKey, SyncMutex},
+    scheduler::get_current_scheduler,
+    sync::{LaneScheduler, RenderObjectCommitResult},
+    tree::{
+        ArcAnyElementNode, ArcElementContextNode, ArcWidget, AweakElementContextNode,
+        ChildElementNode, ElementNode, FullElement,
+    },
+};
+
+use super::CommitResult;
+
+/// Tracks the elements whose widgets carry a [`GlobalKey`], so that they can be moved across the tree during a commit.
+///
+/// When the parent of such an element drops it, the element is detached instead of unmounted.
+/// If a widget with the same key is inflated elsewhere in the same commit, the detached element is moved there.
+/// The parent may also drop the element after it has already been moved, since sibling subtrees are reconciled in parallel.
+/// Elements that are still detached when the commit finishes are unmounted then.
+pub(crate) struct GlobalKeyRegistry {
+    inner: SyncMutex<GlobalKeyRegistryInner>,
+}
+
+#[derive(Default)]
+struct GlobalKeyRegistryInner {
+    entries: HashMap<GlobalKey, GlobalKeyEntry>,
+    /// Detached elements whose keys have been taken by newly inflated elements.
+    orphans: Vec<ArcAnyElementNode>,
+    /// Whether any entry has left the mounted state since the last time detached elements were taken.
+    has_pending: bool,
+}
+
+struct GlobalKeyEntry {
+    /// An `Aweak<ElementNode<E>>` of the element holding this key
+    element: Box<dyn Any + Send + Sync>,
+    element_context: AweakElementContextNode,
+    widget_type_id: TypeId,
+    state: GlobalKeyEntryState,
+}
+
+enum GlobalKeyEntryState {
+    Mounted,
+    /// Dropped by its parent during this commit and not claimed by a new parent yet.
+    Detached(ArcAnyElementNode),
+    /// Claimed by a new parent during this commit, before its old parent has dropped it.
+    Claimed,
+}
+
+impl GlobalKeyRegistry {
+    pub(crate) fn new() -> Self {
+        Self {
+            inner: SyncMutex::new(Default::default()),
+        }
+    }
+
+    pub(crate) fn register<E: FullElement>(
+        &self,
+        node: &Arc<ElementNode<E>>,
+        widget: &E::ArcWidget,
+    ) {
+        let Some(key) = global_key(widget) else {
+            return;
+        };
+        let entry = GlobalKeyEntry {
+            element: Box::new(Arc::downgrade(node)),
+            element_context: Arc::downgrade(&node.context),
+            widget_type_id: widget.widget_type_id(),
+            state: GlobalKeyEntryState::Mounted,
+        };
+        let mut inner = self.inner.lock();
+        let old_entry = inner.entries.insert(key.clone(), entry);
+        if let Some(GlobalKeyEntry {
+            state: GlobalKeyEntryState::Detached(orphan),
+            ..
+        }) = old_entry
+        {
+            inner.orphans.push(orphan);
+            inner.has_pending = true;
+        }
+    }
+
+    /// Register an element that an async build has inflated and is now committing.
+    ///
+    /// Async builds do not move elements with global keys, since their inflated subtrees are not part of the tree
+    /// until they commit, and a move would have to be undone if the build were cancelled.
+    /// Panics if another element still holds the key, which means the async build moved the key to a new place.
+    pub(crate) fn register_async<E: FullElement>(
+        &self,
+        node: &Arc<ElementNode<E>>,
+        widget: &E::ArcWidget,
+    ) {
+        if let Some(key) = global_key(widget) {
+            let inner = self.inner.lock();
+            assert!(
+                !inner
+                    .entries
+                    .get(key)
+                    .is_some_and(|entry| entry.widget_type_id == widget.widget_type_id()
+                        && entry.element_context.strong_count() > 0),
+                "An async build moved the GlobalKey {:?} to a new place. \
+                Elements with a GlobalKey can only be moved by sync builds.",
+                key
+            );
+        }
+        self.register(node, widget)
+    }
+
+    /// Returns true if the element has been detached, in which case it should not be unmounted.
+    pub(crate) fn detach<E: FullElement>(&self, node: &Arc<ElementNode<E>>) -> bool {
+        let widget = node.widget();
+        let Some(key) = global_key(&widget) else {
+            return false;
+        };
+        let mut inner = self.inner.lock();
+        let inner = &mut *inner;
+        let Some(entry) = inner.entries.get_mut(key) else {
+            return false;
+        };
+        if !std::ptr::eq(entry.element_context.as_ptr(), Arc::as_ptr(&node.context)) {
+            return false;
+        }
+        use GlobalKeyEntryState::*;
+        match entry.state {
+            Mounted => {
+                entry.state = Detached(node.clone() as _);
+                inner.has_pending = true;
+                true
+            }
+            // The element has already been moved. Its old parent is just catching up.
+            Claimed => {
+                entry.state = Mounted;
+                true
+            }
+            Detached(_) => panic!("An element should not be detached twice"),
+        }
+    }
+
+    fn claim<E: FullElement>(
+        &self,
+        key: &GlobalKey,
+        widget: &E::ArcWidget,
+        parent_context: &ArcElementContextNode,
+    ) -> Option<Arc<ElementNode<E>>> {
+        let mut inner = self.inner.lock();
+        let entry = inner.entries.get_mut(key)?;
+        if entry.widget_type_id != widget.widget_type_id() {
+            return None;
+        }
+        let node = entry
+            .element
+            .downcast_ref::<Aweak<ElementNode<E>>>()?
+            .upgrade()?;
+        if !node.context.can_reparent_to(parent_context) {
+            return None;
+        }
+        use GlobalKeyEntryState::*;
+        match entry.state {
+            Mounted => {
+                entry.state = Claimed;
+                inner.has_pending = true;
+            }
+            Detached(_) => entry.state = Mounted,
+            Claimed => panic!(
+                "Multiple widgets used the same GlobalKey {:?} in the same build. \
+                A GlobalKey can only appear once in the tree.",
+                key
+            ),
+        }
+        Some(node)
+    }
+
+    /// Take out every element that is still detached, and forget their keys.
+    pub(crate) fn take_detached(&self) -> Vec<ArcAnyElementNode> {
+        let mut inner = self.inner.lock();
+        if !inner.has_pending {
+            return Vec::new();
+        }
+        inner.has_pending = false;
+        let mut detached = std::mem::take(&mut inner.orphans);
+        let removed = inner
+            .entries
+            .extract_if(|_key, entry| !matches!(entry.state, GlobalKeyEntryState::Mounted));
+        for (key, entry) in removed {
+            match entry.state {
+                GlobalKeyEntryState::Detached(node) => detached.push(node),
+                GlobalKeyEntryState::Claimed => panic!(
+                    "Multiple widgets used the same GlobalKey {:?} in the same build. \
+                    A GlobalKey can only appear once in the tree.",
+                    key
+                ),
+                GlobalKeyEntryState::Mounted => unreachable!(),
+            }
+        }
+        detached
+    }
+}
+
+fn global_key<W: ArcWidget>(widget: &W) -> Option<&GlobalKey> {
+    widget.key()?.as_any().downcast_ref::<GlobalKey>()
+}
+
+impl<E: FullElement> ElementNode<E> {
+    /// Move an element with the same global key under the new parent instead of inflating a new one, if there is one.
+    pub(super) fn try_reparent_sync(
+        widget: &E::ArcWidget,
+        parent_context: &ArcElementContextNode,
+        lane_scheduler: &LaneScheduler,
+    ) -> Option<(Arc<Self>, CommitResult<E::ParentProtocol>)> {
+        let key = global_key(widget)?;
+        let node = lane_scheduler
+            .global_keys
+            .claim::<E>(key, widget, parent_context)?;
+        let not_unmounted = node.context.assert_not_unmounted();
+        node.context.reparent(parent_context.clone());
+        node.context.mark_up_async_lanes(not_unmounted);
+
+        let commit_result = get_current_scheduler().sync_threadpool.scope(|scope| {
+            node.reconcile_node_sync(
+                Some(widget.clone()),
+                lane_scheduler.sync_job_ids(),
+                scope,
+                lane_scheduler,
+            )
+        });
+        // The new parent expects a new render object from this child
+        let render_object = match commit_result.render_object {
+            RenderObjectCommitResult::Keep { .. } => RenderObjectCommitResult::New(
+                node.get_current_subtree_render_object()
+                    .expect("A moved element that is not suspended should have a render object"),
+            ),
+            render_object => render_object,
+        };
+        Some((node, CommitResult::new(render_object)))
+    }
+}
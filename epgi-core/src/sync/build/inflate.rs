@@ -1,6 +1,7 @@
 use crate::{
     foundation::{
-        Arc, AsIterator, Container, InlinableDwsizeVec, Protocol, Provide, 
+        catch_build_panic, Arc, AsIterator, BuildError, Container, Error, InlinableDwsizeVec,
+        Protocol, Provide,
     },
     scheduler::{get_current_scheduler, LanePos},
     sync::{LaneScheduler, RenderObjectCommitResult},
@@ -48,6 +49,13 @@ impl<E: FullElement> ElementNode<E> {
         parent_context: Option<ArcElementContextNode>,
         lane_scheduler: &LaneScheduler,
     ) -> (Arc<ElementNode<E>>, CommitResult<E::ParentProtocol>) {
+        if let Some(parent_context) = &parent_context {
+            if let Some(reparented) =
+                Self::try_reparent_sync(widget, parent_context, lane_scheduler)
+            {
+                return reparented;
+            }
+        }
         let node = Arc::new_cyclic(|weak| {
             ElementNode::new(
                 Arc::new(ElementContextNode::new_for::<E>(
@@ -77,6 +85,7 @@ impl<E: FullElement> ElementNode<E> {
             consumed_values,
             lane_scheduler,
         );
+        lane_scheduler.global_keys.register(&node, widget);
         return (node, commit_result);
     }
 
@@ -84,7 +93,7 @@ impl<E: FullElement> ElementNode<E> {
         self: &Arc<Self>,
         widget: &E::ArcWidget,
         suspended_hooks: Option<HooksWithCleanups>,
-        provider_values: InlinableDwsizeVec<Arc<dyn Provide>>,
+        provider_values: Result<InlinableDwsizeVec<Arc<dyn Provide>>, Error>,
         lane_scheduler: &LaneScheduler,
     ) -> CommitResult<E::ParentProtocol> {
         let hook_mode = if suspended_hooks.is_none() {
@@ -98,7 +107,12 @@ impl<E: FullElement> ElementNode<E> {
             element_context: &self.context,
             hook_context: HookContext::new_sync(&mut hooks, hook_mode),
         };
-        let result = E::perform_inflate_element(&widget, &mut ctx, provider_values);
+        let result = provider_values
+            .map_err(BuildError::from)
+            .and_then(|provider_values| {
+                catch_build_panic(|| E::perform_inflate_element(widget, &mut ctx, provider_values))
+                    .unwrap_or_else(|error| Err(error.into()))
+            });
 
         let (state, change) = match result {
             Ok((mut element, child_widgets)) => {
@@ -143,13 +157,20 @@ impl<E: FullElement> ElementNode<E> {
                     render_object_commit_result,
                 )
             }
-            Err(err) => (
-                MainlineState::InflateSuspended {
-                    suspended_hooks: hooks,
-                    waker: err.waker,
-                },
-                RenderObjectCommitResult::Suspend,
-            ),
+            Err(err) => {
+                let (waker, error) = err.into_parts(&self.context, LanePos::SYNC);
+                if let Some(error) = error {
+                    // The failed element stays suspended until the error boundary unmounts it.
+                    self.context.report_build_error(error);
+                }
+                (
+                    MainlineState::InflateSuspended {
+                        suspended_hooks: hooks,
+                        waker,
+                    },
+                    RenderObjectCommitResult::Suspend,
+                )
+            }
         };
         if FIRST_INFLATE {
             self.commit_write_element_first_inflate(state);
@@ -1,7 +1,7 @@
 use linear_map::LinearMap;
 
 use crate::{
-    foundation::{Arc, Container, InlinableDwsizeVec, Provide, TypeKey},
+    foundation::{Arc, Container, Error, ErrorKind, InlinableDwsizeVec, Provide, TypeKey},
     scheduler::{get_current_scheduler, LanePos},
     sync::LaneScheduler,
     tree::{ArcElementContextNode, Element, ElementContextNode, FullElement, ImplProvide},
@@ -16,7 +16,7 @@ impl AsyncWorkNeedsRestarting {
     pub(super) fn push_ref(&mut self, lane_pos: LanePos, node: &ArcElementContextNode) {
         match self.0.entry(lane_pos) {
             linear_map::Entry::Occupied(mut entry) => {
-                if entry.get().depth < node.depth {
+                if entry.get().depth() < node.depth() {
                     entry.insert(node.clone());
                 }
             }
@@ -29,7 +29,7 @@ impl AsyncWorkNeedsRestarting {
     pub(super) fn push(&mut self, lane_pos: LanePos, node: ArcElementContextNode) {
         match self.0.entry(lane_pos) {
             linear_map::Entry::Occupied(mut entry) => {
-                if entry.get().depth < node.depth {
+                if entry.get().depth() < node.depth() {
                     entry.insert(node);
                 }
             }
@@ -59,7 +59,7 @@ pub(super) fn read_and_update_subscriptions_sync(
     old_consumed_types: &[TypeKey],
     element_context: &ArcElementContextNode,
     lane_scheduler: &LaneScheduler,
-) -> InlinableDwsizeVec<Arc<dyn Provide>> {
+) -> Result<InlinableDwsizeVec<Arc<dyn Provide>>, Error> {
     let is_old_consumed_types = std::ptr::eq(new_consumed_types, old_consumed_types);
 
     // Why do we need to restart contending async writers at all?
@@ -74,10 +74,10 @@ pub(super) fn read_and_update_subscriptions_sync(
     // Unregister
     for consumed in old_consumed_types.iter() {
         if !new_consumed_types.contains(consumed) {
-            let provider_node = element_context
-                .provider_map
-                .get(consumed)
-                .expect("ProviderMap should be consistent");
+            // A provider that was not found has never been subscribed to
+            let Some(provider_node) = element_context.provider_map.get(consumed) else {
+                continue;
+            };
             let contending_writer = provider_node
                 .provider_object
                 .as_ref()
@@ -89,14 +89,19 @@ pub(super) fn read_and_update_subscriptions_sync(
         }
     }
 
+    // Keep subscribing to the rest even if a provider is not found, so that the subscriptions stay consistent with the widget.
+    let mut not_found = None;
     let consumed_values = new_consumed_types
         .iter()
-        .map(|consumed| {
+        .filter_map(|consumed| {
             let is_old = is_old_consumed_types || old_consumed_types.contains(consumed);
-            let provider_node = element_context
-                .provider_map
-                .get(consumed)
-                .expect("Requested provider should exist");
+            let Some(provider_node) = element_context.provider_map.get(consumed) else {
+                let default_value = consumed.default_value();
+                if default_value.is_none() {
+                    not_found.get_or_insert(*consumed);
+                }
+                return default_value;
+            };
             let provider_object = provider_node
                 .provider_object
                 .as_ref()
@@ -108,12 +113,15 @@ pub(super) fn read_and_update_subscriptions_sync(
                     async_work_needs_restarting.push_ref(contending_lane, provider_node)
                 }
             }
-            provider_object.read()
+            Some(provider_object.read())
         })
         .collect();
 
     async_work_needs_restarting.execute_restarts(lane_scheduler);
-    return consumed_values;
+    match not_found {
+        Some(type_key) => Err(ErrorKind::ProviderNotFound { type_key }.into()),
+        None => Ok(consumed_values),
+    }
 }
 
 pub(super) fn update_provided_value<E: FullElement>(
@@ -1,6 +1,7 @@
 use crate::{
     foundation::{
-        Arc, Container, ContainerOf, Inlinable64Vec, InlinableDwsizeVec, Protocol, Provide,
+        catch_build_panic, Arc, Container, ContainerOf, Error, HktContainer, Inlinable64Vec,
+        InlinableDwsizeVec, Protocol, Provide,
     },
     scheduler::{get_current_scheduler, JobId, LanePos},
     sync::LaneScheduler,
@@ -72,7 +73,7 @@ impl<E: FullElement> ElementNode<E> {
         // Some(Some()) means ready and render object attached, Some(None) means ready and render object detached
         // Remember that suspended and detached are two sets of state!
         render_object: Option<<<E as Element>::Impl as ImplElementNode<E>>::OptionArcRenderObject>,
-        provider_values: InlinableDwsizeVec<Arc<dyn Provide>>,
+        provider_values: Result<InlinableDwsizeVec<Arc<dyn Provide>>, Error>,
         job_ids: &Inlinable64Vec<JobId>,
         scope: &rayon::Scope<'batch>,
         lane_scheduler: &'batch LaneScheduler,
@@ -85,14 +86,24 @@ impl<E: FullElement> ElementNode<E> {
             element_context: &self.context,
             hook_context,
         };
-        let results = E::perform_rebuild_element(
-            &mut element,
-            &widget,
-            &mut ctx,
-            provider_values,
-            children,
-            &mut nodes_needing_unmount,
-        );
+        let results = match provider_values {
+            Ok(provider_values) => {
+                // The children are moved into the build. Keep a copy so that they can still be unmounted if the build panics.
+                let children_backup = E::ChildContainer::clone_container(&children);
+                catch_build_panic(|| {
+                    E::perform_rebuild_element(
+                        &mut element,
+                        widget,
+                        &mut ctx,
+                        provider_values,
+                        children,
+                        &mut nodes_needing_unmount,
+                    )
+                })
+                .unwrap_or_else(|error| Err((children_backup, error.into())))
+            }
+            Err(error) => Err((children, error.into())),
+        };
 
         let (state, change) = match results {
             Ok((items, shuffle)) => {
@@ -145,9 +156,14 @@ impl<E: FullElement> ElementNode<E> {
             }
             Err((children, err)) => {
                 debug_assert!(
-                    nodes_needing_unmount.is_empty(),
+                    err.is_failed() || nodes_needing_unmount.is_empty(),
                     "An element that suspends itself should not request unmounting any child nodes"
                 );
+                let (waker, error) = err.into_parts(&self.context, LanePos::SYNC);
+                if let Some(error) = error {
+                    // The failed subtree stays suspended until the error boundary unmounts it.
+                    self.context.report_build_error(error);
+                }
 
                 // We need to visit the mainline children instead.
                 // There could be sync updates in the descendants.
@@ -167,7 +183,7 @@ impl<E: FullElement> ElementNode<E> {
                         suspended_hooks: hooks,
                         element,
                         children,
-                        waker: err.waker,
+                        waker,
                     },
                     <E as Element>::Impl::rebuild_suspend_commit_render_object(render_object),
                 )
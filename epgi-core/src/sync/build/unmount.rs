@@ -39,6 +39,9 @@ impl<E: FullElement> ElementNode<E> {
         scope: &rayon::Scope<'batch>,
         lane_scheduler: &'batch LaneScheduler,
     ) {
+        if lane_scheduler.global_keys.detach(self) {
+            return;
+        }
         let unmounted = self.context.unmounted.swap(true, Relaxed);
         if unmounted {
             return;
@@ -109,11 +112,10 @@ impl<E: FullElement> ElementNode<E> {
         // Because those side effects are only fired in the commit phase, which is holding sync scheduler lock, which we are also holding.
         let mut async_work_needs_restarting = AsyncWorkNeedsRestarting::new();
         for consumed_type in E::get_consumed_types(&widget).as_ref().iter() {
-            let provider_node = self
-                .context
-                .provider_map
-                .get(consumed_type)
-                .expect("ProviderMap should be consistent");
+            // A provider that was not found has never been subscribed to
+            let Some(provider_node) = self.context.provider_map.get(consumed_type) else {
+                continue;
+            };
             let contending_writer = provider_node
                 .provider_object
                 .as_ref()
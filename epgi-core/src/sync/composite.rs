@@ -117,6 +117,7 @@ where
                 &layer_cache.paint_results,
             );
             layer_cache.insert_composite_results(composite_results);
+            self.layer_mark.clear_needs_composite();
             orphan_layers
         };
         // return composite_results.orphan_layers.clone();
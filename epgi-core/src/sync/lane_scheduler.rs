@@ -5,10 +5,12 @@ use hashbrown::HashSet;
 use rayon::prelude::{IntoParallelRefIterator, ParallelIterator};
 
 use crate::{
-    foundation::{Asc, PtrEq},
+    foundation::{Asc, Inlinable64Vec, PtrEq},
     scheduler::{
-        get_current_scheduler, BatchConf, BatchId, BatchResult, JobBatcher, LaneMask, LanePos,
+        get_current_scheduler, BatchConf, BatchId, BatchResult, JobBatcher, JobId, LaneMask,
+        LanePos,
     },
+    sync::GlobalKeyRegistry,
     tree::{ArcAnyElementNode, AweakAnyElementNode, AweakElementContextNode},
 };
 
@@ -16,6 +18,7 @@ pub struct LaneScheduler {
     sync_lane: Option<SyncLaneData>,
     async_lanes: [Option<AsyncLaneData>; LaneMask::ASYNC_LANE_COUNT],
     queued_batches: Vec<Asc<BatchConf>>,
+    pub(crate) global_keys: GlobalKeyRegistry,
 }
 
 struct SyncLaneData {
@@ -49,6 +52,7 @@ impl LaneScheduler {
             sync_lane: None,
             async_lanes: [(); LaneMask::ASYNC_LANE_COUNT].map(|_| None),
             queued_batches: Default::default(),
+            global_keys: GlobalKeyRegistry::new(),
         }
     }
 
@@ -133,9 +137,10 @@ impl LaneScheduler {
         }
         if !finished_lanes.is_empty() {
             let root_element = root_element.clone();
-            get_current_scheduler().sync_threadpool.scope(move |scope| {
+            get_current_scheduler().sync_threadpool.scope(|scope| {
                 root_element.visit_and_commit_async_any(finished_lanes, scope, self);
-            })
+            });
+            self.unmount_detached_elements();
         }
     }
 
@@ -150,6 +155,7 @@ impl LaneScheduler {
         get_current_scheduler().sync_threadpool.scope(|scope| {
             root_element.visit_and_work_sync_any(&sync_batch.job_ids, scope, self);
         });
+        self.unmount_detached_elements();
         let batch_id = sync_batch.id;
         self.remove_commited_batch(LanePos::SYNC);
         return Some(batch_id);
@@ -200,6 +206,30 @@ impl LaneScheduler {
 }
 
 impl LaneScheduler {
+    /// The jobs of the sync batch being committed, if any.
+    pub(crate) fn sync_job_ids(&self) -> &Inlinable64Vec<JobId> {
+        static NO_JOB_IDS: Inlinable64Vec<JobId> = Inlinable64Vec::new_const();
+        self.sync_batch()
+            .map(|sync_batch| &sync_batch.job_ids)
+            .unwrap_or(&NO_JOB_IDS)
+    }
+
+    /// Unmount the elements with global keys that were detached during the commit and have not moved elsewhere.
+    fn unmount_detached_elements(&self) {
+        loop {
+            // Unmounting a detached element may in turn detach its descendants with global keys
+            let detached = self.global_keys.take_detached();
+            if detached.is_empty() {
+                break;
+            }
+            get_current_scheduler().sync_threadpool.scope(|scope| {
+                for node in detached {
+                    scope.spawn(|scope| node.unmount(scope, self))
+                }
+            });
+        }
+    }
+
     fn sync_batch(&self) -> Option<&BatchConf> {
         self.sync_lane
             .as_ref()
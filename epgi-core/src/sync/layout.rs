@@ -2,8 +2,8 @@ use crate::{
     foundation::{Container, HktContainer, Protocol},
     scheduler::get_current_scheduler,
     tree::{
-        ArcChildRenderObject, Layout, LayoutByParent, LayoutResults, Render, RenderBase,
-        RenderImpl, RenderObject,
+        ArcChildRenderObject, ImplFullRender, ImplMaybeLayer, Layout, LayoutByParent,
+        LayoutResults, Render, RenderBase, RenderImpl, RenderObject,
     },
 };
 
@@ -71,12 +71,17 @@ pub trait ChildRenderObjectLayoutExt<PP: Protocol> {
     fn layout(&self, constraints: &PP::Constraints);
 
     fn get_intrinsics(&self, intrinsics: &mut PP::Intrinsics);
+
+    /// The distance from the origin of this render object to the requested baseline.
+    ///
+    /// Can only be called after this render object has been laid out in the current layout pass.
+    fn get_distance_to_baseline(&self, baseline: &PP::Baseline) -> Option<f32>;
 }
 
 impl<R> ChildRenderObjectLayoutExt<R::ParentProtocol> for RenderObject<R>
 where
     R: Render,
-    R::Impl: ImplLayout<R>,
+    R::Impl: ImplFullRender<R>,
 {
     fn layout_use_size(
         &self,
@@ -107,6 +112,8 @@ where
             LayoutResults::new(constraints.clone(), size.clone(), memo),
             cache_fresh,
         );
+        // The new layout results come without a paint cache, so a layer has to paint itself again.
+        R::Impl::maybe_layer_mark_needs_paint(self);
 
         self.mark.clear_self_needs_layout();
         self.mark.set_parent_use_size();
@@ -138,6 +145,8 @@ where
             LayoutResults::new(constraints.clone(), size, memo),
             cache_fresh,
         );
+        // The new layout results come without a paint cache, so a layer has to paint itself again.
+        R::Impl::maybe_layer_mark_needs_paint(self);
         self.mark.clear_self_needs_layout();
         self.mark.try_clear_parent_use_size();
     }
@@ -161,6 +170,30 @@ where
             cache_fresh,
         );
         self.mark.clear_self_needs_layout();
+        // The parent depends on the intrinsics, so it has to be laid out again whenever this render object is.
+        self.mark.set_parent_use_size();
+    }
+
+    fn get_distance_to_baseline(
+        &self,
+        baseline: &<R::ParentProtocol as Protocol>::Baseline,
+    ) -> Option<f32> {
+        let Err(no_relayout_token) = self.mark.needs_layout() else {
+            panic!("A baseline can only be queried after the render object has been laid out")
+        };
+        let inner = self.inner.lock();
+        let layout_cache = inner
+            .cache
+            .layout_cache_ref(no_relayout_token.into())
+            .expect("A baseline can only be queried after the render object has been laid out");
+        // The parent depends on the baseline, so it has to be laid out again whenever this render object is.
+        self.mark.set_parent_use_size();
+        inner.render.compute_distance_to_baseline(
+            &layout_cache.layout_results.size,
+            &layout_cache.layout_results.memo,
+            &inner.children,
+            baseline,
+        )
     }
 }
 
@@ -35,6 +35,17 @@ pub trait AdapterRender: Send + Sync + Sized + 'static {
         intrinsics: &mut <Self::ParentProtocol as Protocol>::Intrinsics,
     );
 
+    #[allow(unused_variables)]
+    fn compute_distance_to_baseline(
+        &self,
+        size: &<Self::ParentProtocol as Protocol>::Size,
+        memo: &Self::LayoutMemo,
+        child: &ArcChildRenderObject<Self::ChildProtocol>,
+        baseline: &<Self::ParentProtocol as Protocol>::Baseline,
+    ) -> Option<f32> {
+        None
+    }
+
     #[allow(unused_variables)]
     fn perform_paint(
         &self,
@@ -137,6 +148,16 @@ where
     ) {
         R::compute_intrinsics(render, child, intrinsics)
     }
+
+    fn compute_distance_to_baseline(
+        render: &R,
+        size: &<Self::ParentProtocol as Protocol>::Size,
+        memo: &R::LayoutMemo,
+        [child]: &[ArcChildRenderObject<Self::ChildProtocol>; 1],
+        baseline: &<Self::ParentProtocol as Protocol>::Baseline,
+    ) -> Option<f32> {
+        R::compute_distance_to_baseline(render, size, memo, child, baseline)
+    }
 }
 
 impl<R> TemplateRender<R> for AdapterRenderTemplate
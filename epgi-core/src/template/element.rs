@@ -2,8 +2,8 @@ use std::any::Any;
 
 use crate::{
     foundation::{
-        Arc, Asc, BuildSuspendedError, ContainerOf, HktContainer, InlinableDwsizeVec, Protocol,
-        Provide, TypeKey,
+        Arc, Asc, BuildError, ContainerOf, HktContainer, InlinableDwsizeVec, Protocol, Provide,
+        TypeKey,
     },
     tree::{
         ArcChildElementNode, ArcChildWidget, ArcWidget, BuildContext,
@@ -39,7 +39,7 @@ pub trait TemplateElementBase<E> {
         ),
         (
             ContainerOf<Self::ChildContainer, ArcChildElementNode<Self::ChildProtocol>>,
-            BuildSuspendedError,
+            BuildError,
         ),
     >;
 
@@ -52,7 +52,7 @@ pub trait TemplateElementBase<E> {
             E,
             ContainerOf<Self::ChildContainer, ArcChildWidget<Self::ChildProtocol>>,
         ),
-        BuildSuspendedError,
+        BuildError,
     >;
 
     /// Returns the new parent data and corresponding render action for parent
@@ -105,7 +105,7 @@ where
         ),
         (
             ContainerOf<Self::ChildContainer, ArcChildElementNode<Self::ChildProtocol>>,
-            BuildSuspendedError,
+            BuildError,
         ),
     > {
         E::Template::perform_rebuild_element(
@@ -127,7 +127,7 @@ where
             Self,
             ContainerOf<Self::ChildContainer, ArcChildWidget<Self::ChildProtocol>>,
         ),
-        BuildSuspendedError,
+        BuildError,
     > {
         E::Template::perform_inflate_element(widget, ctx, provider_values)
     }
@@ -176,6 +176,16 @@ pub trait TemplateRenderElement<E: ElementBase> {
     ///
     /// Setting to false will always guarantee the correct behavior.
     const NOOP_UPDATE_RENDER_OBJECT: bool;
+
+    /// Update properties of render object that the element has resolved from its consumed values
+    ///
+    /// See [RenderElement::update_render_from_element].
+    #[allow(unused_variables)]
+    fn update_render_from_element(element: &E, render: &mut Self::Render) -> Option<RenderAction> {
+        None
+    }
+
+    const NOOP_UPDATE_RENDER_FROM_ELEMENT: bool = true;
 }
 
 impl<E> RenderElement for E
@@ -193,6 +203,12 @@ where
     fn update_render(render: &mut Self::Render, widget: &Self::ArcWidget) -> Option<RenderAction> {
         E::Template::update_render(render, widget)
     }
+
+    fn update_render_from_element(&self, render: &mut Self::Render) -> Option<RenderAction> {
+        E::Template::update_render_from_element(self, render)
+    }
+
+    const NOOP_UPDATE_RENDER_FROM_ELEMENT: bool = E::Template::NOOP_UPDATE_RENDER_FROM_ELEMENT;
 }
 
 pub trait TemplateProvideElement<E: ElementBase> {
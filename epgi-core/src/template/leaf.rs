@@ -2,8 +2,8 @@ use std::any::TypeId;
 
 use crate::{
     foundation::{
-        AnyRawPointer, Arc, ArrayContainer, BuildSuspendedError, Canvas, InlinableDwsizeVec,
-        PaintContext, Protocol, Provide,
+        AnyRawPointer, Arc, ArrayContainer, BuildError, Canvas, InlinableDwsizeVec, PaintContext,
+        Protocol, Provide,
     },
     tree::{
         ArcChildElementNode, ArcChildRenderObject, ArcChildWidget, ArcWidget, BuildContext,
@@ -36,7 +36,7 @@ pub trait LeafElement: Clone + Send + Sync + Sized + 'static {
         widget: &Self::ArcWidget,
         ctx: &mut BuildContext<'_>,
         provider_values: InlinableDwsizeVec<Arc<dyn Provide>>,
-    ) -> Result<(), BuildSuspendedError> {
+    ) -> Result<(), BuildError> {
         Ok(())
     }
 
@@ -44,7 +44,7 @@ pub trait LeafElement: Clone + Send + Sync + Sized + 'static {
         widget: &Self::ArcWidget,
         ctx: &mut BuildContext<'_>,
         provider_values: InlinableDwsizeVec<Arc<dyn Provide>>,
-    ) -> Result<Self, BuildSuspendedError>;
+    ) -> Result<Self, BuildError>;
 
     fn create_render(&self, widget: &Self::ArcWidget) -> Self::Render;
     /// Update necessary properties of render object given by the widget
@@ -92,10 +92,7 @@ where
             [ElementReconcileItem<Self::ChildProtocol>; 0],
             Option<ChildRenderObjectsUpdateCallback<Self::ChildContainer, Self::ChildProtocol>>,
         ),
-        (
-            [ArcChildElementNode<Self::ChildProtocol>; 0],
-            BuildSuspendedError,
-        ),
+        ([ArcChildElementNode<Self::ChildProtocol>; 0], BuildError),
     > {
         E::update_element(element, widget, ctx, provider_values)
             .map(|_| ([], None))
@@ -106,7 +103,7 @@ where
         widget: &Self::ArcWidget,
         ctx: &mut BuildContext<'_>,
         provider_values: InlinableDwsizeVec<Arc<dyn Provide>>,
-    ) -> Result<(E, [ArcChildWidget<Self::ChildProtocol>; 0]), BuildSuspendedError> {
+    ) -> Result<(E, [ArcChildWidget<Self::ChildProtocol>; 0]), BuildError> {
         E::create_element(widget, ctx, provider_values).map(|element| (element, []))
     }
 }
@@ -156,6 +153,15 @@ pub trait LeafRender: Send + Sync + Sized + 'static {
         intrinsics: &mut <Self::Protocol as Protocol>::Intrinsics,
     );
 
+    #[allow(unused_variables)]
+    fn compute_distance_to_baseline(
+        &self,
+        size: &<Self::Protocol as Protocol>::Size,
+        baseline: &<Self::Protocol as Protocol>::Baseline,
+    ) -> Option<f32> {
+        None
+    }
+
     #[allow(unused_variables)]
     fn perform_paint(
         &self,
@@ -209,6 +215,16 @@ where
     ) {
         R::compute_intrinsics(render, intrinsics)
     }
+
+    fn compute_distance_to_baseline(
+        render: &R,
+        size: &<R::Protocol as Protocol>::Size,
+        _memo: &(),
+        _children: &[ArcChildRenderObject<R::Protocol>; 0],
+        baseline: &<R::Protocol as Protocol>::Baseline,
+    ) -> Option<f32> {
+        R::compute_distance_to_baseline(render, size, baseline)
+    }
 }
 
 impl<R> TemplateRender<R> for LeafRenderTemplate
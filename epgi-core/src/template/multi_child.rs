@@ -2,8 +2,8 @@ use std::any::TypeId;
 
 use crate::{
     foundation::{
-        AnyRawPointer, Arc, Asc, BuildSuspendedError, Canvas, InlinableDwsizeVec, Key,
-        LayerProtocol, PaintContext, Protocol, Provide, TypeKey, VecContainer,
+        AnyRawPointer, Arc, Asc, BuildError, Canvas, InlinableDwsizeVec, Key, LayerProtocol,
+        PaintContext, Protocol, Provide, TypeKey, VecContainer,
     },
     tree::{
         default_reconcile_vec, ArcChildElementNode, ArcChildRenderObject, ArcChildWidget,
@@ -40,19 +40,24 @@ pub trait MultiChildElement: Clone + Send + Sync + Sized + 'static {
         &[]
     }
 
+    /// Called on every build, including the first one right after [MultiChildElement::create_element].
+    ///
+    /// This is where the element can keep anything it resolves from the consumed values,
+    /// to hand it over to the render object in [MultiChildElement::create_render] and [MultiChildElement::update_render_from_element].
     fn get_child_widgets(
-        element: Option<&mut Self>,
+        element: &mut Self,
         widget: &Self::ArcWidget,
         ctx: &mut BuildContext<'_>,
         provider_values: InlinableDwsizeVec<Arc<dyn Provide>>,
-    ) -> Result<Vec<ArcChildWidget<Self::ChildProtocol>>, BuildSuspendedError>;
+    ) -> Result<Vec<ArcChildWidget<Self::ChildProtocol>>, BuildError>;
 
     /// A major limitation to the Multi child element template is that,
     /// we cannot provide consumed values and build context during the creation the Element itself.
     /// On top of that, since you can no longer access hooks when creating the Element itself,
     /// it also becomes impossible to suspend safely during the process, hence the "must-succeed" signature.
     /// We expect most people does not need provider or hooks during this process.
-    /// If you do need, you can always perform relevant operations in the parent and pass it down in widget.
+    /// If you do need, you can always perform relevant operations in the parent and pass it down in widget,
+    /// or fill in the element from [MultiChildElement::get_child_widgets].
     fn create_element(widget: &Self::ArcWidget) -> Self;
 
     fn create_render(&self, widget: &Self::ArcWidget) -> Self::Render;
@@ -71,6 +76,16 @@ pub trait MultiChildElement: Clone + Send + Sync + Sized + 'static {
     ///
     /// Setting to false will always guarantee the correct behavior.
     const NOOP_UPDATE_RENDER_OBJECT: bool = false;
+
+    /// Update properties of render object that the element has resolved from its consumed values
+    ///
+    /// See [RenderElement::update_render_from_element](crate::tree::RenderElement::update_render_from_element).
+    #[allow(unused_variables)]
+    fn update_render_from_element(&self, render: &mut Self::Render) -> Option<RenderAction> {
+        None
+    }
+
+    const NOOP_UPDATE_RENDER_FROM_ELEMENT: bool = true;
 }
 
 impl<E, const PROVIDE_ELEMENT: bool> TemplateElementBase<E>
@@ -101,12 +116,9 @@ where
             Vec<ElementReconcileItem<E::ChildProtocol>>,
             Option<ChildRenderObjectsUpdateCallback<Self::ChildContainer, E::ChildProtocol>>,
         ),
-        (
-            Vec<ArcChildElementNode<E::ChildProtocol>>,
-            BuildSuspendedError,
-        ),
+        (Vec<ArcChildElementNode<E::ChildProtocol>>, BuildError),
     > {
-        let new_widgets = match E::get_child_widgets(Some(element), widget, ctx, provider_values) {
+        let new_widgets = match E::get_child_widgets(element, widget, ctx, provider_values) {
             Err(error) => return Err((children, error)),
             Ok(new_widgets) => new_widgets,
         };
@@ -121,9 +133,9 @@ where
         widget: &Self::ArcWidget,
         ctx: &mut BuildContext<'_>,
         provider_values: InlinableDwsizeVec<Arc<dyn Provide>>,
-    ) -> Result<(E, Vec<ArcChildWidget<E::ChildProtocol>>), BuildSuspendedError> {
-        let element = E::create_element(widget);
-        let child_widgets = E::get_child_widgets(None, widget, ctx, provider_values)?;
+    ) -> Result<(E, Vec<ArcChildWidget<E::ChildProtocol>>), BuildError> {
+        let mut element = E::create_element(widget);
+        let child_widgets = E::get_child_widgets(&mut element, widget, ctx, provider_values)?;
         Ok((element, child_widgets))
     }
 }
@@ -157,6 +169,12 @@ where
     }
 
     const NOOP_UPDATE_RENDER_OBJECT: bool = E::NOOP_UPDATE_RENDER_OBJECT;
+
+    fn update_render_from_element(element: &E, render: &mut Self::Render) -> Option<RenderAction> {
+        E::update_render_from_element(element, render)
+    }
+
+    const NOOP_UPDATE_RENDER_FROM_ELEMENT: bool = E::NOOP_UPDATE_RENDER_FROM_ELEMENT;
 }
 
 pub trait MultiChildProvideElement: MultiChildElement {
@@ -202,6 +220,17 @@ pub trait MultiChildRender: Send + Sync + Sized + 'static {
         children: &Vec<ArcChildRenderObject<Self::ChildProtocol>>,
         intrinsics: &mut <Self::ParentProtocol as Protocol>::Intrinsics,
     );
+
+    #[allow(unused_variables)]
+    fn compute_distance_to_baseline(
+        &self,
+        size: &<Self::ParentProtocol as Protocol>::Size,
+        memo: &Self::LayoutMemo,
+        children: &[ArcChildRenderObject<Self::ChildProtocol>],
+        baseline: &<Self::ParentProtocol as Protocol>::Baseline,
+    ) -> Option<f32> {
+        None
+    }
 }
 
 impl<
@@ -235,6 +264,16 @@ where
     ) {
         R::compute_intrinsics(render, children, intrinsics)
     }
+
+    fn compute_distance_to_baseline(
+        render: &R,
+        size: &<R::ParentProtocol as Protocol>::Size,
+        memo: &R::LayoutMemo,
+        children: &Vec<ArcChildRenderObject<R::ChildProtocol>>,
+        baseline: &<R::ParentProtocol as Protocol>::Baseline,
+    ) -> Option<f32> {
+        R::compute_distance_to_baseline(render, size, memo, children, baseline)
+    }
 }
 
 impl<
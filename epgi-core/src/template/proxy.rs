@@ -37,6 +37,16 @@ pub trait ProxyRender: Send + Sync + Sized + 'static {
         child.get_intrinsics(intrinsics)
     }
 
+    #[allow(unused_variables)]
+    fn compute_distance_to_baseline(
+        &self,
+        size: &<Self::Protocol as Protocol>::Size,
+        child: &ArcChildRenderObject<Self::Protocol>,
+        baseline: &<Self::Protocol as Protocol>::Baseline,
+    ) -> Option<f32> {
+        child.get_distance_to_baseline(baseline)
+    }
+
     #[allow(unused_variables)]
     fn perform_paint(
         &self,
@@ -134,6 +144,16 @@ where
     ) {
         R::compute_intrinsics(render, child, intrinsics)
     }
+
+    fn compute_distance_to_baseline(
+        render: &R,
+        size: &<R::Protocol as Protocol>::Size,
+        _memo: &(),
+        [child]: &[ArcChildRenderObject<R::Protocol>; 1],
+        baseline: &<R::Protocol as Protocol>::Baseline,
+    ) -> Option<f32> {
+        R::compute_distance_to_baseline(render, size, child, baseline)
+    }
 }
 
 impl<R> TemplateRender<R> for ProxyRenderTemplate
@@ -30,6 +30,14 @@ pub trait TemplateRenderBase<R> {
         children: &ContainerOf<Self::ChildContainer, ArcChildRenderObject<Self::ChildProtocol>>,
         intrinsics: &mut <Self::ParentProtocol as Protocol>::Intrinsics,
     );
+
+    fn compute_distance_to_baseline(
+        render: &R,
+        size: &<Self::ParentProtocol as Protocol>::Size,
+        memo: &Self::LayoutMemo,
+        children: &ContainerOf<Self::ChildContainer, ArcChildRenderObject<Self::ChildProtocol>>,
+        baseline: &<Self::ParentProtocol as Protocol>::Baseline,
+    ) -> Option<f32>;
 }
 
 impl<R> RenderBase for R
@@ -56,6 +64,16 @@ where
     ) {
         R::Template::compute_intrinsics(self, children, intrinsics)
     }
+
+    fn compute_distance_to_baseline(
+        &self,
+        size: &<Self::ParentProtocol as Protocol>::Size,
+        memo: &Self::LayoutMemo,
+        children: &ContainerOf<Self::ChildContainer, ArcChildRenderObject<Self::ChildProtocol>>,
+        baseline: &<Self::ParentProtocol as Protocol>::Baseline,
+    ) -> Option<f32> {
+        R::Template::compute_distance_to_baseline(self, size, memo, children, baseline)
+    }
 }
 
 pub trait TemplateRender<R: RenderBase> {
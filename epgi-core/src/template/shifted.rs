@@ -31,6 +31,17 @@ pub trait ShiftedRender: Send + Sync + Sized + 'static {
         intrinsics: &mut <Self::Protocol as Protocol>::Intrinsics,
     );
 
+    #[allow(unused_variables)]
+    fn compute_distance_to_baseline(
+        &self,
+        size: &<Self::Protocol as Protocol>::Size,
+        memo: &Self::LayoutMemo,
+        child: &ArcChildRenderObject<Self::Protocol>,
+        baseline: &<Self::Protocol as Protocol>::Baseline,
+    ) -> Option<f32> {
+        None
+    }
+
     fn perform_layout(
         &mut self,
         constraints: &<Self::Protocol as Protocol>::Constraints,
@@ -138,6 +149,16 @@ where
     ) {
         R::compute_intrinsics(render, child, intrinsics)
     }
+
+    fn compute_distance_to_baseline(
+        render: &R,
+        size: &<R::Protocol as Protocol>::Size,
+        memo: &R::LayoutMemo,
+        [child]: &[ArcChildRenderObject<R::Protocol>; 1],
+        baseline: &<R::Protocol as Protocol>::Baseline,
+    ) -> Option<f32> {
+        R::compute_distance_to_baseline(render, size, memo, child, baseline)
+    }
 }
 
 impl<R> TemplateRender<R> for ShiftedRenderTemplate
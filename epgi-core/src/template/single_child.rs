@@ -2,8 +2,7 @@ use std::any::Any;
 
 use crate::{
     foundation::{
-        Arc, ArrayContainer, Asc, BuildSuspendedError, InlinableDwsizeVec, Protocol, Provide,
-        TypeKey,
+        Arc, ArrayContainer, Asc, BuildError, InlinableDwsizeVec, Protocol, Provide, TypeKey,
     },
     tree::{
         ArcChildElementNode, ArcChildWidget, ArcWidget, BuildContext,
@@ -35,7 +34,7 @@ pub trait SingleChildElement: Clone + Send + Sync + Sized + 'static {
         widget: &Self::ArcWidget,
         ctx: &mut BuildContext<'_>,
         provider_values: InlinableDwsizeVec<Arc<dyn Provide>>,
-    ) -> Result<ArcChildWidget<Self::ChildProtocol>, BuildSuspendedError>;
+    ) -> Result<ArcChildWidget<Self::ChildProtocol>, BuildError>;
 
     /// A major limitation to the single child element template is that,
     /// we cannot provide consumed values and build context during the creation the Element itself.
@@ -91,10 +90,7 @@ where
             [ElementReconcileItem<E::ChildProtocol>; 1],
             Option<ChildRenderObjectsUpdateCallback<Self::ChildContainer, E::ChildProtocol>>,
         ),
-        (
-            [ArcChildElementNode<E::ChildProtocol>; 1],
-            BuildSuspendedError,
-        ),
+        ([ArcChildElementNode<E::ChildProtocol>; 1], BuildError),
     > {
         let child_widget = match E::get_child_widget(Some(element), widget, ctx, provider_values) {
             Err(error) => return Err(([child], error)),
@@ -114,7 +110,7 @@ where
         widget: &Self::ArcWidget,
         ctx: &mut BuildContext<'_>,
         provider_values: InlinableDwsizeVec<Arc<dyn Provide>>,
-    ) -> Result<(E, [ArcChildWidget<E::ChildProtocol>; 1]), BuildSuspendedError> {
+    ) -> Result<(E, [ArcChildWidget<E::ChildProtocol>; 1]), BuildError> {
         let element = E::create_element(widget);
         let child_widget = E::get_child_widget(None, widget, ctx, provider_values)?;
         Ok((element, [child_widget]))
@@ -12,6 +12,9 @@ pub use r#impl::*;
 mod async_queue;
 pub(crate) use async_queue::*;
 
+mod error_catcher;
+pub(crate) use error_catcher::*;
+
 mod mark;
 pub(crate) use mark::*;
 
@@ -25,8 +28,8 @@ mod waker;
 pub(crate) use waker::*;
 
 use crate::foundation::{
-    Arc, Asc, Aweak, BuildSuspendedError, ContainerOf, HktContainer, InlinableDwsizeVec, Protocol,
-    Provide, PtrEq, TypeKey,
+    Arc, Asc, Aweak, BuildError, ContainerOf, HktContainer, InlinableDwsizeVec, Protocol, Provide,
+    PtrEq, TypeKey,
 };
 
 use super::{
@@ -70,7 +73,7 @@ pub trait ElementBase: Clone + Send + Sync + Sized + 'static {
         ),
         (
             ContainerOf<Self::ChildContainer, ArcChildElementNode<Self::ChildProtocol>>,
-            BuildSuspendedError,
+            BuildError,
         ),
     >;
 
@@ -83,7 +86,7 @@ pub trait ElementBase: Clone + Send + Sync + Sized + 'static {
             Self,
             ContainerOf<Self::ChildContainer, ArcChildWidget<Self::ChildProtocol>>,
         ),
-        BuildSuspendedError,
+        BuildError,
     >;
 
     /// Returns the new parent data and corresponding render action for parent
@@ -198,6 +201,18 @@ pub trait RenderElement: ElementBase {
     ///
     /// Setting to false will always guarantee the correct behavior.
     const NOOP_UPDATE_RENDER_OBJECT: bool = false;
+
+    /// Update properties of render object that the element has resolved from its consumed values
+    ///
+    /// Called during the commit phase after every rebuild, since a rebuild caused by a provider does not come with a new widget.
+    /// Always called after [RenderElement::update_render].
+    #[allow(unused_variables)]
+    fn update_render_from_element(&self, render: &mut Self::Render) -> Option<RenderAction> {
+        None
+    }
+
+    /// Whether [RenderElement::update_render_from_element] is a no-op and always returns None
+    const NOOP_UPDATE_RENDER_FROM_ELEMENT: bool = true;
 }
 
 pub trait ProvideElement: ElementBase {
@@ -1,5 +1,7 @@
 use crate::{
-    foundation::{Asc, ContainerOf, InlinableDwsizeVec, InlinableUsizeVec, VecPushLastExt},
+    foundation::{
+        Asc, ContainerOf, InlinableDwsizeVec, InlinableUsizeVec, TracedError, VecPushLastExt,
+    },
     scheduler::LanePos,
     sync::{CommitBarrier, LaneScheduler},
     tree::{ArcElementContextNode, ElementBase, HooksWithEffects, WorkContext, WorkHandle},
@@ -357,6 +359,8 @@ pub(crate) struct BuildSuspendResults {
     // widget: E::ArcWidget,
     pub(crate) hooks: HooksWithEffects,
     pub(crate) waker: ArcSuspendWaker,
+    /// Set if the build has failed. The error is reported to the nearest error boundary when this result is committed.
+    pub(crate) error: Option<TracedError>,
     // // ~~To check whether we store this result while inflating (new inflate or reconcile on InflateSuspended) or rebuilding~~
     // // Actually we don't need to check. This state can be inferred by the ElementNode state when initiating and commiting the work.
     // // (Because any change to the ElementNode state will cancel the async work, so we can be confident that the ElementNode state has not changed between committing and initiating the async work)
@@ -364,8 +368,16 @@ pub(crate) struct BuildSuspendResults {
 }
 
 impl BuildSuspendResults {
-    pub fn new(hooks: HooksWithEffects, waker: ArcSuspendWaker) -> Self {
-        Self { hooks, waker }
+    pub fn new(
+        hooks: HooksWithEffects,
+        waker: ArcSuspendWaker,
+        error: Option<TracedError>,
+    ) -> Self {
+        Self {
+            hooks,
+            waker,
+            error,
+        }
     }
 }
 
@@ -1,14 +1,19 @@
-use std::sync::atomic::{AtomicBool, Ordering::*};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering::*};
 
+use crossbeam::epoch::{self, Atomic, Owned};
 use hashbrown::HashMap;
 
 use crate::{
     foundation::{Arc, Asc, Aweak, SyncMutex, TypeKey},
     scheduler::{JobBuilder, JobId},
-    tree::Update,
+    sync::ImplCommitRenderObject,
+    tree::{ArcWidget, Update},
 };
 
-use super::{AweakAnyElementNode, Element, ElementMark, ImplProvide, ProviderObject};
+use super::{
+    AweakAnyElementNode, Element, ElementMark, ErrorCatcher, FullElement, ImplProvide,
+    ProviderObject,
+};
 
 pub type ArcElementContextNode = Arc<ElementContextNode>;
 pub type AweakElementContextNode = Aweak<ElementContextNode>;
@@ -18,9 +23,12 @@ pub type ProviderElementMap = HashMap<TypeKey, ArcElementContextNode>;
 pub struct ElementContextNode {
     pub(crate) element_node: AweakAnyElementNode,
     pub(crate) unmounted: AtomicBool,
-    pub(crate) depth: usize,
-    // The context tree points upward, so a strong pointer
-    parent: Option<ArcElementContextNode>,
+    // Both the depth and the parent change when an element with a GlobalKey is moved under a new parent
+    depth: AtomicUsize,
+    // The context tree points upward, so a strong pointer. Null for the root.
+    // Walks up the tree read it under an epoch guard instead of a lock, and a moved node retires
+    // its old parent pointer only after every walk that may still be reading it has finished.
+    parent: Atomic<ArcElementContextNode>,
 
     pub(crate) mark: ElementMark,
     pub(crate) mailbox: SyncMutex<HashMap<JobId, Vec<Update>>>,
@@ -31,6 +39,9 @@ pub struct ElementContextNode {
     // // Abandon this optimization. Provider widget usually has only one child anyway
     // pub provider_map_for_child: Asc<ProviderElementMap>,
     pub(crate) provider_object: Option<Box<ProviderObject>>,
+    pub(crate) error_catcher: Option<Box<ErrorCatcher>>,
+    // For tracing build errors
+    pub(crate) widget_type_name: &'static str,
     // pub(crate) has_render: bool,
 }
 
@@ -43,44 +54,61 @@ impl ElementContextNode {
         node: AweakAnyElementNode,
         parent_context: Option<ArcElementContextNode>,
         provider: Option<Box<ProviderObject>>,
+        error_catcher: Option<Box<ErrorCatcher>>,
+        widget_type_name: &'static str,
     ) -> Self {
         if let Some(parent_context) = parent_context {
             Self {
                 element_node: node,
                 unmounted: false.into(),
-                depth: parent_context.depth + 1,
+                depth: AtomicUsize::new(parent_context.depth() + 1),
                 mark: ElementMark::new(),
                 mailbox: Default::default(),
                 provider_map: parent_context.get_provider_map_for_child(),
                 provider_object: provider,
-                parent: Some(parent_context),
+                error_catcher,
+                widget_type_name,
+                parent: Atomic::new(parent_context),
             }
         } else {
             Self {
                 element_node: node,
                 unmounted: false.into(),
-                depth: 0,
+                depth: AtomicUsize::new(0),
                 mark: ElementMark::new(),
                 mailbox: Default::default(),
                 provider_map: Default::default(),
                 provider_object: provider,
-                parent: None,
+                error_catcher,
+                widget_type_name,
+                parent: Atomic::null(),
             }
         }
     }
 
-    pub(crate) fn new_for<E: Element>(
+    pub(crate) fn new_for<E: FullElement>(
         node: AweakAnyElementNode,
         parent_context: Option<ArcElementContextNode>,
         widget: &E::ArcWidget,
     ) -> Self {
         let provider = <E as Element>::Impl::option_get_provided_key_value_pair(widget)
             .map(|(provided, type_key)| Box::new(ProviderObject::new(provided, type_key)));
-        Self::new(node, parent_context, provider)
+        let error_catcher =
+            <E as FullElement>::Impl::CATCH_BUILD_ERRORS.then(|| Box::new(ErrorCatcher::new()));
+        let widget_type_name = widget.clone().into_any_widget().widget_type_name();
+        Self::new(
+            node,
+            parent_context,
+            provider,
+            error_catcher,
+            widget_type_name,
+        )
     }
 
     #[inline(always)]
-    fn get_provider_map_for_child(self: &Arc<ElementContextNode>) -> Asc<ProviderElementMap> {
+    pub(crate) fn get_provider_map_for_child(
+        self: &Arc<ElementContextNode>,
+    ) -> Asc<ProviderElementMap> {
         if let Some(provider) = self.provider_object.as_ref() {
             let mut provider_map = self.provider_map.as_ref().clone();
             provider_map.insert(provider.type_key, self.clone());
@@ -125,11 +153,105 @@ impl ElementContextNode {
         NotUnmountedToken(())
     }
 
-    pub(crate) fn parent(
+    pub(crate) fn parent(&self, not_unmounted: NotUnmountedToken) -> Option<ArcElementContextNode> {
+        let _ = not_unmounted;
+        let guard = epoch::pin();
+        // SAFETY: `reparent` is the only place that replaces a non-null parent pointer, and it
+        // retires the old one with `defer_destroy`, which frees it only after every guard pinned
+        // before the swap has been dropped. The pointer loaded here was reachable while our guard
+        // was pinned, so it stays allocated until the `Arc` has been cloned out of it.
+        unsafe { self.parent.load(Acquire, &guard).as_ref() }.cloned()
+    }
+
+    /// Visit the ancestors of this node from its parent upward, until `visit` returns false.
+    ///
+    /// Unlike repeatedly calling [`Self::parent`], this neither locks nor clones anything along the way.
+    pub(crate) fn visit_ancestors(
         &self,
         not_unmounted: NotUnmountedToken,
-    ) -> &Option<ArcElementContextNode> {
+        mut visit: impl FnMut(&ElementContextNode) -> bool,
+    ) {
         let _ = not_unmounted;
-        &self.parent
+        let guard = epoch::pin();
+        // SAFETY: `self` is borrowed, so its parent pointer can only be replaced by `reparent`,
+        // which defers freeing the old pointer until the guard pinned above has been dropped.
+        // The loaded pointer therefore outlives the walk, and so does the `Arc` it holds.
+        let mut curr = unsafe { self.parent.load(Acquire, &guard).as_ref() };
+        while let Some(node) = curr {
+            if !visit(node) {
+                return;
+            }
+            // SAFETY: `node` is kept alive by the `Arc` behind the pointer loaded in the previous
+            // step, which the same guard keeps allocated, so `node` cannot run its `Drop` and free
+            // its parent pointer. A concurrent `reparent` of `node` defers freeing the old pointer
+            // past the same guard, so the loaded pointer stays valid for the rest of the walk.
+            curr = unsafe { node.parent.load(Acquire, &guard).as_ref() };
+        }
+    }
+
+    pub(crate) fn depth(&self) -> usize {
+        self.depth.load(Relaxed)
+    }
+
+    /// Whether this node can be moved under `new_parent` without changing what its subtree reads from providers.
+    ///
+    /// A node can never be moved under its own subtree.
+    pub(crate) fn can_reparent_to(self: &Arc<Self>, new_parent: &ArcElementContextNode) -> bool {
+        if Arc::ptr_eq(new_parent, self) {
+            return false;
+        }
+        let mut is_descendant = false;
+        new_parent.visit_ancestors(new_parent.assert_not_unmounted(), |node| {
+            is_descendant = std::ptr::eq(node, Arc::as_ptr(self));
+            !is_descendant
+        });
+        if is_descendant {
+            return false;
+        }
+        let new_provider_map = new_parent.get_provider_map_for_child();
+        new_provider_map.len() == self.provider_map.len()
+            && new_provider_map.iter().all(|(type_key, provider)| {
+                self.provider_map
+                    .get(type_key)
+                    .is_some_and(|old_provider| Arc::ptr_eq(old_provider, provider))
+            })
+    }
+
+    pub(crate) fn reparent(&self, new_parent: ArcElementContextNode) {
+        let depth = new_parent.depth() + 1;
+        let guard = epoch::pin();
+        let old_parent = self.parent.swap(Owned::new(new_parent), AcqRel, &guard);
+        if !old_parent.is_null() {
+            // SAFETY: After the swap the old pointer can no longer be loaded from this node, and
+            // every walk that loaded it before holds a guard pinned before the swap. It is freed
+            // only after all of those guards have been dropped.
+            unsafe { guard.defer_destroy(old_parent) };
+        }
+        self.update_depth(depth);
+    }
+
+    fn update_depth(&self, depth: usize) {
+        if self.depth.swap(depth, Relaxed) == depth {
+            return;
+        }
+        if let Some(node) = self.element_node.upgrade() {
+            for child in node.children_any() {
+                child.context_ref().update_depth(depth + 1);
+            }
+        }
+    }
+}
+
+impl Drop for ElementContextNode {
+    fn drop(&mut self) {
+        // SAFETY: `unprotected` is sound here because no other thread can be reading this pointer.
+        // Walks reach a node only through `&self` or through a parent pointer whose `Arc` keeps
+        // the node alive, and neither can exist while the node is being dropped. `try_into_owned`
+        // takes back the allocation created by `Atomic::new` or by the last `reparent`.
+        drop(unsafe {
+            self.parent
+                .load(Relaxed, epoch::unprotected())
+                .try_into_owned()
+        });
     }
 }
@@ -0,0 +1,89 @@
+use crate::foundation::{Arc, SyncMutex, TracedError};
+
+use super::ElementContextNode;
+
+/// The error slot of an [`ErrorBoundary`](crate::nodes::ErrorBoundary), stored in its element context.
+///
+/// Failed descendants report to the nearest catcher during the build,
+/// and the boundary picks the error up when its own commit runs after its children.
+pub(crate) struct ErrorCatcher {
+    state: SyncMutex<ErrorCatcherState>,
+}
+
+enum ErrorCatcherState {
+    Clear,
+    /// Reported by a descendant, but not yet handled by the boundary.
+    Caught(Arc<TracedError>),
+    /// The boundary is showing its fallback for this error.
+    Handled(Arc<TracedError>),
+}
+
+impl ErrorCatcher {
+    pub(crate) fn new() -> Self {
+        Self {
+            state: SyncMutex::new(ErrorCatcherState::Clear),
+        }
+    }
+
+    /// Returns the error back if this boundary is already showing its fallback,
+    /// in which case the error came from the fallback and should go to the next boundary up.
+    fn catch(&self, error: TracedError) -> Result<(), TracedError> {
+        let mut state = self.state.lock();
+        use ErrorCatcherState::*;
+        match &*state {
+            Clear => *state = Caught(Arc::new(error)),
+            // Only the first error is shown
+            Caught(_) => {}
+            Handled(_) => return Err(error),
+        }
+        Ok(())
+    }
+
+    pub(crate) fn take_caught(&self) -> Option<Arc<TracedError>> {
+        let mut state = self.state.lock();
+        let ErrorCatcherState::Caught(error) = &*state else {
+            return None;
+        };
+        let error = error.clone();
+        *state = ErrorCatcherState::Handled(error.clone());
+        Some(error)
+    }
+
+    pub(crate) fn handled(&self) -> Option<Arc<TracedError>> {
+        match &*self.state.lock() {
+            ErrorCatcherState::Handled(error) => Some(error.clone()),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn reset(&self) {
+        *self.state.lock() = ErrorCatcherState::Clear;
+    }
+}
+
+impl ElementContextNode {
+    /// Hand a build error to the nearest error boundary above this element,
+    /// tracing every element it passes on the way.
+    ///
+    /// Panics if no boundary is there to catch it.
+    pub(crate) fn report_build_error(&self, error: TracedError) {
+        let mut error = error;
+        error.push_trace(self.widget_type_name);
+        let mut parent = self.parent(self.assert_not_unmounted());
+        while let Some(context) = parent {
+            error.push_trace(context.widget_type_name);
+            if let Some(catcher) = &context.error_catcher {
+                match catcher.catch(error) {
+                    Ok(()) => return,
+                    Err(uncaught) => error = uncaught,
+                }
+            }
+            parent = context.parent(context.assert_not_unmounted());
+        }
+        panic!(
+            "Uncaught build error: {}\n    in {}",
+            error,
+            error.trace().join("\n    in ")
+        )
+    }
+}
@@ -1,6 +1,6 @@
 use crate::{
     foundation::{Arc, ArrayContainer, Asc, ContainerOf, Protocol, Provide, TypeKey},
-    nodes::{RenderSuspense, SuspenseElement},
+    nodes::{ErrorBoundaryElement, RenderErrorBoundary, RenderSuspense, SuspenseElement},
     sync::ImplCommitRenderObject,
     tree::{ArcAnyRenderObject, ArcChildRenderObject, RenderObject},
 };
@@ -176,3 +176,27 @@ impl<P: Protocol, const PROVIDE_ELEMENT: bool> ImplElementNode<SuspenseElement<P
             .map(|render_object| render_object.clone() as _)
     }
 }
+
+impl<P: Protocol, const PROVIDE_ELEMENT: bool> ImplElementNode<ErrorBoundaryElement<P>>
+    for ElementImpl<true, PROVIDE_ELEMENT>
+{
+    type OptionArcRenderObject = Option<Arc<RenderObject<RenderErrorBoundary<P>>>>;
+
+    fn get_current_subtree_render_object(
+        render_object: &Self::OptionArcRenderObject,
+        _children: &[ArcChildElementNode<P>; 1],
+    ) -> Option<ArcChildRenderObject<P>> {
+        render_object
+            .as_ref()
+            .map(|render_object| render_object.clone() as _)
+    }
+
+    const HAS_RENDER: bool = true;
+    fn get_render_object(
+        option_render_object: &Self::OptionArcRenderObject,
+    ) -> Option<ArcAnyRenderObject> {
+        option_render_object
+            .as_ref()
+            .map(|render_object| render_object.clone() as _)
+    }
+}
@@ -118,21 +118,26 @@ impl ElementContextNode {
     //     return true;
     // }
 
+    /// Mark the async lanes of this subtree on its ancestors again, after it has been moved under a new parent.
+    ///
+    /// The sync lane does not need marking, since the sync build is already reconciling the moved node.
+    pub(crate) fn mark_up_async_lanes(&self, not_unmounted: NotUnmountedToken) {
+        let lanes = self.mailbox_lanes() | self.consumer_lanes() | self.descendant_lanes();
+        for lane_pos in lanes.iter() {
+            if !lane_pos.is_sync() {
+                self.mark_up(lane_pos, not_unmounted)
+            }
+        }
+    }
+
     fn mark_up(&self, lane_pos: LanePos, not_unmounted: NotUnmountedToken) {
-        let mut curr = self;
-        loop {
-            let Some(parent) = curr.parent(not_unmounted) else {
-                break;
-            };
-            curr = parent.as_ref();
-            let old_descendant_lanes = curr
+        self.visit_ancestors(not_unmounted, |parent| {
+            let old_descendant_lanes = parent
                 .mark
                 .descendant_lanes
                 .fetch_insert_single(lane_pos, Relaxed);
-            if old_descendant_lanes.contains(lane_pos) {
-                break;
-            }
-        }
+            !old_descendant_lanes.contains(lane_pos)
+        })
     }
 
     // pub(crate) fn dec_async_consumer_root(&self, pos: u8) {
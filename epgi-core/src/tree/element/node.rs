@@ -1,4 +1,7 @@
-use crate::foundation::{Arc, Key, Protocol, SyncMutex};
+use crate::{
+    foundation::{Arc, Key, Protocol, SyncMutex},
+    tree::ArcAnyWidget,
+};
 
 use super::{
     ArcAnyElementNode, ArcAnyRenderObject, ArcChildElementNode, ArcChildRenderObject,
@@ -49,6 +52,9 @@ pub trait AnyElementNode:
     // fn push_job(&self, job_id: JobId);
     fn render_object(&self) -> Result<ArcAnyRenderObject, &str>;
     // fn context(&self) -> &ArcElementContextNode;
+    fn widget_any(&self) -> ArcAnyWidget;
+    /// The children on the mainline. Elements that suspended during inflation have no children.
+    fn children_any(&self) -> Vec<ArcAnyElementNode>;
 }
 
 pub trait ChildElementNode<PP: Protocol>:
@@ -147,4 +153,27 @@ impl<E: FullElement> AnyElementNode for ElementNode<E> {
         <E as Element>::Impl::get_render_object(render_object)
             .ok_or("Render object call should only be called on after render object is attached")
     }
+
+    fn widget_any(&self) -> ArcAnyWidget {
+        self.widget().into_any_widget()
+    }
+
+    fn children_any(&self) -> Vec<ArcAnyElementNode> {
+        let children = {
+            let snapshot = self.snapshot.lock();
+            snapshot
+                .inner
+                .mainline_ref()
+                .and_then(|mainline| mainline.state.as_ref())
+                .and_then(MainlineState::children_cloned)
+        };
+        children
+            .map(|children| {
+                children
+                    .into_iter()
+                    .map(|child| child.as_arc_any())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
 }
@@ -65,6 +65,18 @@ impl SuspendWaker {
         })
     }
 
+    /// A waker that will never wake, for builds that have failed rather than suspended.
+    pub(crate) fn new_aborted(
+        node: AweakElementContextNode,
+        lane_pos: LanePos,
+    ) -> std::sync::Arc<Self> {
+        std::sync::Arc::new(Self {
+            state: Atomic::new(SuspendWakerState::Aborted),
+            lane_pos: Atomic::new(lane_pos),
+            element_context: node,
+        })
+    }
+
     pub(crate) fn is_aborted(&self) -> bool {
         debug_assert_sync_phase();
         // Relaxed is okay because we assert now we are in sync phase
@@ -47,6 +47,21 @@ pub trait RenderBase: Send + Sync + Sized + 'static {
         children: &ContainerOf<Self::ChildContainer, ArcChildRenderObject<Self::ChildProtocol>>,
         intrinsics: &mut <Self::ParentProtocol as Protocol>::Intrinsics,
     );
+
+    /// The distance from the origin of this render object to the requested baseline,
+    /// computed from the results of the most recent layout.
+    ///
+    /// Returns `None` if this render object does not have such a baseline.
+    #[allow(unused_variables)]
+    fn compute_distance_to_baseline(
+        &self,
+        size: &<Self::ParentProtocol as Protocol>::Size,
+        memo: &Self::LayoutMemo,
+        children: &ContainerOf<Self::ChildContainer, ArcChildRenderObject<Self::ChildProtocol>>,
+        baseline: &<Self::ParentProtocol as Protocol>::Baseline,
+    ) -> Option<f32> {
+        None
+    }
 }
 
 pub trait Render: RenderBase + HitTest {
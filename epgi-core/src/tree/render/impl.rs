@@ -52,6 +52,10 @@ pub trait ImplMaybeLayer<R: Render<Impl = Self>> {
     ) -> Option<RenderAction>
     where
         Self: ImplFullRender<R>;
+    /// Schedules a repaint of a layer whose paint cache has been dropped by a new layout.
+    fn maybe_layer_mark_needs_paint(render_object: &RenderObject<R>)
+    where
+        Self: ImplFullRender<R>;
 }
 
 impl<
@@ -94,6 +98,12 @@ where
     {
         propagated_render_action
     }
+
+    fn maybe_layer_mark_needs_paint(_render_object: &RenderObject<R>)
+    where
+        Self: ImplFullRender<R>,
+    {
+    }
 }
 
 impl<
@@ -147,4 +157,13 @@ where
         }
         return propagated_render_action;
     }
+
+    fn maybe_layer_mark_needs_paint(render_object: &RenderObject<R>)
+    where
+        Self: ImplFullRender<R>,
+    {
+        get_current_scheduler().push_layer_render_objects_needing_paint(
+            Self::into_aweak_any_layer_render_object(render_object.this.clone()),
+        );
+    }
 }
@@ -12,6 +12,12 @@ pub struct RenderMark {
     pub(crate) is_detached: AtomicBool,
 }
 
+/// Whether the parent used the size of this render object when it was last laid out,
+/// stamped with the layout pass that decided it.
+///
+/// Within a single layout pass, a parent may lay out a child, query its intrinsics and query its baselines, in any order.
+/// Whichever of them uses the size of the child sets the flag, and the flag then stays set for the rest of the pass,
+/// since a later layout without using the size does not make the parent independent of the child.
 struct ParentUseSize(AtomicUsize);
 
 impl ParentUseSize {
@@ -20,36 +26,29 @@ impl ParentUseSize {
         self.0.load(Relaxed) & Self::FLAG_MASK != 0
     }
 
+    /// Clears the flag, unless the parent has already used the size within the current layout pass.
     fn try_clear(&self) {
         loop {
             let layout_pass = LAYOUT_PASS_ID.load(Relaxed);
             let stamp = self.0.load(Relaxed);
-            if stamp & (!Self::FLAG_MASK) != layout_pass {
-                let new_stamp = layout_pass & (!Self::FLAG_MASK);
-                if let Ok(_) = self
-                    .0
-                    .compare_exchange_weak(stamp, new_stamp, Relaxed, Relaxed)
-                {
-                    break;
-                }
+            if stamp & (!Self::FLAG_MASK) == layout_pass {
+                break;
+            }
+            let new_stamp = layout_pass & (!Self::FLAG_MASK);
+            if self
+                .0
+                .compare_exchange_weak(stamp, new_stamp, Relaxed, Relaxed)
+                .is_ok()
+            {
+                break;
             }
         }
     }
 
+    /// Sets the flag. A later clear within the same layout pass is ignored.
     fn set(&self) {
-        loop {
-            let layout_pass = LAYOUT_PASS_ID.load(Relaxed);
-            let stamp = self.0.load(Relaxed);
-            if stamp & (!Self::FLAG_MASK) != layout_pass {
-                let new_stamp = layout_pass | Self::FLAG_MASK;
-                if let Ok(_) = self
-                    .0
-                    .compare_exchange_weak(stamp, new_stamp, Relaxed, Relaxed)
-                {
-                    break;
-                }
-            }
-        }
+        let layout_pass = LAYOUT_PASS_ID.load(Relaxed);
+        self.0.store(layout_pass | Self::FLAG_MASK, Relaxed);
     }
 }
 
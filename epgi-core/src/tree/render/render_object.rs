@@ -30,6 +30,8 @@ where
     R: Render,
 {
     pub(crate) element_context: ArcElementContextNode,
+    // Lets a layer schedule its own repaint from within a layout walk, which only borrows it
+    pub(crate) this: Aweak<Self>,
     pub(crate) mark: RenderMark,
     pub(crate) layer_mark: <R::Impl as ImplRenderObject<R>>::LayerMark,
     pub(crate) inner: SyncMutex<RenderObjectInner<R, <R::Impl as ImplRenderObject<R>>::LayerCache>>,
@@ -40,9 +42,10 @@ impl<R: Render> RenderObject<R> {
         render: R,
         children: ContainerOf<R::ChildContainer, ArcChildRenderObject<R::ChildProtocol>>,
         context: ArcElementContextNode,
-    ) -> Self {
-        Self {
+    ) -> Arc<Self> {
+        Arc::new_cyclic(|this| Self {
             element_context: context,
+            this: this.clone(),
             mark: RenderMark::new(),
             layer_mark: Default::default(),
             inner: SyncMutex::new(RenderObjectInner {
@@ -51,7 +54,7 @@ impl<R: Render> RenderObject<R> {
                 render,
                 children,
             }),
-        }
+        })
     }
 
     pub fn update<T>(
@@ -125,12 +128,35 @@ pub trait ChildRenderObject<PP: Protocol>:
     + Sync
 {
     fn as_arc_any_render_object(self: Arc<Self>) -> ArcAnyRenderObject;
+
+    /// The size and the paint offset recorded by the most recent layout and paint.
+    ///
+    /// The offset is relative to the layer this render object paints into.
+    /// Returns `None` if the render object is pending layout or has not been painted since its last layout.
+    fn last_size_and_paint_offset(&self) -> Option<(PP::Size, PP::Offset)>;
 }
 
 impl<R: FullRender> ChildRenderObject<R::ParentProtocol> for RenderObject<R> {
     fn as_arc_any_render_object(self: Arc<Self>) -> ArcAnyRenderObject {
         self
     }
+
+    fn last_size_and_paint_offset(
+        &self,
+    ) -> Option<(
+        <R::ParentProtocol as Protocol>::Size,
+        <R::ParentProtocol as Protocol>::Offset,
+    )> {
+        let Err(no_relayout_token) = self.mark.needs_layout() else {
+            return None;
+        };
+        let inner = self.inner.lock();
+        let layout_cache = inner.cache.layout_cache_ref(no_relayout_token.into())?;
+        Some((
+            layout_cache.layout_results.size.clone(),
+            layout_cache.paint_offset.clone()?,
+        ))
+    }
 }
 
 pub trait AnyRenderObject: crate::sync::AnyRenderObjectLayoutExt + AsAny + Send + Sync {
@@ -159,6 +159,7 @@ pub trait AnyWidget: std::fmt::Debug + 'static + Send + Sync {
         self: Arc<Self>,
         // context: InflateContext,
     ) -> ArcAnyElementNode;
+    fn widget_type_name(&self) -> &'static str;
 }
 
 impl<T> AnyWidget for T
@@ -212,6 +213,10 @@ where
         //     todo!()// parent,
         // )) as ArcAnyElement
     }
+
+    fn widget_type_name(&self) -> &'static str {
+        std::any::type_name::<T>()
+    }
 }
 
 pub trait ArcAnyWidgetExt {
@@ -0,0 +1,116 @@
+use epgi_2d::TextSpan;
+use epgi_common::{InlineSpan, RichText, Text};
+use epgi_core::{
+    foundation::Key,
+    tree::{ArcAnyElementNode, ArcAnyWidget, Widget},
+};
+
+/// Locates elements in the element tree by inspecting their widgets.
+///
+/// See also: [`crate::WidgetTester::find_all`]
+pub struct Finder {
+    description: String,
+    matcher: Box<dyn Fn(&ArcAnyWidget) -> bool + Send + Sync>,
+}
+
+impl Finder {
+    pub fn by_predicate(
+        description: impl Into<String>,
+        predicate: impl Fn(&ArcAnyWidget) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            description: description.into(),
+            matcher: Box::new(predicate),
+        }
+    }
+
+    /// Matches widgets of the exact type `W`.
+    pub fn by_type<W: Widget>() -> Self {
+        Self::by_predicate(
+            format!("widget of type {}", std::any::type_name::<W>()),
+            |widget| widget.as_any().is::<W>(),
+        )
+    }
+
+    /// Matches widgets whose key equals `key`.
+    pub fn by_key(key: impl Key) -> Self {
+        Self::by_predicate(format!("widget with key {:?}", key), move |widget| {
+            widget
+                .key()
+                .is_some_and(|widget_key| key.eq_key(widget_key))
+        })
+    }
+
+    /// Matches [`Text`] widgets whose full text content equals `text`. Inline widgets are not part of the text content.
+    pub fn text(text: impl Into<String>) -> Self {
+        let text = text.into();
+        Self::by_predicate(format!("text {:?}", text), move |widget| {
+            widget
+                .as_any()
+                .downcast_ref::<Text>()
+                .is_some_and(|widget| {
+                    text_content(widget.text.as_deref(), &widget.text_spans, &widget.children)
+                        == text
+                })
+        })
+    }
+
+    /// Matches [`RichText`] widgets whose full text content equals `text`. Inline widgets are not part of the text content.
+    ///
+    /// Note that a [`Text`] builds a [`RichText`] internally, so this will also match those built by [`Text`].
+    pub fn rich_text(text: impl Into<String>) -> Self {
+        let text = text.into();
+        Self::by_predicate(format!("rich text {:?}", text), move |widget| {
+            widget
+                .as_any()
+                .downcast_ref::<RichText>()
+                .is_some_and(|widget| {
+                    text_content(
+                        widget.text.as_ref().map(|span| span.text.as_ref()),
+                        &widget.text_spans,
+                        &widget.children,
+                    ) == text
+                })
+        })
+    }
+
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    pub fn matches(&self, element: &ArcAnyElementNode) -> bool {
+        (self.matcher)(&element.widget_any())
+    }
+
+    /// All matching elements in the subtree, in depth-first pre-order.
+    pub fn find_all_in(&self, root: &ArcAnyElementNode) -> Vec<ArcAnyElementNode> {
+        let mut results = Vec::new();
+        let mut stack = vec![root.clone()];
+        while let Some(element) = stack.pop() {
+            if self.matches(&element) {
+                results.push(element.clone());
+            }
+            let mut children = element.children_any();
+            children.reverse();
+            stack.extend(children);
+        }
+        results
+    }
+}
+
+impl std::fmt::Debug for Finder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Finder").field(&self.description).finish()
+    }
+}
+
+fn text_content(text: Option<&str>, text_spans: &[TextSpan], children: &[InlineSpan]) -> String {
+    match text {
+        Some(text) => text.to_owned(),
+        None => text_spans
+            .iter()
+            .chain(children.iter().filter_map(InlineSpan::as_text_span))
+            .map(|span| span.text.as_ref())
+            .collect(),
+    }
+}
@@ -0,0 +1,193 @@
+use std::path::{Path, PathBuf};
+
+use tiny_skia::Pixmap;
+
+/// Set this environment variable to a non-empty value to (re)write golden files instead of comparing against them.
+pub const UPDATE_GOLDENS_ENV: &str = "EPGI_UPDATE_GOLDENS";
+
+/// How much a rasterized image may deviate from its golden file.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct GoldenTolerance {
+    /// Two pixels are considered equal if none of their premultiplied channels differ by more than this.
+    pub max_channel_difference: u8,
+    /// The fraction of pixels that are allowed to differ, between 0 and 1.
+    pub max_mismatch_ratio: f32,
+}
+
+impl GoldenTolerance {
+    pub const EXACT: Self = Self {
+        max_channel_difference: 0,
+        max_mismatch_ratio: 0.0,
+    };
+}
+
+impl Default for GoldenTolerance {
+    /// Absorbs anti-aliasing noise from floating point differences across platforms.
+    fn default() -> Self {
+        Self {
+            max_channel_difference: 2,
+            max_mismatch_ratio: 0.001,
+        }
+    }
+}
+
+/// The result of comparing two images of the same size.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct ImageDifference {
+    pub mismatched_pixels: usize,
+    pub total_pixels: usize,
+    pub max_channel_difference: u8,
+}
+
+impl ImageDifference {
+    pub fn mismatch_ratio(&self) -> f32 {
+        self.mismatched_pixels as f32 / self.total_pixels.max(1) as f32
+    }
+
+    pub fn is_within(&self, tolerance: &GoldenTolerance) -> bool {
+        self.mismatch_ratio() <= tolerance.max_mismatch_ratio
+    }
+}
+
+/// Compare two images pixel by pixel. Returns `None` if their sizes differ.
+///
+/// Pixels are counted as mismatched if any channel differs by more than `max_channel_difference`.
+pub fn compare_images(
+    actual: &Pixmap,
+    expected: &Pixmap,
+    max_channel_difference: u8,
+) -> Option<ImageDifference> {
+    if actual.width() != expected.width() || actual.height() != expected.height() {
+        return None;
+    }
+    let mut result = ImageDifference {
+        mismatched_pixels: 0,
+        total_pixels: actual.pixels().len(),
+        max_channel_difference: 0,
+    };
+    for (actual, expected) in actual.pixels().iter().zip(expected.pixels()) {
+        let difference = channel_difference(actual, expected);
+        result.max_channel_difference = result.max_channel_difference.max(difference);
+        if difference > max_channel_difference {
+            result.mismatched_pixels += 1;
+        }
+    }
+    Some(result)
+}
+
+/// Assert that `actual` matches the golden PNG at `path` within `tolerance`.
+///
+/// Relative paths are resolved against the current directory, which is the package root under `cargo test`.
+/// If [`UPDATE_GOLDENS_ENV`] is set, the golden file is written instead.
+/// Otherwise a missing golden file fails the assertion, so that a new, misnamed or deleted golden
+/// cannot pass unnoticed.
+/// On mismatch, the actual image and a difference mask are written next to the golden file
+/// as `<name>.actual.png` and `<name>.diff.png`.
+#[track_caller]
+pub fn match_golden(actual: &Pixmap, path: impl AsRef<Path>, tolerance: GoldenTolerance) {
+    let path = path.as_ref();
+    let update = std::env::var_os(UPDATE_GOLDENS_ENV).is_some_and(|value| !value.is_empty());
+    if update {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).unwrap_or_else(|e| {
+                panic!(
+                    "Failed to create golden directory {}: {}",
+                    parent.display(),
+                    e
+                )
+            });
+        }
+        actual
+            .save_png(path)
+            .unwrap_or_else(|e| panic!("Failed to write golden file {}: {}", path.display(), e));
+        return;
+    }
+    if !path.exists() {
+        panic!(
+            "Golden file {} does not exist. Set {}=1 to write it from the actual image.",
+            path.display(),
+            UPDATE_GOLDENS_ENV
+        );
+    }
+
+    let expected = Pixmap::load_png(path)
+        .unwrap_or_else(|e| panic!("Failed to read golden file {}: {}", path.display(), e));
+    let difference = compare_images(actual, &expected, tolerance.max_channel_difference);
+    if difference.is_some_and(|difference| difference.is_within(&tolerance)) {
+        return;
+    }
+
+    let actual_path = sibling_path(path, "actual");
+    let _ = actual.save_png(&actual_path);
+    let message = match difference {
+        None => format!(
+            "Golden file {} is {}x{}, but the actual image is {}x{}",
+            path.display(),
+            expected.width(),
+            expected.height(),
+            actual.width(),
+            actual.height()
+        ),
+        Some(difference) => {
+            let diff_path = sibling_path(path, "diff");
+            let _ = difference_mask(actual, &expected, tolerance.max_channel_difference)
+                .save_png(&diff_path);
+            format!(
+                "Golden file {} mismatched: {} of {} pixels ({:.3}%) differ by up to {}, \
+                tolerance is {:.3}%. See {}",
+                path.display(),
+                difference.mismatched_pixels,
+                difference.total_pixels,
+                difference.mismatch_ratio() * 100.0,
+                difference.max_channel_difference,
+                tolerance.max_mismatch_ratio * 100.0,
+                diff_path.display(),
+            )
+        }
+    };
+    panic!(
+        "{}. The actual image was written to {}. Set {}=1 to accept it.",
+        message,
+        actual_path.display(),
+        UPDATE_GOLDENS_ENV
+    );
+}
+
+fn channel_difference(
+    a: &tiny_skia::PremultipliedColorU8,
+    b: &tiny_skia::PremultipliedColorU8,
+) -> u8 {
+    [
+        a.red().abs_diff(b.red()),
+        a.green().abs_diff(b.green()),
+        a.blue().abs_diff(b.blue()),
+        a.alpha().abs_diff(b.alpha()),
+    ]
+    .into_iter()
+    .max()
+    .unwrap()
+}
+
+/// Mismatched pixels are painted opaque red over a faded copy of the expected image.
+fn difference_mask(actual: &Pixmap, expected: &Pixmap, max_channel_difference: u8) -> Pixmap {
+    let mut mask = expected.clone();
+    for (pixel, actual) in mask.pixels_mut().iter_mut().zip(actual.pixels()) {
+        *pixel = if channel_difference(pixel, actual) > max_channel_difference {
+            tiny_skia::PremultipliedColorU8::from_rgba(255, 0, 0, 255).unwrap()
+        } else {
+            tiny_skia::PremultipliedColorU8::from_rgba(
+                pixel.red() / 4,
+                pixel.green() / 4,
+                pixel.blue() / 4,
+                pixel.alpha() / 4,
+            )
+            .unwrap()
+        };
+    }
+    mask
+}
+
+fn sibling_path(path: &Path, suffix: &str) -> PathBuf {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    path.with_file_name(format!("{}.{}.png", stem, suffix))
+}
@@ -0,0 +1,229 @@
+use std::sync::{Mutex, MutexGuard, Once, PoisonError};
+
+use epgi_2d::{
+    ArcBoxWidget, BoxConstraints, BoxOffset, BoxProtocol, BoxSize, RootView, TextDirection,
+};
+use epgi_common::{ConstrainedBox, Directionality, FrameInfo};
+use epgi_core::{
+    foundation::{Arc, Asc, SyncMutex, SyncRwLock},
+    hooks::SetState,
+    nodes::Builder,
+    scheduler::{
+        get_current_scheduler, setup_scheduler, BuildStates, FrameResults, Scheduler,
+        SchedulerExtension, SchedulerHandle,
+    },
+    tree::{ArcAnyLayerRenderObjectExt, ArcChildRenderObject, LayoutResults},
+    Provider,
+};
+
+static INIT_SCHEDULER_HANDLE: Once = Once::new();
+
+// The scheduler handle is a process-wide global, while the test harness runs tests in parallel.
+// Only one headless scheduler is allowed to drive the handle at any given time.
+static HEADLESS_SCHEDULER_LOCK: Mutex<()> = Mutex::new(());
+
+/// A scheduler extension that does nothing.
+pub struct NoopSchedulerExtension;
+
+impl SchedulerExtension for NoopSchedulerExtension {
+    fn on_frame_begin(&mut self, _build_states: &BuildStates) {}
+
+    fn on_layout_complete(&mut self, _build_states: &BuildStates) {}
+
+    fn on_frame_complete(_build_states: &BuildStates) {}
+
+    fn on_extension_event(&mut self, _event: Box<dyn std::any::Any + Send + Sync>) {}
+}
+
+/// Drives a [`Scheduler`] without a window or a GPU surface.
+///
+/// The app is wrapped with the same text direction, frame info and constraints bindings as the winit embedding.
+/// Frames are only produced when explicitly pumped.
+/// Headless schedulers are serialized process-wide, since they all share the global [`SchedulerHandle`].
+pub struct HeadlessScheduler {
+    build_states: Asc<SyncRwLock<BuildStates>>,
+    frame_binding: Arc<SyncMutex<Option<SetState<FrameInfo>>>>,
+    constraints_binding: Arc<SyncMutex<Option<SetState<BoxConstraints>>>>,
+    frame_count: u64,
+    scheduler_join_handle: Option<std::thread::JoinHandle<()>>,
+    _lock: MutexGuard<'static, ()>,
+}
+
+impl HeadlessScheduler {
+    pub fn new(app: ArcBoxWidget, size: BoxSize) -> Self {
+        Self::new_with_extension(app, size, NoopSchedulerExtension)
+    }
+
+    pub fn new_with_extension<E: SchedulerExtension + 'static>(
+        app: ArcBoxWidget,
+        size: BoxSize,
+        extension: E,
+    ) -> Self {
+        let lock = HEADLESS_SCHEDULER_LOCK
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        initialize_scheduler_handle();
+
+        let child = Directionality!(text_direction = TextDirection::Ltr, child = app);
+        let (child, frame_binding) = bind_frame_info(child);
+        let (child, constraints_binding) = bind_constraints(child, size);
+
+        let scheduler = Scheduler::new(
+            Asc::new(RootView { child }),
+            LayoutResults::new(BoxConstraints::default(), BoxSize::INFINITY, ()),
+            BoxOffset::ZERO,
+            get_current_scheduler(),
+            extension,
+        );
+        let build_states = scheduler.build_states().clone();
+        let join_handle = std::thread::Builder::new()
+            .name("epgi headless scheduler".into())
+            .spawn(move || scheduler.start_event_loop(get_current_scheduler()))
+            .unwrap();
+
+        Self {
+            build_states,
+            frame_binding,
+            constraints_binding,
+            frame_count: 0,
+            scheduler_join_handle: Some(join_handle),
+            _lock: lock,
+        }
+    }
+
+    /// Produce a new frame and block until it has been composited.
+    pub fn pump_frame(&mut self) -> FrameResults {
+        self.pump_frame_with(FrameInfo::now(self.frame_count))
+    }
+
+    /// Produce a new frame with the given frame info and block until it has been composited.
+    pub fn pump_frame_with(&mut self, frame_info: FrameInfo) -> FrameResults {
+        let scheduler = get_current_scheduler();
+        if let Some(set_frame) = &*self.frame_binding.lock() {
+            scheduler.create_sync_job(|job_builder| {
+                set_frame.set(frame_info, job_builder);
+            });
+        }
+        self.frame_count += 1;
+        scheduler
+            .request_redraw
+            .store(false, std::sync::atomic::Ordering::Release);
+        scheduler
+            .request_new_frame()
+            .recv()
+            .expect("The headless scheduler should produce a frame")
+    }
+
+    /// Whether any work has been scheduled since the last pumped frame.
+    pub fn has_scheduled_frame(&self) -> bool {
+        get_current_scheduler()
+            .request_redraw
+            .load(std::sync::atomic::Ordering::Acquire)
+    }
+
+    /// Change the tight constraints imposed on the app. Takes effect on the next pumped frame.
+    pub fn set_size(&self, size: BoxSize) {
+        if let Some(set_constraints) = &*self.constraints_binding.lock() {
+            get_current_scheduler().create_sync_job(|job_builder| {
+                set_constraints.set(
+                    BoxConstraints::new_tight(size.width, size.height),
+                    job_builder,
+                );
+            });
+        }
+    }
+
+    /// The number of frames that have been pumped so far.
+    pub fn frame_count(&self) -> u64 {
+        self.frame_count
+    }
+
+    pub fn build_states(&self) -> &Asc<SyncRwLock<BuildStates>> {
+        &self.build_states
+    }
+
+    pub fn root_render_object(&self) -> ArcChildRenderObject<BoxProtocol> {
+        self.build_states
+            .read()
+            .root_render_object
+            .clone()
+            .downcast_arc_child::<BoxProtocol>()
+            .expect("Root render object should use BoxProtocol")
+    }
+}
+
+impl Drop for HeadlessScheduler {
+    fn drop(&mut self) {
+        get_current_scheduler().request_shutdown();
+        if let Some(join_handle) = self.scheduler_join_handle.take() {
+            let result = join_handle.join();
+            // Do not double panic if we are already unwinding from a failed test.
+            if !std::thread::panicking() {
+                result.expect("The headless scheduler thread panicked");
+            }
+        }
+    }
+}
+
+fn initialize_scheduler_handle() {
+    INIT_SCHEDULER_HANDLE.call_once(|| {
+        let sync_threadpool = rayon::ThreadPoolBuilder::new()
+            .thread_name(|index| format!("epgi sync pool {}", index))
+            .build()
+            .unwrap();
+        let async_threadpool = rayon::ThreadPoolBuilder::new()
+            .thread_name(|index| format!("epgi async pool {}", index))
+            .build()
+            .unwrap();
+        let scheduler_handle = SchedulerHandle::new(sync_threadpool, async_threadpool);
+        // SAFETY: `call_once` runs this exactly once and blocks other callers until it finishes,
+        // so no thread can read the global scheduler handle while it is being written.
+        unsafe {
+            setup_scheduler(scheduler_handle);
+        }
+    });
+}
+
+fn bind_frame_info(
+    child: ArcBoxWidget,
+) -> (ArcBoxWidget, Arc<SyncMutex<Option<SetState<FrameInfo>>>>) {
+    let frame_binding = Arc::new(SyncMutex::<Option<SetState<FrameInfo>>>::new(None));
+    let result = frame_binding.clone();
+
+    let child = Arc::new(Builder {
+        builder: move |ctx| {
+            let frame_binding = frame_binding.clone();
+            let child = child.clone();
+            let (frame, set_frame) = ctx.use_state_with(|| FrameInfo::now(0));
+            ctx.use_effect(move |_| *frame_binding.lock() = Some(set_frame), ());
+            Provider!(value = frame, child)
+        },
+    });
+    (child, result)
+}
+
+fn bind_constraints(
+    child: ArcBoxWidget,
+    size: BoxSize,
+) -> (
+    ArcBoxWidget,
+    Arc<SyncMutex<Option<SetState<BoxConstraints>>>>,
+) {
+    let constraints_binding = Arc::new(SyncMutex::<Option<SetState<BoxConstraints>>>::new(None));
+    let result = constraints_binding.clone();
+
+    let child = Arc::new(Builder {
+        builder: move |ctx| {
+            let constraints_binding = constraints_binding.clone();
+            let child = child.clone();
+            let (constraints, set_constraints) =
+                ctx.use_state_with(|| BoxConstraints::new_tight(size.width, size.height));
+            ctx.use_effect(
+                move |_| *constraints_binding.lock() = Some(set_constraints),
+                (),
+            );
+            Arc::new(ConstrainedBox { constraints, child })
+        },
+    });
+    (child, result)
+}
@@ -0,0 +1,18 @@
+mod finder;
+pub use finder::*;
+
+mod golden;
+pub use golden::*;
+
+mod headless;
+pub use headless::*;
+
+mod raster;
+pub use raster::*;
+
+mod tester;
+pub use tester::*;
+
+pub mod testing;
+
+pub use tiny_skia;
@@ -0,0 +1,539 @@
+use epgi_2d::{Affine2dEncoding, BoxSize};
+use epgi_core::scheduler::FrameResults;
+use peniko::{Compose, Extend, Mix};
+use tiny_skia::{
+    FillRule, FilterQuality, GradientStop, LineCap, LineJoin, LinearGradient, Mask, Paint, Path,
+    PathBuilder, PathStroker, Pattern, Pixmap, PixmapPaint, Point, PremultipliedColorU8,
+    RadialGradient, Shader, SpreadMode, Stroke,
+};
+use vello_encoding::{
+    math::f16_to_f32, DrawBeginClip, DrawColor, DrawImage, DrawLinearGradient, DrawRadialGradient,
+    DrawSweepGradient, DrawTag, Layout, PathTag, Ramps, Resolver, Style, Transform,
+};
+
+/// Rasterize the composited result of a frame on the CPU.
+///
+/// The frame is expected to be produced by a scheduler rooted at [`epgi_2d::RootView`].
+/// `scale` plays the same role as the scale factor of a window.
+pub fn rasterize_frame(frame: &FrameResults, size: BoxSize, scale: f32) -> Pixmap {
+    let encoding = frame
+        .composited
+        .downcast_ref::<epgi_core::foundation::Arc<Affine2dEncoding>>()
+        .expect("The composited frame should be an Affine2dEncoding");
+    rasterize_encoding(
+        encoding,
+        (size.width * scale).ceil() as u32,
+        (size.height * scale).ceil() as u32,
+        scale,
+    )
+}
+
+/// Rasterize an encoding on the CPU onto a transparent canvas.
+///
+/// This rasterizer aims at catching layout and paint regressions rather than reproducing the GPU renderer pixel by pixel.
+/// Anti-aliasing will differ slightly from vello. Sweep gradients are approximated by the mean color of their ramp,
+/// and radial gradients with distinct centers are approximated by a zero start radius.
+pub fn rasterize_encoding(
+    encoding: &Affine2dEncoding,
+    width: u32,
+    height: u32,
+    scale: f32,
+) -> Pixmap {
+    let mut pixmap = Pixmap::new(width.max(1), height.max(1)).expect("Canvas size should be valid");
+    let device_transform = tiny_skia::Transform::from_scale(scale, scale);
+
+    // Resolving expands glyph runs into outlines and allocates gradient ramps and the image atlas.
+    let mut resolver = Resolver::new();
+    let mut packed = Vec::new();
+    let (layout, ramps, images) = resolver.resolve(encoding, &mut packed);
+    let paths = decode_paths(&layout, &packed);
+    let draw_tags = layout.draw_tags(&packed);
+    let draw_data = layout.draw_data(&packed);
+
+    let (width, height) = (pixmap.width(), pixmap.height());
+    let mut layers: Vec<Layer> = Vec::new();
+    let mut data_offset = 0;
+    for (draw_tag, draw_object) in draw_tags.iter().zip(paths.iter()) {
+        let brush_transform =
+            device_transform.pre_concat(to_skia_transform(&draw_object.brush_transform));
+        let data = &draw_data[data_offset..];
+        data_offset += draw_data_size(*draw_tag);
+        // Patterns borrow their pixmap, which has to outlive the shader.
+        let image_pixmap: Option<Pixmap>;
+        let shader = match *draw_tag {
+            DrawTag::COLOR => Some(Shader::SolidColor(unpack_color(
+                read_draw_data::<DrawColor>(data).rgba,
+            ))),
+            DrawTag::LINEAR_GRADIENT => {
+                let gradient = read_draw_data::<DrawLinearGradient>(data);
+                let (stops, spread_mode) = ramp_stops(&ramps, gradient.index);
+                LinearGradient::new(
+                    to_skia_point(gradient.p0),
+                    to_skia_point(gradient.p1),
+                    stops,
+                    spread_mode,
+                    brush_transform,
+                )
+            }
+            DrawTag::RADIAL_GRADIENT => {
+                let gradient = read_draw_data::<DrawRadialGradient>(data);
+                let (stops, spread_mode) = if gradient.p0 == gradient.p1 && gradient.r1 > 0.0 {
+                    // Concentric gradients can be expressed exactly by rescaling the stops.
+                    let [r0, r1] = [gradient.r0, gradient.r1];
+                    ramp_stops_mapped(&ramps, gradient.index, |t| (r0 + t * (r1 - r0)) / r1)
+                } else {
+                    ramp_stops(&ramps, gradient.index)
+                };
+                RadialGradient::new(
+                    to_skia_point(gradient.p0),
+                    to_skia_point(gradient.p1),
+                    gradient.r1,
+                    stops,
+                    spread_mode,
+                    brush_transform,
+                )
+            }
+            DrawTag::SWEEP_GRADIENT => {
+                let gradient = read_draw_data::<DrawSweepGradient>(data);
+                Some(Shader::SolidColor(ramp_mean_color(&ramps, gradient.index)))
+            }
+            DrawTag::IMAGE => {
+                let image = read_draw_data::<DrawImage>(data);
+                let (x, y) = (image.xy >> 16, image.xy & 0xFFFF);
+                let image = images
+                    .images
+                    .iter()
+                    .find(|(_, image_x, image_y)| *image_x == x && *image_y == y)
+                    .filter(|_| image.width_height != 0)
+                    .map(|(image, _, _)| image);
+                image_pixmap = image.and_then(image_to_pixmap);
+                image
+                    .zip(image_pixmap.as_ref())
+                    .map(|(image, image_pixmap)| {
+                        Pattern::new(
+                            image_pixmap.as_ref(),
+                            to_spread_mode(image.extend),
+                            FilterQuality::Bilinear,
+                            1.0,
+                            brush_transform,
+                        )
+                    })
+            }
+            DrawTag::BEGIN_CLIP => {
+                let begin_clip = read_draw_data::<DrawBeginClip>(data);
+                let mut mask = Mask::new(width, height).expect("Canvas size should be valid");
+                if let Some(path) = &draw_object.path {
+                    if let Some(clip_path) = path.path.clone().transform(
+                        device_transform.pre_concat(to_skia_transform(&path.geometry_transform)),
+                    ) {
+                        mask.fill_path(
+                            &clip_path,
+                            path.fill_rule(),
+                            true,
+                            tiny_skia::Transform::identity(),
+                        );
+                    }
+                }
+                layers.push(Layer {
+                    pixmap: Pixmap::new(width, height).expect("Canvas size should be valid"),
+                    mask,
+                    blend_mode: to_skia_blend_mode(begin_clip.blend_mode),
+                    alpha: begin_clip.alpha,
+                });
+                None
+            }
+            DrawTag::END_CLIP => {
+                if let Some(layer) = layers.pop() {
+                    let target = layers
+                        .last_mut()
+                        .map_or(&mut pixmap, |layer| &mut layer.pixmap);
+                    target.draw_pixmap(
+                        0,
+                        0,
+                        layer.pixmap.as_ref(),
+                        &PixmapPaint {
+                            opacity: layer.alpha,
+                            blend_mode: layer.blend_mode,
+                            quality: FilterQuality::Nearest,
+                        },
+                        tiny_skia::Transform::identity(),
+                        Some(&layer.mask),
+                    );
+                }
+                None
+            }
+            _ => None,
+        };
+        let Some(shader) = shader else {
+            continue;
+        };
+        let Some((path, fill_rule)) = draw_object
+            .path
+            .as_ref()
+            .and_then(|path| path.to_device(&device_transform))
+        else {
+            continue;
+        };
+        let target = layers
+            .last_mut()
+            .map_or(&mut pixmap, |layer| &mut layer.pixmap);
+        target.fill_path(
+            &path,
+            &Paint {
+                shader,
+                ..Default::default()
+            },
+            fill_rule,
+            tiny_skia::Transform::identity(),
+            None,
+        );
+    }
+    pixmap
+}
+
+struct Layer {
+    pixmap: Pixmap,
+    mask: Mask,
+    blend_mode: tiny_skia::BlendMode,
+    alpha: f32,
+}
+
+/// The geometry of a draw object, in its local coordinates.
+struct DecodedPath {
+    path: Path,
+    style: Style,
+    geometry_transform: Transform,
+}
+
+impl DecodedPath {
+    fn fill_rule(&self) -> FillRule {
+        if self.style.flags_and_miter_limit & Style::FLAGS_FILL_BIT != 0 {
+            FillRule::EvenOdd
+        } else {
+            FillRule::Winding
+        }
+    }
+
+    fn is_stroke(&self) -> bool {
+        self.style.flags_and_miter_limit & Style::FLAGS_STYLE_BIT != 0
+    }
+
+    /// Convert to a device space path to be filled. Strokes are expanded in local space before being transformed.
+    fn to_device(&self, device_transform: &tiny_skia::Transform) -> Option<(Path, FillRule)> {
+        let transform = device_transform.pre_concat(to_skia_transform(&self.geometry_transform));
+        if !self.is_stroke() {
+            let path = self.path.clone().transform(transform)?;
+            return Some((path, self.fill_rule()));
+        }
+        let flags = self.style.flags_and_miter_limit;
+        let stroke = Stroke {
+            width: self.style.line_width,
+            miter_limit: f16_to_f32((flags & Style::MITER_LIMIT_MASK) as u16),
+            line_cap: match flags & Style::FLAGS_END_CAP_MASK {
+                Style::FLAGS_END_CAP_BITS_SQUARE => LineCap::Square,
+                Style::FLAGS_END_CAP_BITS_ROUND => LineCap::Round,
+                _ => LineCap::Butt,
+            },
+            line_join: match flags & Style::FLAGS_JOIN_MASK {
+                Style::FLAGS_JOIN_BITS_MITER => LineJoin::Miter,
+                Style::FLAGS_JOIN_BITS_ROUND => LineJoin::Round,
+                _ => LineJoin::Bevel,
+            },
+            dash: None,
+        };
+        let resolution_scale = PathStroker::compute_resolution_scale(&transform);
+        let path = self
+            .path
+            .stroke(&stroke, resolution_scale)?
+            .transform(transform)?;
+        Some((path, FillRule::Winding))
+    }
+}
+
+/// A draw object, which corresponds one-to-one to the draw tags.
+struct DrawObjectPath {
+    /// `None` if the path has no segments.
+    path: Option<DecodedPath>,
+    /// The transform in effect when the path ends, which is used by brushes.
+    brush_transform: Transform,
+}
+
+/// Walk the path tag stream, splitting it into draw objects at each [`PathTag::PATH`].
+///
+/// Transform and style tags apply to all the segments after them.
+fn decode_paths(layout: &Layout, packed: &[u8]) -> Vec<DrawObjectPath> {
+    let path_tags = layout.path_tags(packed);
+    let path_data = layout.path_data(packed);
+    let transforms = layout.transforms(packed);
+    let styles = layout.styles(packed);
+
+    let mut result = Vec::new();
+    let mut transform_count = 0;
+    let mut style_count: usize = 0;
+    let mut offset = 0;
+    let mut current_path: Option<(PathBuilder, Style, Transform)> = None;
+    let mut subpath: Vec<(PathTag, [[f32; 2]; 4])> = Vec::new();
+    let current_transform = |count: usize| {
+        count
+            .checked_sub(1)
+            .map_or(Transform::IDENTITY, |index| transforms[index])
+    };
+
+    for &tag in path_tags {
+        if tag == PathTag::TRANSFORM {
+            transform_count += 1;
+        } else if tag == PathTag::STYLE {
+            style_count += 1;
+        } else if tag == PathTag::PATH {
+            result.push(DrawObjectPath {
+                path: current_path.take().and_then(|(builder, style, transform)| {
+                    Some(DecodedPath {
+                        path: builder.finish()?,
+                        style,
+                        geometry_transform: transform,
+                    })
+                }),
+                brush_transform: current_transform(transform_count),
+            });
+        } else if tag.is_path_segment() {
+            let (builder, style, path_transform) = current_path.get_or_insert_with(|| {
+                let style = style_count
+                    .checked_sub(1)
+                    .map_or(Style::from_fill(peniko::Fill::NonZero), |index| {
+                        styles[index]
+                    });
+                (
+                    PathBuilder::new(),
+                    style,
+                    current_transform(transform_count),
+                )
+            });
+            let num_points = tag.path_segment_type().0 as usize;
+            let point_size = if tag.is_f32() { 8 } else { 4 };
+            let mut points = [[0.0; 2]; 4];
+            for (index, point) in points.iter_mut().take(num_points + 1).enumerate() {
+                *point = read_point(&path_data[offset + index * point_size..], tag.is_f32());
+            }
+            offset += num_points * point_size;
+            // Glyph runs are a single draw object with a transform for each glyph.
+            // Such segments are mapped into the coordinate space of the first segment.
+            let segment_transform = current_transform(transform_count);
+            if segment_transform != *path_transform {
+                if let Some(inverse) = to_skia_transform(path_transform).invert() {
+                    let relative = inverse.pre_concat(to_skia_transform(&segment_transform));
+                    for point in points.iter_mut() {
+                        let mut mapped = to_skia_point(*point);
+                        relative.map_point(&mut mapped);
+                        *point = [mapped.x, mapped.y];
+                    }
+                }
+            }
+            subpath.push((tag, points));
+            if tag.is_subpath_end() {
+                offset += point_size;
+                let is_stroke = style.flags_and_miter_limit & Style::FLAGS_STYLE_BIT != 0;
+                flush_subpath(builder, &mut subpath, is_stroke);
+            }
+        }
+    }
+    result
+}
+
+fn flush_subpath(
+    builder: &mut PathBuilder,
+    subpath: &mut Vec<(PathTag, [[f32; 2]; 4])>,
+    is_stroke: bool,
+) {
+    // Stroke subpaths end with a marker segment carrying the start tangent, which is not part of the geometry.
+    // A line marker indicates a closed subpath while a quad marker indicates an open one.
+    let closed = if is_stroke {
+        subpath.pop().is_some_and(|(tag, _)| {
+            tag.path_segment_type() == vello_encoding::PathSegmentType::LINE_TO
+        })
+    } else {
+        true
+    };
+    if let Some((_, [p0, ..])) = subpath.first() {
+        builder.move_to(p0[0], p0[1]);
+    }
+    for (tag, [_, p1, p2, p3]) in subpath.drain(..) {
+        match tag.path_segment_type().0 {
+            1 => builder.line_to(p1[0], p1[1]),
+            2 => builder.quad_to(p1[0], p1[1], p2[0], p2[1]),
+            _ => builder.cubic_to(p1[0], p1[1], p2[0], p2[1], p3[0], p3[1]),
+        }
+    }
+    if closed {
+        builder.close();
+    }
+}
+
+fn read_point(data: &[u8], is_f32: bool) -> [f32; 2] {
+    if is_f32 {
+        let x: f32 = bytemuck::pod_read_unaligned(&data[0..4]);
+        let y: f32 = bytemuck::pod_read_unaligned(&data[4..8]);
+        [x, y]
+    } else {
+        let x: i16 = bytemuck::pod_read_unaligned(&data[0..2]);
+        let y: i16 = bytemuck::pod_read_unaligned(&data[2..4]);
+        [x as f32, y as f32]
+    }
+}
+
+fn draw_data_size(tag: DrawTag) -> usize {
+    let bytes = match tag {
+        DrawTag::COLOR => std::mem::size_of::<DrawColor>(),
+        DrawTag::LINEAR_GRADIENT => std::mem::size_of::<DrawLinearGradient>(),
+        DrawTag::RADIAL_GRADIENT => std::mem::size_of::<DrawRadialGradient>(),
+        DrawTag::SWEEP_GRADIENT => std::mem::size_of::<DrawSweepGradient>(),
+        DrawTag::IMAGE => std::mem::size_of::<DrawImage>(),
+        DrawTag::BEGIN_CLIP => std::mem::size_of::<DrawBeginClip>(),
+        _ => 0,
+    };
+    bytes / 4
+}
+
+fn read_draw_data<T: bytemuck::Pod>(data: &[u32]) -> T {
+    let words = std::mem::size_of::<T>() / 4;
+    bytemuck::pod_read_unaligned(bytemuck::cast_slice(&data[..words]))
+}
+
+fn to_skia_transform(transform: &Transform) -> tiny_skia::Transform {
+    let [a, b, c, d] = transform.matrix;
+    let [e, f] = transform.translation;
+    tiny_skia::Transform::from_row(a, b, c, d, e, f)
+}
+
+fn to_skia_point([x, y]: [f32; 2]) -> Point {
+    Point::from_xy(x, y)
+}
+
+/// Draw colors are packed premultiplied, with red in the high byte.
+fn unpack_color(rgba: u32) -> tiny_skia::Color {
+    let [r, g, b, a] = rgba.to_be_bytes();
+    premultiplied_to_color(r, g, b, a)
+}
+
+fn premultiplied_to_color(r: u8, g: u8, b: u8, a: u8) -> tiny_skia::Color {
+    let color = PremultipliedColorU8::from_rgba(r.min(a), g.min(a), b.min(a), a)
+        .expect("Color components should not exceed alpha")
+        .demultiply();
+    tiny_skia::Color::from_rgba8(color.red(), color.green(), color.blue(), color.alpha())
+}
+
+/// Ramp texels are packed premultiplied, with red in the low byte.
+fn ramp_texels<'a>(ramps: &'a Ramps, index_mode: u32) -> &'a [u32] {
+    let ramp_id = (index_mode >> 2) as usize;
+    let width = ramps.width as usize;
+    &ramps.data[ramp_id * width..(ramp_id + 1) * width]
+}
+
+fn ramp_stops(ramps: &Ramps, index_mode: u32) -> (Vec<GradientStop>, SpreadMode) {
+    ramp_stops_mapped(ramps, index_mode, |t| t)
+}
+
+fn ramp_stops_mapped(
+    ramps: &Ramps,
+    index_mode: u32,
+    map_position: impl Fn(f32) -> f32,
+) -> (Vec<GradientStop>, SpreadMode) {
+    // Sampling every few texels is indistinguishable from the full ramp at golden image resolutions.
+    const RAMP_SAMPLE_STRIDE: usize = 8;
+    let texels = ramp_texels(ramps, index_mode);
+    let last = texels.len() - 1;
+    let stops = (0..last)
+        .step_by(RAMP_SAMPLE_STRIDE)
+        .chain(std::iter::once(last))
+        .map(|index| {
+            let [r, g, b, a] = texels[index].to_le_bytes();
+            GradientStop::new(
+                map_position(index as f32 / last as f32),
+                premultiplied_to_color(r, g, b, a),
+            )
+        })
+        .collect();
+    let extend = match index_mode & 0x3 {
+        mode if mode == Extend::Repeat as u32 => Extend::Repeat,
+        mode if mode == Extend::Reflect as u32 => Extend::Reflect,
+        _ => Extend::Pad,
+    };
+    (stops, to_spread_mode(extend))
+}
+
+fn to_spread_mode(extend: Extend) -> SpreadMode {
+    match extend {
+        Extend::Pad => SpreadMode::Pad,
+        Extend::Repeat => SpreadMode::Repeat,
+        Extend::Reflect => SpreadMode::Reflect,
+    }
+}
+
+fn ramp_mean_color(ramps: &Ramps, index_mode: u32) -> tiny_skia::Color {
+    let texels = ramp_texels(ramps, index_mode);
+    let mut sum = [0u32; 4];
+    for texel in texels {
+        for (sum, component) in sum.iter_mut().zip(texel.to_le_bytes()) {
+            *sum += component as u32;
+        }
+    }
+    let [r, g, b, a] = sum.map(|sum| (sum / texels.len() as u32) as u8);
+    premultiplied_to_color(r, g, b, a)
+}
+
+fn image_to_pixmap(image: &peniko::Image) -> Option<Pixmap> {
+    let mut pixmap = Pixmap::new(image.width, image.height)?;
+    for (pixel, rgba) in pixmap
+        .pixels_mut()
+        .iter_mut()
+        .zip(image.data.data().chunks_exact(4))
+    {
+        *pixel = tiny_skia::ColorU8::from_rgba(rgba[0], rgba[1], rgba[2], rgba[3]).premultiply();
+    }
+    Some(pixmap)
+}
+
+fn to_skia_blend_mode(blend_mode: u32) -> tiny_skia::BlendMode {
+    use tiny_skia::BlendMode::*;
+    let mix = (blend_mode >> 8) as u8;
+    let compose = (blend_mode & 0xFF) as u8;
+    const MIX_MODES: [(Mix, tiny_skia::BlendMode); 15] = [
+        (Mix::Multiply, Multiply),
+        (Mix::Screen, Screen),
+        (Mix::Overlay, Overlay),
+        (Mix::Darken, Darken),
+        (Mix::Lighten, Lighten),
+        (Mix::ColorDodge, ColorDodge),
+        (Mix::ColorBurn, ColorBurn),
+        (Mix::HardLight, HardLight),
+        (Mix::SoftLight, SoftLight),
+        (Mix::Difference, Difference),
+        (Mix::Exclusion, Exclusion),
+        (Mix::Hue, Hue),
+        (Mix::Saturation, Saturation),
+        (Mix::Color, Color),
+        (Mix::Luminosity, Luminosity),
+    ];
+    const COMPOSE_MODES: [(Compose, tiny_skia::BlendMode); 14] = [
+        (Compose::Clear, Clear),
+        (Compose::Copy, Source),
+        (Compose::Dest, Destination),
+        (Compose::SrcOver, SourceOver),
+        (Compose::DestOver, DestinationOver),
+        (Compose::SrcIn, SourceIn),
+        (Compose::DestIn, DestinationIn),
+        (Compose::SrcOut, SourceOut),
+        (Compose::DestOut, DestinationOut),
+        (Compose::SrcAtop, SourceAtop),
+        (Compose::DestAtop, DestinationAtop),
+        (Compose::Xor, Xor),
+        (Compose::Plus, Plus),
+        (Compose::PlusLighter, Plus),
+    ];
+    if let Some((_, mode)) = MIX_MODES.iter().find(|(m, _)| *m as u8 == mix) {
+        return *mode;
+    }
+    COMPOSE_MODES
+        .iter()
+        .find(|(c, _)| *c as u8 == compose)
+        .map_or(SourceOver, |(_, mode)| *mode)
+}
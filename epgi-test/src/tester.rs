@@ -0,0 +1,484 @@
+use std::{
+    path::Path,
+    time::{Duration, Instant, SystemTime},
+};
+
+use epgi_2d::{ArcBoxWidget, BoxOffset, BoxProtocol, BoxSize, Point2d};
+use epgi_common::{
+    gesture::PointerGestureManager, CursorIcon, FocusManager, FocusNode, FrameInfo, ImeEvent,
+    KeyCode, KeyEvent, KeyEventHandlerRegistry, KeyModifiers, KeyboardManager, LogicalKey,
+    MouseTracker, PointerButtons, PointerContactData, PointerDeviceKind, PointerEvent,
+    PointerEventCommonData, PointerHoverData, PointerInteractionId, PointerSignalData,
+};
+use epgi_core::{
+    foundation::{unbounded_channel_sync, Arc, Asc, SyncMpscReceiver, SyncMpscSender, SyncMutex},
+    hooks::SetState,
+    nodes::Builder,
+    scheduler::{get_current_scheduler, BuildStates, FrameResults, SchedulerExtension},
+    tree::{ArcAnyElementNode, ArcAnyLayerRenderObjectExt, ArcChildRenderObject, Widget},
+    Provider,
+};
+
+use tiny_skia::Pixmap;
+
+use crate::{match_golden, rasterize_frame, Finder, GoldenTolerance, HeadlessScheduler};
+
+/// The interval used by [`WidgetTester::pump_and_settle`] between two frames.
+pub const SETTLE_FRAME_INTERVAL: Duration = Duration::from_millis(16);
+
+/// [`WidgetTester::pump_and_settle`] gives up after this much simulated time.
+pub const SETTLE_TIMEOUT: Duration = Duration::from_secs(600);
+
+/// Drives a widget tree frame by frame with a simulated clock and simulated pointer and keyboard input.
+///
+/// The simulated clock only advances when [`WidgetTester::pump`] is called,
+/// and is delivered to the app through [`FrameInfo`] as well as to the gesture arenas.
+/// Note that some animations sample their starting time from the wall clock.
+pub struct WidgetTester {
+    size: BoxSize,
+    scheduler: Option<HeadlessScheduler>,
+    app_binding: Arc<SyncMutex<Option<SetState<ArcBoxWidget>>>>,
+    clock: Arc<SyncMutex<Instant>>,
+    pointer_tx: SyncMpscSender<PointerEvent>,
+    pointer_rx: Option<SyncMpscReceiver<PointerEvent>>,
+    key_tx: SyncMpscSender<KeyEvent>,
+    key_rx: Option<SyncMpscReceiver<KeyEvent>>,
+    ime_tx: SyncMpscSender<ImeEvent>,
+    ime_rx: Option<SyncMpscReceiver<ImeEvent>>,
+    focus_manager: FocusManager,
+    mouse_tracker: MouseTracker,
+    next_interaction_id: u64,
+    last_frame: Option<FrameResults>,
+}
+
+impl Default for WidgetTester {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WidgetTester {
+    pub const DEFAULT_SIZE: BoxSize = BoxSize {
+        width: 800.0,
+        height: 600.0,
+    };
+
+    pub fn new() -> Self {
+        Self::new_with_size(Self::DEFAULT_SIZE)
+    }
+
+    pub fn new_with_size(size: BoxSize) -> Self {
+        let (pointer_tx, pointer_rx) = unbounded_channel_sync();
+        let (key_tx, key_rx) = unbounded_channel_sync();
+        let (ime_tx, ime_rx) = unbounded_channel_sync();
+        Self {
+            size,
+            scheduler: None,
+            app_binding: Default::default(),
+            clock: Arc::new(SyncMutex::new(Instant::now())),
+            pointer_tx,
+            pointer_rx: Some(pointer_rx),
+            key_tx,
+            key_rx: Some(key_rx),
+            ime_tx,
+            ime_rx: Some(ime_rx),
+            focus_manager: FocusManager::new(),
+            mouse_tracker: MouseTracker::new(),
+            next_interaction_id: 0,
+            last_frame: None,
+        }
+    }
+
+    /// Replace the app with `widget` and pump a frame.
+    ///
+    /// The first call mounts the tree. Later calls rebuild the existing tree against the new widget.
+    pub fn pump_widget(&mut self, widget: ArcBoxWidget) -> FrameResults {
+        match &self.scheduler {
+            None => {
+                let keyboard_manager = KeyboardManager::new(
+                    self.key_rx
+                        .take()
+                        .expect("Key events should only be bound once"),
+                    self.ime_rx
+                        .take()
+                        .expect("Input method events should only be bound once"),
+                );
+                let (app, app_binding) = bind_app(
+                    widget,
+                    keyboard_manager.registry().clone(),
+                    self.focus_manager.root_scope(),
+                );
+                self.app_binding = app_binding;
+                let extension = TesterSchedulerExtension {
+                    pointer_gesture_manager: PointerGestureManager::new(
+                        self.pointer_rx
+                            .take()
+                            .expect("Pointer events should only be bound once"),
+                        self.mouse_tracker.clone(),
+                    ),
+                    keyboard_manager,
+                    focus_manager: self.focus_manager.clone(),
+                    clock: self.clock.clone(),
+                };
+                self.scheduler = Some(HeadlessScheduler::new_with_extension(
+                    app, self.size, extension,
+                ));
+            }
+            Some(_) => {
+                if let Some(set_app) = &*self.app_binding.lock() {
+                    get_current_scheduler().create_sync_job(|job_builder| {
+                        set_app.set(widget, job_builder);
+                    });
+                }
+            }
+        }
+        self.pump(Duration::ZERO)
+    }
+
+    /// Advance the simulated clock by `duration` and pump a frame.
+    pub fn pump(&mut self, duration: Duration) -> FrameResults {
+        let instant = {
+            let mut clock = self.clock.lock();
+            *clock += duration;
+            *clock
+        };
+        let scheduler = self.scheduler_mut();
+        let frame_count = scheduler.frame_count();
+        let frame = scheduler.pump_frame_with(FrameInfo {
+            instant,
+            system_time: SystemTime::now(),
+            frame_count,
+        });
+        self.last_frame = Some(frame.clone());
+        frame
+    }
+
+    /// Keep pumping frames at [`SETTLE_FRAME_INTERVAL`] until no more work is scheduled.
+    ///
+    /// Returns the number of frames pumped.
+    /// Panics if the tree has not settled after [`SETTLE_TIMEOUT`] of simulated time.
+    pub fn pump_and_settle(&mut self) -> usize {
+        let mut count = 0;
+        let mut elapsed = Duration::ZERO;
+        loop {
+            self.pump(SETTLE_FRAME_INTERVAL);
+            count += 1;
+            elapsed += SETTLE_FRAME_INTERVAL;
+            if !self.scheduler().has_scheduled_frame() {
+                return count;
+            }
+            assert!(
+                elapsed < SETTLE_TIMEOUT,
+                "pump_and_settle timed out after {} frames",
+                count
+            );
+        }
+    }
+
+    /// The current value of the simulated clock.
+    pub fn now(&self) -> Instant {
+        *self.clock.lock()
+    }
+
+    pub fn scheduler(&self) -> &HeadlessScheduler {
+        self.scheduler
+            .as_ref()
+            .expect("A widget should be pumped before interacting with the tree")
+    }
+
+    fn scheduler_mut(&mut self) -> &mut HeadlessScheduler {
+        self.scheduler
+            .as_mut()
+            .expect("A widget should be pumped before interacting with the tree")
+    }
+
+    /// All elements matching the finder, in depth-first pre-order.
+    pub fn find_all(&self, finder: &Finder) -> Vec<ArcAnyElementNode> {
+        let root_element = self.scheduler().build_states().read().root_element.clone();
+        finder.find_all_in(&root_element)
+    }
+
+    /// The only element matching the finder. Panics if there is not exactly one.
+    pub fn find(&self, finder: &Finder) -> ArcAnyElementNode {
+        let mut results = self.find_all(finder);
+        assert_eq!(
+            results.len(),
+            1,
+            "Expected exactly one element matching {}, found {}",
+            finder.description(),
+            results.len()
+        );
+        results.pop().unwrap()
+    }
+
+    pub fn any(&self, finder: &Finder) -> bool {
+        !self.find_all(finder).is_empty()
+    }
+
+    /// The widget of the only element matching the finder.
+    pub fn widget<W: Widget>(&self, finder: &Finder) -> Asc<W> {
+        self.find(finder)
+            .widget_any()
+            .as_any_arc()
+            .downcast::<W>()
+            .unwrap_or_else(|_| {
+                panic!(
+                    "The widget matching {} is not a {}",
+                    finder.description(),
+                    std::any::type_name::<W>()
+                )
+            })
+    }
+
+    /// The size of the topmost render object under the only element matching the finder.
+    pub fn get_size(&self, finder: &Finder) -> BoxSize {
+        self.get_rect(finder).1
+    }
+
+    /// The center of the topmost render object under the only element matching the finder.
+    ///
+    /// Transforms applied by layers are not taken into account.
+    pub fn get_center(&self, finder: &Finder) -> Point2d {
+        let (offset, size) = self.get_rect(finder);
+        Point2d {
+            x: offset.x + size.width / 2.0,
+            y: offset.y + size.height / 2.0,
+        }
+    }
+
+    fn get_rect(&self, finder: &Finder) -> (Point2d, BoxSize) {
+        let element = self.find(finder);
+        let render_object = first_box_render_object(&element).unwrap_or_else(|| {
+            panic!(
+                "The element matching {} has no box render object",
+                finder.description()
+            )
+        });
+        let (size, offset) = render_object
+            .last_size_and_paint_offset()
+            .unwrap_or_else(|| {
+                panic!(
+                    "The element matching {} has not been laid out and painted",
+                    finder.description()
+                )
+            });
+        (offset, size)
+    }
+
+    /// Rasterize the last pumped frame on the CPU at a scale factor of 1.
+    pub fn rasterize(&self) -> Pixmap {
+        let frame = self
+            .last_frame
+            .as_ref()
+            .expect("A widget should be pumped before rasterizing");
+        rasterize_frame(frame, self.size, 1.0)
+    }
+
+    /// Assert that the last pumped frame matches the golden PNG at `path`. See [`match_golden`].
+    #[track_caller]
+    pub fn match_golden(&self, path: impl AsRef<Path>, tolerance: GoldenTolerance) {
+        match_golden(&self.rasterize(), path, tolerance)
+    }
+
+    /// Tap the center of the only element matching the finder.
+    ///
+    /// A pointer down and a pointer up are sent, and a frame is pumped to deliver them.
+    pub fn tap(&mut self, finder: &Finder) -> FrameResults {
+        let position = self.get_center(finder);
+        self.tap_at(position)
+    }
+
+    pub fn tap_at(&mut self, position: Point2d) -> FrameResults {
+        let interaction_id = self.new_interaction_id();
+        self.send_pointer_event(PointerEvent::new_down(
+            self.pointer_common_data(position),
+            interaction_id,
+            PointerContactData::new_mouse(PointerButtons::PRIMARY_MOUSE_BUTTON),
+        ));
+        self.send_pointer_event(PointerEvent::new_up(
+            self.pointer_common_data(position),
+            interaction_id,
+            PointerHoverData::new_mouse(),
+        ));
+        self.pump(Duration::ZERO)
+    }
+
+    /// Roll a mouse wheel over `position` by `delta`, and pump a frame to deliver it.
+    ///
+    /// A positive delta scrolls towards the end of the content.
+    pub fn scroll_at(&mut self, position: Point2d, delta: BoxOffset) -> FrameResults {
+        self.send_pointer_event(PointerEvent::new_signal(
+            self.pointer_common_data(position),
+            PointerSignalData::Scroll {
+                physical_delta: delta,
+            },
+        ));
+        self.pump(Duration::ZERO)
+    }
+
+    /// Move a mouse without any button pressed to `position`, and pump a frame to deliver it.
+    pub fn hover_at(&mut self, position: Point2d) -> FrameResults {
+        self.send_pointer_event(PointerEvent::new_hover(
+            self.pointer_common_data(position),
+            PointerHoverData::new_mouse(),
+        ));
+        self.pump(Duration::ZERO)
+    }
+
+    /// Move the mouse out of the window, and pump a frame to deliver it.
+    pub fn remove_mouse(&mut self) -> FrameResults {
+        self.send_pointer_event(PointerEvent::new_removed(
+            self.pointer_common_data(Point2d::ZERO),
+        ));
+        self.pump(Duration::ZERO)
+    }
+
+    /// The cursor requested by the mouse regions under the mouse, as of the last pumped frame.
+    pub fn mouse_cursor(&self) -> CursorIcon {
+        self.mouse_tracker.cursor()
+    }
+
+    /// Queue a raw pointer event. It is delivered at the beginning of the next pumped frame.
+    pub fn send_pointer_event(&self, event: PointerEvent) {
+        self.pointer_tx
+            .send(event)
+            .expect("Gesture manager should be up and running to receive pointer events")
+    }
+
+    pub fn new_interaction_id(&mut self) -> PointerInteractionId {
+        let id = PointerInteractionId::new(self.next_interaction_id);
+        self.next_interaction_id += 1;
+        id
+    }
+
+    /// Press and release a key, and pump a frame to deliver the events.
+    ///
+    /// `logical_key` is what the key means under the simulated keyboard layout, e.g. `LogicalKey::character("s")` for [`KeyCode::KeyS`].
+    pub fn press_key(
+        &mut self,
+        physical_key: KeyCode,
+        logical_key: LogicalKey,
+        modifiers: KeyModifiers,
+    ) -> FrameResults {
+        self.send_key_event(KeyEvent::new_down(
+            self.now(),
+            physical_key.into(),
+            logical_key.clone(),
+            modifiers,
+        ));
+        self.send_key_event(KeyEvent::new_up(
+            self.now(),
+            physical_key.into(),
+            logical_key,
+            modifiers,
+        ));
+        self.pump(Duration::ZERO)
+    }
+
+    /// The node holding the primary focus, as of the last pumped frame.
+    pub fn primary_focus(&self) -> Option<FocusNode> {
+        self.focus_manager.primary_focus()
+    }
+
+    /// Queue a raw key event. It is delivered at the beginning of the next pumped frame.
+    pub fn send_key_event(&self, event: KeyEvent) {
+        self.key_tx
+            .send(event)
+            .expect("Keyboard manager should be up and running to receive key events")
+    }
+
+    /// Commit `text` through the simulated input method, and pump a frame to deliver it.
+    ///
+    /// The text is inserted by the focused text field, replacing its selection or its composing text.
+    pub fn commit_text(&mut self, text: impl Into<String>) -> FrameResults {
+        self.send_ime_event(ImeEvent::Commit(text.into()));
+        self.pump(Duration::ZERO)
+    }
+
+    /// Queue a raw input method event. It is delivered at the beginning of the next pumped frame.
+    pub fn send_ime_event(&self, event: ImeEvent) {
+        self.ime_tx
+            .send(event)
+            .expect("Keyboard manager should be up and running to receive input method events")
+    }
+
+    /// Common pointer event data for a mouse at `position`, stamped with the simulated clock.
+    pub fn pointer_common_data(&self, position: Point2d) -> PointerEventCommonData {
+        PointerEventCommonData {
+            time_stamp: self.now(),
+            position,
+            pointer_kind: PointerDeviceKind::Mouse,
+            synthesized: false,
+        }
+    }
+}
+
+struct TesterSchedulerExtension {
+    pointer_gesture_manager: PointerGestureManager,
+    keyboard_manager: KeyboardManager,
+    focus_manager: FocusManager,
+    clock: Arc<SyncMutex<Instant>>,
+}
+
+impl SchedulerExtension for TesterSchedulerExtension {
+    fn on_frame_begin(&mut self, build_states: &BuildStates) {
+        let root_render_object = build_states
+            .root_render_object
+            .clone()
+            .downcast_arc_child::<BoxProtocol>()
+            .expect("Root render object should use BoxProtocol");
+        self.pointer_gesture_manager
+            .flush_events(&root_render_object);
+        let now = *self.clock.lock();
+        self.pointer_gesture_manager.poll_revisit_all(now);
+        let focus_manager = &self.focus_manager;
+        self.keyboard_manager
+            .flush_events(|event| focus_manager.handle_key_event(event));
+        self.keyboard_manager
+            .flush_ime_events(|event| focus_manager.handle_ime_event(event));
+        self.focus_manager.flush_focus_changes();
+    }
+
+    fn on_layout_complete(&mut self, _build_states: &BuildStates) {}
+
+    fn on_frame_complete(_build_states: &BuildStates) {}
+
+    fn on_extension_event(&mut self, _event: Box<dyn std::any::Any + Send + Sync>) {}
+}
+
+fn first_box_render_object(
+    element: &ArcAnyElementNode,
+) -> Option<ArcChildRenderObject<BoxProtocol>> {
+    let mut element = element.clone();
+    loop {
+        if let Ok(render_object) = element.render_object() {
+            return render_object
+                .as_any_arc_child()
+                .downcast::<ArcChildRenderObject<BoxProtocol>>()
+                .ok()
+                .map(|render_object| *render_object);
+        }
+        element = element.children_any().into_iter().next()?;
+    }
+}
+
+fn bind_app(
+    app: ArcBoxWidget,
+    key_event_handler_registry: KeyEventHandlerRegistry,
+    root_focus_scope: FocusNode,
+) -> (ArcBoxWidget, Arc<SyncMutex<Option<SetState<ArcBoxWidget>>>>) {
+    let app_binding = Arc::new(SyncMutex::<Option<SetState<ArcBoxWidget>>>::new(None));
+    let result = app_binding.clone();
+
+    let child = Arc::new(Builder {
+        builder: move |ctx| {
+            let app_binding = app_binding.clone();
+            let (app, set_app) = ctx.use_state_with(|| app.clone());
+            ctx.use_effect(move |_| *app_binding.lock() = Some(set_app), ());
+            app
+        },
+    });
+    let child = Provider!(value = key_event_handler_registry, child);
+    let child = Provider!(value = root_focus_scope, child);
+    (child, result)
+}
@@ -0,0 +1,72 @@
+//! Assertions and helpers shared by the tests of the framework.
+
+use std::{
+    ops::Range,
+    sync::{Arc, Mutex},
+};
+
+use epgi_2d::Point2d;
+use epgi_common::Container;
+
+use crate::{Finder, WidgetTester};
+
+/// Assert that two lengths are equal up to floating point error.
+#[track_caller]
+pub fn assert_close(actual: f32, expected: f32) {
+    assert_close_within(actual, expected, 1e-3)
+}
+
+/// Assert that two lengths differ by less than `tolerance`.
+#[track_caller]
+pub fn assert_close_within(actual: f32, expected: f32, tolerance: f32) {
+    assert!(
+        (actual - expected).abs() < tolerance,
+        "expected {expected}, got {actual}"
+    );
+}
+
+/// Total coverage of everything painted in a column range of the last pumped frame.
+pub fn ink(tester: &WidgetTester, columns: Range<u32>) -> u32 {
+    let pixmap = tester.rasterize();
+    let mut ink = 0;
+    for y in 0..pixmap.height() {
+        for x in columns.clone() {
+            ink += pixmap.pixel(x, y).unwrap().alpha() as u32;
+        }
+    }
+    ink
+}
+
+/// The top left corner of the topmost render object under the only element matching the finder.
+///
+/// Like [`WidgetTester::get_center`], transforms applied by layers are not taken into account.
+pub fn top_left(tester: &WidgetTester, finder: &Finder) -> Point2d {
+    let center = tester.get_center(finder);
+    let size = tester.get_size(finder);
+    Point2d {
+        x: center.x - size.width / 2.0,
+        y: center.y - size.height / 2.0,
+    }
+}
+
+/// Matches the [`Container`]s with the given width.
+pub fn container_of_width(width: f32) -> Finder {
+    Finder::by_predicate(format!("container of width {width}"), move |widget| {
+        widget
+            .as_any()
+            .downcast_ref::<Container>()
+            .is_some_and(|widget| widget.width == Some(width))
+    })
+}
+
+/// A log of the callbacks a test has observed, shared with the callbacks themselves.
+pub type Log = Arc<Mutex<Vec<String>>>;
+
+pub fn record(log: &Log, entry: impl Into<String>) {
+    log.lock().unwrap().push(entry.into());
+}
+
+/// Take the entries recorded so far, leaving the log empty.
+pub fn take(log: &Log) -> Vec<String> {
+    std::mem::take(&mut *log.lock().unwrap())
+}
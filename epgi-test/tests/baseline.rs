@@ -0,0 +1,117 @@
+use epgi_2d::TextBaseline;
+use epgi_common::{
+    Center, Column, Container, CrossAxisAlignment, EdgeInsets, EditableText, MainAxisSize, Padding,
+    Row, Text, TextEditingValue,
+};
+use epgi_material::{black_mountain_view_body_medium, MaterialApp};
+use epgi_test::{
+    testing::{assert_close, top_left},
+    Finder, WidgetTester,
+};
+
+fn editable_text(text: &str) -> Finder {
+    let text = text.to_owned();
+    Finder::by_predicate(format!("editable text {:?}", text), move |widget| {
+        widget
+            .as_any()
+            .downcast_ref::<EditableText>()
+            .is_some_and(|widget| widget.value.text == text)
+    })
+}
+
+#[test]
+fn baseline_row_lines_up_shifted_text() {
+    let mut tester = WidgetTester::new();
+    tester.pump_widget(MaterialApp!(
+        child = Center!(
+            child = Row!(
+                main_axis_size = MainAxisSize::Min,
+                cross_axis_alignment = CrossAxisAlignment::Baseline(TextBaseline::Alphabetic),
+                children = vec![
+                    Padding!(
+                        padding = EdgeInsets::new().t(20.0),
+                        child = Text!(text = "label")
+                    ),
+                    Text!(text = "value"),
+                ]
+            )
+        )
+    ));
+    let row = Finder::by_type::<Row>();
+    let value = Finder::text("value");
+    // The padding moves the baseline of the label down, so the value follows it.
+    assert_close(
+        top_left(&tester, &value).y,
+        top_left(&tester, &Finder::text("label")).y,
+    );
+    assert_close(
+        top_left(&tester, &value).y - top_left(&tester, &row).y,
+        20.0,
+    );
+    assert_close(
+        tester.get_size(&row).height,
+        20.0 + tester.get_size(&value).height,
+    );
+}
+
+#[test]
+fn baseline_row_aligns_inputs_and_top_aligns_children_without_baselines() {
+    let style = black_mountain_view_body_medium();
+    let mut tester = WidgetTester::new();
+    tester.pump_widget(Center!(
+        child = Row!(
+            main_axis_size = MainAxisSize::Min,
+            cross_axis_alignment = CrossAxisAlignment::Baseline(TextBaseline::Alphabetic),
+            children = vec![
+                Container!(width = 10.0, height = 100.0),
+                Padding!(
+                    padding = EdgeInsets::new().t(20.0),
+                    child = EditableText!(
+                        value = TextEditingValue::new("first"),
+                        style = style.clone()
+                    )
+                ),
+                EditableText!(value = TextEditingValue::new("second"), style = style),
+            ]
+        )
+    ));
+    let row_top = top_left(&tester, &Finder::by_type::<Row>()).y;
+    assert_close(
+        top_left(&tester, &Finder::by_type::<Container>()).y,
+        row_top,
+    );
+    assert_close(
+        top_left(&tester, &editable_text("second")).y - row_top,
+        20.0,
+    );
+}
+
+#[test]
+fn column_reports_the_baseline_of_its_first_child() {
+    let mut tester = WidgetTester::new();
+    tester.pump_widget(MaterialApp!(
+        child = Center!(
+            child = Row!(
+                main_axis_size = MainAxisSize::Min,
+                cross_axis_alignment = CrossAxisAlignment::Baseline(TextBaseline::Alphabetic),
+                children = vec![
+                    Column!(
+                        main_axis_size = MainAxisSize::Min,
+                        children = vec![
+                            Padding!(
+                                padding = EdgeInsets::new().t(20.0),
+                                child = Text!(text = "first")
+                            ),
+                            Text!(text = "second"),
+                        ]
+                    ),
+                    Text!(text = "beside"),
+                ]
+            )
+        )
+    ));
+    assert_close(
+        top_left(&tester, &Finder::text("beside")).y,
+        top_left(&tester, &Finder::text("first")).y,
+    );
+}
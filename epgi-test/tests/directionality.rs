@@ -0,0 +1,210 @@
+use std::borrow::Cow;
+
+use epgi_2d::{
+    ArcBoxWidget, BoxSize, Color, LocalTextStyle, Paragraph, Point2d, TextAlign, TextDirection,
+    TextOverFlow, TextSpan,
+};
+use epgi_common::{
+    Align, Alignment, AlignmentDirectional, Container, Directionality, EdgeInsetsDirectional,
+    MainAxisSize, Padding, Positioned, Row, Stack, Text,
+};
+use epgi_material::{black_mountain_view_body_medium, MaterialApp};
+use epgi_test::{
+    testing::{assert_close, assert_close_within, container_of_width, ink},
+    Finder, WidgetTester,
+};
+
+const HEBREW: &str = "שלום עולם";
+const MIXED: &str = "abc שלום עולם";
+
+fn paragraph(text: &'static str, text_direction: TextDirection) -> (Paragraph, f32) {
+    let mut paragraph = Paragraph::new(
+        &[TextSpan {
+            text: Cow::Borrowed(text),
+            style: None,
+        }],
+        &black_mountain_view_body_medium(),
+        text_direction,
+    );
+    let sizes = paragraph.layout(None, TextAlign::Start);
+    assert_eq!(sizes.len(), 1);
+    (paragraph, sizes[0].advance)
+}
+
+#[test]
+fn right_to_left_paragraph_starts_at_the_right() {
+    let (paragraph, width) = paragraph(HEBREW, TextDirection::Rtl);
+    assert_close_within(paragraph.caret_rect(0).unwrap().l, width, 1.0);
+    assert_close_within(paragraph.caret_rect(HEBREW.len()).unwrap().l, 0.0, 1.0);
+    let y = 5.0;
+    assert_eq!(
+        paragraph.hit_test_text_position(Point2d { x: width - 0.5, y }),
+        0
+    );
+    assert_eq!(
+        paragraph.hit_test_text_position(Point2d { x: 0.5, y }),
+        HEBREW.len()
+    );
+    // Below the last line is the end of the text, whichever side it is on
+    assert_eq!(
+        paragraph.hit_test_text_position(Point2d { x: width, y: 100.0 }),
+        HEBREW.len()
+    );
+}
+
+#[test]
+fn base_direction_orders_mixed_runs() {
+    let (ltr, _) = paragraph(MIXED, TextDirection::Ltr);
+    assert_close_within(ltr.caret_rect(0).unwrap().l, 0.0, 1.0);
+    let selection = ltr.selection_rects(0..3);
+    assert_eq!(selection.len(), 1);
+    assert_close_within(selection[0].l, 0.0, 1.0);
+
+    // The latin run comes first, so it is placed at the right of a right-to-left paragraph.
+    let (rtl, width) = paragraph(MIXED, TextDirection::Rtl);
+    let selection = rtl.selection_rects(0..3);
+    assert_eq!(selection.len(), 1);
+    assert_close_within(selection[0].r, width, 1.0);
+    assert!(selection[0].l > width / 2.0);
+    assert_close_within(rtl.caret_rect(MIXED.len()).unwrap().l, 0.0, 1.0);
+}
+
+fn pump_row(tester: &mut WidgetTester, text_direction: TextDirection) {
+    tester.pump_widget(MaterialApp!(
+        child = Directionality!(
+            text_direction,
+            child = Align!(
+                alignment = AlignmentDirectional::TOP_START,
+                child = Row!(
+                    main_axis_size = MainAxisSize::Min,
+                    children = vec![
+                        Container!(width = 20.0, height = 10.0, color = Color::BLACK),
+                        Padding!(
+                            padding = EdgeInsetsDirectional::new().start(10.0),
+                            child = Container!(width = 30.0, height = 10.0, color = Color::BLACK)
+                        ),
+                    ]
+                )
+            )
+        )
+    ));
+}
+
+#[test]
+fn directional_layouts_resolve_start_against_directionality() {
+    let mut tester = WidgetTester::new_with_size(BoxSize {
+        width: 200.0,
+        height: 100.0,
+    });
+    pump_row(&mut tester, TextDirection::Ltr);
+    assert_close(tester.get_center(&container_of_width(20.0)).x, 10.0);
+    assert_close(tester.get_center(&container_of_width(30.0)).x, 45.0);
+
+    pump_row(&mut tester, TextDirection::Rtl);
+    assert_close(tester.get_center(&container_of_width(20.0)).x, 190.0);
+    assert_close(tester.get_center(&container_of_width(30.0)).x, 155.0);
+    assert_close(tester.get_center(&Finder::by_type::<Row>()).y, 5.0);
+}
+
+fn pump_in_direction(tester: &mut WidgetTester, text_direction: TextDirection, row: ArcBoxWidget) {
+    tester.pump_widget(MaterialApp!(
+        child = Directionality!(
+            text_direction,
+            child = Align!(alignment = Alignment::TOP_LEFT, child = row)
+        )
+    ));
+}
+
+fn two_boxes() -> Vec<ArcBoxWidget> {
+    vec![
+        Container!(width = 20.0, height = 10.0, color = Color::BLACK),
+        Container!(width = 30.0, height = 10.0, color = Color::BLACK),
+    ]
+}
+
+#[test]
+fn row_follows_the_ambient_direction_unless_given_one() {
+    let mut tester = WidgetTester::new_with_size(BoxSize {
+        width: 200.0,
+        height: 100.0,
+    });
+    // The same row widget is kept, so only the change of the ambient direction rebuilds it
+    let row = Row!(main_axis_size = MainAxisSize::Min, children = two_boxes());
+    pump_in_direction(&mut tester, TextDirection::Ltr, row.clone());
+    assert_close(tester.get_center(&container_of_width(20.0)).x, 10.0);
+    pump_in_direction(&mut tester, TextDirection::Rtl, row);
+    assert_close(tester.get_center(&container_of_width(20.0)).x, 40.0);
+
+    pump_in_direction(
+        &mut tester,
+        TextDirection::Rtl,
+        Row!(
+            main_axis_size = MainAxisSize::Min,
+            text_direction = TextDirection::Ltr,
+            children = two_boxes()
+        ),
+    );
+    assert_close(tester.get_center(&container_of_width(20.0)).x, 10.0);
+}
+
+#[test]
+fn positioned_start_follows_the_stack_direction() {
+    let mut tester = WidgetTester::new_with_size(BoxSize {
+        width: 200.0,
+        height: 100.0,
+    });
+    for (text_direction, center) in [(TextDirection::Ltr, 20.0), (TextDirection::Rtl, 180.0)] {
+        tester.pump_widget(Directionality!(
+            text_direction,
+            child = Stack!(
+                children = vec![Positioned!(
+                    start = 10.0,
+                    t = 0.0,
+                    child = Container!(width = 20.0, height = 20.0, color = Color::BLACK)
+                )]
+            )
+        ));
+        assert_close(tester.get_center(&container_of_width(20.0)).x, center);
+    }
+}
+
+#[test]
+fn right_to_left_label_is_truncated_on_the_left() {
+    let mut tester = WidgetTester::new_with_size(BoxSize {
+        width: 200.0,
+        height: 100.0,
+    });
+    let mut label_ink = Vec::new();
+    for overflow in [
+        TextOverFlow::Clip,
+        TextOverFlow::Fade,
+        TextOverFlow::Ellipsis,
+    ] {
+        tester.pump_widget(MaterialApp!(
+            child = Directionality!(
+                text_direction = TextDirection::Rtl,
+                child = Align!(
+                    alignment = AlignmentDirectional::TOP_START,
+                    child = Container!(
+                        width = 120.0,
+                        child = Text!(
+                            text = "שלום עולם, זוהי שורה ארוכה שאינה נכנסת ברוחב",
+                            style = LocalTextStyle {
+                                overflow: Some(overflow),
+                                ..Default::default()
+                            },
+                            max_lines = 1
+                        )
+                    )
+                )
+            )
+        ));
+        // The label starts at the right edge, and nothing is painted past its left edge
+        assert!(ink(&tester, 190..200) > 0, "{overflow:?}");
+        assert_eq!(ink(&tester, 0..80), 0, "{overflow:?}");
+        label_ink.push(ink(&tester, 80..200));
+    }
+    // The fade erases the left end of the line, and the ellipsis replaces it
+    assert!(label_ink[1] < label_ink[0]);
+    assert_ne!(label_ink[2], label_ink[0]);
+}
@@ -0,0 +1,162 @@
+use std::sync::atomic::{AtomicBool, Ordering::*};
+
+use epgi_2d::ArcBoxWidget;
+use epgi_common::{Center, Column, GestureDetector, Text};
+use epgi_core::{
+    foundation::{Arc, Asc, BuildPanic, Error, TracedError},
+    nodes::{Builder, Consumer, ErrorBoundary, ErrorBoundaryReset, SuspendableBuilder},
+};
+use epgi_material::MaterialApp;
+use epgi_test::{Finder, WidgetTester};
+
+fn fallback(label: &'static str) -> impl Fn(&TracedError, ErrorBoundaryReset) -> ArcBoxWidget {
+    move |error, reset| {
+        GestureDetector!(
+            on_tap = move |job_builder| reset.reset(job_builder),
+            child = Text!(text = format!("{label}: {error}"))
+        )
+    }
+}
+
+fn app(child: ArcBoxWidget) -> ArcBoxWidget {
+    MaterialApp!(child = Center!(child = child))
+}
+
+fn panicking_unless(ok: &Arc<AtomicBool>) -> ArcBoxWidget {
+    let ok = ok.clone();
+    Arc::new(Builder {
+        builder: move |_ctx| {
+            if !ok.load(Relaxed) {
+                panic!("boom");
+            }
+            Text!(text = "content") as _
+        },
+    })
+}
+
+#[test]
+fn shows_fallback_when_a_rebuild_panics() {
+    let ok = Arc::new(AtomicBool::new(true));
+    let failing_app = |ok: &Arc<AtomicBool>| {
+        app(ErrorBoundary!(
+            child = Column!(children = vec![Text!(text = "sibling"), panicking_unless(ok)]),
+            fallback = fallback("caught")
+        ))
+    };
+    let mut tester = WidgetTester::new();
+    tester.pump_widget(failing_app(&ok));
+    assert!(tester.any(&Finder::text("content")));
+
+    ok.store(false, Relaxed);
+    tester.pump_widget(failing_app(&ok));
+    assert!(tester.any(&Finder::text("caught: build panicked: boom")));
+    assert!(!tester.any(&Finder::text("sibling")));
+}
+
+#[test]
+fn reset_inflates_the_child_again() {
+    let ok = Arc::new(AtomicBool::new(false));
+    let mut tester = WidgetTester::new();
+    tester.pump_widget(app(ErrorBoundary!(
+        child = panicking_unless(&ok),
+        fallback = fallback("caught")
+    )));
+    let fallback_text = Finder::text("caught: build panicked: boom");
+    assert!(tester.any(&fallback_text));
+
+    ok.store(true, Relaxed);
+    tester.tap(&fallback_text);
+    assert!(tester.any(&Finder::text("content")));
+    assert!(!tester.any(&fallback_text));
+}
+
+#[test]
+fn errors_go_to_the_nearest_boundary() {
+    let failing = || -> ArcBoxWidget {
+        Arc::new(SuspendableBuilder {
+            builder: |_ctx| Err(Error::Custom("bad input".into()).into()),
+        })
+    };
+    let mut tester = WidgetTester::new();
+    tester.pump_widget(app(ErrorBoundary!(
+        child = ErrorBoundary!(child = failing(), fallback = fallback("inner")),
+        fallback = fallback("outer")
+    )));
+    assert!(tester.any(&Finder::text("inner: bad input")));
+
+    // A failing fallback is caught by the boundary above.
+    tester.pump_widget(app(ErrorBoundary!(
+        child = ErrorBoundary!(
+            child = failing(),
+            fallback = move |_error, _reset| failing()
+        ),
+        fallback = fallback("outer")
+    )));
+    assert!(tester.any(&Finder::text("outer: bad input")));
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Missing;
+
+#[test]
+fn missing_provider_is_traced_to_the_boundary() {
+    let mut tester = WidgetTester::new();
+    tester.pump_widget(app(ErrorBoundary!(
+        child = Consumer!(builder = |_ctx, _value: Asc<Missing>| Text!(text = "content")),
+        fallback = |error: &TracedError, _reset| {
+            assert_eq!(error.trace().len(), 2);
+            assert!(error.trace()[0].contains("Consumer"));
+            assert!(error.trace()[1].contains("ErrorBoundary"));
+            Text!(text = error.to_string())
+        }
+    )));
+    assert!(tester.any(&Finder::text("provider not found: error_boundary::Missing")));
+}
+
+#[derive(Debug)]
+struct BadInput;
+
+impl std::fmt::Display for BadInput {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("bad input")
+    }
+}
+
+impl std::error::Error for BadInput {}
+
+/// Whether the boundary received a `BadInput` error, or a panic of a component build.
+fn error_source(error: &TracedError) -> &'static str {
+    match &error.error {
+        Error::Custom(error) if error.is::<BadInput>() => "typed",
+        Error::Custom(error) if error.is::<BuildPanic>() => "panic",
+        _ => "other",
+    }
+}
+
+#[test]
+fn suspendable_components_fail_with_typed_errors() {
+    let mut tester = WidgetTester::new();
+    tester.pump_widget(app(ErrorBoundary!(
+        child = Arc::new(SuspendableBuilder {
+            builder: |_ctx| Err(Error::Custom(Box::new(BadInput)).into()),
+        }),
+        fallback = |error: &TracedError, _reset| Text!(text = error_source(error))
+    )));
+    assert!(tester.any(&Finder::text("typed")));
+}
+
+#[test]
+fn components_fail_only_by_panicking() {
+    // A component build has no error to return, so its failure arrives as a panic
+    let mut tester = WidgetTester::new();
+    tester.pump_widget(app(ErrorBoundary!(
+        child = Arc::new(Builder {
+            builder: |_ctx| -> ArcBoxWidget { panic!("{}", BadInput) },
+        }),
+        fallback = |error: &TracedError, _reset| {
+            assert!(error.trace()[0].contains("Builder"));
+            Text!(text = error_source(error))
+        }
+    )));
+    assert!(tester.any(&Finder::text("panic")));
+}
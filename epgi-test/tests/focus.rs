@@ -0,0 +1,190 @@
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering::*};
+
+use epgi_2d::TextDirection;
+use epgi_common::{
+    Container, Directionality, Focus, FocusNode, FocusScope, KeyCode, KeyEventResult, KeyModifiers,
+    LogicalKey, NamedKey, Positioned, Row, Stack,
+};
+use epgi_core::foundation::Arc;
+use epgi_test::WidgetTester;
+
+fn press_tab(tester: &mut WidgetTester, modifiers: KeyModifiers) {
+    tester.press_key(KeyCode::Tab, NamedKey::Tab.into(), modifiers);
+}
+
+#[test]
+fn tab_traverses_in_layout_order() {
+    let [a, b, c] = [FocusNode::new(), FocusNode::new(), FocusNode::new()];
+    let mut tester = WidgetTester::new();
+    // The nodes are declared in the reverse order of where they are laid out.
+    tester.pump_widget(Stack!(
+        children = vec![
+            Positioned!(
+                l = 0.0,
+                t = 50.0,
+                child = Focus!(
+                    focus_node = c.clone(),
+                    child = Container!(width = 40.0, height = 40.0)
+                )
+            ),
+            Positioned!(
+                l = 50.0,
+                t = 0.0,
+                child = Focus!(
+                    focus_node = b.clone(),
+                    child = Container!(width = 40.0, height = 40.0)
+                )
+            ),
+            Positioned!(
+                l = 0.0,
+                t = 0.0,
+                child = Focus!(
+                    focus_node = a.clone(),
+                    child = Container!(width = 40.0, height = 40.0)
+                )
+            ),
+        ]
+    ));
+    assert_eq!(tester.primary_focus(), None);
+
+    for expected in [&a, &b, &c, &a] {
+        press_tab(&mut tester, KeyModifiers::empty());
+        assert_eq!(tester.primary_focus().as_ref(), Some(expected));
+    }
+    press_tab(&mut tester, KeyModifiers::SHIFT);
+    assert_eq!(tester.primary_focus().as_ref(), Some(&c));
+    assert!(c.has_primary_focus());
+    assert!(!a.has_focus());
+}
+
+#[test]
+fn key_events_bubble_from_primary_focus() {
+    let escape_count = Arc::new(AtomicUsize::new(0));
+    let inner_focused = Arc::new(AtomicBool::new(false));
+    let mut tester = WidgetTester::new();
+    tester.pump_widget(Focus!(
+        on_key_event = {
+            let escape_count = escape_count.clone();
+            move |event, _job_builder| {
+                if event.is_shortcut(&NamedKey::Escape.into(), KeyModifiers::empty()) {
+                    escape_count.fetch_add(1, Relaxed);
+                    return KeyEventResult::Handled;
+                }
+                KeyEventResult::Ignored
+            }
+        },
+        child = Focus!(
+            autofocus = true,
+            on_focus_change = {
+                let inner_focused = inner_focused.clone();
+                move |has_focus, _job_builder| inner_focused.store(has_focus, Relaxed)
+            },
+            child = Container!(width = 10.0, height = 10.0)
+        )
+    ));
+    // The autofocus request is applied at the beginning of the next frame.
+    tester.pump(Default::default());
+    assert!(inner_focused.load(Relaxed));
+
+    tester.press_key(
+        KeyCode::Escape,
+        NamedKey::Escape.into(),
+        KeyModifiers::empty(),
+    );
+    assert_eq!(escape_count.load(Relaxed), 1);
+
+    // Without focus, the event does not reach the node.
+    tester.primary_focus().unwrap().unfocus();
+    tester.pump(Default::default());
+    assert!(!inner_focused.load(Relaxed));
+    tester.press_key(
+        KeyCode::KeyA,
+        LogicalKey::character("a"),
+        KeyModifiers::empty(),
+    );
+    assert_eq!(escape_count.load(Relaxed), 1);
+}
+
+#[test]
+fn traversal_stays_within_scope() {
+    let [outside, first, second] = [FocusNode::new(), FocusNode::new(), FocusNode::new()];
+    let scope = FocusNode::new_scope();
+    let mut tester = WidgetTester::new();
+    tester.pump_widget(Row!(
+        children = vec![
+            Focus!(
+                focus_node = outside.clone(),
+                child = Container!(width = 10.0, height = 10.0)
+            ),
+            FocusScope!(
+                node = scope.clone(),
+                child = Row!(
+                    children = vec![
+                        Focus!(
+                            focus_node = first.clone(),
+                            child = Container!(width = 10.0, height = 10.0)
+                        ),
+                        Focus!(
+                            focus_node = second.clone(),
+                            child = Container!(width = 10.0, height = 10.0)
+                        ),
+                    ]
+                )
+            ),
+        ]
+    ));
+    first.request_focus();
+    tester.pump(Default::default());
+    for expected in [&second, &first, &second] {
+        press_tab(&mut tester, KeyModifiers::empty());
+        assert_eq!(tester.primary_focus().as_ref(), Some(expected));
+    }
+    assert!(scope.has_focus());
+
+    // The scope restores its last focused descendant.
+    outside.request_focus();
+    tester.pump(Default::default());
+    assert!(!scope.has_focus());
+    scope.request_focus();
+    tester.pump(Default::default());
+    assert_eq!(tester.primary_focus().as_ref(), Some(&second));
+}
+
+#[test]
+fn rtl_scope_traverses_rows_from_right_to_left() {
+    let [a, b, c] = [FocusNode::new(), FocusNode::new(), FocusNode::new()];
+    let mut tester = WidgetTester::new();
+    // The nodes are placed with physical offsets, so only the traversal depends on the direction.
+    let positioned = |l: f32, t: f32, node: &FocusNode| {
+        Positioned!(
+            l = l,
+            t = t,
+            child = Focus!(
+                focus_node = node.clone(),
+                child = Container!(width = 40.0, height = 40.0)
+            )
+        )
+    };
+    tester.pump_widget(Directionality!(
+        text_direction = TextDirection::Rtl,
+        child = FocusScope!(
+            node = FocusNode::new_scope(),
+            child = Stack!(
+                children = vec![
+                    positioned(0.0, 0.0, &a),
+                    positioned(50.0, 0.0, &b),
+                    positioned(0.0, 50.0, &c),
+                ]
+            )
+        )
+    ));
+    a.request_focus();
+    tester.pump(Default::default());
+
+    for expected in [&c, &b, &a] {
+        press_tab(&mut tester, KeyModifiers::empty());
+        assert_eq!(tester.primary_focus().as_ref(), Some(expected));
+    }
+    press_tab(&mut tester, KeyModifiers::SHIFT);
+    assert_eq!(tester.primary_focus().as_ref(), Some(&b));
+}
@@ -0,0 +1,87 @@
+use std::borrow::Cow;
+
+use epgi_2d::{
+    FontFamily, FontLoadError, FontLoader, FontSource, FontWeight, LocalTextStyle, Paragraph,
+    TextDirection, TextSpan,
+};
+use epgi_material::black_mountain_view_body_medium;
+
+/// The ASCII subset of DejaVu Serif, renamed so that it never clashes with an installed copy.
+const TEST_SERIF: &str = "Epgi Test Serif";
+
+fn test_serif() -> FontSource {
+    FontSource::memory(&include_bytes!("fonts/EpgiTestSerif.ttf")[..])
+}
+
+fn test_serif_bold() -> FontSource {
+    FontSource::file(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fonts/EpgiTestSerif-Bold.ttf"
+    ))
+}
+
+fn max_width(text: impl Into<Cow<'static, str>>, style: LocalTextStyle) -> f32 {
+    Paragraph::new(
+        &[TextSpan {
+            text: text.into(),
+            style: Some(style),
+        }],
+        &black_mountain_view_body_medium(),
+        TextDirection::Ltr,
+    )
+    .max_intrinsic_width()
+}
+
+fn family(name: &'static str) -> LocalTextStyle {
+    LocalTextStyle {
+        font_family: Some(FontFamily::Named(name)),
+        font_family_fallback: Some(Vec::new()),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn loading_returns_the_registered_families() {
+    assert_eq!(FontLoader.load(&test_serif()).unwrap(), vec![TEST_SERIF]);
+    assert_eq!(
+        FontLoader.load(&test_serif_bold()).unwrap(),
+        vec![TEST_SERIF]
+    );
+}
+
+#[test]
+fn loading_invalid_data_fails() {
+    assert!(matches!(
+        FontLoader.load(&FontSource::memory(&b"not a font"[..])),
+        Err(FontLoadError::NoFonts)
+    ));
+    assert!(matches!(
+        FontLoader.load(&FontSource::file("/nonexistent/font.ttf")),
+        Err(FontLoadError::Io(_))
+    ));
+}
+
+#[test]
+fn aliases_resolve_to_the_loaded_family() {
+    // Aliases cannot be removed from the process-wide loader, so no other test may use this name.
+    const ALIAS: &str = "Aliased Epgi Test Serif";
+    let text = "Aliased families are resolved when shaping";
+    // Shape twice, so that the layout is cached before the alias exists.
+    let unresolved = max_width(text, family(ALIAS));
+    assert_eq!(max_width(text, family(ALIAS)), unresolved);
+
+    FontLoader.load_as(&test_serif(), ALIAS).unwrap();
+    FontLoader.load(&test_serif_bold()).unwrap();
+    let aliased = max_width(text, family(ALIAS));
+    assert_ne!(aliased, unresolved);
+    assert_eq!(aliased, max_width(text, family(TEST_SERIF)));
+
+    // The weight of the text picks the matching font of the family
+    let bold_style = |name| LocalTextStyle {
+        font_weight: Some(FontWeight::BOLD),
+        ..family(name)
+    };
+    let aliased_bold = max_width(text, bold_style(ALIAS));
+    assert!(aliased_bold > aliased);
+    assert_eq!(aliased_bold, max_width(text, bold_style(TEST_SERIF)));
+}
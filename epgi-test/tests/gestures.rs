@@ -0,0 +1,235 @@
+use std::time::Duration;
+
+use epgi_2d::{ArcBoxWidget, Color, Point2d};
+use epgi_common::{
+    Center, Container, GestureDetector, PointerButtons, PointerContactData, PointerEvent,
+    PointerHoverData, PointerInteractionId, PointerPanZoomUpdateData,
+};
+use epgi_core::foundation::Asc;
+use epgi_test::{
+    testing::{record, take, Log},
+    WidgetTester,
+};
+
+/// The center of the default window, where the detector sits.
+const CENTER: Point2d = Point2d { x: 400.0, y: 300.0 };
+
+fn pump_detector(
+    tester: &mut WidgetTester,
+    detector: impl FnOnce(Log, ArcBoxWidget) -> Asc<GestureDetector>,
+) -> Log {
+    let log = Log::default();
+    tester.pump_widget(Center!(
+        child = detector(
+            log.clone(),
+            Container!(width = 200.0, height = 200.0, color = Color::BLACK)
+        )
+    ));
+    log
+}
+
+fn down(tester: &mut WidgetTester, position: Point2d) -> PointerInteractionId {
+    let interaction_id = tester.new_interaction_id();
+    tester.send_pointer_event(PointerEvent::new_down(
+        tester.pointer_common_data(position),
+        interaction_id,
+        PointerContactData::new_mouse(PointerButtons::PRIMARY_MOUSE_BUTTON),
+    ));
+    tester.pump(Duration::ZERO);
+    interaction_id
+}
+
+fn move_to(tester: &mut WidgetTester, interaction_id: PointerInteractionId, position: Point2d) {
+    tester.send_pointer_event(PointerEvent::new_move(
+        tester.pointer_common_data(position),
+        interaction_id,
+        PointerContactData::new_mouse(PointerButtons::PRIMARY_MOUSE_BUTTON),
+    ));
+    tester.pump(Duration::ZERO);
+}
+
+fn up(tester: &mut WidgetTester, interaction_id: PointerInteractionId, position: Point2d) {
+    tester.send_pointer_event(PointerEvent::new_up(
+        tester.pointer_common_data(position),
+        interaction_id,
+        PointerHoverData::new_mouse(),
+    ));
+    tester.pump(Duration::ZERO);
+}
+
+fn offset_center(dx: f32, dy: f32) -> Point2d {
+    Point2d {
+        x: CENTER.x + dx,
+        y: CENTER.y + dy,
+    }
+}
+
+#[test]
+fn tap_is_cancelled_by_moving_past_the_slop() {
+    let mut tester = WidgetTester::new();
+    let log = pump_detector(&mut tester, |log, child| {
+        GestureDetector!(on_tap = move |_job_builder| record(&log, "tap"), child)
+    });
+
+    let interaction_id = down(&mut tester, CENTER);
+    move_to(&mut tester, interaction_id, offset_center(5.0, 5.0));
+    up(&mut tester, interaction_id, offset_center(5.0, 5.0));
+    assert_eq!(take(&log), ["tap"]);
+
+    let interaction_id = down(&mut tester, CENTER);
+    move_to(&mut tester, interaction_id, offset_center(40.0, 0.0));
+    move_to(&mut tester, interaction_id, CENTER);
+    up(&mut tester, interaction_id, CENTER);
+    assert!(take(&log).is_empty());
+}
+
+#[test]
+fn horizontal_drag_wins_over_tap_once_past_the_slop() {
+    let mut tester = WidgetTester::new();
+    let log = pump_detector(&mut tester, |log, child| {
+        GestureDetector!(
+            on_tap = {
+                let log = log.clone();
+                move |_job_builder| record(&log, "tap")
+            },
+            on_horizontal_drag_start = {
+                let log = log.clone();
+                move |_details, _job_builder| record(&log, "start")
+            },
+            on_horizontal_drag_update = {
+                let log = log.clone();
+                move |details, _job_builder| {
+                    record(
+                        &log,
+                        format!("update {} {}", details.delta.x, details.delta.y),
+                    )
+                }
+            },
+            on_horizontal_drag_end = move |details, _job_builder| {
+                record(&log, format!("end {}", details.velocity.y))
+            },
+            child
+        )
+    });
+
+    let interaction_id = down(&mut tester, CENTER);
+    move_to(&mut tester, interaction_id, offset_center(10.0, 5.0));
+    assert!(take(&log).is_empty());
+    // The pending movement is reported right after the start, without the vertical part.
+    move_to(&mut tester, interaction_id, offset_center(30.0, 10.0));
+    assert_eq!(take(&log), ["start", "update 30 0"]);
+    move_to(&mut tester, interaction_id, offset_center(20.0, 40.0));
+    assert_eq!(take(&log), ["update -10 0"]);
+    up(&mut tester, interaction_id, offset_center(20.0, 40.0));
+    assert_eq!(take(&log), ["end 0"]);
+}
+
+#[test]
+fn long_press_is_recognized_after_the_timeout() {
+    let mut tester = WidgetTester::new();
+    let log = pump_detector(&mut tester, |log, child| {
+        GestureDetector!(
+            on_tap = {
+                let log = log.clone();
+                move |_job_builder| record(&log, "tap")
+            },
+            on_long_press = move |_job_builder| record(&log, "long press"),
+            child
+        )
+    });
+
+    let interaction_id = down(&mut tester, CENTER);
+    tester.pump(Duration::from_millis(300));
+    up(&mut tester, interaction_id, CENTER);
+    assert_eq!(take(&log), ["tap"]);
+
+    let interaction_id = down(&mut tester, CENTER);
+    tester.pump(Duration::from_millis(300));
+    assert!(take(&log).is_empty());
+    tester.pump(Duration::from_millis(300));
+    assert_eq!(take(&log), ["long press"]);
+    up(&mut tester, interaction_id, CENTER);
+    assert!(take(&log).is_empty());
+}
+
+#[test]
+fn double_tap_holds_the_tap_until_the_timeout() {
+    let mut tester = WidgetTester::new();
+    let log = pump_detector(&mut tester, |log, child| {
+        GestureDetector!(
+            on_tap = {
+                let log = log.clone();
+                move |_job_builder| record(&log, "tap")
+            },
+            on_double_tap = move |_job_builder| record(&log, "double tap"),
+            child
+        )
+    });
+
+    tester.tap_at(CENTER);
+    tester.pump(Duration::from_millis(100));
+    assert!(take(&log).is_empty());
+    tester.tap_at(offset_center(10.0, 10.0));
+    assert_eq!(take(&log), ["double tap"]);
+    tester.pump_and_settle();
+    assert!(take(&log).is_empty());
+
+    // A single tap is only called once no second tap can follow.
+    tester.tap_at(CENTER);
+    assert!(take(&log).is_empty());
+    tester.pump(Duration::from_millis(400));
+    assert_eq!(take(&log), ["tap"]);
+
+    // Two taps too far apart in time are two separate taps.
+    tester.tap_at(CENTER);
+    tester.pump(Duration::from_millis(400));
+    tester.tap_at(CENTER);
+    tester.pump(Duration::from_millis(400));
+    assert_eq!(take(&log), ["tap", "tap"]);
+}
+
+#[test]
+fn scale_follows_trackpad_pinch() {
+    let mut tester = WidgetTester::new();
+    let log = pump_detector(&mut tester, |log, child| {
+        GestureDetector!(
+            on_scale_start = {
+                let log = log.clone();
+                move |_details, _job_builder| record(&log, "start")
+            },
+            on_scale_update = {
+                let log = log.clone();
+                move |details, _job_builder| record(&log, format!("update {}", details.scale))
+            },
+            on_scale_end = move |_details, _job_builder| record(&log, "end"),
+            child
+        )
+    });
+
+    let interaction_id = tester.new_interaction_id();
+    tester.send_pointer_event(PointerEvent::new_pan_zoom_start(
+        tester.pointer_common_data(CENTER),
+        interaction_id,
+    ));
+    for scale in [1.02, 1.5, 2.0] {
+        tester.send_pointer_event(PointerEvent::new_pan_zoom_update(
+            tester.pointer_common_data(CENTER),
+            interaction_id,
+            PointerPanZoomUpdateData {
+                pan: Default::default(),
+                scale,
+                rotation: 0.0,
+            },
+        ));
+    }
+    tester.send_pointer_event(PointerEvent::new_pan_zoom_end(
+        tester.pointer_common_data(CENTER),
+        interaction_id,
+    ));
+    tester.pump(Duration::ZERO);
+    // As the only recognizer, the scale wins the arena as soon as the gesture starts.
+    assert_eq!(
+        take(&log),
+        ["start", "update 1.02", "update 1.5", "update 2", "end"]
+    );
+}
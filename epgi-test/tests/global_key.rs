@@ -0,0 +1,70 @@
+use std::sync::atomic::{AtomicUsize, Ordering::*};
+
+use epgi_2d::{ArcBoxWidget, Color};
+use epgi_common::{Center, Column, Container, Row};
+use epgi_core::{
+    foundation::{Arc, GlobalKey},
+    nodes::{Builder, KeyedSubtree},
+};
+use epgi_test::{Finder, WidgetTester};
+
+/// A box that counts how many times its state has been created.
+fn counted_box(key: &GlobalKey, mount_count: &Arc<AtomicUsize>) -> ArcBoxWidget {
+    let mount_count = mount_count.clone();
+    KeyedSubtree!(
+        key = key.clone(),
+        child = Arc::new(Builder {
+            builder: move |ctx| {
+                ctx.use_state_with(|| mount_count.fetch_add(1, Relaxed));
+                Container!(width = 50.0, height = 50.0, color = Color::BLACK) as _
+            }
+        })
+    )
+}
+
+fn panels(left: Vec<ArcBoxWidget>, right: Vec<ArcBoxWidget>) -> ArcBoxWidget {
+    Row!(children = vec![Column!(children = left), Column!(children = right)])
+}
+
+#[test]
+fn moves_between_parents_without_losing_state() {
+    let key = GlobalKey::new();
+    let mount_count = Arc::new(AtomicUsize::new(0));
+    let spacer = || Container!(width = 200.0, height = 50.0) as ArcBoxWidget;
+    let mut tester = WidgetTester::new();
+    tester.pump_widget(panels(
+        vec![counted_box(&key, &mount_count)],
+        vec![spacer()],
+    ));
+    let finder = Finder::by_key(key.clone());
+    let element = tester.find(&finder);
+    let left_center = tester.get_center(&finder);
+
+    tester.pump_widget(panels(
+        vec![spacer()],
+        vec![counted_box(&key, &mount_count)],
+    ));
+    assert_eq!(mount_count.load(Relaxed), 1);
+    assert!(Arc::ptr_eq(&tester.find(&finder), &element));
+    assert!(tester.get_center(&finder).x > left_center.x);
+
+    // Moving into a parent that is inflated in the same build.
+    tester.pump_widget(Center!(
+        child = Column!(children = vec![counted_box(&key, &mount_count)])
+    ));
+    assert_eq!(mount_count.load(Relaxed), 1);
+    assert!(Arc::ptr_eq(&tester.find(&finder), &element));
+}
+
+#[test]
+fn remounts_when_removed_for_a_build() {
+    let key = GlobalKey::new();
+    let mount_count = Arc::new(AtomicUsize::new(0));
+    let mut tester = WidgetTester::new();
+    tester.pump_widget(panels(vec![counted_box(&key, &mount_count)], vec![]));
+    tester.pump_widget(panels(vec![], vec![]));
+    assert!(!tester.any(&Finder::by_key(key.clone())));
+
+    tester.pump_widget(panels(vec![], vec![counted_box(&key, &mount_count)]));
+    assert_eq!(mount_count.load(Relaxed), 2);
+}
@@ -0,0 +1,118 @@
+use epgi_2d::{ArcBoxWidget, BoxSize, Color};
+use epgi_common::{ColoredBox, Container, EdgeInsets, Padding, Row, ARC_PHANTOM_BOX};
+use epgi_test::{match_golden, tiny_skia::PremultipliedColorU8, GoldenTolerance, WidgetTester};
+
+const SIZE: BoxSize = BoxSize {
+    width: 100.0,
+    height: 60.0,
+};
+
+fn pixel(tester: &WidgetTester, x: u32, y: u32) -> PremultipliedColorU8 {
+    tester.rasterize().pixel(x, y).unwrap()
+}
+
+fn rgba(pixel: PremultipliedColorU8) -> [u8; 4] {
+    [pixel.red(), pixel.green(), pixel.blue(), pixel.alpha()]
+}
+
+#[test]
+fn rasterizes_padded_box() {
+    let mut tester = WidgetTester::new_with_size(SIZE);
+    tester.pump_widget(Padding!(
+        padding = EdgeInsets::new_all(10.0),
+        child = ColoredBox!(
+            color = Color::rgb(1.0, 0.0, 0.0),
+            child = ARC_PHANTOM_BOX.clone()
+        )
+    ));
+    let image = tester.rasterize();
+    assert_eq!((image.width(), image.height()), (100, 60));
+    assert_eq!(rgba(pixel(&tester, 5, 5)), [0, 0, 0, 0]);
+    assert_eq!(rgba(pixel(&tester, 10, 10)), [255, 0, 0, 255]);
+    assert_eq!(rgba(pixel(&tester, 89, 49)), [255, 0, 0, 255]);
+    assert_eq!(rgba(pixel(&tester, 90, 50)), [0, 0, 0, 0]);
+}
+
+#[test]
+fn row_matches_golden() {
+    let mut tester = WidgetTester::new_with_size(SIZE);
+    tester.pump_widget(Row!(
+        children = vec![
+            Container!(
+                width = 20.0,
+                height = 20.0,
+                color = Color::rgb(1.0, 0.0, 0.0)
+            ),
+            Container!(
+                width = 30.0,
+                height = 40.0,
+                color = Color::rgb(0.0, 1.0, 0.0)
+            ),
+            Container!(
+                width = 20.0,
+                height = 60.0,
+                color = Color::rgba(0.0, 0.0, 1.0, 0.5)
+            ),
+        ]
+    ));
+    tester.match_golden("tests/goldens/row.png", GoldenTolerance::default());
+}
+
+#[test]
+#[should_panic(expected = "mismatched")]
+fn mismatch_is_reported() {
+    let path =
+        std::env::temp_dir().join(format!("epgi_golden_mismatch_{}.png", std::process::id()));
+    let mut tester = WidgetTester::new_with_size(SIZE);
+    tester.pump_widget(ColoredBox!(
+        color = Color::rgb(1.0, 0.0, 0.0),
+        child = ARC_PHANTOM_BOX.clone()
+    ));
+    tester.rasterize().save_png(&path).unwrap();
+    tester.pump_widget(ColoredBox!(
+        color = Color::rgb(0.0, 0.0, 1.0),
+        child = ARC_PHANTOM_BOX.clone()
+    ));
+    match_golden(&tester.rasterize(), &path, GoldenTolerance::EXACT);
+}
+
+#[test]
+#[should_panic(expected = "EPGI_UPDATE_GOLDENS")]
+fn missing_golden_is_reported() {
+    let path = std::env::temp_dir().join(format!("epgi_golden_missing_{}.png", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let mut tester = WidgetTester::new_with_size(SIZE);
+    tester.pump_widget(ColoredBox!(
+        color = Color::rgb(1.0, 0.0, 0.0),
+        child = ARC_PHANTOM_BOX.clone()
+    ));
+    match_golden(&tester.rasterize(), &path, GoldenTolerance::EXACT);
+}
+
+#[test]
+fn paints_many_siblings_in_order() {
+    let red = Color::rgb(1.0, 0.0, 0.0);
+    let blue = Color::rgb(0.0, 0.0, 1.0);
+    let mut tester = WidgetTester::new_with_size(SIZE);
+    tester.pump_widget(Padding!(
+        padding = EdgeInsets::new_all(10.0),
+        child = Row!(
+            children = (0..80)
+                .map(|index| Container!(
+                    width = 1.0,
+                    height = 40.0,
+                    color = if index % 2 == 0 { red } else { blue }
+                ) as ArcBoxWidget)
+                .collect::<Vec<_>>()
+        )
+    ));
+    let image = tester.rasterize();
+    for index in 0..80 {
+        let expected = if index % 2 == 0 {
+            [255, 0, 0, 255]
+        } else {
+            [0, 0, 255, 255]
+        };
+        assert_eq!(rgba(image.pixel(10 + index, 30).unwrap()), expected);
+    }
+}
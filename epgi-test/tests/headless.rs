@@ -0,0 +1,76 @@
+use std::sync::atomic::{AtomicUsize, Ordering::*};
+
+use epgi_2d::{Affine2dEncoding, BoxSize, Color};
+use epgi_common::{ColoredBox, ARC_PHANTOM_BOX};
+use epgi_core::{
+    foundation::{Arc, SyncMutex},
+    hooks::SetState,
+    nodes::Builder,
+    scheduler::get_current_scheduler,
+};
+use epgi_test::HeadlessScheduler;
+
+const SIZE: BoxSize = BoxSize {
+    width: 100.0,
+    height: 100.0,
+};
+
+#[test]
+fn pumps_composited_frames() {
+    let app = Arc::new(ColoredBox {
+        color: Color::rgb(1.0, 0.0, 0.0),
+        child: ARC_PHANTOM_BOX.clone(),
+    });
+    let mut scheduler = HeadlessScheduler::new(app, SIZE);
+    let first = scheduler.pump_frame();
+    let second = scheduler.pump_frame();
+    assert!(second.id > first.id);
+    assert!(second
+        .composited
+        .downcast_ref::<Arc<Affine2dEncoding>>()
+        .is_some());
+    assert_eq!(scheduler.frame_count(), 2);
+}
+
+#[test]
+fn rebuilds_on_state_change() {
+    let build_count = Arc::new(AtomicUsize::new(0));
+    let set_counter = Arc::new(SyncMutex::<Option<SetState<u32>>>::new(None));
+    let app = Arc::new(Builder {
+        builder: {
+            let build_count = build_count.clone();
+            let set_counter = set_counter.clone();
+            move |ctx| {
+                build_count.fetch_add(1, Relaxed);
+                let set_counter = set_counter.clone();
+                let (_counter, set) = ctx.use_state(0u32);
+                ctx.use_effect(move |_| *set_counter.lock() = Some(set), ());
+                ARC_PHANTOM_BOX.clone()
+            }
+        },
+    });
+    let mut scheduler = HeadlessScheduler::new(app, SIZE);
+    scheduler.pump_frame();
+    let builds_before = build_count.load(Relaxed);
+    assert!(!scheduler.has_scheduled_frame());
+
+    let set = set_counter.lock().clone().unwrap();
+    get_current_scheduler().create_sync_job(|job_builder| {
+        set.set(1, job_builder);
+    });
+    assert!(scheduler.has_scheduled_frame());
+    scheduler.pump_frame();
+    assert_eq!(build_count.load(Relaxed), builds_before + 1);
+}
+
+#[test]
+fn restarts_after_drop() {
+    for _ in 0..3 {
+        let mut scheduler = HeadlessScheduler::new(ARC_PHANTOM_BOX.clone(), SIZE);
+        scheduler.set_size(BoxSize {
+            width: 50.0,
+            height: 50.0,
+        });
+        scheduler.pump_frame();
+    }
+}
@@ -0,0 +1,240 @@
+use std::{
+    io::Cursor,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use epgi_2d::{ArcBoxWidget, BoxSize, Color};
+use epgi_common::{
+    decode_image, Alignment, BoxFit, Center, ColoredBox, FittedSizes, Image, ImageCache,
+    ImageProvider, RawImage, Row, ARC_PHANTOM_BOX,
+};
+use epgi_core::nodes::{ErrorBoundary, Suspense};
+use epgi_test::{Finder, WidgetTester};
+
+const RED: [u8; 4] = [255, 0, 0, 255];
+const BLUE: [u8; 4] = [0, 0, 255, 255];
+
+/// A PNG whose left half is red and whose right half is blue.
+fn red_blue_png(width: u32, height: u32) -> Vec<u8> {
+    let image = image::RgbaImage::from_fn(width, height, |x, _y| {
+        image::Rgba(if x < width / 2 { RED } else { BLUE })
+    });
+    let mut bytes = Vec::new();
+    image
+        .write_to(&mut Cursor::new(&mut bytes), image::ImageFormat::Png)
+        .unwrap();
+    bytes
+}
+
+/// Pump frames until `done`, giving the async threadpool time to decode images in between.
+fn pump_until(tester: &mut WidgetTester, done: impl Fn(&WidgetTester) -> bool) {
+    for _ in 0..1000 {
+        if done(tester) {
+            return;
+        }
+        std::thread::sleep(Duration::from_millis(2));
+        tester.pump(Duration::ZERO);
+    }
+    panic!("The tree never finished loading");
+}
+
+fn pump_until_loaded(tester: &mut WidgetTester) {
+    pump_until(tester, |tester| tester.any(&Finder::by_type::<RawImage>()))
+}
+
+fn rgba(tester: &WidgetTester, x: u32, y: u32) -> [u8; 4] {
+    let pixel = tester.rasterize().pixel(x, y).unwrap().demultiply();
+    [pixel.red(), pixel.green(), pixel.blue(), pixel.alpha()]
+}
+
+fn suspense(child: ArcBoxWidget) -> ArcBoxWidget {
+    Suspense!(child = child, fallback = ARC_PHANTOM_BOX.clone())
+}
+
+#[test]
+fn suspends_until_the_image_is_decoded() {
+    let mut tester = WidgetTester::new_with_size(BoxSize {
+        width: 40.0,
+        height: 20.0,
+    });
+    tester.pump_widget(suspense(Image!(
+        image = ImageProvider::memory(red_blue_png(4, 2)),
+        fit = BoxFit::Fill
+    )));
+    pump_until_loaded(&mut tester);
+    assert_eq!(rgba(&tester, 5, 10), RED);
+    assert_eq!(rgba(&tester, 35, 10), BLUE);
+}
+
+#[test]
+fn sizes_to_preserve_the_aspect_ratio() {
+    let mut tester = WidgetTester::new();
+    tester.pump_widget(suspense(Center!(
+        child = Image!(
+            image = ImageProvider::memory(red_blue_png(4, 2)),
+            width = 30.0
+        )
+    )));
+    pump_until_loaded(&mut tester);
+    assert_eq!(
+        tester.get_size(&Finder::by_type::<RawImage>()),
+        BoxSize {
+            width: 30.0,
+            height: 15.0
+        }
+    );
+}
+
+#[test]
+fn aligns_the_fitted_image() {
+    let mut tester = WidgetTester::new_with_size(BoxSize {
+        width: 40.0,
+        height: 20.0,
+    });
+    // Contain a square image within a wide box, pushed to the right.
+    tester.pump_widget(suspense(Image!(
+        image = ImageProvider::memory(red_blue_png(2, 2)),
+        fit = BoxFit::Contain,
+        alignment = Alignment::CENTER_RIGHT
+    )));
+    pump_until_loaded(&mut tester);
+    assert_eq!(rgba(&tester, 10, 10)[3], 0);
+    assert_eq!(rgba(&tester, 22, 10), RED);
+    assert_eq!(rgba(&tester, 38, 10), BLUE);
+}
+
+#[test]
+fn decode_errors_go_to_the_error_boundary() {
+    let mut tester = WidgetTester::new_with_size(BoxSize {
+        width: 40.0,
+        height: 20.0,
+    });
+    tester.pump_widget(ErrorBoundary!(
+        child = suspense(Image!(
+            image = ImageProvider::memory(b"not an image".to_vec())
+        )),
+        fallback = |error, _reset| {
+            assert!(error.to_string().starts_with("Failed to decode image"));
+            ColoredBox!(color = Color::LIME, child = ARC_PHANTOM_BOX.clone())
+        }
+    ));
+    pump_until(&mut tester, |tester| {
+        tester.any(&Finder::by_type::<ColoredBox>())
+    });
+    assert_eq!(rgba(&tester, 20, 10), [0, 255, 0, 255]);
+}
+
+#[test]
+fn box_fit_cover_crops_the_source() {
+    let FittedSizes {
+        source,
+        destination,
+    } = BoxFit::Cover.apply(
+        BoxSize {
+            width: 100.0,
+            height: 100.0,
+        },
+        BoxSize {
+            width: 40.0,
+            height: 20.0,
+        },
+    );
+    assert_eq!(
+        source,
+        BoxSize {
+            width: 100.0,
+            height: 50.0
+        }
+    );
+    assert_eq!(
+        destination,
+        BoxSize {
+            width: 40.0,
+            height: 20.0
+        }
+    );
+}
+
+#[test]
+fn widgets_loading_the_same_image_share_one_decode() {
+    let decodes = Arc::new(AtomicUsize::new(0));
+    // Hold the decode until both widgets have been built, so that the second one joins it.
+    let released = Arc::new(AtomicBool::new(false));
+    let provider = {
+        let decodes = decodes.clone();
+        let released = released.clone();
+        let png = red_blue_png(4, 2);
+        ImageProvider::custom(move || {
+            decodes.fetch_add(1, Ordering::Relaxed);
+            while !released.load(Ordering::Acquire) {
+                std::thread::sleep(Duration::from_millis(1));
+            }
+            decode_image(&png)
+        })
+    };
+    // A cache of its own, so that images loaded by other tests do not show up in its stats.
+    let cache = ImageCache::new(ImageCache::DEFAULT_MAX_BYTES);
+    let mut tester = WidgetTester::new();
+    tester.pump_widget(suspense(Row!(
+        children = vec![
+            Image!(image = provider.clone(), cache = cache.clone()) as ArcBoxWidget,
+            Image!(image = provider.clone(), cache = cache.clone()),
+        ]
+    )));
+    released.store(true, Ordering::Release);
+    let raw_images = |tester: &WidgetTester| {
+        tester
+            .find_all(&Finder::by_type::<RawImage>())
+            .into_iter()
+            .map(|element| {
+                element
+                    .widget_any()
+                    .as_any_arc()
+                    .downcast::<RawImage>()
+                    .unwrap()
+            })
+            .collect::<Vec<_>>()
+    };
+    pump_until(&mut tester, |tester| raw_images(tester).len() == 2);
+    let raw_images = raw_images(&tester);
+    assert_eq!(decodes.load(Ordering::Relaxed), 1);
+    let stats = cache.stats();
+    assert_eq!((stats.misses, stats.joined), (1, 1));
+    assert_eq!(stats.entries, 1);
+    assert_eq!(raw_images[0].image, raw_images[1].image);
+    assert_eq!(cache.get(&provider), Some(raw_images[0].image.clone()));
+}
+
+#[test]
+fn cache_evicts_the_least_recently_used_images() {
+    let images = [(); 3].map(|_| {
+        let provider = ImageProvider::memory(red_blue_png(4, 4));
+        let image = provider.load_sync().unwrap();
+        (provider, image)
+    });
+    let [(a, image_a), (b, image_b), (c, image_c)] = images;
+    let image_bytes = 4 * 4 * 4;
+    let cache = ImageCache::new(2 * image_bytes);
+    cache.insert(a.clone(), image_a);
+    cache.insert(b.clone(), image_b);
+    assert!(cache.get(&a).is_some());
+    cache.insert(c.clone(), image_c);
+
+    assert!(cache.get(&a).is_some());
+    assert!(cache.get(&b).is_none());
+    assert!(cache.get(&c).is_some());
+    let stats = cache.stats();
+    assert_eq!(stats.evictions, 1);
+    assert_eq!(stats.evicted_bytes, image_bytes as u64);
+    assert_eq!(stats.entries, 2);
+    assert_eq!(stats.current_bytes, 2 * image_bytes);
+
+    cache.set_max_bytes(image_bytes);
+    assert!(cache.get(&a).is_none());
+    assert!(cache.get(&c).is_some());
+    assert_eq!(cache.stats().evictions, 2);
+}
@@ -0,0 +1,153 @@
+use epgi_2d::{BoxSize, TextBaseline};
+use epgi_common::{
+    Center, Column, Container, CrossAxisAlignment, EdgeInsets, FlexFit, Flexible, IntrinsicHeight,
+    IntrinsicWidth, MainAxisSize, Padding, Row, Text,
+};
+use epgi_material::MaterialApp;
+use epgi_test::{Finder, WidgetTester};
+
+#[test]
+fn intrinsic_width_stretches_to_the_widest_child() {
+    let mut tester = WidgetTester::new();
+    tester.pump_widget(Center!(
+        child = IntrinsicWidth!(
+            child = Column!(
+                main_axis_size = MainAxisSize::Min,
+                cross_axis_alignment = CrossAxisAlignment::Stretch,
+                children = vec![
+                    Container!(width = 50.0, height = 10.0),
+                    Container!(width = 120.0, height = 20.0),
+                ]
+            )
+        )
+    ));
+    assert_eq!(
+        tester.get_size(&Finder::by_type::<IntrinsicWidth>()),
+        BoxSize {
+            width: 120.0,
+            height: 30.0
+        }
+    );
+}
+
+#[test]
+fn intrinsic_height_stretches_to_the_tallest_child() {
+    let mut tester = WidgetTester::new();
+    tester.pump_widget(Center!(
+        child = IntrinsicHeight!(
+            child = Row!(
+                main_axis_size = MainAxisSize::Min,
+                cross_axis_alignment = CrossAxisAlignment::Stretch,
+                children = vec![
+                    Container!(width = 10.0, height = 40.0),
+                    Container!(width = 20.0, height = 70.0),
+                ]
+            )
+        )
+    ));
+    assert_eq!(
+        tester.get_size(&Finder::by_type::<IntrinsicHeight>()),
+        BoxSize {
+            width: 30.0,
+            height: 70.0
+        }
+    );
+}
+
+#[test]
+fn intrinsic_width_accounts_for_padding_and_flex() {
+    let mut tester = WidgetTester::new();
+    tester.pump_widget(Center!(
+        child = IntrinsicWidth!(
+            child = Padding!(
+                padding = EdgeInsets::new_all(5.0),
+                child = Row!(
+                    main_axis_size = MainAxisSize::Min,
+                    children = vec![
+                        Container!(width = 30.0, height = 10.0),
+                        Flexible!(
+                            flex = 2,
+                            fit = FlexFit::Tight,
+                            child = Container!(width = 10.0, height = 10.0)
+                        ),
+                    ]
+                )
+            )
+        )
+    ));
+    // A flex of 2 needs 5 per flex for the flexible child, on top of the 30 taken by the other child.
+    assert_eq!(
+        tester.get_size(&Finder::by_type::<IntrinsicWidth>()),
+        BoxSize {
+            width: 50.0,
+            height: 20.0
+        }
+    );
+}
+
+#[test]
+fn intrinsic_width_follows_text_changes() {
+    let app = |text: &'static str| {
+        MaterialApp!(child = Center!(child = IntrinsicWidth!(child = Text!(text = text))))
+    };
+    let mut tester = WidgetTester::new();
+    tester.pump_widget(MaterialApp!(
+        child = Center!(child = Text!(text = "hello world"))
+    ));
+    let text_size = tester.get_size(&Finder::text("hello world"));
+
+    tester.pump_widget(app("hello world"));
+    assert_eq!(
+        tester.get_size(&Finder::by_type::<IntrinsicWidth>()),
+        text_size
+    );
+
+    tester.pump_widget(app("hello world, again"));
+    assert!(tester.get_size(&Finder::by_type::<IntrinsicWidth>()).width > text_size.width);
+}
+
+#[test]
+fn intrinsic_width_follows_a_child_laid_out_with_tight_constraints() {
+    // The container tightens the constraints of the text, so the text is laid out without its parent using its size.
+    // The intrinsic width still depends on the text, so it has to be laid out again when the text changes.
+    let app = |text: &'static str| {
+        MaterialApp!(
+            child = Center!(
+                child =
+                    IntrinsicWidth!(child = Container!(height = 20.0, child = Text!(text = text)))
+            )
+        )
+    };
+    let mut tester = WidgetTester::new();
+    tester.pump_widget(app("hello world"));
+    let width = tester.get_size(&Finder::by_type::<IntrinsicWidth>()).width;
+
+    tester.pump_widget(app("hello world, again"));
+    assert!(tester.get_size(&Finder::by_type::<IntrinsicWidth>()).width > width);
+}
+
+#[test]
+fn layout_finishes_when_a_child_is_measured_several_ways_in_one_pass() {
+    // Within one layout pass, the texts are asked for their intrinsics, their sizes and their baselines.
+    // Each of them marks that the parent uses the size, and none of them may wait for the others.
+    let app = |text: &'static str| {
+        MaterialApp!(
+            child = Center!(
+                child = IntrinsicWidth!(
+                    child = Row!(
+                        main_axis_size = MainAxisSize::Min,
+                        cross_axis_alignment =
+                            CrossAxisAlignment::Baseline(TextBaseline::Alphabetic),
+                        children = vec![Text!(text = "label"), Text!(text = text)]
+                    )
+                )
+            )
+        )
+    };
+    let mut tester = WidgetTester::new();
+    tester.pump_widget(app("value"));
+    let width = tester.get_size(&Finder::by_type::<IntrinsicWidth>()).width;
+
+    tester.pump_widget(app("a longer value"));
+    assert!(tester.get_size(&Finder::by_type::<IntrinsicWidth>()).width > width);
+}
@@ -0,0 +1,212 @@
+use std::{
+    sync::atomic::{AtomicUsize, Ordering::*},
+    time::Duration,
+};
+
+use epgi_2d::{ArcBoxWidget, BoxOffset, Point2d};
+use epgi_common::{Container, GestureDetector, ListView, ScrollController};
+use epgi_core::{foundation::Arc, nodes::Builder, scheduler::get_current_scheduler};
+use epgi_material::MaterialApp;
+use epgi_test::{Finder, WidgetTester};
+
+const ITEM_COUNT: usize = 100_000;
+const ITEM_EXTENT: f32 = 50.0;
+/// The default window is 600 pixels tall.
+const VISIBLE_ITEM_COUNT: usize = 12;
+
+/// The extents of the items of a list without a fixed item extent, repeating every three items.
+fn varying_extent(index: usize) -> f32 {
+    [20.0, 45.0, 70.0][index % 3]
+}
+
+struct Counters {
+    mounted: AtomicUsize,
+    last_tapped: AtomicUsize,
+}
+
+/// Pump a list whose items are `item_extent` long, or as long as [`varying_extent`] if it is `None`.
+///
+/// The list is pumped for a single frame, which should already show the visible items.
+fn pump_list_view(
+    tester: &mut WidgetTester,
+    controller: &ScrollController,
+    item_extent: Option<f32>,
+) -> Arc<Counters> {
+    let counters = Arc::new(Counters {
+        mounted: AtomicUsize::new(0),
+        last_tapped: AtomicUsize::new(usize::MAX),
+    });
+    let counters_clone = counters.clone();
+    let item_builder = move |index| -> ArcBoxWidget {
+        let counters = counters_clone.clone();
+        Builder!(
+            builder = move |ctx| {
+                let counters_clone = counters.clone();
+                ctx.use_effect(
+                    move |_| {
+                        counters_clone.mounted.fetch_add(1, Relaxed);
+                    },
+                    (),
+                );
+                let counters = counters.clone();
+                GestureDetector!(
+                    on_tap = move |_job_builder| counters.last_tapped.store(index, Relaxed),
+                    child = match item_extent {
+                        Some(_) => Container!(),
+                        None => Container!(height = varying_extent(index)),
+                    }
+                )
+            }
+        )
+    };
+    tester.pump_widget(MaterialApp!(
+        child = match item_extent {
+            Some(item_extent) => ListView!(
+                controller = controller.clone(),
+                item_count = ITEM_COUNT,
+                item_extent,
+                item_builder
+            ),
+            None => ListView!(
+                controller = controller.clone(),
+                item_count = ITEM_COUNT,
+                item_builder
+            ),
+        }
+    ));
+    counters
+}
+
+fn tapped_item_at(tester: &mut WidgetTester, counters: &Counters, y: f32) -> usize {
+    tester.tap_at(Point2d { x: 400.0, y });
+    counters.last_tapped.load(Relaxed)
+}
+
+fn built_item_count(tester: &WidgetTester) -> usize {
+    tester.find_all(&Finder::by_type::<GestureDetector>()).len()
+}
+
+#[test]
+fn only_visible_items_are_built() {
+    let controller = ScrollController::new();
+    let mut tester = WidgetTester::new();
+    let counters = pump_list_view(&mut tester, &controller, Some(ITEM_EXTENT));
+    assert_eq!(
+        controller.max_scroll_extent(),
+        ITEM_COUNT as f32 * ITEM_EXTENT - 600.0
+    );
+    assert_eq!(built_item_count(&tester), VISIBLE_ITEM_COUNT);
+    assert_eq!(counters.mounted.load(Relaxed), VISIBLE_ITEM_COUNT);
+    assert_eq!(tapped_item_at(&mut tester, &counters, 10.0), 0);
+    assert_eq!(tapped_item_at(&mut tester, &counters, 590.0), 11);
+}
+
+#[test]
+fn scrolling_keeps_the_items_still_in_view() {
+    let controller = ScrollController::new();
+    let mut tester = WidgetTester::new();
+    let counters = pump_list_view(&mut tester, &controller, Some(ITEM_EXTENT));
+
+    let item = tester.find(&Finder::by_key(5usize));
+    tester.scroll_at(
+        Point2d { x: 400.0, y: 300.0 },
+        BoxOffset { x: 0.0, y: 120.0 },
+    );
+    assert_eq!(controller.offset(), 120.0);
+    // Items 0 and 1 have left the view, while items 12 to 14 have entered it.
+    assert_eq!(built_item_count(&tester), VISIBLE_ITEM_COUNT + 1);
+    assert_eq!(counters.mounted.load(Relaxed), VISIBLE_ITEM_COUNT + 3);
+    assert!(Arc::ptr_eq(&tester.find(&Finder::by_key(5usize)), &item));
+    assert_eq!(tapped_item_at(&mut tester, &counters, 10.0), 2);
+    assert_eq!(tapped_item_at(&mut tester, &counters, 590.0), 14);
+}
+
+#[test]
+fn jumps_far_into_the_list() {
+    let controller = ScrollController::new();
+    let mut tester = WidgetTester::new();
+    let counters = pump_list_view(&mut tester, &controller, Some(ITEM_EXTENT));
+
+    get_current_scheduler()
+        .create_sync_job(|job_builder| controller.jump_to(4_000_000.0, job_builder));
+    tester.pump(Duration::ZERO);
+    assert_eq!(built_item_count(&tester), VISIBLE_ITEM_COUNT);
+    assert_eq!(tapped_item_at(&mut tester, &counters, 10.0), 80_000);
+
+    get_current_scheduler()
+        .create_sync_job(|job_builder| controller.jump_to(f32::MAX, job_builder));
+    tester.pump(Duration::ZERO);
+    assert_eq!(
+        tapped_item_at(&mut tester, &counters, 590.0),
+        ITEM_COUNT - 1
+    );
+}
+
+#[test]
+fn items_of_different_extents_are_built_on_the_first_frame() {
+    let controller = ScrollController::new();
+    let mut tester = WidgetTester::new();
+    let counters = pump_list_view(&mut tester, &controller, None);
+    // Items 0 to 12 end at 560, and item 13 ends at 605.
+    assert_eq!(built_item_count(&tester), 14);
+    assert!(tester.find_all(&Finder::by_key(14usize)).is_empty());
+    assert_eq!(tapped_item_at(&mut tester, &counters, 10.0), 0);
+    assert_eq!(tapped_item_at(&mut tester, &counters, 30.0), 1);
+    assert_eq!(tapped_item_at(&mut tester, &counters, 70.0), 2);
+    assert_eq!(tapped_item_at(&mut tester, &counters, 590.0), 13);
+}
+
+#[test]
+fn scrolling_builds_the_items_of_different_extents_entering_the_view() {
+    let controller = ScrollController::new();
+    let mut tester = WidgetTester::new();
+    let counters = pump_list_view(&mut tester, &controller, None);
+
+    let item = tester.find(&Finder::by_key(5usize));
+    tester.scroll_at(
+        Point2d { x: 400.0, y: 300.0 },
+        BoxOffset { x: 0.0, y: 100.0 },
+    );
+    assert_eq!(controller.offset(), 100.0);
+    // Item 2 spans from 65 to 135, and item 16 from 695 to 715.
+    assert!(tester.find_all(&Finder::by_key(1usize)).is_empty());
+    assert_eq!(built_item_count(&tester), 15);
+    assert!(Arc::ptr_eq(&tester.find(&Finder::by_key(5usize)), &item));
+    assert_eq!(tapped_item_at(&mut tester, &counters, 10.0), 2);
+    assert_eq!(tapped_item_at(&mut tester, &counters, 40.0), 3);
+    assert_eq!(tapped_item_at(&mut tester, &counters, 590.0), 15);
+    assert_eq!(tapped_item_at(&mut tester, &counters, 598.0), 16);
+}
+
+#[test]
+fn jumps_far_into_a_list_of_different_extents() {
+    let controller = ScrollController::new();
+    let mut tester = WidgetTester::new();
+    let counters = pump_list_view(&mut tester, &controller, None);
+
+    // Far from the measured items, the items are placed by the average extent of the first ones, about 43.
+    get_current_scheduler()
+        .create_sync_job(|job_builder| controller.jump_to(2_250_000.0, job_builder));
+    tester.pump(Duration::ZERO);
+    assert!(tester.find_all(&Finder::by_key(0usize)).is_empty());
+    let first_item = tapped_item_at(&mut tester, &counters, 1.0);
+    let last_item = tapped_item_at(&mut tester, &counters, 599.0);
+    assert!((50_000..54_000).contains(&first_item));
+    assert_eq!(built_item_count(&tester), last_item - first_item + 1);
+
+    // Scrolling on from there leaves the items in view in place.
+    let center = tester.get_center(&Finder::by_key(first_item + 5));
+    tester.scroll_at(
+        Point2d { x: 400.0, y: 300.0 },
+        BoxOffset { x: 0.0, y: 100.0 },
+    );
+    assert_eq!(
+        tester.get_center(&Finder::by_key(first_item + 5)).y,
+        center.y - 100.0
+    );
+    assert_eq!(
+        built_item_count(&tester),
+        tapped_item_at(&mut tester, &counters, 599.0) - tapped_item_at(&mut tester, &counters, 1.0)
+            + 1
+    );
+}
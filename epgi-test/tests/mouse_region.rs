@@ -0,0 +1,128 @@
+use std::time::Duration;
+
+use epgi_2d::{Color, Point2d};
+use epgi_common::{Center, Container, CursorIcon, MouseRegion};
+use epgi_test::{
+    testing::{record, take, Log},
+    WidgetTester,
+};
+
+/// The center of the default window, where the regions sit.
+const CENTER: Point2d = Point2d { x: 400.0, y: 300.0 };
+const OUTSIDE: Point2d = Point2d { x: 10.0, y: 10.0 };
+
+fn logged_region(
+    log: &Log,
+    name: &'static str,
+    cursor: CursorIcon,
+    size: f32,
+    child: Option<epgi_2d::ArcBoxWidget>,
+) -> epgi_2d::ArcBoxWidget {
+    let child =
+        child.unwrap_or_else(|| Container!(width = size, height = size, color = Color::BLACK) as _);
+    MouseRegion!(
+        on_enter = {
+            let log = log.clone();
+            move |_event, _job_builder| record(&log, format!("{name} enter"))
+        },
+        on_hover = {
+            let log = log.clone();
+            move |event, _job_builder| {
+                record(
+                    &log,
+                    format!(
+                        "{name} hover {} {}",
+                        event.local_position.x, event.local_position.y
+                    ),
+                )
+            }
+        },
+        on_exit = {
+            let log = log.clone();
+            move |_event, _job_builder| record(&log, format!("{name} exit"))
+        },
+        cursor,
+        child
+    )
+}
+
+#[test]
+fn reports_enter_hover_exit_and_cursor() {
+    let log = Log::default();
+    let mut tester = WidgetTester::new();
+    tester.pump_widget(Center!(
+        child = logged_region(&log, "region", CursorIcon::Pointer, 200.0, None)
+    ));
+
+    tester.hover_at(OUTSIDE);
+    assert!(take(&log).is_empty());
+    assert_eq!(tester.mouse_cursor(), CursorIcon::Default);
+
+    tester.hover_at(CENTER);
+    assert_eq!(take(&log), ["region enter", "region hover 400 300"]);
+    assert_eq!(tester.mouse_cursor(), CursorIcon::Pointer);
+
+    tester.hover_at(Point2d { x: 420.0, y: 300.0 });
+    assert_eq!(take(&log), ["region hover 420 300"]);
+
+    tester.hover_at(OUTSIDE);
+    assert_eq!(take(&log), ["region exit"]);
+    assert_eq!(tester.mouse_cursor(), CursorIcon::Default);
+
+    tester.hover_at(CENTER);
+    tester.remove_mouse();
+    assert_eq!(
+        take(&log),
+        ["region enter", "region hover 400 300", "region exit"]
+    );
+    assert_eq!(tester.mouse_cursor(), CursorIcon::Default);
+}
+
+#[test]
+fn innermost_region_decides_the_cursor() {
+    let log = Log::default();
+    let mut tester = WidgetTester::new();
+    let inner = logged_region(&log, "inner", CursorIcon::Text, 50.0, None);
+    let outer_child = Center!(child = inner);
+    tester.pump_widget(Center!(
+        child = Container!(
+            width = 200.0,
+            height = 200.0,
+            child = logged_region(&log, "outer", CursorIcon::Grab, 200.0, Some(outer_child))
+        )
+    ));
+
+    tester.hover_at(Point2d { x: 320.0, y: 300.0 });
+    assert_eq!(take(&log), ["outer enter", "outer hover 320 300"]);
+    assert_eq!(tester.mouse_cursor(), CursorIcon::Grab);
+
+    tester.hover_at(CENTER);
+    assert_eq!(
+        take(&log),
+        ["inner enter", "inner hover 400 300", "outer hover 400 300"]
+    );
+    assert_eq!(tester.mouse_cursor(), CursorIcon::Text);
+
+    // Regions are exited from the innermost.
+    tester.hover_at(OUTSIDE);
+    assert_eq!(take(&log), ["inner exit", "outer exit"]);
+}
+
+#[test]
+fn region_moving_under_a_stationary_mouse() {
+    let log = Log::default();
+    let mut tester = WidgetTester::new();
+    tester.pump_widget(Center!(
+        child = logged_region(&log, "region", CursorIcon::Pointer, 200.0, None)
+    ));
+    tester.hover_at(Point2d { x: 450.0, y: 300.0 });
+    assert_eq!(take(&log), ["region enter", "region hover 450 300"]);
+
+    tester.pump_widget(Center!(
+        child = logged_region(&log, "region", CursorIcon::Pointer, 50.0, None)
+    ));
+    // The mouse is hit-tested against the new layout at the beginning of the next frame.
+    tester.pump(Duration::ZERO);
+    assert_eq!(take(&log), ["region exit"]);
+    assert_eq!(tester.mouse_cursor(), CursorIcon::Default);
+}
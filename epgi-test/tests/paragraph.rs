@@ -0,0 +1,70 @@
+use std::borrow::Cow;
+
+use epgi_2d::{LocalTextStyle, Paragraph, TextDirection, TextSpan};
+use epgi_material::black_mountain_view_body_medium;
+use rayon::prelude::*;
+
+fn span(text: impl Into<Cow<'static, str>>, style: Option<LocalTextStyle>) -> TextSpan {
+    TextSpan {
+        text: text.into(),
+        style,
+    }
+}
+
+fn max_width(spans: &[TextSpan]) -> f32 {
+    Paragraph::new(
+        spans,
+        &black_mountain_view_body_medium(),
+        TextDirection::Ltr,
+    )
+    .max_intrinsic_width()
+}
+
+#[test]
+fn shaping_cache_tells_styles_apart() {
+    let text = "Cached paragraphs must respect their style";
+    let regular = max_width(&[span(text, None)]);
+    let large = max_width(&[span(
+        text,
+        Some(LocalTextStyle {
+            font_size: Some(28.0),
+            ..Default::default()
+        }),
+    )]);
+    assert!(regular > 0.0);
+    assert!(large > regular * 1.5);
+    assert_eq!(max_width(&[span(text, None)]), regular);
+
+    // Same text, split differently
+    let split = max_width(&[
+        span("Cached paragraphs must respect ", None),
+        span(
+            "their style",
+            Some(LocalTextStyle {
+                font_size: Some(28.0),
+                ..Default::default()
+            }),
+        ),
+    ]);
+    assert!(split > regular && split < large);
+}
+
+#[test]
+fn every_thread_shapes_the_same_layout() {
+    let texts = (0..64)
+        .map(|index| format!("Paragraph number {index} shaped on any thread"))
+        .collect::<Vec<_>>();
+    let widths = |texts: &[String]| {
+        texts
+            .iter()
+            .map(|text| max_width(&[span(text.clone(), None)]))
+            .collect::<Vec<_>>()
+    };
+    let expected = widths(&texts);
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(4)
+        .build()
+        .unwrap();
+    let actual = pool.install(|| texts.par_chunks(8).flat_map(widths).collect::<Vec<_>>());
+    assert_eq!(actual, expected);
+}
@@ -0,0 +1,120 @@
+use epgi_2d::{ArcBoxWidget, Color, Point2d};
+use epgi_common::{Center, Column, Container, GestureDetector, Text};
+use epgi_core::nodes::{Builder, RepaintBoundary, RepaintCounter};
+use epgi_material::MaterialApp;
+use epgi_test::{Finder, WidgetTester};
+
+fn toggling_box() -> ArcBoxWidget {
+    Builder!(
+        builder = |ctx| {
+            let (on, set_on) = ctx.use_state(false);
+            GestureDetector!(
+                on_tap = move |job_builder| {
+                    set_on.set(!on, job_builder);
+                },
+                child = Container!(
+                    width = 100.0,
+                    height = 100.0,
+                    color = if on { Color::BLACK } else { Color::WHITE }
+                )
+            )
+        }
+    )
+}
+
+/// Offsets reported by the tester are relative to the enclosing layer,
+/// so the origins of the enclosing boundaries are added up by hand.
+fn global_center(tester: &WidgetTester, finder: &Finder, layers: &[&'static str]) -> Point2d {
+    let mut position = tester.get_center(finder);
+    for layer in layers {
+        let layer = Finder::by_key(*layer);
+        let (center, size) = (tester.get_center(&layer), tester.get_size(&layer));
+        position.x += center.x - size.width / 2.0;
+        position.y += center.y - size.height / 2.0;
+    }
+    position
+}
+
+#[test]
+fn repaint_stops_at_the_nearest_boundary() {
+    let outer = RepaintCounter::new();
+    let inner = RepaintCounter::new();
+    let mut tester = WidgetTester::new();
+    tester.pump_widget(MaterialApp!(
+        child = RepaintBoundary!(
+            key = "outer",
+            counter = outer.clone(),
+            child = Center!(
+                child = Column!(
+                    children = vec![
+                        Text!(text = "static"),
+                        RepaintBoundary!(
+                            key = "inner",
+                            counter = inner.clone(),
+                            child = toggling_box()
+                        ),
+                    ]
+                )
+            )
+        )
+    ));
+    assert_eq!((outer.count(), inner.count()), (1, 1));
+
+    // The tap is hit-tested through both layers.
+    let toggle = global_center(&tester, &Finder::by_key("inner"), &["outer"]);
+    tester.tap_at(toggle);
+    assert_eq!((outer.count(), inner.count()), (1, 2));
+
+    tester.tap_at(toggle);
+    assert_eq!((outer.count(), inner.count()), (1, 3));
+}
+
+#[test]
+fn relayout_inside_a_boundary_repaints_it() {
+    let counter = RepaintCounter::new();
+    let app = |text: &'static str| {
+        MaterialApp!(
+            child = Center!(
+                child = Column!(
+                    children = vec![RepaintBoundary!(
+                        counter = counter.clone(),
+                        child = Text!(text = text)
+                    )]
+                )
+            )
+        )
+    };
+    let mut tester = WidgetTester::new();
+    tester.pump_widget(app("before"));
+    tester.pump_widget(app("after"));
+    assert!(tester.any(&Finder::text("after")));
+    assert_eq!(counter.count(), 2);
+}
+
+#[test]
+fn new_constraints_from_above_repaint_the_boundary() {
+    let counter = RepaintCounter::new();
+    let app = |width: f32| {
+        MaterialApp!(
+            child = Center!(
+                child = Container!(
+                    width = width,
+                    height = 100.0,
+                    child = RepaintBoundary!(
+                        key = "boundary",
+                        counter = counter.clone(),
+                        child = Container!(color = Color::BLACK)
+                    )
+                )
+            )
+        )
+    };
+    let mut tester = WidgetTester::new();
+    tester.pump_widget(app(100.0));
+    assert_eq!(counter.count(), 1);
+
+    // Nothing inside the boundary changed, but its size did
+    tester.pump_widget(app(200.0));
+    assert_eq!(counter.count(), 2);
+    assert_eq!(tester.get_size(&Finder::by_key("boundary")).width, 200.0);
+}
@@ -0,0 +1,161 @@
+use std::{
+    sync::atomic::{AtomicUsize, Ordering::*},
+    time::Duration,
+};
+
+use epgi_2d::{BoxOffset, Point2d};
+use epgi_common::{
+    Column, Container, GestureDetector, MainAxisSize, PointerEvent, PointerPanZoomUpdateData,
+    ScrollController, SingleChildScrollView,
+};
+use epgi_core::{foundation::Arc, scheduler::get_current_scheduler};
+use epgi_material::MaterialApp;
+use epgi_test::WidgetTester;
+
+const ITEM_COUNT: usize = 10;
+const ITEM_EXTENT: f32 = 100.0;
+
+/// A column of tappable items that is taller than the window. Returns the index of the last tapped item.
+fn pump_scroll_view(tester: &mut WidgetTester, controller: &ScrollController) -> Arc<AtomicUsize> {
+    let last_tapped = Arc::new(AtomicUsize::new(usize::MAX));
+    let children = (0..ITEM_COUNT)
+        .map(|index| {
+            let last_tapped = last_tapped.clone();
+            GestureDetector!(
+                on_tap = move |_job_builder| last_tapped.store(index, Relaxed),
+                child = Container!(width = 800.0, height = ITEM_EXTENT)
+            ) as _
+        })
+        .collect();
+    tester.pump_widget(MaterialApp!(
+        child = SingleChildScrollView!(
+            controller = controller.clone(),
+            child = Column!(main_axis_size = MainAxisSize::Min, children)
+        )
+    ));
+    last_tapped
+}
+
+fn tapped_item_at(tester: &mut WidgetTester, last_tapped: &AtomicUsize, y: f32) -> usize {
+    tester.tap_at(Point2d { x: 400.0, y });
+    last_tapped.load(Relaxed)
+}
+
+/// Pan over the trackpad by `distance` in `steps` updates 10ms apart, without releasing.
+fn pan(
+    tester: &mut WidgetTester,
+    distance: f32,
+    steps: usize,
+) -> epgi_common::PointerInteractionId {
+    let position = Point2d { x: 400.0, y: 300.0 };
+    let interaction_id = tester.new_interaction_id();
+    tester.send_pointer_event(PointerEvent::new_pan_zoom_start(
+        tester.pointer_common_data(position),
+        interaction_id,
+    ));
+    for step in 1..=steps {
+        tester.pump(Duration::from_millis(10));
+        tester.send_pointer_event(PointerEvent::new_pan_zoom_update(
+            tester.pointer_common_data(position),
+            interaction_id,
+            PointerPanZoomUpdateData {
+                pan: BoxOffset {
+                    x: 0.0,
+                    y: distance * step as f32 / steps as f32,
+                },
+                scale: 1.0,
+                rotation: 0.0,
+            },
+        ));
+    }
+    tester.pump(Duration::ZERO);
+    interaction_id
+}
+
+fn release_pan(tester: &mut WidgetTester, interaction_id: epgi_common::PointerInteractionId) {
+    tester.send_pointer_event(PointerEvent::new_pan_zoom_end(
+        tester.pointer_common_data(Point2d { x: 400.0, y: 300.0 }),
+        interaction_id,
+    ));
+    tester.pump(Duration::ZERO);
+}
+
+#[test]
+fn mouse_wheel_scrolls_within_extents() {
+    let controller = ScrollController::new();
+    let mut tester = WidgetTester::new();
+    let last_tapped = pump_scroll_view(&mut tester, &controller);
+    assert_eq!(controller.viewport_extent(), 600.0);
+    assert_eq!(controller.max_scroll_extent(), 400.0);
+    assert_eq!(tapped_item_at(&mut tester, &last_tapped, 10.0), 0);
+
+    tester.scroll_at(
+        Point2d { x: 400.0, y: 300.0 },
+        BoxOffset { x: 0.0, y: 250.0 },
+    );
+    assert_eq!(controller.offset(), 250.0);
+    // Hit testing follows the scrolled content.
+    assert_eq!(tapped_item_at(&mut tester, &last_tapped, 10.0), 2);
+
+    tester.scroll_at(
+        Point2d { x: 400.0, y: 300.0 },
+        BoxOffset { x: 0.0, y: 1000.0 },
+    );
+    assert_eq!(controller.offset(), 400.0);
+    assert_eq!(tapped_item_at(&mut tester, &last_tapped, 590.0), 9);
+
+    tester.scroll_at(
+        Point2d { x: 400.0, y: 300.0 },
+        BoxOffset { x: 0.0, y: -1000.0 },
+    );
+    assert_eq!(controller.offset(), 0.0);
+}
+
+#[test]
+fn jump_to_moves_the_content() {
+    let controller = ScrollController::new();
+    let mut tester = WidgetTester::new();
+    let last_tapped = pump_scroll_view(&mut tester, &controller);
+
+    get_current_scheduler().create_sync_job(|job_builder| controller.jump_to(300.0, job_builder));
+    tester.pump(Duration::ZERO);
+    assert_eq!(controller.offset(), 300.0);
+    assert_eq!(tapped_item_at(&mut tester, &last_tapped, 50.0), 3);
+}
+
+#[test]
+fn trackpad_pan_flings_and_settles() {
+    let controller = ScrollController::new();
+    let mut tester = WidgetTester::new();
+    pump_scroll_view(&mut tester, &controller);
+
+    let interaction_id = pan(&mut tester, -100.0, 5);
+    assert_eq!(controller.offset(), 100.0);
+    release_pan(&mut tester, interaction_id);
+    assert!(controller.is_animating());
+
+    tester.pump(Duration::from_millis(100));
+    let coasting_offset = controller.offset();
+    assert!(coasting_offset > 100.0 && coasting_offset < 400.0);
+    tester.pump_and_settle();
+    assert!(!controller.is_animating());
+    assert!(controller.offset() > coasting_offset && controller.offset() <= 400.0);
+}
+
+#[test]
+fn overscroll_springs_back() {
+    let controller = ScrollController::new();
+    let mut tester = WidgetTester::new();
+    pump_scroll_view(&mut tester, &controller);
+
+    let interaction_id = pan(&mut tester, 100.0, 5);
+    // The content resists being pulled past its start.
+    assert_eq!(controller.offset(), -50.0);
+    // Resting before releasing the pan does not fling.
+    tester.pump(Duration::from_millis(200));
+    release_pan(&mut tester, interaction_id);
+    assert!(controller.is_animating());
+
+    tester.pump_and_settle();
+    assert_eq!(controller.offset(), 0.0);
+}
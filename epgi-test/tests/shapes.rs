@@ -0,0 +1,155 @@
+use epgi_2d::{
+    Affine2dCanvas, Affine2dPaintContextExt, ArcBoxRenderObject, ArcBoxWidget, BoxOffset,
+    BoxProtocol, BoxSingleChildElement, BoxSingleChildElementTemplate, BoxSingleChildRenderElement,
+    BoxSize, Brush, Color, Fill, FillPainter, RRect, RRectRadius, Rect,
+};
+use epgi_common::ARC_PHANTOM_BOX;
+use epgi_core::{
+    foundation::{Arc, Asc, BuildError, InlinableDwsizeVec, PaintContext, Provide},
+    template::{ImplByTemplate, ProxyRender, ProxyRenderTemplate},
+    tree::{BuildContext, RenderAction, Widget},
+};
+use epgi_test::WidgetTester;
+
+const SIZE: BoxSize = BoxSize {
+    width: 100.0,
+    height: 60.0,
+};
+
+/// Fills a rounded border of the given width along the edges of its child.
+#[derive(Debug)]
+struct RoundedBorder {
+    width: f32,
+    fill: Fill,
+    child: ArcBoxWidget,
+}
+
+impl Widget for RoundedBorder {
+    type ParentProtocol = BoxProtocol;
+    type ChildProtocol = BoxProtocol;
+    type Element = RoundedBorderElement;
+
+    fn into_arc_widget(self: Asc<Self>) -> Asc<Self> {
+        self
+    }
+}
+
+#[derive(Clone)]
+struct RoundedBorderElement;
+
+impl ImplByTemplate for RoundedBorderElement {
+    type Template = BoxSingleChildElementTemplate<true, false>;
+}
+
+impl BoxSingleChildElement for RoundedBorderElement {
+    type ArcWidget = Asc<RoundedBorder>;
+
+    fn get_child_widget(
+        _element: Option<&mut Self>,
+        widget: &Self::ArcWidget,
+        _ctx: &mut BuildContext<'_>,
+        _provider_values: InlinableDwsizeVec<Arc<dyn Provide>>,
+    ) -> Result<ArcBoxWidget, BuildError> {
+        Ok(widget.child.clone())
+    }
+
+    fn create_element(_widget: &Self::ArcWidget) -> Self {
+        Self
+    }
+}
+
+impl BoxSingleChildRenderElement for RoundedBorderElement {
+    type Render = RenderRoundedBorder;
+
+    fn create_render(&self, widget: &Self::ArcWidget) -> Self::Render {
+        RenderRoundedBorder {
+            width: widget.width,
+            fill: widget.fill,
+        }
+    }
+
+    fn update_render(
+        _render: &mut Self::Render,
+        _widget: &Self::ArcWidget,
+    ) -> Option<RenderAction> {
+        None
+    }
+}
+
+struct RenderRoundedBorder {
+    width: f32,
+    fill: Fill,
+}
+
+impl ImplByTemplate for RenderRoundedBorder {
+    type Template = ProxyRenderTemplate;
+}
+
+impl ProxyRender for RenderRoundedBorder {
+    type Protocol = BoxProtocol;
+
+    fn perform_paint(
+        &self,
+        size: &BoxSize,
+        offset: &BoxOffset,
+        child: &ArcBoxRenderObject,
+        paint_ctx: &mut impl PaintContext<Canvas = Affine2dCanvas>,
+    ) {
+        paint_ctx.fill_rrect_padding(
+            RRect {
+                rect: Rect::new_point_size(*offset, *size),
+                radius: Box::new(RRectRadius {
+                    tl: 20.0,
+                    tr: 20.0,
+                    bl: 20.0,
+                    br: 20.0,
+                }),
+            },
+            self.width,
+            FillPainter {
+                fill: self.fill,
+                brush: Brush::Solid(Color::rgb(1.0, 0.0, 0.0)),
+                transform: None,
+            },
+        );
+        paint_ctx.paint(child, offset);
+    }
+
+    const NOOP_DETACH: bool = true;
+}
+
+fn is_painted(tester: &WidgetTester, x: u32, y: u32) -> bool {
+    tester.rasterize().pixel(x, y).unwrap().alpha() > 0
+}
+
+#[test]
+fn rrect_padding_fills_only_the_border() {
+    for fill in [Fill::NonZero, Fill::EvenOdd] {
+        let mut tester = WidgetTester::new_with_size(SIZE);
+        tester.pump_widget(Asc::new(RoundedBorder {
+            width: 5.0,
+            fill,
+            child: ARC_PHANTOM_BOX.clone(),
+        }));
+        // Along the straight edges
+        assert!(is_painted(&tester, 50, 2));
+        assert!(is_painted(&tester, 2, 30));
+        assert!(is_painted(&tester, 97, 30));
+        // Outside the rounded corner, and inside the border
+        assert!(!is_painted(&tester, 1, 1));
+        assert!(!is_painted(&tester, 50, 30));
+        assert!(!is_painted(&tester, 10, 30));
+    }
+}
+
+#[test]
+fn rrect_padding_wider_than_the_rect_fills_it_entirely() {
+    let mut tester = WidgetTester::new_with_size(SIZE);
+    tester.pump_widget(Asc::new(RoundedBorder {
+        width: 40.0,
+        fill: Fill::NonZero,
+        child: ARC_PHANTOM_BOX.clone(),
+    }));
+    assert!(is_painted(&tester, 50, 30));
+    assert!(!is_painted(&tester, 1, 1));
+}
@@ -0,0 +1,122 @@
+use std::{
+    sync::atomic::{AtomicUsize, Ordering::*},
+    time::Duration,
+};
+
+use epgi_2d::{BoxSize, Color};
+use epgi_common::{
+    AnimatedContainer, Center, Container, GestureDetector, KeyCode, KeyEventResult, KeyModifiers,
+    KeyboardListener, LogicalKey, Text,
+};
+use epgi_core::{foundation::Arc, nodes::KeyedSubtree};
+use epgi_material::MaterialApp;
+use epgi_test::{Finder, WidgetTester};
+
+#[test]
+fn finds_widgets_by_type_key_and_text() {
+    let mut tester = WidgetTester::new();
+    tester.pump_widget(MaterialApp!(
+        child = Center!(child = KeyedSubtree!(key = "label", child = Text!(text = "Hello")))
+    ));
+    assert_eq!(tester.find_all(&Finder::by_type::<Text>()).len(), 1);
+    assert!(tester.any(&Finder::by_key("label")));
+    assert!(!tester.any(&Finder::by_key("missing")));
+    assert!(tester.any(&Finder::text("Hello")));
+    assert!(!tester.any(&Finder::text("World")));
+    assert_eq!(tester.find_all(&Finder::rich_text("Hello")).len(), 1);
+    assert_eq!(
+        tester
+            .widget::<Text>(&Finder::text("Hello"))
+            .text
+            .as_deref(),
+        Some("Hello")
+    );
+}
+
+#[test]
+fn tap_triggers_gesture_detector() {
+    let tap_count = Arc::new(AtomicUsize::new(0));
+    let mut tester = WidgetTester::new();
+    tester.pump_widget(Center!(
+        child = GestureDetector!(
+            on_tap = {
+                let tap_count = tap_count.clone();
+                move |_job_builder| {
+                    tap_count.fetch_add(1, Relaxed);
+                }
+            },
+            child = Container!(width = 100.0, height = 50.0, color = Color::BLACK)
+        )
+    ));
+    let target = Finder::by_type::<GestureDetector>();
+    assert_eq!(
+        tester.get_size(&target),
+        BoxSize {
+            width: 100.0,
+            height: 50.0
+        }
+    );
+    tester.tap(&target);
+    assert_eq!(tap_count.load(Relaxed), 1);
+
+    // Tapping outside of the detector should not count.
+    tester.tap_at([5.0, 5.0].into());
+    assert_eq!(tap_count.load(Relaxed), 1);
+}
+
+#[test]
+fn pump_and_settle_finishes_implicit_animations() {
+    let container = |width: f32| {
+        MaterialApp!(
+            child = Center!(
+                child = AnimatedContainer!(
+                    duration = Duration::from_millis(300),
+                    width,
+                    height = 10.0,
+                )
+            )
+        )
+    };
+    let target = Finder::by_type::<AnimatedContainer>();
+    let mut tester = WidgetTester::new();
+    tester.pump_widget(container(100.0));
+    assert_eq!(tester.get_size(&target).width, 100.0);
+
+    tester.pump_widget(container(200.0));
+    let frames = tester.pump_and_settle();
+    assert!(frames > 1);
+    assert!((tester.get_size(&target).width - 200.0).abs() < 1e-3);
+}
+
+#[test]
+fn keyboard_listener_receives_shortcuts() {
+    let save_count = Arc::new(AtomicUsize::new(0));
+    let mut tester = WidgetTester::new();
+    tester.pump_widget(KeyboardListener!(
+        on_key_event = {
+            let save_count = save_count.clone();
+            move |event, _job_builder| {
+                if event.is_shortcut(&LogicalKey::character("s"), KeyModifiers::CONTROL) {
+                    save_count.fetch_add(1, Relaxed);
+                    return KeyEventResult::Handled;
+                }
+                KeyEventResult::Ignored
+            }
+        },
+        child = Container!(width = 10.0, height = 10.0)
+    ));
+    tester.press_key(
+        KeyCode::KeyS,
+        LogicalKey::character("s"),
+        KeyModifiers::CONTROL,
+    );
+    assert_eq!(save_count.load(Relaxed), 1);
+
+    // The same key without the modifier is not the shortcut.
+    tester.press_key(
+        KeyCode::KeyS,
+        LogicalKey::character("s"),
+        KeyModifiers::empty(),
+    );
+    assert_eq!(save_count.load(Relaxed), 1);
+}
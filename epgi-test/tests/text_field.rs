@@ -0,0 +1,207 @@
+use std::sync::atomic::{AtomicUsize, Ordering::*};
+
+use epgi_2d::Point2d;
+use epgi_common::{
+    CaretMovement, Center, EditableText, ImeEvent, KeyCode, KeyModifiers, LogicalKey, NamedKey,
+    TextEditingController, TextEditingValue, TextField, TextSelection,
+};
+use epgi_core::foundation::Arc;
+use epgi_material::MaterialApp;
+use epgi_test::{Finder, WidgetTester};
+
+fn type_character(tester: &mut WidgetTester, physical_key: KeyCode, character: &str) {
+    tester.press_key(
+        physical_key,
+        LogicalKey::character(character),
+        KeyModifiers::empty(),
+    );
+}
+
+fn press_named(tester: &mut WidgetTester, key: NamedKey, modifiers: KeyModifiers) {
+    let physical_key = match key {
+        NamedKey::ArrowLeft => KeyCode::ArrowLeft,
+        NamedKey::ArrowRight => KeyCode::ArrowRight,
+        NamedKey::Backspace => KeyCode::Backspace,
+        NamedKey::Delete => KeyCode::Delete,
+        NamedKey::Home => KeyCode::Home,
+        NamedKey::End => KeyCode::End,
+        NamedKey::Enter => KeyCode::Enter,
+        _ => unimplemented!(),
+    };
+    tester.press_key(physical_key, key.into(), modifiers);
+}
+
+#[test]
+fn typing_edits_text_at_the_caret() {
+    let controller = TextEditingController::new("helo");
+    let change_count = Arc::new(AtomicUsize::new(0));
+    let mut tester = WidgetTester::new();
+    tester.pump_widget(MaterialApp!(
+        child = Center!(
+            child = TextField!(
+                controller = controller.clone(),
+                autofocus = true,
+                on_changed = {
+                    let change_count = change_count.clone();
+                    move |_text, _job_builder| {
+                        change_count.fetch_add(1, Relaxed);
+                    }
+                }
+            )
+        )
+    ));
+    tester.pump(Default::default());
+
+    press_named(&mut tester, NamedKey::ArrowLeft, KeyModifiers::empty());
+    type_character(&mut tester, KeyCode::KeyL, "l");
+    assert_eq!(controller.text(), "hello");
+    assert_eq!(controller.selection(), TextSelection::collapsed(4));
+
+    press_named(&mut tester, NamedKey::End, KeyModifiers::empty());
+    type_character(&mut tester, KeyCode::Digit1, "!");
+    press_named(&mut tester, NamedKey::Backspace, KeyModifiers::empty());
+    press_named(&mut tester, NamedKey::Home, KeyModifiers::empty());
+    press_named(&mut tester, NamedKey::Delete, KeyModifiers::empty());
+    assert_eq!(controller.text(), "ello");
+    // Moving the caret does not change the text.
+    assert_eq!(change_count.load(Relaxed), 4);
+
+    // The rendered value follows the controller.
+    let editable_text = tester.widget::<EditableText>(&Finder::by_type::<EditableText>());
+    assert_eq!(editable_text.value.text, "ello");
+    assert!(editable_text.show_caret);
+}
+
+#[test]
+fn shift_selection_is_replaced_by_typing() {
+    let controller = TextEditingController::new("hello world");
+    let mut tester = WidgetTester::new();
+    tester.pump_widget(MaterialApp!(
+        child = TextField!(controller = controller.clone(), autofocus = true)
+    ));
+    tester.pump(Default::default());
+
+    press_named(
+        &mut tester,
+        NamedKey::ArrowLeft,
+        KeyModifiers::PRIMARY | KeyModifiers::SHIFT,
+    );
+    assert_eq!(controller.value().selected_text(), "world");
+    type_character(&mut tester, KeyCode::KeyT, "t");
+    assert_eq!(controller.text(), "hello t");
+
+    tester.press_key(
+        KeyCode::KeyA,
+        LogicalKey::character("a"),
+        KeyModifiers::PRIMARY,
+    );
+    assert_eq!(controller.selection(), TextSelection::new(0, 7));
+    press_named(&mut tester, NamedKey::Backspace, KeyModifiers::empty());
+    assert_eq!(controller.text(), "");
+}
+
+#[test]
+fn caret_moves_and_deletes_by_grapheme_cluster() {
+    // An "e" with a combining acute accent, and a woman technologist made of a ZWJ sequence.
+    const ACCENTED: &str = "e\u{301}";
+    const TECHNOLOGIST: &str = "\u{1F469}\u{200D}\u{1F4BB}";
+    let text = format!("a{ACCENTED}{TECHNOLOGIST}b");
+    let controller = TextEditingController::new(text.clone());
+    let mut tester = WidgetTester::new();
+    tester.pump_widget(MaterialApp!(
+        child = TextField!(controller = controller.clone(), autofocus = true)
+    ));
+    tester.pump(Default::default());
+
+    press_named(&mut tester, NamedKey::ArrowLeft, KeyModifiers::empty());
+    press_named(&mut tester, NamedKey::ArrowLeft, KeyModifiers::empty());
+    assert_eq!(
+        controller.selection(),
+        TextSelection::collapsed(1 + ACCENTED.len())
+    );
+    press_named(&mut tester, NamedKey::ArrowLeft, KeyModifiers::SHIFT);
+    assert_eq!(controller.value().selected_text(), ACCENTED);
+    press_named(&mut tester, NamedKey::ArrowRight, KeyModifiers::empty());
+    press_named(&mut tester, NamedKey::ArrowRight, KeyModifiers::SHIFT);
+    assert_eq!(controller.value().selected_text(), TECHNOLOGIST);
+
+    press_named(&mut tester, NamedKey::ArrowLeft, KeyModifiers::empty());
+    press_named(&mut tester, NamedKey::Delete, KeyModifiers::empty());
+    assert_eq!(controller.text(), format!("a{ACCENTED}b"));
+    press_named(&mut tester, NamedKey::Backspace, KeyModifiers::empty());
+    assert_eq!(controller.text(), "ab");
+    assert_eq!(controller.selection(), TextSelection::collapsed(1));
+}
+
+#[test]
+fn word_movement_keeps_combining_marks_with_their_word() {
+    let text = "cafe\u{301} au lait";
+    let mut value = TextEditingValue::new(text);
+    value.set_selection(TextSelection::collapsed(0));
+    value.move_caret(CaretMovement::WordRight, false);
+    assert_eq!(
+        value.selection,
+        TextSelection::collapsed(text.find(' ').unwrap())
+    );
+    value.move_caret(CaretMovement::WordLeft, false);
+    assert_eq!(value.selection, TextSelection::collapsed(0));
+}
+
+#[test]
+fn ime_composition_is_replaced_by_commit() {
+    let controller = TextEditingController::new("ab");
+    let mut tester = WidgetTester::new();
+    tester.pump_widget(MaterialApp!(
+        child = TextField!(controller = controller.clone(), autofocus = true)
+    ));
+    tester.pump(Default::default());
+
+    tester.send_ime_event(ImeEvent::Enabled);
+    tester.send_ime_event(ImeEvent::Preedit {
+        text: "ni".to_owned(),
+        cursor: Some((2, 2)),
+    });
+    tester.pump(Default::default());
+    let value = controller.value();
+    assert_eq!(value.text, "abni");
+    assert_eq!(value.composing, Some(2..4));
+    assert_eq!(value.selection, TextSelection::collapsed(4));
+
+    tester.send_ime_event(ImeEvent::Preedit {
+        text: String::new(),
+        cursor: None,
+    });
+    tester.commit_text("你");
+    let value = controller.value();
+    assert_eq!(value.text, "ab你");
+    assert_eq!(value.composing, None);
+    assert_eq!(value.selection, TextSelection::collapsed(5));
+}
+
+#[test]
+fn tapping_takes_focus_and_places_the_caret() {
+    let controller = TextEditingController::new("hello");
+    let mut tester = WidgetTester::new();
+    tester.pump_widget(MaterialApp!(
+        child = Center!(child = TextField!(controller = controller.clone()))
+    ));
+    let target = Finder::by_type::<EditableText>();
+    assert!(!tester.widget::<EditableText>(&target).show_caret);
+
+    let center = tester.get_center(&target);
+    let size = tester.get_size(&target);
+    tester.tap_at(Point2d {
+        x: center.x - size.width / 2.0 + 1.0,
+        y: center.y,
+    });
+    // The focus request is applied at the beginning of the next frame.
+    tester.pump(Default::default());
+    assert_eq!(controller.selection(), TextSelection::collapsed(0));
+    assert!(tester.widget::<EditableText>(&target).show_caret);
+
+    tester.tap_at(Point2d {
+        x: center.x + size.width / 2.0 - 1.0,
+        y: center.y,
+    });
+    assert_eq!(controller.selection(), TextSelection::collapsed(5));
+}
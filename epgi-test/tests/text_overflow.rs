@@ -0,0 +1,150 @@
+use std::borrow::Cow;
+
+use epgi_2d::{
+    BoxSize, LocalTextStyle, MultiLineConstraints, Paragraph, Point2d, SingleLineSize, TextAlign,
+    TextDirection, TextOverFlow, TextSpan, TextStyle,
+};
+use epgi_common::{Align, Alignment, Container, Text};
+use epgi_material::{black_mountain_view_body_medium, MaterialApp};
+use epgi_test::{testing::ink, Finder, WidgetTester};
+
+const TEXT: &str = "A long label that does not fit on two lines of a narrow column";
+const WIDTH: f32 = 120.0;
+
+fn style(overflow: TextOverFlow) -> TextStyle {
+    TextStyle {
+        overflow,
+        ..black_mountain_view_body_medium()
+    }
+}
+
+fn paragraph(overflow: TextOverFlow) -> Paragraph {
+    Paragraph::new(
+        &[TextSpan {
+            text: Cow::Borrowed(TEXT),
+            style: None,
+        }],
+        &style(overflow),
+        TextDirection::Ltr,
+    )
+}
+
+fn constraints() -> MultiLineConstraints {
+    MultiLineConstraints {
+        first_line_existing_advance: 0.0,
+        max_width: WIDTH,
+        last_line_append_advance: 0.0,
+        max_height: f32::INFINITY,
+    }
+}
+
+#[test]
+fn max_lines_hides_the_remaining_lines() {
+    let mut paragraph = paragraph(TextOverFlow::Clip);
+    let all_lines = paragraph.layout_multi_line(&constraints(), TextAlign::Start, None);
+    assert!(all_lines.len() > 2);
+    assert!(!paragraph.is_truncated());
+
+    let sizes = paragraph.layout_multi_line(&constraints(), TextAlign::Start, Some(2));
+    assert_eq!(sizes.len(), 2);
+    assert_eq!(paragraph.line_count(), 2);
+    assert!(paragraph.is_truncated());
+    assert_eq!(sizes[1].advance, all_lines[1].advance);
+
+    let height = |sizes: &[SingleLineSize]| {
+        sizes
+            .iter()
+            .map(|size| size.above + size.below)
+            .sum::<f32>()
+    };
+    assert_eq!(paragraph.intrinsic_height(WIDTH, Some(2)), height(&sizes));
+    assert_eq!(paragraph.intrinsic_height(WIDTH, None), height(&all_lines));
+
+    // Enough room for every line
+    paragraph.layout_multi_line(&constraints(), TextAlign::Start, Some(all_lines.len()));
+    assert!(!paragraph.is_truncated());
+}
+
+#[test]
+fn ellipsis_replaces_the_end_of_the_last_line() {
+    let mut paragraph = paragraph(TextOverFlow::Ellipsis);
+    let sizes = paragraph.layout_multi_line(&constraints(), TextAlign::Start, Some(2));
+    assert_eq!(sizes.len(), 2);
+    assert!(sizes[1].advance <= WIDTH);
+
+    // Hit tests past the ellipsis, or below the last visible line, land where the visible text ends
+    let line_height = sizes[0].above + sizes[0].below;
+    let end_of_text = paragraph.hit_test_text_position(Point2d {
+        x: WIDTH,
+        y: line_height + sizes[1].above,
+    });
+    assert!(end_of_text < TEXT.len());
+    assert!(!TEXT[..end_of_text].ends_with(' '));
+    assert_eq!(
+        paragraph.hit_test_text_position(Point2d {
+            x: 0.0,
+            y: line_height * 5.0
+        }),
+        end_of_text
+    );
+    assert_eq!(
+        paragraph.hit_test_text_position(Point2d {
+            x: 0.0,
+            y: line_height * 0.5
+        }),
+        0
+    );
+
+    // Carets and selections of the hidden text stay in front of the ellipsis
+    let caret = paragraph.caret_rect(TEXT.len()).unwrap();
+    assert_eq!(caret.l, paragraph.caret_rect(end_of_text).unwrap().l);
+    assert!(caret.l < sizes[1].advance && caret.t >= line_height - 1e-3);
+    let selection = paragraph.selection_rects(0..TEXT.len());
+    assert_eq!(selection.len(), 2);
+    assert!((selection[1].r - caret.l).abs() < 1e-3);
+}
+
+fn pump_label(tester: &mut WidgetTester, overflow: TextOverFlow) {
+    tester.pump_widget(MaterialApp!(
+        child = Align!(
+            alignment = Alignment::TOP_LEFT,
+            child = Container!(
+                width = WIDTH,
+                child = Text!(
+                    text = TEXT,
+                    style = LocalTextStyle {
+                        overflow: Some(overflow),
+                        ..Default::default()
+                    },
+                    max_lines = 1
+                )
+            )
+        )
+    ));
+}
+
+#[test]
+fn text_overflow_ends_a_single_line_label() {
+    let mut tester = WidgetTester::new_with_size(BoxSize {
+        width: 200.0,
+        height: 100.0,
+    });
+    pump_label(&mut tester, TextOverFlow::Clip);
+    let text = Finder::by_type::<Text>();
+    let size = tester.get_size(&text);
+    let clip_ink = ink(&tester, 0..WIDTH as u32);
+    assert!(clip_ink > 0);
+
+    pump_label(&mut tester, TextOverFlow::Fade);
+    assert_eq!(tester.get_size(&text).height, size.height);
+    let fade_ink = ink(&tester, 0..WIDTH as u32);
+    assert!(fade_ink > 0 && fade_ink < clip_ink);
+
+    pump_label(&mut tester, TextOverFlow::Ellipsis);
+    let ellipsis_size = tester.get_size(&text);
+    assert_eq!(ellipsis_size.height, size.height);
+    assert!(ellipsis_size.width <= WIDTH);
+    assert_ne!(ink(&tester, 0..WIDTH as u32), clip_ink);
+    // Nothing is painted past the ellipsis
+    assert_eq!(ink(&tester, WIDTH as u32..200), 0);
+}
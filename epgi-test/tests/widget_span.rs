@@ -0,0 +1,265 @@
+use std::{
+    borrow::Cow,
+    sync::{Arc, Mutex},
+};
+
+use epgi_2d::{
+    ArcBoxWidget, BoxSize, Color, LocalTextStyle, Paragraph, Point2d, SingleLineSize, TextAlign,
+    TextBaseline, TextDirection, TextSpan, TextStyle,
+};
+use epgi_common::{
+    Align, Alignment, Center, Container, Directionality, GestureDetector, InlineSpan,
+    PlaceholderAlignment, Text, WidgetSpan,
+};
+use epgi_material::{black_mountain_view_body_medium, MaterialApp};
+use epgi_test::{
+    testing::{assert_close, assert_close_within, container_of_width, top_left},
+    Finder, WidgetTester,
+};
+
+const WIDTH: f32 = 200.0;
+
+fn text_span(text: &'static str) -> InlineSpan {
+    TextSpan {
+        text: Cow::Borrowed(text),
+        style: None,
+    }
+    .into()
+}
+
+fn widget_span(child: ArcBoxWidget, alignment: PlaceholderAlignment) -> InlineSpan {
+    WidgetSpan { child, alignment }.into()
+}
+
+fn boxed(width: f32, height: f32) -> ArcBoxWidget {
+    Container!(width = width, height = height, color = Color::BLACK)
+}
+
+/// The metrics of a single line of text in the style.
+fn text_line(text: &'static str, style: TextStyle) -> SingleLineSize {
+    let mut paragraph = Paragraph::new(
+        &[TextSpan {
+            text: Cow::Borrowed(text),
+            style: None,
+        }],
+        &style,
+        TextDirection::Ltr,
+    );
+    let sizes = paragraph.layout(None, TextAlign::Start);
+    assert_eq!(sizes.len(), 1);
+    sizes[0]
+}
+
+fn large_style() -> TextStyle {
+    let style = black_mountain_view_body_medium();
+    TextStyle {
+        font_size: style.font_size * 2.0,
+        ..style
+    }
+}
+
+fn pump_text(tester: &mut WidgetTester, children: Vec<InlineSpan>) {
+    tester.pump_widget(MaterialApp!(
+        child = Align!(
+            alignment = Alignment::TOP_LEFT,
+            child = Container!(
+                width = WIDTH,
+                child = Align!(
+                    alignment = Alignment::TOP_LEFT,
+                    child = Text!(children = children)
+                )
+            )
+        )
+    ));
+}
+
+#[test]
+fn inline_widgets_flow_with_the_text_around_them() {
+    let mut tester = WidgetTester::new_with_size(BoxSize {
+        width: 400.0,
+        height: 300.0,
+    });
+    pump_text(
+        &mut tester,
+        vec![
+            text_span("Hi "),
+            widget_span(boxed(20.0, 10.0), PlaceholderAlignment::AboveBaseline),
+            text_span(" there"),
+        ],
+    );
+    let hi = text_line("Hi ", black_mountain_view_body_medium());
+    let there = text_line(" there", black_mountain_view_body_medium());
+    let text = Finder::text("Hi  there");
+    // The widget sits on the baseline between the two runs of text
+    let position = top_left(&tester, &container_of_width(20.0));
+    assert_close(position.x, hi.advance);
+    assert_close(position.y, hi.above - 10.0);
+    let size = tester.get_size(&text);
+    assert_close(size.width, hi.advance + 20.0 + there.advance);
+    assert_close(size.height, hi.above + hi.below);
+}
+
+#[test]
+fn inline_widget_that_does_not_fit_starts_a_new_line() {
+    let mut tester = WidgetTester::new_with_size(BoxSize {
+        width: 400.0,
+        height: 300.0,
+    });
+    let hi = text_line("Hi ", black_mountain_view_body_medium());
+    let wide = WIDTH - hi.advance / 2.0;
+    pump_text(
+        &mut tester,
+        vec![
+            text_span("Hi "),
+            widget_span(boxed(wide, 10.0), PlaceholderAlignment::AboveBaseline),
+        ],
+    );
+    let position = top_left(&tester, &container_of_width(wide));
+    assert_close(position.x, 0.0);
+    assert_close(position.y, hi.above + hi.below);
+    let size = tester.get_size(&Finder::text("Hi "));
+    assert_close(size.width, wide);
+    assert_close(size.height, hi.above + hi.below + 10.0);
+}
+
+#[test]
+fn placeholder_alignment_places_the_widget_against_the_baseline() {
+    let mut tester = WidgetTester::new_with_size(BoxSize {
+        width: 400.0,
+        height: 300.0,
+    });
+    let hi = text_line("Hi ", black_mountain_view_body_medium());
+    let tall = hi.above + hi.below + 10.0;
+
+    pump_text(
+        &mut tester,
+        vec![
+            text_span("Hi "),
+            widget_span(boxed(20.0, tall), PlaceholderAlignment::AboveBaseline),
+        ],
+    );
+    assert_close(top_left(&tester, &container_of_width(20.0)).y, 0.0);
+    let text = Finder::text("Hi ");
+    assert_close(tester.get_size(&text).height, tall + hi.below);
+
+    pump_text(
+        &mut tester,
+        vec![
+            text_span("Hi "),
+            widget_span(boxed(20.0, tall), PlaceholderAlignment::BelowBaseline),
+        ],
+    );
+    assert_close(top_left(&tester, &container_of_width(20.0)).y, hi.above);
+    assert_close(tester.get_size(&text).height, hi.above + tall);
+
+    // An inline text with a larger font shares the baseline of the line
+    pump_text(
+        &mut tester,
+        vec![
+            text_span("Hi "),
+            widget_span(
+                Text!(
+                    text = "large",
+                    style = LocalTextStyle {
+                        font_size: Some(large_style().font_size),
+                        ..Default::default()
+                    }
+                ),
+                PlaceholderAlignment::Baseline(TextBaseline::Alphabetic),
+            ),
+        ],
+    );
+    let inline = Finder::text("large");
+    let inline_size = tester.get_size(&inline);
+    assert!(inline_size.height > hi.above + hi.below);
+    let inline_above = text_line("large", large_style()).above;
+    assert_close(top_left(&tester, &inline).y, 0.0);
+    assert_close(
+        tester.get_size(&text).height,
+        inline_above + (inline_size.height - inline_above).max(hi.below),
+    );
+}
+
+#[test]
+fn taps_reach_inline_widgets() {
+    let mut tester = WidgetTester::new();
+    let taps = Arc::new(Mutex::new(0));
+    tester.pump_widget(MaterialApp!(
+        child = Center!(
+            child = Text!(
+                children = vec![
+                    text_span("Tap "),
+                    widget_span(
+                        GestureDetector!(
+                            on_tap = {
+                                let taps = taps.clone();
+                                move |_job_builder| *taps.lock().unwrap() += 1
+                            },
+                            child = boxed(20.0, 10.0)
+                        ),
+                        PlaceholderAlignment::AboveBaseline,
+                    ),
+                    text_span(" here"),
+                ]
+            )
+        )
+    ));
+    // The inline widget is laid out relative to the text, which is centered in the window
+    let text = top_left(&tester, &Finder::text("Tap  here"));
+    let inline = tester.get_center(&container_of_width(20.0));
+    tester.tap_at(Point2d {
+        x: text.x + inline.x,
+        y: text.y + inline.y,
+    });
+    assert_eq!(*taps.lock().unwrap(), 1);
+
+    // Tapping the text next to it does not reach the widget
+    tester.tap_at(Point2d {
+        x: text.x + 1.0,
+        y: text.y + inline.y,
+    });
+    assert_eq!(*taps.lock().unwrap(), 1);
+}
+
+#[test]
+fn inline_widgets_follow_the_direction_and_the_max_lines_of_the_text() {
+    let mut tester = WidgetTester::new_with_size(BoxSize {
+        width: 400.0,
+        height: 300.0,
+    });
+    tester.pump_widget(MaterialApp!(
+        child = Directionality!(
+            text_direction = TextDirection::Rtl,
+            child = Align!(
+                alignment = Alignment::TOP_LEFT,
+                child = Text!(
+                    children = vec![
+                        text_span("שלום "),
+                        widget_span(boxed(20.0, 10.0), PlaceholderAlignment::AboveBaseline),
+                        text_span(" עולם\nעוד "),
+                        widget_span(
+                            Container!(width = 30.0, height = 10.0, color = Color::RED),
+                            PlaceholderAlignment::AboveBaseline
+                        ),
+                    ],
+                    max_lines = 1
+                )
+            )
+        )
+    ));
+    let shalom = text_line("שלום ", black_mountain_view_body_medium());
+    let text = Finder::text("שלום  עולם\nעוד ");
+    let width = tester.get_size(&text).width;
+    // The first word starts at the right, and the widget after it is on its left
+    let position = top_left(&tester, &container_of_width(20.0));
+    assert_close_within(position.x, width - shalom.advance - 20.0, 1.0);
+    assert_close(position.y, shalom.above - 10.0);
+    assert_close(tester.get_size(&text).height, shalom.above + shalom.below);
+
+    // The widget on the hidden second line is not painted
+    let pixmap = tester.rasterize();
+    assert!(pixmap
+        .pixels()
+        .iter()
+        .all(|pixel| pixel.red() <= pixel.green()));
+}
@@ -1,5 +1,11 @@
-use epgi_2d::{Affine2dEncoding, ArcBoxWidget, BoxConstraints, BoxOffset, BoxSize, RootView};
-use epgi_common::{ConstrainedBox, FrameInfo, PointerEvent};
+use epgi_2d::{
+    Affine2dEncoding, ArcBoxWidget, BoxConstraints, BoxOffset, BoxSize, FontLoader, RootView,
+    TextDirection,
+};
+use epgi_common::{
+    ConstrainedBox, CursorIcon, Directionality, FocusManager, FrameInfo, ImeEvent, KeyEvent,
+    KeyboardManager, MouseTracker, PointerEvent,
+};
 use epgi_core::{
     foundation::{unbounded_channel_sync, Arc, Asc, SyncMpscReceiver, SyncMutex},
     hooks::SetState,
@@ -27,7 +33,8 @@ use winit::{
 pub use winit::window::{Window, WindowAttributes};
 
 use crate::{
-    EpgiGlazierSchedulerExtension, FrameStatSample, FrameStats, WinitPointerEventConverter,
+    utils::ToWinitExt, EpgiGlazierSchedulerExtension, FrameStatSample, FrameStats,
+    WinitKeyEventConverter, WinitPointerEventConverter,
 };
 
 pub enum WindowState<'a> {
@@ -57,11 +64,17 @@ struct MainState<'a> {
     frame_binding: Arc<SyncMutex<Option<SetState<FrameInfo>>>>,
     constraints_binding: Arc<SyncMutex<Option<SetState<BoxConstraints>>>>,
     pointer_event_converter: WinitPointerEventConverter,
+    key_event_converter: WinitKeyEventConverter,
+    mouse_tracker: MouseTracker,
+    /// The cursor last applied to the window.
+    cursor: CursorIcon,
 
     frame_stats: FrameStats,
     print_stats: bool,
 }
 
+type LoadFonts = Box<dyn FnOnce(&FontLoader)>;
+
 #[derive(TypedBuilder)]
 pub struct AppLauncher {
     app: ArcBoxWidget,
@@ -76,6 +89,10 @@ pub struct AppLauncher {
     #[builder(default)]
     print_stats: bool,
 
+    /// Registers custom fonts before the app is first built.
+    #[builder(default, setter(transform = |load: impl FnOnce(&FontLoader) + 'static| Some(Box::new(load) as _)))]
+    fonts: Option<LoadFonts>,
+
     window: WindowAttributes,
     #[builder(default = EventLoop::new().unwrap(), setter(skip))]
     event_loop: EventLoop<()>,
@@ -84,9 +101,14 @@ pub struct AppLauncher {
 impl AppLauncher {
     pub fn run(self) {
         pretty_env_logger::init();
+        if let Some(load) = self.fonts {
+            load(&FontLoader);
+        }
         let render_cx = RenderContext::new();
 
         let (tx, rx) = unbounded_channel_sync();
+        let (key_tx, key_rx) = unbounded_channel_sync();
+        let (ime_tx, ime_rx) = unbounded_channel_sync();
         let mut main_state = MainState {
             window: WindowState::Uninitialized(self.window),
             render_cx,
@@ -97,6 +119,9 @@ impl AppLauncher {
             frame_binding: Default::default(),
             constraints_binding: Default::default(),
             pointer_event_converter: WinitPointerEventConverter::new(tx),
+            key_event_converter: WinitKeyEventConverter::new(key_tx, ime_tx),
+            mouse_tracker: MouseTracker::new(),
+            cursor: CursorIcon::Default,
 
             frame_stats: FrameStats::new(),
             print_stats: self.print_stats,
@@ -161,7 +186,7 @@ impl AppLauncher {
         let _ = try_init_tracing();
 
         initialize_scheduler_handle(sync_threadpool, async_threadpool);
-        main_state.start_scheduler_with(self.app, rx, spawn_hook);
+        main_state.start_scheduler_with(self.app, rx, key_rx, ime_rx, spawn_hook);
 
         self.event_loop.run_app(&mut main_state).unwrap()
     }
@@ -235,6 +260,8 @@ impl ApplicationHandler for MainState<'_> {
                 let window = event_loop.create_window(attributes).unwrap();
 
                 window.set_visible(visible);
+                // Text input relies on IME events, which winit only delivers after opting in.
+                window.set_ime_allowed(true);
                 let window = Arc::new(window);
                 let size = window.inner_size();
                 let surface = futures::executor::block_on(self.render_cx.create_surface(
@@ -311,7 +338,10 @@ impl ApplicationHandler for MainState<'_> {
                     } / (scale as f32),
                 );
             }
-            ModifiersChanged(modifiers) => {}
+            ModifiersChanged(_) | KeyboardInput { .. } | Ime(_) => {
+                self.key_event_converter.convert(&event);
+                window.request_redraw();
+            }
             CursorMoved { .. }
             | CursorEntered { .. }
             | CursorLeft { .. }
@@ -384,6 +414,13 @@ impl<'a> MainState<'a> {
 
         let frame_results = scheduler.request_new_frame().recv().unwrap();
 
+        // The mouse tracker has been updated at the beginning of the frame.
+        let cursor = self.mouse_tracker.cursor();
+        if cursor != self.cursor {
+            window.set_cursor(cursor.to_winit());
+            self.cursor = cursor;
+        }
+
         let raster_start_time = Instant::now();
 
         let encoding = frame_results
@@ -516,6 +553,8 @@ impl<'a> MainState<'a> {
         &mut self,
         app: ArcBoxWidget,
         rx: SyncMpscReceiver<PointerEvent>,
+        key_rx: SyncMpscReceiver<KeyEvent>,
+        ime_rx: SyncMpscReceiver<ImeEvent>,
         spawn_hook: impl SpawnHook,
     ) {
         // Now we wrap the application in wrapper widgets that provides bindigns to basic functionalities,
@@ -530,7 +569,16 @@ impl<'a> MainState<'a> {
         //
         // The most frequently updated binding should comes in the innermost wrapper.
 
-        let child = app;
+        // Left-to-right until the embedding can tell the direction of the locale.
+        let child = Directionality!(text_direction = TextDirection::Ltr, child = app);
+
+        // Widgets register their key event handlers through the registry provided here.
+        let keyboard_manager = KeyboardManager::new(key_rx, ime_rx);
+        let child = Provider!(value = keyboard_manager.registry().clone(), child);
+
+        // Top-level focus nodes are attached to the root scope.
+        let focus_manager = FocusManager::new();
+        let child = Provider!(value = focus_manager.root_scope(), child);
 
         let (child, frame_binding) = bind_frame_info(child);
         self.frame_binding = frame_binding;
@@ -543,7 +591,12 @@ impl<'a> MainState<'a> {
             LayoutResults::new(BoxConstraints::default(), BoxSize::INFINITY, ()),
             BoxOffset::ZERO,
             get_current_scheduler(),
-            EpgiGlazierSchedulerExtension::new(rx),
+            EpgiGlazierSchedulerExtension::new(
+                rx,
+                self.mouse_tracker.clone(),
+                keyboard_manager,
+                focus_manager,
+            ),
         );
         let join_handle = std::thread::Builder::new()
             .name("epgi scheduler".into())
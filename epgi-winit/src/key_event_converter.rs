@@ -0,0 +1,174 @@
+use std::time::Instant;
+
+use epgi_common::{
+    ImeEvent, KeyCode, KeyEvent, KeyLocation, KeyModifiers, KeyState, LogicalKey, NamedKey,
+    PhysicalKey,
+};
+use epgi_core::foundation::SyncMpscSender;
+
+use crate::utils::ToEpgiExt;
+
+pub(crate) struct WinitKeyEventConverter {
+    // Winit reports modifiers separately from key events, so we have to keep track of them.
+    modifiers: KeyModifiers,
+    tx: SyncMpscSender<KeyEvent>,
+    ime_tx: SyncMpscSender<ImeEvent>,
+}
+
+impl WinitKeyEventConverter {
+    pub(crate) fn new(tx: SyncMpscSender<KeyEvent>, ime_tx: SyncMpscSender<ImeEvent>) -> Self {
+        Self {
+            modifiers: KeyModifiers::empty(),
+            tx,
+            ime_tx,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn convert(&mut self, event: &winit::event::WindowEvent) {
+        use winit::event::WindowEvent::*;
+        match event {
+            ModifiersChanged(modifiers) => self.modifiers = modifiers.state().to_epgi(),
+            KeyboardInput { event, .. } => {
+                self.tx
+                    .send(KeyEvent {
+                        time_stamp: Instant::now(),
+                        physical_key: event.physical_key.to_epgi(),
+                        logical_key: event.logical_key.to_epgi(),
+                        text: event.text.as_ref().map(|text| text.to_string()),
+                        location: event.location.to_epgi(),
+                        state: match event.state {
+                            winit::event::ElementState::Pressed => KeyState::Pressed,
+                            winit::event::ElementState::Released => KeyState::Released,
+                        },
+                        repeat: event.repeat,
+                        modifiers: self.modifiers,
+                    })
+                    .unwrap();
+            }
+            Ime(ime) => self.ime_tx.send(ime.to_epgi()).unwrap(),
+            _ => {}
+        }
+    }
+}
+
+impl ToEpgiExt for winit::keyboard::ModifiersState {
+    type Output = KeyModifiers;
+
+    fn to_epgi(&self) -> Self::Output {
+        let mut result = KeyModifiers::empty();
+        result.set(KeyModifiers::SHIFT, self.shift_key());
+        result.set(KeyModifiers::CONTROL, self.control_key());
+        result.set(KeyModifiers::ALT, self.alt_key());
+        result.set(KeyModifiers::SUPER, self.super_key());
+        result
+    }
+}
+
+impl ToEpgiExt for winit::event::Ime {
+    type Output = ImeEvent;
+
+    fn to_epgi(&self) -> Self::Output {
+        use winit::event::Ime::*;
+        match self {
+            Enabled => ImeEvent::Enabled,
+            Preedit(text, cursor) => ImeEvent::Preedit {
+                text: text.clone(),
+                cursor: *cursor,
+            },
+            Commit(text) => ImeEvent::Commit(text.clone()),
+            Disabled => ImeEvent::Disabled,
+        }
+    }
+}
+
+impl ToEpgiExt for winit::keyboard::KeyLocation {
+    type Output = KeyLocation;
+
+    fn to_epgi(&self) -> Self::Output {
+        use winit::keyboard::KeyLocation::*;
+        match self {
+            Standard => KeyLocation::Standard,
+            Left => KeyLocation::Left,
+            Right => KeyLocation::Right,
+            Numpad => KeyLocation::Numpad,
+        }
+    }
+}
+
+impl ToEpgiExt for winit::keyboard::Key {
+    type Output = LogicalKey;
+
+    fn to_epgi(&self) -> Self::Output {
+        use winit::keyboard::Key::*;
+        match self {
+            Named(named_key) => named_key
+                .to_epgi()
+                .map_or(LogicalKey::Unidentified, LogicalKey::Named),
+            Character(character) => LogicalKey::Character(character.to_string()),
+            Dead(diacritic) => LogicalKey::Dead(*diacritic),
+            Unidentified(_) => LogicalKey::Unidentified,
+        }
+    }
+}
+
+impl ToEpgiExt for winit::keyboard::PhysicalKey {
+    type Output = PhysicalKey;
+
+    fn to_epgi(&self) -> Self::Output {
+        match self {
+            winit::keyboard::PhysicalKey::Code(code) => code
+                .to_epgi()
+                .map_or(PhysicalKey::Unidentified, PhysicalKey::Code),
+            winit::keyboard::PhysicalKey::Unidentified(_) => PhysicalKey::Unidentified,
+        }
+    }
+}
+
+// Our key enums are subsets of winit's with identical variant names.
+macro_rules! impl_subset_conversion {
+    ($from: ty => $to: ident { $($variant: ident),* $(,)? }) => {
+        impl ToEpgiExt for $from {
+            type Output = Option<$to>;
+
+            fn to_epgi(&self) -> Self::Output {
+                match self {
+                    $(<$from>::$variant => Some($to::$variant),)*
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+impl_subset_conversion!(winit::keyboard::NamedKey => NamedKey {
+    Alt, AltGraph, CapsLock, Control, Fn, NumLock, ScrollLock, Shift, Super, Meta,
+    Enter, Tab, Space,
+    ArrowDown, ArrowLeft, ArrowRight, ArrowUp, End, Home, PageDown, PageUp,
+    Backspace, Clear, Copy, Cut, Delete, Insert, Paste, Redo, Undo,
+    Escape, ContextMenu, Pause, PrintScreen,
+    AudioVolumeDown, AudioVolumeUp, AudioVolumeMute,
+    MediaPlayPause, MediaStop, MediaTrackNext, MediaTrackPrevious,
+    BrowserBack, BrowserForward,
+    F1, F2, F3, F4, F5, F6, F7, F8, F9, F10, F11, F12,
+    F13, F14, F15, F16, F17, F18, F19, F20, F21, F22, F23, F24,
+});
+
+impl_subset_conversion!(winit::keyboard::KeyCode => KeyCode {
+    Backquote, Backslash, BracketLeft, BracketRight, Comma,
+    Digit0, Digit1, Digit2, Digit3, Digit4, Digit5, Digit6, Digit7, Digit8, Digit9,
+    Equal, IntlBackslash, IntlRo, IntlYen,
+    KeyA, KeyB, KeyC, KeyD, KeyE, KeyF, KeyG, KeyH, KeyI, KeyJ, KeyK, KeyL, KeyM,
+    KeyN, KeyO, KeyP, KeyQ, KeyR, KeyS, KeyT, KeyU, KeyV, KeyW, KeyX, KeyY, KeyZ,
+    Minus, Period, Quote, Semicolon, Slash,
+    AltLeft, AltRight, Backspace, CapsLock, ContextMenu, ControlLeft, ControlRight, Enter,
+    SuperLeft, SuperRight, ShiftLeft, ShiftRight, Space, Tab,
+    Delete, End, Home, Insert, PageDown, PageUp,
+    ArrowDown, ArrowLeft, ArrowRight, ArrowUp,
+    NumLock, Numpad0, Numpad1, Numpad2, Numpad3, Numpad4, Numpad5, Numpad6, Numpad7, Numpad8,
+    Numpad9, NumpadAdd, NumpadDecimal, NumpadDivide, NumpadEnter, NumpadEqual, NumpadMultiply,
+    NumpadSubtract,
+    Escape, Fn, PrintScreen, ScrollLock, Pause,
+    F1, F2, F3, F4, F5, F6, F7, F8, F9, F10, F11, F12,
+    F13, F14, F15, F16, F17, F18, F19, F20, F21, F22, F23, F24,
+});
@@ -7,6 +7,9 @@ use scheduler_extension::*;
 mod pointer_event_converter;
 use pointer_event_converter::*;
 
+mod key_event_converter;
+use key_event_converter::*;
+
 mod stat;
 use stat::*;
 
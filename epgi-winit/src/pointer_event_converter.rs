@@ -1,17 +1,23 @@
 use std::time::Instant;
 
-use epgi_2d::Point2d;
+use epgi_2d::{BoxOffset, Point2d};
 use epgi_common::{
     PointerButtons, PointerContactData, PointerContactProfile, PointerDeviceKind, PointerEvent,
-    PointerEventCommonData, PointerHoverData, PointerInteractionId,
+    PointerEventCommonData, PointerHoverData, PointerInteractionId, PointerPanZoomUpdateData,
+    PointerSignalData,
 };
 use epgi_core::foundation::SyncMpscSender;
 use hashbrown::{hash_map::Entry, HashMap};
 
 use crate::utils::ToEpgiExt;
 
+/// The scroll distance of one line reported by a mouse wheel, in logical pixels.
+const SCROLL_LINE_DELTA: f32 = 20.0;
+
 pub(crate) struct WinitPointerEventConverter {
     pointer_devices: HashMap<winit::event::DeviceId, WinitPointerDeviceState>,
+    /// Trackpad pans in progress, with the distance panned so far.
+    pan_zoom_interactions: HashMap<winit::event::DeviceId, (PointerInteractionId, BoxOffset)>,
     interaction_id_counter: InteractonIdCounter,
     tx: SyncMpscSender<PointerEvent>,
 }
@@ -47,6 +53,7 @@ impl WinitPointerEventConverter {
     pub(crate) fn new(tx: SyncMpscSender<PointerEvent>) -> Self {
         Self {
             pointer_devices: Default::default(),
+            pan_zoom_interactions: Default::default(),
             interaction_id_counter: InteractonIdCounter(0),
             tx,
         }
@@ -183,9 +190,7 @@ impl WinitPointerEventConverter {
                 device_id,
                 delta,
                 phase,
-            } => {
-                log::error!("Pointer signal event is not implemented")
-            }
+            } => self.convert_mouse_wheel(device_id, delta, phase, scale, time_stamp),
             MouseInput {
                 device_id,
                 state: press,
@@ -318,6 +323,118 @@ impl WinitPointerEventConverter {
     }
 }
 
+impl WinitPointerEventConverter {
+    /// Mouse wheels scroll by lines and are converted into scroll signals.
+    /// Trackpads scroll by pixels within a gesture phase and are converted into pan interactions,
+    /// unless the platform reports pixel deltas outside of a phase.
+    fn convert_mouse_wheel(
+        &mut self,
+        device_id: &winit::event::DeviceId,
+        delta: &winit::event::MouseScrollDelta,
+        phase: &winit::event::TouchPhase,
+        scale: f32,
+        time_stamp: Instant,
+    ) {
+        let Some(WinitPointerDeviceState::Cursor(
+            PointerState::Added { last_position } | PointerState::Interacting { last_position, .. },
+        )) = self.pointer_devices.get(device_id)
+        else {
+            log::warn!("Winit produces MouseWheel event while the cursor has not been registered");
+            return;
+        };
+        let common = |pointer_kind| PointerEventCommonData {
+            time_stamp,
+            position: *last_position,
+            pointer_kind,
+            synthesized: false,
+        };
+        use winit::event::MouseScrollDelta::*;
+        let delta = match delta {
+            LineDelta(x, y) => {
+                // Winit reports a positive delta when the wheel is rolled away from the user,
+                // which scrolls towards the start of the content.
+                self.tx
+                    .send(PointerEvent::new_signal(
+                        common(PointerDeviceKind::Mouse),
+                        PointerSignalData::Scroll {
+                            physical_delta: BoxOffset {
+                                x: -x * SCROLL_LINE_DELTA,
+                                y: -y * SCROLL_LINE_DELTA,
+                            },
+                        },
+                    ))
+                    .unwrap();
+                return;
+            }
+            PixelDelta(delta) => BoxOffset {
+                x: delta.x as f32 / scale,
+                y: delta.y as f32 / scale,
+            },
+        };
+
+        use winit::event::TouchPhase::*;
+        if let Started = phase {
+            let interaction_id = self.interaction_id_counter.generate();
+            self.tx
+                .send(PointerEvent::new_pan_zoom_start(
+                    common(PointerDeviceKind::Trackpad),
+                    interaction_id,
+                ))
+                .unwrap();
+            if let Some((interaction_id, _)) = self
+                .pan_zoom_interactions
+                .insert(*device_id, (interaction_id, BoxOffset::ZERO))
+            {
+                log::warn!("Winit starts a trackpad pan while the last one has not ended");
+                self.tx
+                    .send(PointerEvent::new_pan_zoom_end(
+                        common(PointerDeviceKind::Trackpad),
+                        interaction_id,
+                    ))
+                    .unwrap();
+            }
+        }
+        let Some((interaction_id, pan)) = self.pan_zoom_interactions.get_mut(device_id) else {
+            self.tx
+                .send(PointerEvent::new_signal(
+                    common(PointerDeviceKind::Trackpad),
+                    PointerSignalData::Scroll {
+                        physical_delta: BoxOffset {
+                            x: -delta.x,
+                            y: -delta.y,
+                        },
+                    },
+                ))
+                .unwrap();
+            return;
+        };
+        let interaction_id = *interaction_id;
+        if delta != BoxOffset::ZERO {
+            *pan = *pan + delta;
+            self.tx
+                .send(PointerEvent::new_pan_zoom_update(
+                    common(PointerDeviceKind::Trackpad),
+                    interaction_id,
+                    PointerPanZoomUpdateData {
+                        pan: *pan,
+                        scale: 1.0,
+                        rotation: 0.0,
+                    },
+                ))
+                .unwrap();
+        }
+        if let Ended | Cancelled = phase {
+            self.pan_zoom_interactions.remove(device_id);
+            self.tx
+                .send(PointerEvent::new_pan_zoom_end(
+                    common(PointerDeviceKind::Trackpad),
+                    interaction_id,
+                ))
+                .unwrap();
+        }
+    }
+}
+
 fn convert_winit_touch(
     converter: &mut WinitPointerEventConverter,
     touch: &winit::event::Touch,
@@ -1,7 +1,9 @@
 use std::time::Instant;
 
 use epgi_2d::BoxProtocol;
-use epgi_common::{gesture::PointerGestureManager, PointerEvent};
+use epgi_common::{
+    gesture::PointerGestureManager, FocusManager, KeyboardManager, MouseTracker, PointerEvent,
+};
 use epgi_core::{
     foundation::SyncMpscReceiver,
     scheduler::{BuildStates, SchedulerExtension},
@@ -10,12 +12,21 @@ use epgi_core::{
 
 pub(crate) struct EpgiGlazierSchedulerExtension {
     pointer_gesture_manager: PointerGestureManager,
+    keyboard_manager: KeyboardManager,
+    focus_manager: FocusManager,
 }
 
 impl EpgiGlazierSchedulerExtension {
-    pub(crate) fn new(rx: SyncMpscReceiver<PointerEvent>) -> Self {
+    pub(crate) fn new(
+        rx: SyncMpscReceiver<PointerEvent>,
+        mouse_tracker: MouseTracker,
+        keyboard_manager: KeyboardManager,
+        focus_manager: FocusManager,
+    ) -> Self {
         Self {
-            pointer_gesture_manager: PointerGestureManager::new(rx),
+            pointer_gesture_manager: PointerGestureManager::new(rx, mouse_tracker),
+            keyboard_manager,
+            focus_manager,
         }
     }
 }
@@ -31,6 +42,12 @@ impl SchedulerExtension for EpgiGlazierSchedulerExtension {
             .flush_events(&root_render_object);
         self.pointer_gesture_manager
             .poll_revisit_all(Instant::now());
+        let focus_manager = &self.focus_manager;
+        self.keyboard_manager
+            .flush_events(|event| focus_manager.handle_key_event(event));
+        self.keyboard_manager
+            .flush_ime_events(|event| focus_manager.handle_ime_event(event));
+        self.focus_manager.flush_focus_changes();
     }
 
     fn on_layout_complete(&mut self, build_states: &BuildStates) {}
@@ -1,5 +1,5 @@
 use epgi_2d::Point2d;
-use epgi_common::PointerButtons;
+use epgi_common::{CursorIcon, PointerButtons};
 
 pub(crate) trait ToEpgiExt {
     type Output;
@@ -31,3 +31,37 @@ impl ToEpgiExt for winit::event::MouseButton {
         }
     }
 }
+
+pub(crate) trait ToWinitExt {
+    type Output;
+    fn to_winit(&self) -> Self::Output;
+}
+
+impl ToWinitExt for CursorIcon {
+    type Output = winit::window::CursorIcon;
+
+    fn to_winit(&self) -> Self::Output {
+        use winit::window::CursorIcon as Winit;
+        match self {
+            CursorIcon::Default => Winit::Default,
+            CursorIcon::Pointer => Winit::Pointer,
+            CursorIcon::Text => Winit::Text,
+            CursorIcon::Crosshair => Winit::Crosshair,
+            CursorIcon::Help => Winit::Help,
+            CursorIcon::Wait => Winit::Wait,
+            CursorIcon::Progress => Winit::Progress,
+            CursorIcon::Move => Winit::Move,
+            CursorIcon::Grab => Winit::Grab,
+            CursorIcon::Grabbing => Winit::Grabbing,
+            CursorIcon::NotAllowed => Winit::NotAllowed,
+            CursorIcon::EwResize => Winit::EwResize,
+            CursorIcon::NsResize => Winit::NsResize,
+            CursorIcon::NeswResize => Winit::NeswResize,
+            CursorIcon::NwseResize => Winit::NwseResize,
+            CursorIcon::ColResize => Winit::ColResize,
+            CursorIcon::RowResize => Winit::RowResize,
+            CursorIcon::ZoomIn => Winit::ZoomIn,
+            CursorIcon::ZoomOut => Winit::ZoomOut,
+        }
+    }
+}
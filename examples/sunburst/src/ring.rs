@@ -251,6 +251,7 @@ impl Protocol for RingProtocol {
     type Offset = RingOffset;
     type Size = RingSize;
     type Intrinsics = RingIntrinsics;
+    type Baseline = ();
     type Canvas = Affine2dCanvas;
 
     fn position_in_shape(position: &Point2d, offset: &RingOffset, size: &RingSize) -> bool {
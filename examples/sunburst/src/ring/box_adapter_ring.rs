@@ -1,6 +1,6 @@
 use epgi_2d::{Affine2d, Affine2dCanvas, BoxConstraints, BoxOffset, BoxProtocol, BoxSize};
 use epgi_core::{
-    foundation::{Arc, Asc, BuildSuspendedError, InlinableDwsizeVec, PaintContext, Provide},
+    foundation::{Arc, Asc, BuildError, InlinableDwsizeVec, PaintContext, Provide},
     template::{
         AdapterRender, AdapterRenderTemplate, ImplByTemplate, SingleChildElement,
         SingleChildElementTemplate, SingleChildRenderElement,
@@ -45,7 +45,7 @@ impl SingleChildElement for BoxAdapterRingElement {
         widget: &Self::ArcWidget,
         _ctx: &mut BuildContext<'_>,
         _provider_values: InlinableDwsizeVec<Arc<dyn Provide>>,
-    ) -> Result<ArcRingWidget, BuildSuspendedError> {
+    ) -> Result<ArcRingWidget, BuildError> {
         Ok(widget.child.clone())
     }
 
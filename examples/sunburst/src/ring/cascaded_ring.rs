@@ -2,7 +2,7 @@ use std::marker::PhantomData;
 
 use epgi_common::{Axis, RenderFlex};
 use epgi_core::{
-    foundation::{set_if_changed, Arc, Asc, BuildSuspendedError, InlinableDwsizeVec, Provide},
+    foundation::{set_if_changed, Arc, Asc, BuildError, InlinableDwsizeVec, Provide},
     template::{ImplByTemplate, MultiChildElement, MultiChildElementTemplate},
     tree::{BuildContext, RenderAction, Widget},
 };
@@ -63,11 +63,11 @@ impl MultiChildElement for CascadedRingElement {
     type Render = RenderFlex<RingProtocol>;
 
     fn get_child_widgets(
-        _element: Option<&mut Self>,
+        _element: &mut Self,
         widget: &Self::ArcWidget,
         _ctx: &mut BuildContext<'_>,
         _provider_values: InlinableDwsizeVec<Arc<dyn Provide>>,
-    ) -> Result<Vec<ArcRingWidget>, BuildSuspendedError> {
+    ) -> Result<Vec<ArcRingWidget>, BuildError> {
         Ok(widget.children.clone())
     }
 
@@ -3,9 +3,7 @@ use epgi_2d::{
     Point2d, RingSector,
 };
 use epgi_core::{
-    foundation::{
-        set_if_changed, Arc, Asc, BuildSuspendedError, InlinableDwsizeVec, PaintContext, Provide,
-    },
+    foundation::{set_if_changed, Arc, Asc, BuildError, InlinableDwsizeVec, PaintContext, Provide},
     template::{
         ImplByTemplate, ProxyRender, ProxyRenderTemplate, SingleChildElement,
         SingleChildElementTemplate, SingleChildRenderElement,
@@ -52,7 +50,7 @@ impl SingleChildElement for ColoredRingElement {
         widget: &Self::ArcWidget,
         _ctx: &mut BuildContext<'_>,
         _provider_values: InlinableDwsizeVec<Arc<dyn Provide>>,
-    ) -> Result<ArcRingWidget, BuildSuspendedError> {
+    ) -> Result<ArcRingWidget, BuildError> {
         Ok(widget.child.clone())
     }
 
@@ -1,7 +1,7 @@
 use std::sync::Arc;
 
 use epgi_core::{
-    foundation::{set_if_changed, Asc, BuildSuspendedError, InlinableDwsizeVec, Provide},
+    foundation::{set_if_changed, Asc, BuildError, InlinableDwsizeVec, Provide},
     template::{
         ImplByTemplate, ProxyRender, ProxyRenderTemplate, SingleChildElement,
         SingleChildElementTemplate, SingleChildRenderElement,
@@ -51,7 +51,7 @@ impl SingleChildElement for ConstrainedRingElement {
         widget: &Self::ArcWidget,
         _ctx: &mut BuildContext<'_>,
         _provider_values: InlinableDwsizeVec<Arc<dyn Provide>>,
-    ) -> Result<ArcRingWidget, BuildSuspendedError> {
+    ) -> Result<ArcRingWidget, BuildError> {
         Ok(widget.child.clone())
     }
 
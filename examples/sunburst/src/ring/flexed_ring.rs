@@ -13,9 +13,7 @@ pub use epgi_common::{
     CrossAxisAlignment, Flexible, FlexibleConfig, MainAxisAlignment, MainAxisSize,
 };
 use epgi_core::{
-    foundation::{
-        set_if_changed, Arc, Asc, BuildSuspendedError, InlinableDwsizeVec, PaintContext, Provide,
-    },
+    foundation::{set_if_changed, Arc, Asc, BuildError, InlinableDwsizeVec, PaintContext, Provide},
     template::{ImplByTemplate, MultiChildElement, MultiChildElementTemplate},
     tree::{BuildContext, RenderAction, Widget},
 };
@@ -23,7 +21,8 @@ use epgi_macro::Declarative;
 use typed_builder::TypedBuilder;
 
 use super::{
-    ArcRingRenderObject, ArcRingWidget, RingConstraints, RingOffset, RingProtocol, RingSize,
+    ArcRingRenderObject, ArcRingWidget, RingConstraints, RingIntrinsics, RingOffset, RingProtocol,
+    RingSize,
 };
 
 #[derive(PartialEq, Eq, Clone, Copy, Debug)]
@@ -95,11 +94,11 @@ impl MultiChildElement for FlexedRingElement {
     type Render = RenderFlex<RingProtocol>;
 
     fn get_child_widgets(
-        _element: Option<&mut Self>,
+        _element: &mut Self,
         widget: &Self::ArcWidget,
         _ctx: &mut BuildContext<'_>,
         _provider_values: InlinableDwsizeVec<Arc<dyn Provide>>,
-    ) -> Result<Vec<ArcRingWidget>, BuildSuspendedError> {
+    ) -> Result<Vec<ArcRingWidget>, BuildError> {
         Ok(widget.children.clone())
     }
 
@@ -243,6 +242,7 @@ impl FlexRender<RingProtocol> for RenderFlex<RingProtocol> {
         main_offset: f32,
         cross_size: f32,
         child_size: &RingSize,
+        _baseline_offset: Option<f32>,
         _parent_constraints: &RingConstraints,
     ) -> RingOffset {
         let child_cross_position = match self.cross_axis_alignment {
@@ -256,6 +256,8 @@ impl FlexRender<RingProtocol> for RenderFlex<RingProtocol> {
             }
             CrossAxisAlignment::Center => (cross_size - self.get_cross_size(child_size)) / 2.0,
             CrossAxisAlignment::Stretch => 0.0,
+            // Rings have no baselines.
+            CrossAxisAlignment::Baseline(_) => 0.0,
         };
         let child_offset = match self.direction {
             Axis::Horizontal => RingOffset {
@@ -302,4 +304,12 @@ impl FlexRender<RingProtocol> for RenderFlex<RingProtocol> {
             // todo!(paint overflow indicator)
         };
     }
+
+    fn compute_intrinsics(
+        &self,
+        _children: &[ArcRingRenderObject],
+        _intrinsics: &mut RingIntrinsics,
+    ) {
+        // No-op
+    }
 }
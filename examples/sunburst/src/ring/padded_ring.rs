@@ -1,6 +1,6 @@
 use epgi_common::Lerp;
 use epgi_core::{
-    foundation::{set_if_changed, Arc, Asc, BuildSuspendedError, InlinableDwsizeVec, Provide},
+    foundation::{set_if_changed, Arc, Asc, BuildError, InlinableDwsizeVec, Provide},
     max,
     template::{
         ImplByTemplate, ShiftedRender, ShiftedRenderTemplate, SingleChildElement,
@@ -153,7 +153,7 @@ impl SingleChildElement for PaddedRingElement {
         widget: &Self::ArcWidget,
         _ctx: &mut BuildContext<'_>,
         _provider_values: InlinableDwsizeVec<Arc<dyn Provide>>,
-    ) -> Result<ArcRingWidget, BuildSuspendedError> {
+    ) -> Result<ArcRingWidget, BuildError> {
         Ok(widget.child.clone())
     }
 
@@ -2,7 +2,7 @@ use std::sync::Arc;
 
 use epgi_2d::Affine2dCanvas;
 use epgi_core::{
-    foundation::{Asc, BuildSuspendedError, InlinableDwsizeVec, PaintContext, Provide},
+    foundation::{Asc, BuildError, InlinableDwsizeVec, PaintContext, Provide},
     template::{ImplByTemplate, LeafElement, LeafElementTemplate, LeafRender, LeafRenderTemplate},
     tree::{BuildContext, Widget},
 };
@@ -46,7 +46,7 @@ impl LeafElement for PhantomRingElement {
         _widget: &Self::ArcWidget,
         _ctx: &mut BuildContext,
         _provider_values: InlinableDwsizeVec<Arc<dyn Provide>>,
-    ) -> Result<Self, BuildSuspendedError> {
+    ) -> Result<Self, BuildError> {
         Ok(Self)
     }
 